@@ -0,0 +1,66 @@
+#!/usr/bin/env node
+
+// Rough cold-index timing with symbol extraction on the main thread vs on worker threads.
+// pnpm run bench:parse <root> [--workers=N] [--runs=N]
+
+import { parseArgs } from 'util';
+import { CodebaseIndexer } from '../dist/core/indexer.js';
+import { analyzerRegistry } from '../dist/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../dist/analyzers/generic/index.js';
+import { defaultParseConcurrency } from '../dist/utils/concurrency.js';
+
+const { values, positionals } = parseArgs({
+  allowPositionals: true,
+  options: {
+    workers: { type: 'string' },
+    runs: { type: 'string', default: '3' }
+  }
+});
+
+const rootPath = positionals[0];
+if (!rootPath) {
+  console.error('Usage: node scripts/bench-parse.mjs <root> [--workers=N] [--runs=N]');
+  process.exit(1);
+}
+
+const workers = values.workers ? Number.parseInt(values.workers, 10) : defaultParseConcurrency();
+const runs = Math.max(1, Number.parseInt(values.runs, 10) || 1);
+
+analyzerRegistry.register(new GenericAnalyzer());
+
+async function timeIndex(parseWorkers) {
+  const started = performance.now();
+  const stats = await new CodebaseIndexer({
+    rootPath,
+    config: {
+      skipEmbedding: true,
+      symbolCache: { enabled: false },
+      parsing: { workers: parseWorkers }
+    }
+  }).index();
+  return { ms: performance.now() - started, files: stats.indexedFiles };
+}
+
+function median(samples) {
+  const sorted = [...samples].sort((a, b) => a - b);
+  return sorted[Math.floor(sorted.length / 2)];
+}
+
+const results = {};
+for (const [label, parseWorkers] of [
+  ['main thread', 0],
+  [`${workers} workers`, workers]
+]) {
+  const samples = [];
+  let files = 0;
+  for (let run = 0; run < runs; run++) {
+    const { ms, files: indexed } = await timeIndex(parseWorkers);
+    samples.push(ms);
+    files = indexed;
+  }
+  results[label] = median(samples);
+  console.log(`${label.padEnd(14)} ${results[label].toFixed(0).padStart(7)} ms  (${files} files)`);
+}
+
+const [sequential, parallel] = Object.values(results);
+console.log(`speedup        ${(sequential / parallel).toFixed(2)}x`);
@@ -18,12 +18,18 @@ import {
 } from '../../types/index.js';
 import { createChunksFromCode } from '../../utils/chunking.js';
 import {
+  annotateSymbolKinds,
   createASTAlignedChunks,
   MAX_AST_CHUNK_FILE_SIZE,
   MAX_AST_CHUNK_FILE_LINES
 } from '../../utils/ast-chunker.js';
-import { detectLanguage } from '../../utils/language-detection.js';
-import { extractTreeSitterSymbols, type TreeSitterSymbol } from '../../utils/tree-sitter.js';
+import { detectLanguage, isBinaryFile } from '../../utils/language-detection.js';
+import {
+  extractTreeSitterSymbols,
+  supportsTreeSitter,
+  type TreeSitterErrorRegion,
+  type TreeSitterSymbol
+} from '../../utils/tree-sitter.js';
 import {
   detectWorkspaceType,
   scanWorkspacePackageJsons,
@@ -32,6 +38,24 @@ import {
 } from '../../utils/dependency-detection.js';
 import type { WorkspacePackageJson } from '../../utils/workspace-detection.js';
 
+// Languages parsed with typescript-estree for imports and exports (JSX included)
+const JS_TS_LANGUAGES = new Set(['typescript', 'typescriptreact', 'javascript', 'javascriptreact']);
+
+// Languages without a grammar whose `def`/`class`/`func` lines the regex fallback can still find
+const REGEX_SYMBOL_LANGUAGES = new Set(['ruby', 'php', 'kotlin', 'swift', 'scala']);
+
+/**
+ * Files with no grammar, JS/TS parser or regex fallback (SQL, shell, config, markup, plain
+ * text) are indexed as line chunks with no symbols, so keyword search still reaches them.
+ */
+export function isTextOnlyLanguage(language: string): boolean {
+  return (
+    !supportsTreeSitter(language) &&
+    !JS_TS_LANGUAGES.has(language) &&
+    !REGEX_SYMBOL_LANGUAGES.has(language)
+  );
+}
+
 export class GenericAnalyzer implements FrameworkAnalyzer {
   readonly name = 'generic';
   readonly version = '1.0.0';
@@ -94,15 +118,39 @@ export class GenericAnalyzer implements FrameworkAnalyzer {
   ];
   readonly priority = 10; // Low priority - fallback analyzer
 
-  canAnalyze(filePath: string, _content?: string): boolean {
+  canAnalyze(filePath: string, content?: string): boolean {
     const ext = path.extname(filePath).toLowerCase();
-    return this.supportedExtensions.includes(ext);
+    if (this.supportedExtensions.includes(ext)) return true;
+    // Any other text file the scan kept is indexed as text only
+    if (ext && ext !== '.in') return !isBinaryFile(filePath);
+    // Extensionless scripts and `.in` templates: trust the shebang or the inner extension
+    return detectLanguage(filePath, content) !== 'plaintext';
   }
 
   async analyze(filePath: string, content: string): Promise<AnalysisResult> {
-    const language = detectLanguage(filePath);
+    const language = detectLanguage(filePath, content);
     const relativePath = path.relative(process.cwd(), filePath);
 
+    if (isTextOnlyLanguage(language)) {
+      const metadata: Record<string, unknown> = {
+        analyzer: this.name,
+        fileSize: content.length,
+        lineCount: content.split('\n').length,
+        chunkStrategy: 'lines',
+        textOnly: true
+      };
+      return {
+        filePath,
+        language,
+        components: [],
+        imports: [],
+        exports: [],
+        dependencies: [],
+        metadata,
+        chunks: await createChunksFromCode(content, filePath, relativePath, language, [], metadata)
+      };
+    }
+
     // Parse based on language
     let components: CodeComponent[] = [];
     let imports: ImportStatement[] = [];
@@ -110,9 +158,11 @@ export class GenericAnalyzer implements FrameworkAnalyzer {
     let treeSitterGrammar: string | undefined;
     let usesTreeSitterSymbols = false;
     let treeSitterSymbols: TreeSitterSymbol[] = [];
+    let parseErrorRegions: TreeSitterErrorRegion[] = [];
 
     try {
       const treeSitterResult = await extractTreeSitterSymbols(content, language);
+      parseErrorRegions = treeSitterResult?.errorRegions ?? [];
       if (treeSitterResult && treeSitterResult.symbols.length > 0) {
         treeSitterSymbols = treeSitterResult.symbols;
         // Legacy: replaced by createASTAlignedChunks for AST-aligned chunking
@@ -121,8 +171,8 @@ export class GenericAnalyzer implements FrameworkAnalyzer {
         usesTreeSitterSymbols = true;
       }
 
-      if (language === 'typescript' || language === 'javascript') {
-        const parsed = await this.parseJSTSFile(filePath, content, language);
+      if (JS_TS_LANGUAGES.has(language)) {
+        const parsed = await this.parseJSTSFile(filePath, content);
         imports = parsed.imports;
         exports = parsed.exports;
 
@@ -153,6 +203,11 @@ export class GenericAnalyzer implements FrameworkAnalyzer {
       metadata.treeSitterGrammar = treeSitterGrammar;
       metadata.symbolAware = true;
     }
+    // Symbols were recovered around syntax errors; the regions say where some may be missing
+    if (parseErrorRegions.length > 0) {
+      metadata.hasParseErrors = true;
+      metadata.parseErrorRegions = parseErrorRegions;
+    }
 
     // Create chunks — use AST-aligned chunker when Tree-sitter symbols are available
     // File ceiling pre-check: skip AST chunking for very large files
@@ -167,7 +222,9 @@ export class GenericAnalyzer implements FrameworkAnalyzer {
     let chunks: CodeChunk[];
     if (useASTChunking) {
       try {
-        chunks = createASTAlignedChunks(content, treeSitterSymbols, {
+        // Class fields stay in components but are too small to be chunk boundaries
+        const chunkSymbols = treeSitterSymbols.filter((symbol) => symbol.kind !== 'field');
+        chunks = createASTAlignedChunks(content, chunkSymbols, {
           minChunkLines: 10,
           maxChunkLines: 150,
           filePath,
@@ -175,6 +232,7 @@ export class GenericAnalyzer implements FrameworkAnalyzer {
           framework: 'generic',
           componentType: 'module'
         });
+        annotateSymbolKinds(chunks, treeSitterSymbols);
         // Enrich AST chunks with the correct relativePath
         for (const chunk of chunks) {
           chunk.relativePath = relativePath;
@@ -207,6 +265,12 @@ export class GenericAnalyzer implements FrameworkAnalyzer {
       );
     }
 
+    if (parseErrorRegions.length > 0) {
+      for (const chunk of chunks) {
+        chunk.metadata = { ...chunk.metadata, hasParseErrors: true };
+      }
+    }
+
     return {
       filePath,
       language,
@@ -332,8 +396,7 @@ export class GenericAnalyzer implements FrameworkAnalyzer {
 
   private async parseJSTSFile(
     filePath: string,
-    content: string,
-    _language: 'typescript' | 'javascript'
+    content: string
   ): Promise<{
     components: CodeComponent[];
     imports: ImportStatement[];
@@ -436,6 +499,17 @@ export class GenericAnalyzer implements FrameworkAnalyzer {
             }
           }
 
+          // Re-exports (`export { foo } from './bar'`) also make this file depend on the source
+          if (node.source?.value) {
+            imports.push({
+              source: node.source.value,
+              imports: node.specifiers.map((spec) => spec.local.name),
+              isDefault: false,
+              isDynamic: false,
+              line: node.loc?.start.line
+            });
+          }
+
           if (node.specifiers) {
             for (const spec of node.specifiers) {
               if (spec.type === 'ExportSpecifier') {
@@ -449,6 +523,23 @@ export class GenericAnalyzer implements FrameworkAnalyzer {
           }
         }
 
+        if (node.type === 'ExportAllDeclaration' && node.source.value) {
+          imports.push({
+            source: node.source.value,
+            imports: ['*'],
+            isDefault: false,
+            isDynamic: false,
+            line: node.loc?.start.line
+          });
+          if (node.exported) {
+            exports.push({
+              name: node.exported.name,
+              isDefault: false,
+              type: 'named'
+            });
+          }
+        }
+
         if (node.type === 'ExportDefaultDeclaration') {
           const name = node.declaration.type === 'Identifier' ? node.declaration.name : 'default';
           exports.push({
@@ -511,6 +602,13 @@ export class GenericAnalyzer implements FrameworkAnalyzer {
       return `${language} ${componentType || 'code'} in ${fileName}`;
     }
 
+    if (chunk.metadata?.textOnly) {
+      const firstLine = content.split('\n').find((line) => line.trim());
+      return `${language} text in ${fileName} (text-only, no symbols): ${
+        firstLine ? firstLine.trim().slice(0, 60) : 'empty'
+      }`;
+    }
+
     // Try to extract meaningful information
     const firstComment = this.extractFirstComment(content);
     if (firstComment) {
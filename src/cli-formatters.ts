@@ -12,6 +12,7 @@ import type {
   SearchResultItem,
   RefsResponse,
   RefsUsage,
+  FuzzyRefsResponse,
   CyclesResponse,
   CycleItem,
   MetadataResponse,
@@ -404,6 +405,12 @@ export function formatSearch(
         console.log(`    ${g.warn} ${r.patternWarning}`);
       }
 
+      if (r.duplicates && r.duplicates.length > 0) {
+        const copies = r.duplicates.slice(0, 3).map((d) => shortPath(d, rootPath));
+        const more = r.duplicates.length > 3 ? ` (+${r.duplicates.length - 3} more)` : '';
+        console.log(`    identical in: ${copies.join(', ')}${more}`);
+      }
+
       const hints = r.hints;
       if (hints?.callers && hints.callers.length > 0) {
         const shortCallers = hints.callers.slice(0, 3).map((c) => shortPath(c, rootPath));
@@ -487,6 +494,30 @@ export function formatRefs(data: RefsResponse, rootPath: string): void {
   console.log('');
 }
 
+export function formatFuzzyRefs(data: FuzzyRefsResponse, rootPath: string): void {
+  const g = getGlyphs();
+  const matches = data.matches ?? [];
+
+  const lines: string[] = [''];
+  if (matches.length === 0) {
+    lines.push('No matching symbols');
+  }
+  for (const m of matches) {
+    const kind = m.kind ? ` (${m.kind})` : '';
+    lines.push(`${m.name}${kind}  ${m.match} ${m.score.toFixed(2)}`);
+    lines.push(`  ${shortPath(m.file, rootPath)}:${m.line}`);
+  }
+  lines.push('');
+
+  const boxTitle = `${data.query} ${g.box.h.repeat(3)} ${data.totalMatches} matching symbols`;
+  const boxOut = drawBox(boxTitle, lines, BOX_WIDTH);
+  console.log('');
+  for (const l of boxOut) {
+    console.log(l);
+  }
+  console.log('');
+}
+
 export function formatCycles(data: CyclesResponse, rootPath: string): void {
   const g = getGlyphs();
   const cycles = data.cycles ?? [];
@@ -858,7 +889,11 @@ export function formatJson(
     }
     case 'refs': {
       try {
-        formatRefs(data as RefsResponse, rootPath ?? '');
+        if ((data as { mode?: unknown }).mode === 'fuzzy') {
+          formatFuzzyRefs(data as FuzzyRefsResponse, rootPath ?? '');
+        } else {
+          formatRefs(data as RefsResponse, rootPath ?? '');
+        }
       } catch {
         console.log(JSON.stringify(data, null, 2));
       }
@@ -872,6 +907,17 @@ export function formatJson(
       }
       break;
     }
+    case 'pack': {
+      const d = data as {
+        packed?: string;
+        excluded?: Array<{ file?: string; symbol?: string; reason: string }>;
+      };
+      console.log(d.packed ?? '');
+      for (const ref of d.excluded ?? []) {
+        console.error(`excluded (${ref.reason}): ${ref.symbol ?? ref.file ?? ''}`);
+      }
+      break;
+    }
     default: {
       console.log(JSON.stringify(data, null, 2));
     }
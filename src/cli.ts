@@ -1,7 +1,7 @@
 /**
  * CLI subcommands for codebase-context.
  * Memory list/add/remove — vendor-neutral access without any AI agent.
- * search/metadata/status/reindex/style-guide/patterns/refs/cycles — all MCP tools.
+ * search/metadata/status/reindex/style-guide/patterns/refs/cycles and the rest map onto MCP tools.
  */
 
 import path from 'path';
@@ -14,14 +14,23 @@ import {
   VECTOR_DB_DIRNAME
 } from './constants/codebase-context.js';
 import { CodebaseIndexer } from './core/indexer.js';
+import { parseWorkspaceRootList, resolveWorkspaceRoots } from './core/workspace-roots.js';
+import {
+  isSnippetDedupMode,
+  SNIPPET_DEDUP_MODES,
+  type SnippetDedupMode
+} from './core/snippet-dedup.js';
 import { dispatchTool } from './tools/index.js';
 import type { ToolContext } from './tools/index.js';
 import type { IndexState } from './tools/types.js';
+import type { CodebaseConfig } from './types/index.js';
 import { analyzerRegistry } from './core/analyzer-registry.js';
 import { AngularAnalyzer } from './analyzers/angular/index.js';
 import { GenericAnalyzer } from './analyzers/generic/index.js';
 import { formatJson } from './cli-formatters.js';
 import { handleMemoryCli } from './cli-memory.js';
+import { describeCustomQueryError, installCustomSymbolQueries } from './core/custom-queries.js';
+import { DETAIL_LEVELS, isDetailLevel, type DetailLevel } from './core/detail-level.js';
 export { handleMemoryCli } from './cli-memory.js';
 
 analyzerRegistry.register(new AngularAnalyzer());
@@ -36,7 +45,23 @@ const _CLI_COMMANDS = [
   'style-guide',
   'patterns',
   'refs',
-  'cycles'
+  'references',
+  'cycles',
+  'context',
+  'outline',
+  'calls',
+  'export-chunks',
+  'definition',
+  'deps',
+  'stats',
+  'structural',
+  'changed',
+  'semantic',
+  'around',
+  'files',
+  'pack',
+  'related',
+  'resolve'
 ] as const;
 
 type CliCommand = (typeof _CLI_COMMANDS)[number];
@@ -60,6 +85,13 @@ function isTeamPatternCategory(value: string): value is TeamPatternCategory {
   return TEAM_PATTERN_CATEGORY_SET.has(value);
 }
 
+const DEPENDENCY_DIRECTIONS = ['dependencies', 'dependents', 'both'] as const;
+type DependencyDirection = (typeof DEPENDENCY_DIRECTIONS)[number];
+const DEPENDENCY_DIRECTION_SET: ReadonlySet<string> = new Set(DEPENDENCY_DIRECTIONS);
+function isDependencyDirection(value: string): value is DependencyDirection {
+  return DEPENDENCY_DIRECTION_SET.has(value);
+}
+
 function printUsage(): void {
   console.log('codebase-context <command> [options]');
   console.log('');
@@ -68,13 +100,53 @@ function printUsage(): void {
   console.log('  search --query <q>                 Search the indexed codebase');
   console.log('         [--intent explore|edit|refactor|migrate]');
   console.log('         [--limit <n>] [--lang <l>] [--framework <f>] [--layer <l>]');
+  console.log('         [--no-git-boost]            Rank without git recency/frequency');
+  console.log('         [--dedup exact|whitespace]  Collapse identical code from other files');
+  console.log('         [--detail names|signatures|full]  How much each result carries');
   console.log('  metadata                           Project structure, frameworks, deps');
   console.log('  status                             Index state and progress');
   console.log('  reindex [--incremental] [--reason <r>]  Re-index the codebase');
+  console.log('         [--no-cache]                Re-parse every file, ignoring the symbol cache');
   console.log('  style-guide [--query <q>] [--category <c>]  Style guide rules');
   console.log('  patterns [--category all|di|state|testing|libraries]  Team patterns');
   console.log('  refs --symbol <name> [--limit <n>]  Symbol references');
+  console.log('         [--fuzzy]                   Ranked symbol names matching a partial name');
+  console.log('  references --symbol <name>         Scope-aware definition + references');
+  console.log('         [--file <path> --line <n>] [--limit <n>]');
   console.log('  cycles [--scope <path>]            Circular dependency detection');
+  console.log('  context --symbol <name> [--max-tokens <n>]  Budgeted symbol context');
+  console.log('         [--docs]                    Start definitions at their doc comment');
+  console.log('         [--signatures]              Definitions without their bodies');
+  console.log('         [--detail names|signatures|full]  One line, signatures or whole bodies');
+  console.log('  outline --file <path> [--depth <n>]  File structure without bodies');
+  console.log('         [--docs]                    Include doc comments and docstrings');
+  console.log('         [--signatures]              Include each symbol signature');
+  console.log('         [--detail names|signatures|full]  Add signatures, or docs and source');
+  console.log('  calls --symbol <name> [--callees]  Call graph edges (callers by default)');
+  console.log('         [--depth <n>] [--limit <n>]');
+  console.log('  export-chunks [--out <file>]       Per-symbol chunks as JSON Lines');
+  console.log('         [--scope <path>] [--max-lines <n>] [--overlap <n>]');
+  console.log('  definition --symbol <name>         Exact source span of a definition');
+  console.log('         [--file <path>] [--max-lines <n>]');
+  console.log('  deps --file <path>                 File-level imports and importers');
+  console.log('         [--direction dependencies|dependents|both] [--depth <n>] [--limit <n>]');
+  console.log('  stats                              Index health: counts, skips, errors, grammars');
+  console.log('  structural --language <lang>       Tree-sitter query matches by syntax shape');
+  console.log('         --query <sexp> [--scope <path>] [--limit <n>]');
+  console.log('  changed [--base <ref>] [--head <ref>]  Symbols touched by a git diff');
+  console.log('         [--kinds <k,...>] [--limit <n>]');
+  console.log('  semantic --query <text> [--k <n>]  Nearest chunks by embedding similarity');
+  console.log('           [--content]');
+  console.log('  around --file <path> --line <n>    Numbered lines around one line');
+  console.log('         [--before <n>] [--after <n>] [--snap]');
+  console.log('  files [--glob <pattern>]           Indexed files with language and symbols');
+  console.log('         [--lang <l>] [--limit <n>] [--cursor <path>]');
+  console.log('  pack [--files <path[:a-b],...>]    Ranges and symbols as one prompt block');
+  console.log('         [--symbols <name,...>] [--max-tokens <n>]');
+  console.log('  related --file <path>              Files most likely relevant to a file');
+  console.log('         [--limit <n>]');
+  console.log('  resolve --path <qualified>         One symbol by its qualified path');
+  console.log('         [--file <path>]');
   console.log('');
   console.log('Global flags:');
   console.log('  --json    Output raw JSON (default: human-readable)');
@@ -82,11 +154,12 @@ function printUsage(): void {
   console.log('');
   console.log('Environment:');
   console.log('  CODEBASE_ROOT    Project root path (default: cwd)');
+  console.log('  CODEBASE_CONTEXT_EXTRA_ROOTS  More workspace roots, separated like PATH');
   console.log('  CODEBASE_CONTEXT_ASCII=1  Force ASCII-only box output');
   console.log('  CODEBASE_CONTEXT_DEBUG=1  Enable verbose logs');
 }
 
-async function initToolContext(): Promise<ToolContext> {
+async function initToolContext(indexerConfig?: Partial<CodebaseConfig>): Promise<ToolContext> {
   const rootPath = path.resolve(process.env.CODEBASE_ROOT || process.cwd());
 
   const paths = {
@@ -97,6 +170,10 @@ async function initToolContext(): Promise<ToolContext> {
     vectorDb: path.join(rootPath, CODEBASE_CONTEXT_DIRNAME, VECTOR_DB_DIRNAME)
   };
 
+  for (const error of await installCustomSymbolQueries(rootPath)) {
+    console.error(describeCustomQueryError(error));
+  }
+
   let indexExists = false;
   try {
     await fs.access(paths.keywordIndex);
@@ -118,6 +195,7 @@ async function initToolContext(): Promise<ToolContext> {
       let lastLoggedProgress = { phase: '', percentage: -1 };
       const indexer = new CodebaseIndexer({
         rootPath,
+        config: indexerConfig,
         incrementalOnly,
         onProgress: (progress) => {
           const shouldLog =
@@ -149,7 +227,12 @@ async function initToolContext(): Promise<ToolContext> {
     }
   };
 
-  return { indexState, paths, rootPath, performIndexing };
+  const workspaceRoots = resolveWorkspaceRoots(
+    rootPath,
+    parseWorkspaceRootList(process.env.CODEBASE_CONTEXT_EXTRA_ROOTS)
+  );
+
+  return { indexState, paths, rootPath, workspaceRoots, performIndexing };
 }
 
 function extractText(result: { content?: Array<{ type: string; text: string }> }): string {
@@ -215,6 +298,33 @@ function optionalPositiveIntFlag(flags: Flags, key: string, usage: string): numb
   return Math.floor(num);
 }
 
+function optionalNonNegativeIntFlag(
+  flags: Flags,
+  key: string,
+  usage: string
+): number | undefined {
+  const value = flags[key];
+  if (value === undefined) return undefined;
+  if (typeof value !== 'string') {
+    exitWithError(`Error: --${key} requires a value\nUsage: ${usage}`);
+  }
+  const num = Number(value);
+  if (!Number.isFinite(num) || num < 0) {
+    exitWithError(`Error: --${key} must be zero or a positive number\nUsage: ${usage}`);
+  }
+  return Math.floor(num);
+}
+
+function optionalDetailFlag(flags: Flags, usage: string): DetailLevel | undefined {
+  const value = optionalStringFlag(flags, 'detail', usage);
+  if (value !== undefined && !isDetailLevel(value)) {
+    exitWithError(
+      `Error: invalid --detail "${value}". Allowed: ${DETAIL_LEVELS.join(', ')}\nUsage: ${usage}`
+    );
+  }
+  return value;
+}
+
 function booleanFlag(flags: Flags, key: string, usage: string): boolean {
   const value = flags[key];
   if (value === undefined) return false;
@@ -252,7 +362,9 @@ export async function handleCliCommand(argv: string[]): Promise<void> {
 
   const flags = parseFlags(argv);
 
-  const ctx = await initToolContext();
+  // --no-cache re-parses every file instead of reusing symbols from the on-disk cache
+  const noCache = command === 'reindex' && flags['no-cache'] === true;
+  const ctx = await initToolContext(noCache ? { symbolCache: { enabled: false } } : undefined);
 
   type DispatchSpec =
     | { toolName: 'search_codebase'; toolArgs: SearchToolArgs }
@@ -261,20 +373,110 @@ export async function handleCliCommand(argv: string[]): Promise<void> {
     | { toolName: 'get_style_guide'; toolArgs: StyleGuideToolArgs }
     | { toolName: 'get_team_patterns'; toolArgs: TeamPatternsToolArgs }
     | { toolName: 'get_symbol_references'; toolArgs: SymbolReferencesToolArgs }
-    | { toolName: 'detect_circular_dependencies'; toolArgs: DetectCircularDependenciesToolArgs };
+    | { toolName: 'find_references'; toolArgs: FindReferencesToolArgs }
+    | { toolName: 'detect_circular_dependencies'; toolArgs: DetectCircularDependenciesToolArgs }
+    | { toolName: 'get_symbol_context'; toolArgs: SymbolContextToolArgs }
+    | { toolName: 'file_outline'; toolArgs: FileOutlineToolArgs }
+    | { toolName: 'call_graph'; toolArgs: CallGraphToolArgs }
+    | { toolName: 'export_chunks'; toolArgs: ExportChunksToolArgs }
+    | { toolName: 'get_definition'; toolArgs: GetDefinitionToolArgs }
+    | { toolName: 'dependency_graph'; toolArgs: DependencyGraphToolArgs }
+    | { toolName: 'stats'; toolArgs: Record<never, never> }
+    | { toolName: 'structural_search'; toolArgs: StructuralSearchToolArgs }
+    | { toolName: 'changed_symbols'; toolArgs: ChangedSymbolsToolArgs }
+    | { toolName: 'semantic_search'; toolArgs: SemanticSearchToolArgs }
+    | { toolName: 'context_around'; toolArgs: ContextAroundToolArgs }
+    | { toolName: 'list_files'; toolArgs: ListFilesToolArgs }
+    | { toolName: 'pack_context'; toolArgs: PackContextToolArgs }
+    | { toolName: 'related_files'; toolArgs: RelatedFilesToolArgs }
+    | { toolName: 'resolve_path'; toolArgs: ResolvePathToolArgs };
 
   type SearchToolArgs = {
     query: string;
     includeSnippets: boolean;
     intent?: SearchIntent;
     limit?: number;
+    kinds?: string[];
     filters?: { language?: string; framework?: string; layer?: string };
+    gitBoost?: boolean;
+    dedupSnippets?: SnippetDedupMode;
+    detail?: DetailLevel;
   };
 
   type StyleGuideToolArgs = { query?: string; category?: string };
   type TeamPatternsToolArgs = { category?: TeamPatternCategory };
-  type SymbolReferencesToolArgs = { symbol: string; limit?: number };
+  type SymbolReferencesToolArgs = { symbol: string; limit?: number; mode?: 'exact' | 'fuzzy' };
+  type FindReferencesToolArgs = { symbol: string; file?: string; line?: number; limit?: number };
   type DetectCircularDependenciesToolArgs = { scope?: string };
+  type SymbolContextToolArgs = {
+    symbol: string;
+    maxTokens?: number;
+    includeDocs?: boolean;
+    signaturesOnly?: boolean;
+    detail?: DetailLevel;
+  };
+  type FileOutlineToolArgs = {
+    file: string;
+    depth?: number;
+    includeDocs?: boolean;
+    signaturesOnly?: boolean;
+    detail?: DetailLevel;
+  };
+  type CallGraphToolArgs = {
+    symbol: string;
+    direction?: 'callers' | 'callees';
+    depth?: number;
+    limit?: number;
+  };
+  type ExportChunksToolArgs = {
+    output?: string;
+    scope?: string;
+    maxLines?: number;
+    overlapLines?: number;
+  };
+  type GetDefinitionToolArgs = {
+    symbol: string;
+    file?: string;
+    maxLines?: number;
+  };
+  type DependencyGraphToolArgs = {
+    file: string;
+    direction?: DependencyDirection;
+    depth?: number;
+    limit?: number;
+  };
+  type StructuralSearchToolArgs = {
+    query: string;
+    language: string;
+    scope?: string;
+    limit?: number;
+  };
+  type ChangedSymbolsToolArgs = {
+    base?: string;
+    head?: string;
+    kinds?: string[];
+    limit?: number;
+  };
+  type SemanticSearchToolArgs = { query: string; k?: number; includeContent?: boolean };
+  type ContextAroundToolArgs = {
+    file: string;
+    line: number;
+    before?: number;
+    after?: number;
+    snapToSymbol?: boolean;
+  };
+  type ListFilesToolArgs = {
+    glob?: string;
+    language?: string;
+    limit?: number;
+    cursor?: string;
+  };
+  type PackContextToolArgs = {
+    references: Array<{ file?: string; startLine?: number; endLine?: number; symbol?: string }>;
+    maxTokens?: number;
+  };
+  type RelatedFilesToolArgs = { path: string; limit?: number };
+  type ResolvePathToolArgs = { path: string; file?: string };
 
   let dispatch: DispatchSpec;
   let formatQuery: string | undefined;
@@ -282,7 +484,9 @@ export async function handleCliCommand(argv: string[]): Promise<void> {
 
   switch (command) {
     case 'search': {
-      const usage = 'codebase-context search --query <text> [--intent <i>] [--limit <n>]';
+      const usage =
+        'codebase-context search --query <text> [--intent <i>] [--limit <n>] [--kinds <k,...>] ' +
+        '[--no-git-boost] [--dedup exact|whitespace] [--detail names|signatures|full]';
       const query = requireStringFlag(flags, 'query', usage);
       const intentValue = optionalStringFlag(flags, 'intent', usage);
       let intent: SearchIntent | undefined;
@@ -298,6 +502,18 @@ export async function handleCliCommand(argv: string[]): Promise<void> {
       const lang = optionalStringFlag(flags, 'lang', usage);
       const framework = optionalStringFlag(flags, 'framework', usage);
       const layer = optionalStringFlag(flags, 'layer', usage);
+      const kinds = optionalStringFlag(flags, 'kinds', usage)
+        ?.split(',')
+        .map((kind) => kind.trim())
+        .filter(Boolean);
+      const noGitBoost = booleanFlag(flags, 'no-git-boost', usage);
+      const dedupValue = optionalStringFlag(flags, 'dedup', usage);
+      if (dedupValue !== undefined && !isSnippetDedupMode(dedupValue)) {
+        exitWithError(
+          `Error: invalid --dedup "${dedupValue}". Allowed: ${SNIPPET_DEDUP_MODES.join(', ')}\nUsage: ${usage}`
+        );
+      }
+      const detail = optionalDetailFlag(flags, usage);
 
       const filters: { language?: string; framework?: string; layer?: string } = {};
       if (lang) filters.language = lang;
@@ -309,7 +525,11 @@ export async function handleCliCommand(argv: string[]): Promise<void> {
         includeSnippets: true,
         ...(intent ? { intent } : {}),
         ...(limit != null ? { limit } : {}),
-        ...(Object.keys(filters).length > 0 ? { filters } : {})
+        ...(kinds && kinds.length > 0 ? { kinds } : {}),
+        ...(Object.keys(filters).length > 0 ? { filters } : {}),
+        ...(noGitBoost ? { gitBoost: false } : {}),
+        ...(dedupValue ? { dedupSnippets: dedupValue } : {}),
+        ...(detail ? { detail } : {})
       };
       dispatch = { toolName: 'search_codebase', toolArgs: args };
       formatQuery = query;
@@ -325,7 +545,7 @@ export async function handleCliCommand(argv: string[]): Promise<void> {
       break;
     }
     case 'reindex': {
-      const usage = 'codebase-context reindex [--incremental] [--reason <r>]';
+      const usage = 'codebase-context reindex [--incremental] [--reason <r>] [--no-cache]';
       const reason = optionalStringFlag(flags, 'reason', usage);
       const incremental = booleanFlag(flags, 'incremental', usage);
       await ctx.performIndexing(incremental, reason);
@@ -367,13 +587,33 @@ export async function handleCliCommand(argv: string[]): Promise<void> {
       break;
     }
     case 'refs': {
-      const usage = 'codebase-context refs --symbol <name> [--limit <n>]';
+      const usage = 'codebase-context refs --symbol <name> [--limit <n>] [--fuzzy]';
       const symbol = requireStringFlag(flags, 'symbol', usage);
       const limit = optionalPositiveIntFlag(flags, 'limit', usage);
+      const fuzzy = booleanFlag(flags, 'fuzzy', usage);
       dispatch = {
         toolName: 'get_symbol_references',
         toolArgs: {
           symbol,
+          ...(limit != null ? { limit } : {}),
+          ...(fuzzy ? { mode: 'fuzzy' as const } : {})
+        }
+      };
+      break;
+    }
+    case 'references': {
+      const usage =
+        'codebase-context references --symbol <name> [--file <path> --line <n>] [--limit <n>]';
+      const symbol = requireStringFlag(flags, 'symbol', usage);
+      const file = optionalStringFlag(flags, 'file', usage);
+      const line = optionalPositiveIntFlag(flags, 'line', usage);
+      const limit = optionalPositiveIntFlag(flags, 'limit', usage);
+      dispatch = {
+        toolName: 'find_references',
+        toolArgs: {
+          symbol,
+          ...(file ? { file } : {}),
+          ...(line != null ? { line } : {}),
           ...(limit != null ? { limit } : {})
         }
       };
@@ -390,6 +630,283 @@ export async function handleCliCommand(argv: string[]): Promise<void> {
       };
       break;
     }
+    case 'context': {
+      const usage =
+        'codebase-context context --symbol <name> [--max-tokens <n>] [--docs] [--signatures] ' +
+        '[--detail names|signatures|full]';
+      const symbol = requireStringFlag(flags, 'symbol', usage);
+      const maxTokens = optionalPositiveIntFlag(flags, 'max-tokens', usage);
+      const includeDocs = booleanFlag(flags, 'docs', usage);
+      const signaturesOnly = booleanFlag(flags, 'signatures', usage);
+      const detail = optionalDetailFlag(flags, usage);
+      dispatch = {
+        toolName: 'get_symbol_context',
+        toolArgs: {
+          symbol,
+          ...(maxTokens != null ? { maxTokens } : {}),
+          ...(includeDocs ? { includeDocs } : {}),
+          ...(signaturesOnly ? { signaturesOnly } : {}),
+          ...(detail ? { detail } : {})
+        }
+      };
+      break;
+    }
+    case 'outline': {
+      const usage =
+        'codebase-context outline --file <path> [--depth <n>] [--docs] [--signatures] ' +
+        '[--detail names|signatures|full]';
+      const file = requireStringFlag(flags, 'file', usage);
+      const depth = optionalPositiveIntFlag(flags, 'depth', usage);
+      const includeDocs = booleanFlag(flags, 'docs', usage);
+      const signaturesOnly = booleanFlag(flags, 'signatures', usage);
+      const detail = optionalDetailFlag(flags, usage);
+      dispatch = {
+        toolName: 'file_outline',
+        toolArgs: {
+          file,
+          ...(depth != null ? { depth } : {}),
+          ...(includeDocs ? { includeDocs } : {}),
+          ...(signaturesOnly ? { signaturesOnly } : {}),
+          ...(detail ? { detail } : {})
+        }
+      };
+      break;
+    }
+    case 'calls': {
+      const usage =
+        'codebase-context calls --symbol <name> [--callees] [--depth <n>] [--limit <n>]';
+      const symbol = requireStringFlag(flags, 'symbol', usage);
+      const callees = booleanFlag(flags, 'callees', usage);
+      const depth = optionalPositiveIntFlag(flags, 'depth', usage);
+      const limit = optionalPositiveIntFlag(flags, 'limit', usage);
+      dispatch = {
+        toolName: 'call_graph',
+        toolArgs: {
+          symbol,
+          ...(callees ? { direction: 'callees' as const } : {}),
+          ...(depth != null ? { depth } : {}),
+          ...(limit != null ? { limit } : {})
+        }
+      };
+      break;
+    }
+    case 'export-chunks': {
+      const usage =
+        'codebase-context export-chunks [--out <file>] [--scope <path>] [--max-lines <n>] [--overlap <n>]';
+      const output = optionalStringFlag(flags, 'out', usage);
+      const scope = optionalStringFlag(flags, 'scope', usage);
+      const maxLines = optionalPositiveIntFlag(flags, 'max-lines', usage);
+      const overlapLines = optionalNonNegativeIntFlag(flags, 'overlap', usage);
+      dispatch = {
+        toolName: 'export_chunks',
+        toolArgs: {
+          ...(output ? { output } : {}),
+          ...(scope ? { scope } : {}),
+          ...(maxLines != null ? { maxLines } : {}),
+          ...(overlapLines != null ? { overlapLines } : {})
+        }
+      };
+      break;
+    }
+    case 'definition': {
+      const usage = 'codebase-context definition --symbol <name> [--file <path>] [--max-lines <n>]';
+      const symbol = requireStringFlag(flags, 'symbol', usage);
+      const file = optionalStringFlag(flags, 'file', usage);
+      const maxLines = optionalPositiveIntFlag(flags, 'max-lines', usage);
+      dispatch = {
+        toolName: 'get_definition',
+        toolArgs: {
+          symbol,
+          ...(file ? { file } : {}),
+          ...(maxLines != null ? { maxLines } : {})
+        }
+      };
+      break;
+    }
+    case 'deps': {
+      const usage =
+        'codebase-context deps --file <path> [--direction dependencies|dependents|both] ' +
+        '[--depth <n>] [--limit <n>]';
+      const file = requireStringFlag(flags, 'file', usage);
+      const directionValue = optionalStringFlag(flags, 'direction', usage);
+      let direction: DependencyDirection | undefined;
+      if (directionValue) {
+        if (!isDependencyDirection(directionValue)) {
+          exitWithError(
+            `Error: invalid --direction "${directionValue}". Allowed: ${DEPENDENCY_DIRECTIONS.join(', ')}\nUsage: ${usage}`
+          );
+        }
+        direction = directionValue;
+      }
+      const depth = optionalPositiveIntFlag(flags, 'depth', usage);
+      const limit = optionalPositiveIntFlag(flags, 'limit', usage);
+      dispatch = {
+        toolName: 'dependency_graph',
+        toolArgs: {
+          file,
+          ...(direction ? { direction } : {}),
+          ...(depth != null ? { depth } : {}),
+          ...(limit != null ? { limit } : {})
+        }
+      };
+      break;
+    }
+    case 'stats': {
+      dispatch = { toolName: 'stats', toolArgs: {} };
+      break;
+    }
+    case 'structural': {
+      const usage =
+        'codebase-context structural --language <lang> --query <sexp> [--scope <path>] [--limit <n>]';
+      const language = requireStringFlag(flags, 'language', usage);
+      const query = requireStringFlag(flags, 'query', usage);
+      const scope = optionalStringFlag(flags, 'scope', usage);
+      const limit = optionalPositiveIntFlag(flags, 'limit', usage);
+      dispatch = {
+        toolName: 'structural_search',
+        toolArgs: {
+          query,
+          language,
+          ...(scope ? { scope } : {}),
+          ...(limit != null ? { limit } : {})
+        }
+      };
+      break;
+    }
+    case 'changed': {
+      const usage =
+        'codebase-context changed [--base <ref>] [--head <ref>] [--kinds <k,...>] [--limit <n>]';
+      const base = optionalStringFlag(flags, 'base', usage);
+      const head = optionalStringFlag(flags, 'head', usage);
+      const kinds = optionalStringFlag(flags, 'kinds', usage)
+        ?.split(',')
+        .map((kind) => kind.trim())
+        .filter(Boolean);
+      const limit = optionalPositiveIntFlag(flags, 'limit', usage);
+      dispatch = {
+        toolName: 'changed_symbols',
+        toolArgs: {
+          ...(base ? { base } : {}),
+          ...(head ? { head } : {}),
+          ...(kinds && kinds.length > 0 ? { kinds } : {}),
+          ...(limit != null ? { limit } : {})
+        }
+      };
+      break;
+    }
+    case 'semantic': {
+      const usage = 'codebase-context semantic --query <text> [--k <n>] [--content]';
+      const query = requireStringFlag(flags, 'query', usage);
+      const k = optionalPositiveIntFlag(flags, 'k', usage);
+      const includeContent = booleanFlag(flags, 'content', usage);
+      dispatch = {
+        toolName: 'semantic_search',
+        toolArgs: {
+          query,
+          ...(k != null ? { k } : {}),
+          ...(includeContent ? { includeContent } : {})
+        }
+      };
+      break;
+    }
+    case 'around': {
+      const usage =
+        'codebase-context around --file <path> --line <n> [--before <n>] [--after <n>] [--snap]';
+      const file = requireStringFlag(flags, 'file', usage);
+      const line = optionalPositiveIntFlag(flags, 'line', usage);
+      if (line == null) {
+        exitWithError(`Error: --line is required\nUsage: ${usage}`);
+      }
+      const before = optionalNonNegativeIntFlag(flags, 'before', usage);
+      const after = optionalNonNegativeIntFlag(flags, 'after', usage);
+      const snapToSymbol = booleanFlag(flags, 'snap', usage);
+      dispatch = {
+        toolName: 'context_around',
+        toolArgs: {
+          file,
+          line,
+          ...(before != null ? { before } : {}),
+          ...(after != null ? { after } : {}),
+          ...(snapToSymbol ? { snapToSymbol } : {})
+        }
+      };
+      break;
+    }
+    case 'files': {
+      const usage =
+        'codebase-context files [--glob <pattern>] [--lang <l>] [--limit <n>] [--cursor <path>]';
+      const globValue = optionalStringFlag(flags, 'glob', usage);
+      const language = optionalStringFlag(flags, 'lang', usage);
+      const limit = optionalPositiveIntFlag(flags, 'limit', usage);
+      const cursor = optionalStringFlag(flags, 'cursor', usage);
+      dispatch = {
+        toolName: 'list_files',
+        toolArgs: {
+          ...(globValue ? { glob: globValue } : {}),
+          ...(language ? { language } : {}),
+          ...(limit != null ? { limit } : {}),
+          ...(cursor ? { cursor } : {})
+        }
+      };
+      break;
+    }
+    case 'pack': {
+      const usage =
+        'codebase-context pack [--files <path[:start-end],...>] [--symbols <name,...>] ' +
+        '[--max-tokens <n>]';
+      const files = optionalStringFlag(flags, 'files', usage)
+        ?.split(',')
+        .map((ref) => ref.trim())
+        .filter(Boolean);
+      const symbols = optionalStringFlag(flags, 'symbols', usage)
+        ?.split(',')
+        .map((symbol) => symbol.trim())
+        .filter(Boolean);
+      const maxTokens = optionalPositiveIntFlag(flags, 'max-tokens', usage);
+      const references: PackContextToolArgs['references'] = [];
+      for (const ref of files ?? []) {
+        const match = ref.match(/^(.+?):(\d+)(?:-(\d+))?$/);
+        if (!match) {
+          references.push({ file: ref });
+          continue;
+        }
+        const startLine = Number(match[2]);
+        references.push({
+          file: match[1],
+          startLine,
+          endLine: match[3] ? Number(match[3]) : startLine
+        });
+      }
+      for (const symbol of symbols ?? []) references.push({ symbol });
+      if (references.length === 0) {
+        exitWithError(`Error: --files or --symbols is required\nUsage: ${usage}`);
+      }
+      dispatch = {
+        toolName: 'pack_context',
+        toolArgs: { references, ...(maxTokens != null ? { maxTokens } : {}) }
+      };
+      break;
+    }
+    case 'related': {
+      const usage = 'codebase-context related --file <path> [--limit <n>]';
+      const file = requireStringFlag(flags, 'file', usage);
+      const limit = optionalPositiveIntFlag(flags, 'limit', usage);
+      dispatch = {
+        toolName: 'related_files',
+        toolArgs: { path: file, ...(limit != null ? { limit } : {}) }
+      };
+      break;
+    }
+    case 'resolve': {
+      const usage = 'codebase-context resolve --path <qualified> [--file <path>]';
+      const qualifiedPath = requireStringFlag(flags, 'path', usage);
+      const file = optionalStringFlag(flags, 'file', usage);
+      dispatch = {
+        toolName: 'resolve_path',
+        toolArgs: { path: qualifiedPath, ...(file ? { file } : {}) }
+      };
+      break;
+    }
     default: {
       console.error(`Unknown command: ${command}`);
       console.error('');
@@ -25,3 +25,12 @@ export const INDEXING_STATS_FILENAME = 'indexing-stats.json' as const;
 export const VECTOR_DB_DIRNAME = 'index' as const;
 export const MANIFEST_FILENAME = 'manifest.json' as const;
 export const RELATIONSHIPS_FILENAME = 'relationships.json' as const;
+
+/** Default location (under `.codebase-context/`) of the on-disk symbol cache. */
+export const SYMBOL_CACHE_DIRNAME = 'cache' as const;
+export const SYMBOL_CACHE_FILENAME = 'symbols.json' as const;
+
+/** Default directory (under `.codebase-context/`) of user tree-sitter queries, `<language>.scm`. */
+export const CUSTOM_QUERIES_DIRNAME = 'queries' as const;
+export const CUSTOM_QUERY_KINDS_FILENAME = 'kinds.json' as const;
+export const EMBEDDING_CACHE_FILENAME = 'embeddings.json' as const;
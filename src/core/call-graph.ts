@@ -0,0 +1,182 @@
+/**
+ * Name-based call graph: for every function or method, the identifiers it calls.
+ * Built during indexing from Tree-sitter call expressions and stored in the relationships
+ * sidecar. Callees are matched by name only, so edges are candidates, not resolved bindings.
+ */
+
+import { promises as fs } from 'fs';
+import path from 'path';
+import { CODEBASE_CONTEXT_DIRNAME, RELATIONSHIPS_FILENAME } from '../constants/codebase-context.js';
+import {
+  extractTreeSitterSymbols,
+  supportsTreeSitter,
+  type CallSite,
+  type TreeSitterSymbol
+} from '../utils/tree-sitter.js';
+
+export interface CallEdge {
+  /** Function or method containing the call */
+  caller: string;
+  /** Called name */
+  callee: string;
+  /** File containing the call, relative to the project root */
+  file: string;
+  /** Line of the call expression */
+  line: number;
+}
+
+export type CallGraphDirection = 'callers' | 'callees';
+
+export const MAX_CALL_GRAPH_DEPTH = 5;
+export const DEFAULT_CALL_GRAPH_EDGE_LIMIT = 50;
+
+const CALLER_KINDS = new Set(['function', 'method']);
+
+/** Attribute each call site to the innermost function or method whose span contains it. */
+export function buildFileCallEdges(
+  file: string,
+  symbols: TreeSitterSymbol[],
+  calls: CallSite[]
+): CallEdge[] {
+  const callers = symbols.filter((symbol) => CALLER_KINDS.has(symbol.kind));
+  const edges: CallEdge[] = [];
+
+  for (const call of calls) {
+    let innermost: TreeSitterSymbol | null = null;
+    for (const symbol of callers) {
+      if (call.startIndex < symbol.startIndex || call.startIndex >= symbol.endIndex) continue;
+      const span = symbol.endIndex - symbol.startIndex;
+      if (!innermost || span < innermost.endIndex - innermost.startIndex) {
+        innermost = symbol;
+      }
+    }
+    // Top-level calls have no caller function to attribute them to.
+    if (!innermost) continue;
+    edges.push({ caller: innermost.name, callee: call.callee, file, line: call.line });
+  }
+
+  return edges;
+}
+
+/**
+ * Call edges of one file, or an empty list when its language has no Tree-sitter grammar.
+ * Symbols and call sites come from the same error-tolerant parse and are cached together, so
+ * this does not parse a file the analyzer already extracted.
+ */
+export async function collectFileCallEdges(
+  relativePath: string,
+  content: string,
+  language: string
+): Promise<CallEdge[]> {
+  if (!supportsTreeSitter(language)) return [];
+  const extraction = await extractTreeSitterSymbols(content, language, { withSites: true });
+  if (!extraction?.callSites) return [];
+  return buildFileCallEdges(relativePath, extraction.symbols, extraction.callSites);
+}
+
+export interface CallGraphTraversal {
+  edges: CallEdge[];
+  /** True when the edge limit cut the traversal short */
+  truncated: boolean;
+}
+
+function edgeKey(edge: CallEdge): string {
+  return `${edge.caller}\0${edge.callee}\0${edge.file}\0${edge.line}`;
+}
+
+export class CallGraph {
+  readonly edges: CallEdge[];
+  private readonly byCaller = new Map<string, CallEdge[]>();
+  private readonly byCallee = new Map<string, CallEdge[]>();
+
+  constructor(edges: CallEdge[]) {
+    this.edges = edges;
+    for (const edge of edges) {
+      if (!this.byCaller.has(edge.caller)) this.byCaller.set(edge.caller, []);
+      this.byCaller.get(edge.caller)!.push(edge);
+      if (!this.byCallee.has(edge.callee)) this.byCallee.set(edge.callee, []);
+      this.byCallee.get(edge.callee)!.push(edge);
+    }
+  }
+
+  /**
+   * Read the call graph from the relationships sidecar.
+   * Returns null when the index predates call graphs and needs a re-index.
+   */
+  static async load(rootPath: string): Promise<CallGraph | null> {
+    const relationshipsPath = path.join(rootPath, CODEBASE_CONTEXT_DIRNAME, RELATIONSHIPS_FILENAME);
+    let parsed: unknown;
+    try {
+      parsed = JSON.parse(await fs.readFile(relationshipsPath, 'utf-8'));
+    } catch {
+      return null;
+    }
+
+    const callGraph =
+      typeof parsed === 'object' && parsed !== null
+        ? (parsed as { callGraph?: { edges?: unknown } }).callGraph
+        : undefined;
+    if (!callGraph || !Array.isArray(callGraph.edges)) return null;
+
+    const edges = callGraph.edges.filter(
+      (edge): edge is CallEdge =>
+        typeof edge === 'object' &&
+        edge !== null &&
+        typeof (edge as CallEdge).caller === 'string' &&
+        typeof (edge as CallEdge).callee === 'string' &&
+        typeof (edge as CallEdge).file === 'string' &&
+        typeof (edge as CallEdge).line === 'number'
+    );
+    return new CallGraph(edges);
+  }
+
+  /** Calls to `symbol`, each naming the function that makes it. */
+  callersOf(symbol: string): CallEdge[] {
+    return this.byCallee.get(symbol) ?? [];
+  }
+
+  /** Calls made from inside functions named `symbol`. */
+  calleesOf(symbol: string): CallEdge[] {
+    return this.byCaller.get(symbol) ?? [];
+  }
+
+  /**
+   * Breadth-first walk up to `depth` hops, returned as a flat, de-duplicated edge list.
+   * Each name is expanded once, so cycles and shared callees never multiply the output.
+   */
+  traverse(
+    symbol: string,
+    direction: CallGraphDirection,
+    depth = 1,
+    limit = DEFAULT_CALL_GRAPH_EDGE_LIMIT
+  ): CallGraphTraversal {
+    const maxDepth = Math.max(1, Math.min(MAX_CALL_GRAPH_DEPTH, Math.floor(depth)));
+    const edges: CallEdge[] = [];
+    const seenEdges = new Set<string>();
+    const expanded = new Set<string>([symbol]);
+    let frontier = [symbol];
+
+    for (let hop = 0; hop < maxDepth && frontier.length > 0; hop++) {
+      const next: string[] = [];
+      for (const name of frontier) {
+        const step = direction === 'callers' ? this.callersOf(name) : this.calleesOf(name);
+        for (const edge of step) {
+          const key = edgeKey(edge);
+          if (seenEdges.has(key)) continue;
+          if (edges.length >= limit) return { edges, truncated: true };
+          seenEdges.add(key);
+          edges.push(edge);
+
+          const neighbour = direction === 'callers' ? edge.caller : edge.callee;
+          if (!expanded.has(neighbour)) {
+            expanded.add(neighbour);
+            next.push(neighbour);
+          }
+        }
+      }
+      frontier = next;
+    }
+
+    return { edges, truncated: false };
+  }
+}
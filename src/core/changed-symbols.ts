@@ -0,0 +1,127 @@
+/**
+ * Symbols a git diff touched. Each changed file is parsed on the head side of the diff and a
+ * symbol counts as changed when a changed line falls inside it and outside its child symbols,
+ * so editing one method reports the method rather than every enclosing class as well.
+ */
+
+import path from 'path';
+import type { LineRange } from '../types/index.js';
+import { buildSymbolTree, type SymbolNode } from '../utils/ast-chunker.js';
+import type { ChangedFileStatus, GitDiff } from '../utils/git-diff.js';
+import { readDiffHeadFile } from '../utils/git-diff.js';
+import { detectLanguage } from '../utils/language-detection.js';
+import { normalizeSourceText } from '../utils/text-decoding.js';
+import { extractTreeSitterSymbols, supportsTreeSitter } from '../utils/tree-sitter.js';
+
+export interface ChangedSymbol {
+  file: string;
+  name: string;
+  kind: string;
+  /** Enclosing symbol, or the qualifier (e.g. a Go receiver) when nesting does not show one */
+  parent?: string;
+  startLine: number;
+  endLine: number;
+  /** Changed lines inside the symbol, children included */
+  changedLines: number;
+}
+
+export interface ChangedFileSummary {
+  file: string;
+  status: ChangedFileStatus;
+  previousFile?: string;
+  changedLines: number;
+}
+
+export interface ChangedSymbolsResult {
+  files: ChangedFileSummary[];
+  symbols: ChangedSymbol[];
+}
+
+/** Changed line ranges keyed by file, as `SearchFilters.lineRanges` expects them. */
+export function diffLineRanges(diff: GitDiff): Record<string, LineRange[]> {
+  const lineRanges: Record<string, LineRange[]> = {};
+  for (const file of diff.files) {
+    if (file.ranges.length > 0) lineRanges[file.file] = file.ranges;
+  }
+  return lineRanges;
+}
+
+function overlap(ranges: LineRange[], startLine: number, endLine: number): number {
+  let lines = 0;
+  for (const range of ranges) {
+    const from = Math.max(startLine, range.startLine);
+    const to = Math.min(endLine, range.endLine);
+    lines += Math.max(0, to - from + 1);
+  }
+  return lines;
+}
+
+function collectChanged(
+  nodes: SymbolNode[],
+  file: string,
+  ranges: LineRange[],
+  parent: string | undefined,
+  into: ChangedSymbol[]
+): void {
+  for (const { symbol, children } of nodes) {
+    const changedLines = overlap(ranges, symbol.startLine, symbol.endLine);
+    if (changedLines === 0) continue;
+
+    const inChildren = children.reduce(
+      (sum, child) => sum + overlap(ranges, child.symbol.startLine, child.symbol.endLine),
+      0
+    );
+    if (changedLines > inChildren) {
+      into.push({
+        file,
+        name: symbol.name,
+        kind: symbol.kind,
+        parent: parent ?? symbol.qualifier,
+        startLine: symbol.startLine,
+        endLine: symbol.endLine,
+        changedLines
+      });
+    }
+    collectChanged(children, file, ranges, symbol.name, into);
+  }
+}
+
+/**
+ * Resolve `diff` to changed symbols. Deleted files and files without a tree-sitter grammar are
+ * listed in `files` but contribute no symbols. `kinds`, when given, keeps only those kinds.
+ */
+export async function findChangedSymbols(
+  rootPath: string,
+  diff: GitDiff,
+  options: { kinds?: string[] } = {}
+): Promise<ChangedSymbolsResult> {
+  const kinds = options.kinds && options.kinds.length > 0 ? new Set(options.kinds) : null;
+  const files: ChangedFileSummary[] = [];
+  const symbols: ChangedSymbol[] = [];
+
+  for (const changed of diff.files) {
+    files.push({
+      file: changed.file,
+      status: changed.status,
+      ...(changed.previousFile ? { previousFile: changed.previousFile } : {}),
+      changedLines: overlap(changed.ranges, 1, Number.MAX_SAFE_INTEGER)
+    });
+    if (changed.ranges.length === 0) continue;
+
+    const content = await readDiffHeadFile(rootPath, changed.file, diff.head);
+    if (content === null) continue;
+    const normalized = normalizeSourceText(content);
+    const language = detectLanguage(path.resolve(rootPath, changed.file), normalized);
+    if (!supportsTreeSitter(language)) continue;
+
+    const extraction = await extractTreeSitterSymbols(normalized, language);
+    if (!extraction) continue;
+
+    const fileSymbols: ChangedSymbol[] = [];
+    const tree = buildSymbolTree(extraction.symbols);
+    collectChanged(tree, changed.file, changed.ranges, undefined, fileSymbols);
+    symbols.push(...(kinds ? fileSymbols.filter((symbol) => kinds.has(symbol.kind)) : fileSymbols));
+  }
+
+  return { files, symbols };
+}
@@ -0,0 +1,195 @@
+/**
+ * Per-symbol chunks for external embedding pipelines.
+ * Each chunk is one symbol's source text plus its location and a content-derived id, so
+ * consumers can upsert into their own vector store and skip chunks whose id they already hold.
+ */
+
+import { createHash } from 'crypto';
+import path from 'path';
+import { CODEBASE_CONTEXT_DIRNAME, MANIFEST_FILENAME } from '../constants/codebase-context.js';
+import { readManifest } from './manifest.js';
+import { buildSymbolTree, type SymbolNode } from '../utils/ast-chunker.js';
+import { detectLanguage } from '../utils/language-detection.js';
+import { readSourceText } from '../utils/text-decoding.js';
+import {
+  extractTreeSitterSymbols,
+  findStatementStartLines,
+  supportsTreeSitter
+} from '../utils/tree-sitter.js';
+
+export interface SemanticChunk {
+  /** Stable id: a hash of file, line range and text */
+  id: string;
+  /** File path relative to the project root */
+  file: string;
+  startLine: number;
+  endLine: number;
+  name: string;
+  kind: string;
+  /** Name of the enclosing symbol (class, impl, module), if any */
+  parent?: string;
+  language: string;
+  /** 1-based position and total count when an oversized symbol was split */
+  part?: number;
+  parts?: number;
+  text: string;
+}
+
+export interface ChunkExportOptions {
+  /** Symbols longer than this many lines are split at statement boundaries */
+  maxLines?: number;
+  /** Lines repeated at the start of each split part from the end of the previous one */
+  overlapLines?: number;
+}
+
+export const DEFAULT_EXPORT_MAX_LINES = 120;
+
+export function semanticChunkId(
+  file: string,
+  startLine: number,
+  endLine: number,
+  text: string
+): string {
+  return createHash('sha256')
+    .update(`${file}\0${startLine}\0${endLine}\0`)
+    .update(text)
+    .digest('hex')
+    .slice(0, 16);
+}
+
+/** Files recorded in the index manifest, sorted; null when the project has not been indexed. */
+export async function readIndexedFiles(rootPath: string): Promise<string[] | null> {
+  const manifest = await readManifest(
+    path.join(rootPath, CODEBASE_CONTEXT_DIRNAME, MANIFEST_FILENAME)
+  );
+  return manifest ? Object.keys(manifest.files).sort() : null;
+}
+
+/**
+ * Cut `[startLine, endLine]` into ranges of at most `maxLines`, ending each range just before a
+ * statement boundary when one is in reach and at the line limit otherwise.
+ */
+export function splitLineRange(
+  startLine: number,
+  endLine: number,
+  boundaries: number[],
+  maxLines: number,
+  overlapLines = 0
+): Array<[number, number]> {
+  const overlap = Math.max(0, Math.min(overlapLines, maxLines - 1));
+  const ranges: Array<[number, number]> = [];
+  let partStart = startLine;
+
+  while (endLine - partStart + 1 > maxLines) {
+    const limit = partStart + maxLines;
+    // The next part must start after this one, even once the overlap is taken back.
+    let cut = limit;
+    for (const boundary of boundaries) {
+      if (boundary > partStart + overlap && boundary <= limit) cut = boundary;
+    }
+    ranges.push([partStart, cut - 1]);
+    partStart = cut - overlap;
+  }
+
+  ranges.push([partStart, endLine]);
+  return ranges;
+}
+
+interface SymbolSpan {
+  node: SymbolNode;
+  parent?: string;
+  startLine: number;
+  endLine: number;
+}
+
+/**
+ * Leaf symbols cover their full range. Containers contribute only their header, from the
+ * declaration down to the first child, so no line is exported twice.
+ */
+function collectSymbolSpans(nodes: SymbolNode[], parent?: string): SymbolSpan[] {
+  const spans: SymbolSpan[] = [];
+  for (const node of [...nodes].sort((a, b) => a.symbol.startLine - b.symbol.startLine)) {
+    const { symbol, children } = node;
+    if (children.length === 0) {
+      spans.push({ node, parent, startLine: symbol.startLine, endLine: symbol.endLine });
+      continue;
+    }
+
+    const firstChildLine = Math.min(...children.map((child) => child.symbol.startLine));
+    if (firstChildLine > symbol.startLine) {
+      spans.push({ node, parent, startLine: symbol.startLine, endLine: firstChildLine - 1 });
+    }
+    spans.push(...collectSymbolSpans(children, symbol.name));
+  }
+  return spans;
+}
+
+async function* exportFileChunks(
+  rootPath: string,
+  file: string,
+  maxLines: number,
+  overlapLines: number
+): AsyncGenerator<SemanticChunk> {
+  let content: string;
+  try {
+    content = await readSourceText(path.join(rootPath, file));
+  } catch {
+    return;
+  }
+
+  const language = detectLanguage(path.resolve(rootPath, file), content);
+  if (!supportsTreeSitter(language)) return;
+
+  const extraction = await extractTreeSitterSymbols(content, language);
+  if (!extraction || extraction.symbols.length === 0) return;
+
+  const lines = content.split('\n');
+  const spans = collectSymbolSpans(buildSymbolTree(extraction.symbols));
+  // Parsed for statements only when some symbol actually needs splitting.
+  let boundaries: number[] | null = null;
+
+  for (const span of spans) {
+    let ranges: Array<[number, number]> = [[span.startLine, span.endLine]];
+    if (span.endLine - span.startLine + 1 > maxLines) {
+      boundaries ??= (await findStatementStartLines(content, language)) ?? [];
+      ranges = splitLineRange(span.startLine, span.endLine, boundaries, maxLines, overlapLines);
+    }
+
+    for (let i = 0; i < ranges.length; i++) {
+      const [startLine, endLine] = ranges[i];
+      const text = lines.slice(startLine - 1, endLine).join('\n');
+      if (!text.trim()) continue;
+
+      const chunk: SemanticChunk = {
+        id: semanticChunkId(file, startLine, endLine, text),
+        file,
+        startLine,
+        endLine,
+        name: span.node.symbol.name,
+        kind: span.node.symbol.kind,
+        ...(span.parent ? { parent: span.parent } : {}),
+        language,
+        ...(ranges.length > 1 ? { part: i + 1, parts: ranges.length } : {}),
+        text
+      };
+      yield chunk;
+    }
+  }
+}
+
+/**
+ * Yield chunks file by file, so a whole repository can be exported without holding every chunk
+ * in memory. Files without a Tree-sitter grammar, or that fail to parse, yield nothing.
+ */
+export async function* exportSemanticChunks(
+  rootPath: string,
+  files: string[],
+  options: ChunkExportOptions = {}
+): AsyncGenerator<SemanticChunk> {
+  const maxLines = Math.max(1, Math.floor(options.maxLines ?? DEFAULT_EXPORT_MAX_LINES));
+  const overlapLines = Math.max(0, Math.floor(options.overlapLines ?? 0));
+
+  for (const file of files) {
+    yield* exportFileChunks(rootPath, file.replace(/\\/g, '/'), maxLines, overlapLines);
+  }
+}
@@ -0,0 +1,117 @@
+/**
+ * Numbered source window around one line, grep `-B`/`-A` style, clamped to the file. Optionally
+ * widened to the innermost symbol containing the line so callers get a whole function.
+ */
+
+import path from 'path';
+import { detectLanguage } from '../utils/language-detection.js';
+import { readSourceText } from '../utils/text-decoding.js';
+import { extractTreeSitterSymbols } from '../utils/tree-sitter.js';
+import { relativeInside } from '../utils/symlinks.js';
+
+export const DEFAULT_CONTEXT_AROUND_LINES = 5;
+export const MAX_CONTEXT_AROUND_LINES = 200;
+
+export interface ContextAroundOptions {
+  /** Lines before `line` (default 5) */
+  before?: number;
+  /** Lines after `line` (default 5) */
+  after?: number;
+  /** Widen the window to cover the innermost symbol containing `line` */
+  snapToSymbol?: boolean;
+}
+
+export interface ContextAroundResult {
+  status: 'success';
+  file: string;
+  line: number;
+  /** First and last line returned, after clamping and snapping */
+  startLine: number;
+  endLine: number;
+  totalLines: number;
+  /** Symbol the window was widened to, when snapping found one */
+  symbol?: { name: string; kind: string; startLine: number; endLine: number };
+  /** The window hit MAX_CONTEXT_AROUND_LINES and stops early */
+  truncated: boolean;
+  lines: Array<{ line: number; text: string }>;
+}
+
+function lineCount(value: number | undefined): number {
+  return value !== undefined && Number.isFinite(value) && value >= 0
+    ? Math.floor(value)
+    : DEFAULT_CONTEXT_AROUND_LINES;
+}
+
+export async function getContextAround(
+  rootPath: string,
+  file: string,
+  line: number,
+  options: ContextAroundOptions = {}
+): Promise<ContextAroundResult | { status: 'error'; message: string }> {
+  const resolvedRoot = path.resolve(rootPath);
+  const absPath = path.resolve(resolvedRoot, file);
+  const relPath = relativeInside(resolvedRoot, absPath);
+  if (!relPath) {
+    return { status: 'error', message: `File is outside the project root: ${file}` };
+  }
+
+  let content: string;
+  try {
+    content = await readSourceText(absPath);
+  } catch {
+    return { status: 'error', message: `File not found: ${file}` };
+  }
+
+  const lines = content.split('\n');
+  // A trailing newline does not start another line
+  if (lines.length > 1 && lines[lines.length - 1] === '') lines.pop();
+  const totalLines = lines.length;
+  if (!Number.isInteger(line) || line < 1 || line > totalLines) {
+    return {
+      status: 'error',
+      message: `Line ${line} is outside ${file}, which has ${totalLines} lines.`
+    };
+  }
+
+  let startLine = Math.max(1, line - lineCount(options.before));
+  let endLine = Math.min(totalLines, line + lineCount(options.after));
+
+  let symbol: ContextAroundResult['symbol'];
+  if (options.snapToSymbol) {
+    const extraction = await extractTreeSitterSymbols(content, detectLanguage(absPath, content));
+    const enclosing = extraction?.symbols
+      .filter((s) => s.startLine <= line && s.endLine >= line)
+      .sort((a, b) => a.endLine - a.startLine - (b.endLine - b.startLine))[0];
+    if (enclosing) {
+      symbol = {
+        name: enclosing.name,
+        kind: enclosing.kind,
+        startLine: enclosing.startLine,
+        endLine: enclosing.endLine
+      };
+      startLine = Math.min(startLine, enclosing.startLine);
+      endLine = Math.max(endLine, Math.min(totalLines, enclosing.endLine));
+    }
+  }
+
+  const truncated = endLine - startLine + 1 > MAX_CONTEXT_AROUND_LINES;
+  if (truncated) {
+    // Keep the requested line in view: trim what lies past it first, then what lies before it
+    endLine = Math.max(line, startLine + MAX_CONTEXT_AROUND_LINES - 1);
+    startLine = Math.max(startLine, endLine - MAX_CONTEXT_AROUND_LINES + 1);
+  }
+
+  return {
+    status: 'success',
+    file: relPath.replace(/\\/g, '/'),
+    line,
+    startLine,
+    endLine,
+    totalLines,
+    ...(symbol ? { symbol } : {}),
+    truncated,
+    lines: lines
+      .slice(startLine - 1, endLine)
+      .map((text, index) => ({ line: startLine + index, text }))
+  };
+}
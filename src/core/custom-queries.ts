@@ -0,0 +1,161 @@
+/**
+ * User tree-sitter queries that add symbols on top of the built-in extraction.
+ *
+ * The queries directory (`.codebase-context/queries/`, or `CODEBASE_CONTEXT_QUERIES_DIR`) holds
+ * one `<language>.scm` per language. Every capture with a kind becomes a symbol: captures named
+ * after a kind (`@function`, `@definition.function`) map to it directly, and `kinds.json` maps
+ * any other name, e.g. `{ "handler": "function" }`. An `@name` capture in the same match names
+ * the symbol; other captures (predicate helpers) are ignored. Files that fail to load are
+ * reported and skipped, so a bad query never stops the server.
+ */
+
+import { createHash } from 'crypto';
+import { promises as fs } from 'fs';
+import path from 'path';
+import {
+  CODEBASE_CONTEXT_DIRNAME,
+  CUSTOM_QUERIES_DIRNAME,
+  CUSTOM_QUERY_KINDS_FILENAME
+} from '../constants/codebase-context.js';
+import {
+  TreeSitterQueryError,
+  compileTreeSitterQuery,
+  isSymbolKind,
+  setCustomSymbolQueries,
+  type CustomSymbolQuery,
+  type SymbolKind
+} from '../utils/tree-sitter.js';
+
+export interface CustomQueryLoadError {
+  /** File name inside the queries directory */
+  file: string;
+  message: string;
+}
+
+export interface CustomQueryLoadResult {
+  queries: CustomSymbolQuery[];
+  errors: CustomQueryLoadError[];
+}
+
+export function describeCustomQueryError(error: CustomQueryLoadError): string {
+  return `Custom query ${error.file} skipped: ${error.message}`;
+}
+
+export function customQueriesDir(rootPath: string): string {
+  const override = process.env.CODEBASE_CONTEXT_QUERIES_DIR?.trim();
+  if (override) return path.resolve(rootPath, override);
+  return path.join(rootPath, CODEBASE_CONTEXT_DIRNAME, CUSTOM_QUERIES_DIRNAME);
+}
+
+async function readKindMapping(
+  dir: string,
+  errors: CustomQueryLoadError[]
+): Promise<Record<string, SymbolKind>> {
+  let raw: string;
+  try {
+    raw = await fs.readFile(path.join(dir, CUSTOM_QUERY_KINDS_FILENAME), 'utf-8');
+  } catch {
+    return {};
+  }
+
+  const file = CUSTOM_QUERY_KINDS_FILENAME;
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(raw);
+  } catch (error) {
+    errors.push({ file, message: `Invalid JSON: ${(error as Error).message}` });
+    return {};
+  }
+  if (!parsed || typeof parsed !== 'object' || Array.isArray(parsed)) {
+    errors.push({ file, message: 'Expected an object mapping capture names to symbol kinds.' });
+    return {};
+  }
+
+  const kinds: Record<string, SymbolKind> = {};
+  for (const [capture, kind] of Object.entries(parsed)) {
+    if (typeof kind === 'string' && isSymbolKind(kind)) {
+      kinds[capture.replace(/^@/, '')] = kind;
+    } else {
+      errors.push({ file, message: `Capture '${capture}' maps to unknown kind '${kind}'.` });
+    }
+  }
+  return kinds;
+}
+
+function captureKind(capture: string, mapping: Record<string, SymbolKind>): SymbolKind | null {
+  const mapped = mapping[capture];
+  if (mapped) return mapped;
+  const bare = capture.replace(/^definition\./, '');
+  return isSymbolKind(bare) ? bare : null;
+}
+
+/** Compile every query file in `dir`; a missing directory yields no queries and no errors. */
+export async function loadCustomSymbolQueries(dir: string): Promise<CustomQueryLoadResult> {
+  let entries: string[];
+  try {
+    entries = (await fs.readdir(dir)).sort();
+  } catch {
+    return { queries: [], errors: [] };
+  }
+
+  const errors: CustomQueryLoadError[] = [];
+  const mapping = await readKindMapping(dir, errors);
+  const queries: CustomSymbolQuery[] = [];
+
+  for (const file of entries.filter((entry) => entry.endsWith('.scm'))) {
+    const language = path.basename(file, '.scm').toLowerCase();
+    const source = await fs.readFile(path.join(dir, file), 'utf-8');
+    let compiled: Awaited<ReturnType<typeof compileTreeSitterQuery>>;
+    try {
+      compiled = await compileTreeSitterQuery(language, source);
+    } catch (error) {
+      const offset = error instanceof TreeSitterQueryError ? error.offset : undefined;
+      errors.push({
+        file,
+        message:
+          (error instanceof Error ? error.message : String(error)) +
+          (offset !== undefined ? ` (at offset ${offset})` : '')
+      });
+      continue;
+    }
+
+    const kinds: Record<string, SymbolKind> = {};
+    for (const capture of compiled.query.captureNames) {
+      const kind = captureKind(capture, mapping);
+      if (kind) kinds[capture] = kind;
+    }
+    if (Object.keys(kinds).length === 0) {
+      compiled.dispose();
+      errors.push({
+        file,
+        message: `No capture maps to a symbol kind (see ${CUSTOM_QUERY_KINDS_FILENAME}).`
+      });
+      continue;
+    }
+
+    const fingerprint = createHash('sha256')
+      .update(source)
+      .update('\0')
+      .update(JSON.stringify(kinds))
+      .digest('hex');
+    queries.push({ source: file, compiled, kinds, fingerprint });
+  }
+
+  return { queries, errors };
+}
+
+let installedQueries: CustomSymbolQuery[] = [];
+
+/**
+ * Load the project's custom queries and merge them into symbol extraction, replacing any loaded
+ * before. Returns the files that failed to load.
+ */
+export async function installCustomSymbolQueries(
+  rootPath: string
+): Promise<CustomQueryLoadError[]> {
+  const { queries, errors } = await loadCustomSymbolQueries(customQueriesDir(rootPath));
+  setCustomSymbolQueries(queries);
+  for (const query of installedQueries) query.compiled.dispose();
+  installedQueries = queries;
+  return errors;
+}
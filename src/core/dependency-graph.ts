@@ -0,0 +1,376 @@
+/**
+ * File-level dependency graph: which workspace files each file imports.
+ * Built during indexing from Tree-sitter import/use statements (TS/JS `import`, Python
+ * `import`/`from`, Rust `use` and `mod`) and stored in the relationships sidecar. Imports that
+ * do not resolve to a workspace file, such as third-party packages and crates, are kept as
+ * unresolved module names.
+ */
+
+import { promises as fs } from 'fs';
+import path from 'path';
+import { CODEBASE_CONTEXT_DIRNAME, RELATIONSHIPS_FILENAME } from '../constants/codebase-context.js';
+import {
+  extractTreeSitterSymbols,
+  supportsTreeSitter,
+  type ImportSite
+} from '../utils/tree-sitter.js';
+
+export interface DependencyEdge {
+  /** Importing file, relative to the project root */
+  from: string;
+  /** Imported file, relative to the project root */
+  to: string;
+  /** Line of the first import of `to` in `from` */
+  line: number;
+}
+
+export interface FileImports {
+  language: string;
+  sites: ImportSite[];
+}
+
+export type DependencyDirection = 'dependencies' | 'dependents' | 'both';
+
+export const MAX_DEPENDENCY_GRAPH_DEPTH = 5;
+export const DEFAULT_DEPENDENCY_EDGE_LIMIT = 100;
+
+const JS_TS_EXTENSIONS = ['.ts', '.tsx', '.mts', '.cts', '.js', '.jsx', '.mjs', '.cjs'];
+// ESM TypeScript imports the emitted name: `./a.js` is `./a.ts` on disk
+const JS_TO_TS_EXTENSIONS: Record<string, string[]> = {
+  '.js': ['.ts', '.tsx'],
+  '.jsx': ['.tsx'],
+  '.mjs': ['.mts'],
+  '.cjs': ['.cts']
+};
+const JS_TS_LANGUAGES = new Set(['typescript', 'typescriptreact', 'javascript', 'javascriptreact']);
+const RUST_CRATE_ROOTS = ['lib.rs', 'main.rs'];
+const PYTHON_SOURCE_ROOTS = ['', 'src'];
+
+/**
+ * Import sites of one file, or an empty list when its language has no Tree-sitter grammar.
+ * They come from the file's cached, error-tolerant symbol parse, like its call sites.
+ */
+export async function collectFileImports(
+  content: string,
+  language: string
+): Promise<ImportSite[]> {
+  if (!supportsTreeSitter(language)) return [];
+  const extraction = await extractTreeSitterSymbols(content, language, { withSites: true });
+  return extraction?.importSites ?? [];
+}
+
+function firstExisting(candidates: string[], files: Set<string>): string | null {
+  for (const candidate of candidates) {
+    const normalized = path.posix.normalize(candidate).replace(/^\.\//, '');
+    const outside = normalized === '..' || normalized.startsWith('../');
+    if (!outside && files.has(normalized)) return normalized;
+  }
+  return null;
+}
+
+function resolveJsTsImport(from: string, specifier: string, files: Set<string>): string | null {
+  if (!specifier.startsWith('.')) return null;
+  const base = path.posix.join(path.posix.dirname(from), specifier);
+  const ext = path.posix.extname(base);
+  const stem = base.slice(0, base.length - ext.length);
+  return firstExisting(
+    [
+      base,
+      ...(JS_TO_TS_EXTENSIONS[ext] ?? []).map((tsExt) => stem + tsExt),
+      ...JS_TS_EXTENSIONS.map((candidate) => base + candidate),
+      ...JS_TS_EXTENSIONS.map((candidate) => `${base}/index${candidate}`)
+    ],
+    files
+  );
+}
+
+function pythonModuleCandidates(modulePath: string): string[] {
+  return [`${modulePath}.py`, `${modulePath}.pyi`, `${modulePath}/__init__.py`];
+}
+
+function resolvePythonImport(from: string, site: ImportSite, files: Set<string>): string[] {
+  const dots = site.specifier.match(/^\.*/)?.[0].length ?? 0;
+  const moduleParts = site.specifier.slice(dots).split('.').filter(Boolean);
+
+  let bases: string[];
+  if (dots > 0) {
+    let dir = path.posix.dirname(from);
+    for (let i = 1; i < dots; i++) dir = path.posix.dirname(dir);
+    bases = [dir === '.' ? '' : dir];
+  } else {
+    bases = PYTHON_SOURCE_ROOTS;
+  }
+
+  for (const base of bases) {
+    const modulePath = path.posix.join(base, ...moduleParts);
+    const resolved = new Set<string>();
+    // `from pkg import mod` imports the submodule file when there is one
+    for (const name of site.names ?? []) {
+      const submodule = firstExisting(
+        pythonModuleCandidates(path.posix.join(modulePath, name)),
+        files
+      );
+      if (submodule) resolved.add(submodule);
+    }
+    const module =
+      moduleParts.length > 0 ? firstExisting(pythonModuleCandidates(modulePath), files) : null;
+    if (module) resolved.add(module);
+    if (resolved.size > 0) return Array.from(resolved);
+  }
+  return [];
+}
+
+/** Directory holding the crate root (`src/lib.rs`, `src/main.rs`) for a Rust file. */
+function rustCrateDir(from: string, files: Set<string>): string {
+  const fileDir = path.posix.dirname(from).replace(/^\.$/, '');
+  const segments = fileDir ? fileDir.split('/') : [];
+  for (let length = segments.length; length >= 0; length--) {
+    const dir = segments.slice(0, length).join('/');
+    if (RUST_CRATE_ROOTS.some((root) => files.has(path.posix.join(dir, root)))) return dir;
+  }
+  return fileDir;
+}
+
+/** Directory where the child modules of `from` live: `a/b.rs` -> `a/b`, `a/mod.rs` -> `a`. */
+function rustModuleDir(from: string): string {
+  const dir = path.posix.dirname(from).replace(/^\.$/, '');
+  const base = path.posix.basename(from);
+  if (base === 'mod.rs' || RUST_CRATE_ROOTS.includes(base)) return dir;
+  return path.posix.join(dir, base.slice(0, -'.rs'.length));
+}
+
+/** The file of the longest module prefix of `segments` under `dir`, trailing items ignored. */
+function resolveRustModulePath(dir: string, segments: string[], files: Set<string>): string | null {
+  for (let length = segments.length; length > 0; length--) {
+    const modulePath = path.posix.join(dir, ...segments.slice(0, length));
+    const found = firstExisting([`${modulePath}.rs`, `${modulePath}/mod.rs`], files);
+    if (found) return found;
+  }
+  return null;
+}
+
+function resolveRustImport(
+  from: string,
+  site: ImportSite,
+  files: Set<string>
+): { file: string | null; external: string | null } {
+  if (site.kind === 'module') {
+    const dir = rustModuleDir(from);
+    const file = firstExisting(
+      [
+        path.posix.join(dir, `${site.specifier}.rs`),
+        path.posix.join(dir, site.specifier, 'mod.rs')
+      ],
+      files
+    );
+    return { file, external: null };
+  }
+
+  const segments = site.specifier.split('::').filter((segment) => segment && segment !== '*');
+  const [head, ...rest] = segments;
+  if (!head) return { file: null, external: null };
+
+  if (head === 'crate') {
+    const crateDir = rustCrateDir(from, files);
+    const file =
+      resolveRustModulePath(crateDir, rest, files) ??
+      firstExisting(RUST_CRATE_ROOTS.map((root) => path.posix.join(crateDir, root)), files);
+    return { file: file === from ? null : file, external: null };
+  }
+
+  if (head === 'self' || head === 'super') {
+    let dir = rustModuleDir(from);
+    let remaining = rest;
+    if (head === 'super') {
+      dir = path.posix.dirname(dir).replace(/^\.$/, '');
+      while (remaining[0] === 'super') {
+        dir = path.posix.dirname(dir).replace(/^\.$/, '');
+        remaining = remaining.slice(1);
+      }
+    }
+    const file = resolveRustModulePath(dir, remaining, files);
+    if (file) return { file, external: null };
+    // `super::item` names something in the parent module file itself
+    const parent = firstExisting(
+      [`${dir}.rs`, `${dir}/mod.rs`, ...RUST_CRATE_ROOTS.map((root) => path.posix.join(dir, root))],
+      files
+    );
+    return { file: parent === from ? null : parent, external: null };
+  }
+
+  // 2018 paths: a child module in scope first, otherwise an external crate
+  const local = resolveRustModulePath(rustModuleDir(from), segments, files);
+  return local ? { file: local, external: null } : { file: null, external: head };
+}
+
+/**
+ * Resolve every file's import sites against the set of indexed files.
+ * Imports that match no file are reported per file as module names (the crate name for Rust).
+ */
+export function buildDependencyGraph(
+  importsByFile: Map<string, FileImports>,
+  indexedFiles: Iterable<string>
+): { edges: DependencyEdge[]; unresolved: Record<string, string[]> } {
+  const files = new Set(indexedFiles);
+  const edges: DependencyEdge[] = [];
+  const unresolved: Record<string, string[]> = {};
+
+  for (const [from, { language, sites }] of importsByFile) {
+    const targets = new Map<string, number>();
+    const external = new Set<string>();
+    const addTarget = (to: string | null, line: number) => {
+      if (to && to !== from && !targets.has(to)) targets.set(to, line);
+    };
+
+    for (const site of sites) {
+      if (JS_TS_LANGUAGES.has(language)) {
+        const to = resolveJsTsImport(from, site.specifier, files);
+        if (to) addTarget(to, site.line);
+        else if (!site.specifier.startsWith('.')) external.add(site.specifier);
+      } else if (language === 'python') {
+        const resolved = resolvePythonImport(from, site, files);
+        for (const to of resolved) addTarget(to, site.line);
+        if (resolved.length === 0 && !site.specifier.startsWith('.')) external.add(site.specifier);
+      } else if (language === 'rust') {
+        const resolved = resolveRustImport(from, site, files);
+        addTarget(resolved.file, site.line);
+        if (resolved.external) external.add(resolved.external);
+      }
+    }
+
+    for (const [to, line] of targets) edges.push({ from, to, line });
+    if (external.size > 0) unresolved[from] = Array.from(external).sort();
+  }
+
+  edges.sort((a, b) => a.from.localeCompare(b.from) || a.line - b.line);
+  return { edges, unresolved };
+}
+
+export interface DependencySubgraph {
+  /** Edges reached within `depth` hops, each listed once */
+  edges: DependencyEdge[];
+  /** Unresolved imports of every file whose dependencies were expanded */
+  unresolved: Record<string, string[]>;
+  truncated: boolean;
+}
+
+export class DependencyGraph {
+  readonly edges: DependencyEdge[];
+  readonly unresolved: Record<string, string[]>;
+  private readonly byFrom = new Map<string, DependencyEdge[]>();
+  private readonly byTo = new Map<string, DependencyEdge[]>();
+
+  constructor(edges: DependencyEdge[], unresolved: Record<string, string[]> = {}) {
+    this.edges = edges;
+    this.unresolved = unresolved;
+    for (const edge of edges) {
+      if (!this.byFrom.has(edge.from)) this.byFrom.set(edge.from, []);
+      this.byFrom.get(edge.from)!.push(edge);
+      if (!this.byTo.has(edge.to)) this.byTo.set(edge.to, []);
+      this.byTo.get(edge.to)!.push(edge);
+    }
+  }
+
+  /**
+   * Read the dependency graph from the relationships sidecar.
+   * Returns null when the index predates dependency graphs and needs a re-index.
+   */
+  static async load(rootPath: string): Promise<DependencyGraph | null> {
+    const relationshipsPath = path.join(rootPath, CODEBASE_CONTEXT_DIRNAME, RELATIONSHIPS_FILENAME);
+    let parsed: unknown;
+    try {
+      parsed = JSON.parse(await fs.readFile(relationshipsPath, 'utf-8'));
+    } catch {
+      return null;
+    }
+
+    const graph =
+      typeof parsed === 'object' && parsed !== null
+        ? (parsed as { dependencyGraph?: { edges?: unknown; unresolved?: unknown } })
+            .dependencyGraph
+        : undefined;
+    if (!graph || !Array.isArray(graph.edges)) return null;
+
+    const edges = graph.edges.filter(
+      (edge): edge is DependencyEdge =>
+        typeof edge === 'object' &&
+        edge !== null &&
+        typeof (edge as DependencyEdge).from === 'string' &&
+        typeof (edge as DependencyEdge).to === 'string' &&
+        typeof (edge as DependencyEdge).line === 'number'
+    );
+    const unresolved: Record<string, string[]> = {};
+    if (typeof graph.unresolved === 'object' && graph.unresolved !== null) {
+      for (const [file, modules] of Object.entries(graph.unresolved)) {
+        if (Array.isArray(modules)) {
+          unresolved[file] = modules.filter((name): name is string => typeof name === 'string');
+        }
+      }
+    }
+    return new DependencyGraph(edges, unresolved);
+  }
+
+  /** Files that `file` imports. */
+  dependenciesOf(file: string): DependencyEdge[] {
+    return this.byFrom.get(file) ?? [];
+  }
+
+  /** Files that import `file`. */
+  dependentsOf(file: string): DependencyEdge[] {
+    return this.byTo.get(file) ?? [];
+  }
+
+  /** True when the file appears in the graph at all, as importer, import or unresolved. */
+  has(file: string): boolean {
+    return this.byFrom.has(file) || this.byTo.has(file) || file in this.unresolved;
+  }
+
+  /**
+   * Breadth-first walk up to `depth` hops from `file`. Every file is expanded at most once per
+   * direction, so import cycles end the walk instead of looping.
+   */
+  subgraph(
+    file: string,
+    direction: DependencyDirection = 'both',
+    depth = 1,
+    limit = DEFAULT_DEPENDENCY_EDGE_LIMIT
+  ): DependencySubgraph {
+    const maxDepth = Math.max(1, Math.min(MAX_DEPENDENCY_GRAPH_DEPTH, Math.floor(depth)));
+    const edges: DependencyEdge[] = [];
+    const seenEdges = new Set<string>();
+    const unresolved: Record<string, string[]> = {};
+    const directions: Array<'dependencies' | 'dependents'> =
+      direction === 'both' ? ['dependencies', 'dependents'] : [direction];
+
+    for (const step of directions) {
+      const expanded = new Set<string>([file]);
+      let frontier = [file];
+      for (let hop = 0; hop < maxDepth && frontier.length > 0; hop++) {
+        const next: string[] = [];
+        for (const current of frontier) {
+          if (step === 'dependencies' && this.unresolved[current]?.length) {
+            unresolved[current] = this.unresolved[current];
+          }
+          const reached =
+            step === 'dependencies' ? this.dependenciesOf(current) : this.dependentsOf(current);
+          for (const edge of reached) {
+            const key = `${edge.from}\0${edge.to}`;
+            if (seenEdges.has(key)) continue;
+            if (edges.length >= limit) return { edges, unresolved, truncated: true };
+            seenEdges.add(key);
+            edges.push(edge);
+
+            const neighbour = step === 'dependencies' ? edge.to : edge.from;
+            if (!expanded.has(neighbour)) {
+              expanded.add(neighbour);
+              next.push(neighbour);
+            }
+          }
+        }
+        frontier = next;
+      }
+    }
+
+    return { edges, unresolved, truncated: false };
+  }
+}
@@ -0,0 +1,31 @@
+/**
+ * How much each result of `search_codebase`, `file_outline` and `get_symbol_context` carries.
+ * Every level fills the tool's one result type; lower levels just leave optional fields unset:
+ *   - names: what each result is and where — name or qualified path, kind, file and lines
+ *   - signatures: also each symbol's declaration up to its body
+ *   - full: also the source inline
+ * Without a level each tool keeps its existing output and flags.
+ */
+
+export const DETAIL_LEVELS = ['names', 'signatures', 'full'] as const;
+export type DetailLevel = (typeof DETAIL_LEVELS)[number];
+
+export function isDetailLevel(value: unknown): value is DetailLevel {
+  return typeof value === 'string' && (DETAIL_LEVELS as readonly string[]).includes(value);
+}
+
+/** Input schema shared by the tools that accept `detail`. */
+export const DETAIL_LEVEL_SCHEMA = {
+  type: 'string',
+  enum: [...DETAIL_LEVELS],
+  description:
+    'How much each result carries: "names" (names and locations), "signatures" (plus ' +
+    'declarations) or "full" (plus source). Omit for the default output.'
+} as const;
+
+export function invalidDetailMessage(value: unknown): string {
+  return (
+    `Invalid params: unknown detail ${JSON.stringify(value)}. ` +
+    `Allowed: ${DETAIL_LEVELS.join(', ')}.`
+  );
+}
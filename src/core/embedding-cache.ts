@@ -0,0 +1,108 @@
+/**
+ * On-disk cache of chunk embeddings, next to the symbol cache.
+ * Entries are keyed by a hash of the exact text sent to the provider, so a chunk whose content
+ * (and path prefix) is unchanged is never re-embedded, even when its file changed elsewhere or
+ * the index is rebuilt from scratch. Vectors are stored as base64 Float32 to keep the file small.
+ */
+
+import { createHash } from 'crypto';
+import { promises as fs } from 'fs';
+import path from 'path';
+import { EMBEDDING_CACHE_FILENAME } from '../constants/codebase-context.js';
+import type { EmbeddingProvider } from '../embeddings/index.js';
+
+export const EMBEDDING_CACHE_VERSION = 1;
+
+interface EmbeddingCacheFile {
+  version: number;
+  /** Provider, model and dimensions the vectors came from; any change discards the cache */
+  model: string;
+  entries: Record<string, string>;
+}
+
+export function embeddingModelKey(provider: EmbeddingProvider): string {
+  return `${provider.name}:${provider.modelName}:${provider.dimensions}`;
+}
+
+function textKey(text: string): string {
+  return createHash('sha256').update(text).digest('hex').slice(0, 32);
+}
+
+function encodeVector(vector: number[]): string {
+  return Buffer.from(new Float32Array(vector).buffer).toString('base64');
+}
+
+function decodeVector(encoded: string): number[] {
+  const bytes = Buffer.from(encoded, 'base64');
+  return Array.from(new Float32Array(bytes.buffer, bytes.byteOffset, bytes.byteLength / 4));
+}
+
+export class DiskEmbeddingCache {
+  readonly cachePath: string;
+  hits = 0;
+  misses = 0;
+
+  private readonly stored: Map<string, string>;
+  private readonly used = new Map<string, string>();
+
+  private constructor(
+    cachePath: string,
+    private readonly model: string,
+    stored: Map<string, string>
+  ) {
+    this.cachePath = cachePath;
+    this.stored = stored;
+  }
+
+  /** Load the cache in `cacheDir` for `model`; missing, corrupt or mismatched caches are empty. */
+  static async load(cacheDir: string, model: string): Promise<DiskEmbeddingCache> {
+    const cachePath = path.join(cacheDir, EMBEDDING_CACHE_FILENAME);
+    const stored = new Map<string, string>();
+
+    try {
+      const parsed = JSON.parse(
+        await fs.readFile(cachePath, 'utf-8')
+      ) as Partial<EmbeddingCacheFile>;
+      if (parsed.version === EMBEDDING_CACHE_VERSION && parsed.model === model && parsed.entries) {
+        for (const [key, vector] of Object.entries(parsed.entries)) {
+          if (typeof vector === 'string') stored.set(key, vector);
+        }
+      }
+    } catch {
+      // Cold cache
+    }
+
+    return new DiskEmbeddingCache(cachePath, model, stored);
+  }
+
+  get(text: string): number[] | undefined {
+    const key = textKey(text);
+    const encoded = this.used.get(key) ?? this.stored.get(key);
+    if (encoded) {
+      this.hits++;
+      this.used.set(key, encoded);
+      return decodeVector(encoded);
+    }
+    this.misses++;
+    return undefined;
+  }
+
+  set(text: string, vector: number[]): void {
+    this.used.set(textKey(text), encodeVector(vector));
+  }
+
+  /**
+   * Persist the cache. A full rebuild embeds every chunk, so `prune` keeps only the entries
+   * touched in this run; incremental runs only see changed chunks and keep the rest too.
+   */
+  async save(options: { prune: boolean }): Promise<void> {
+    const entries = options.prune ? this.used : new Map([...this.stored, ...this.used]);
+    const file: EmbeddingCacheFile = {
+      version: EMBEDDING_CACHE_VERSION,
+      model: this.model,
+      entries: Object.fromEntries(entries)
+    };
+    await fs.mkdir(path.dirname(this.cachePath), { recursive: true });
+    await fs.writeFile(this.cachePath, JSON.stringify(file));
+  }
+}
@@ -0,0 +1,161 @@
+/**
+ * Structural outline of a single file: its symbols and their nesting, without bodies.
+ * Derived from the Tree-sitter symbol tree so agents can decide what to read before reading it.
+ */
+
+import path from 'path';
+import { buildSymbolTree, type SymbolNode } from '../utils/ast-chunker.js';
+import type { SymbolIndex } from './symbol-index.js';
+import { detectLanguage } from '../utils/language-detection.js';
+import { readSourceText } from '../utils/text-decoding.js';
+import {
+  extractTreeSitterSymbols,
+  supportsTreeSitter,
+  type TreeSitterErrorRegion,
+  type TreeSitterSymbolExtraction
+} from '../utils/tree-sitter.js';
+import { relativeInside } from '../utils/symlinks.js';
+
+export interface OutlineNode {
+  name: string;
+  kind: string;
+  startLine: number;
+  endLine: number;
+  /** Receiver type of a Go method */
+  qualifier?: string;
+  /** Doc comment or docstring, when requested */
+  doc?: string;
+  /** Declaration without its body, when requested */
+  signature?: string;
+  /** Source of a symbol whose children are not listed, when requested */
+  source?: string;
+  children?: OutlineNode[];
+  /** Number of direct children omitted because of the depth limit */
+  hiddenChildren?: number;
+}
+
+export interface FileOutlineOptions {
+  /** Levels to return; 1 = top-level symbols only. Omit for the full tree. */
+  depth?: number;
+  /** Attach each symbol's doc comment or docstring */
+  includeDocs?: boolean;
+  /** Attach each symbol's signature */
+  signaturesOnly?: boolean;
+  /** Attach the source of every symbol listed without its children */
+  includeSource?: boolean;
+  /** Take the symbols from here when it has the file's current contents, instead of parsing */
+  symbolIndex?: SymbolIndex;
+}
+
+export interface FileOutlineResult {
+  status: 'success';
+  file: string;
+  language: string;
+  symbolCount: number;
+  outline: OutlineNode[];
+  /** Lines that failed to parse (in C/C++ usually macros); symbols broken by them are missing */
+  errorRegions?: TreeSitterErrorRegion[];
+}
+
+function toOutlineNodes(
+  nodes: SymbolNode[],
+  remainingDepth: number,
+  options: { includeDocs: boolean; signaturesOnly: boolean; includeSource: boolean }
+): OutlineNode[] {
+  return [...nodes]
+    .sort((a, b) => a.symbol.startLine - b.symbol.startLine)
+    .map((node) => {
+      const { children } = node;
+      const outlineNode: OutlineNode = {
+        name: node.symbol.name,
+        kind: node.symbol.kind,
+        startLine: node.symbol.startLine,
+        endLine: node.symbol.endLine
+      };
+      if (node.symbol.qualifier) {
+        outlineNode.qualifier = node.symbol.qualifier;
+      }
+      if (options.includeDocs && node.symbol.doc) {
+        outlineNode.doc = node.symbol.doc;
+      }
+      if (options.signaturesOnly) {
+        outlineNode.signature = node.symbol.signature ?? node.symbol.content.split('\n')[0].trim();
+      }
+      if (children.length > 0) {
+        if (remainingDepth > 1) {
+          outlineNode.children = toOutlineNodes(children, remainingDepth - 1, options);
+        } else {
+          outlineNode.hiddenChildren = children.length;
+        }
+      }
+      // A listed child carries its own source, so containers would only repeat it
+      if (options.includeSource && !outlineNode.children) {
+        outlineNode.source = node.symbol.content;
+      }
+      return outlineNode;
+    });
+}
+
+export async function getFileOutline(
+  rootPath: string,
+  file: string,
+  options: FileOutlineOptions = {}
+): Promise<FileOutlineResult | { status: 'error'; message: string }> {
+  const resolvedRoot = path.resolve(rootPath);
+  const absPath = path.resolve(resolvedRoot, file);
+  const relPath = relativeInside(resolvedRoot, absPath);
+  if (!relPath) {
+    return { status: 'error', message: `File is outside the project root: ${file}` };
+  }
+
+  const indexed = await options.symbolIndex?.readIndexed(absPath);
+  let language: string;
+  let extraction: Pick<TreeSitterSymbolExtraction, 'symbols' | 'errorRegions'> | null;
+  if (indexed) {
+    language = indexed.entry.language;
+    extraction = { symbols: indexed.symbols, errorRegions: indexed.entry.errorRegions };
+  } else {
+    let content: string;
+    try {
+      content = await readSourceText(absPath);
+    } catch {
+      return { status: 'error', message: `File not found: ${file}` };
+    }
+
+    language = detectLanguage(absPath, content);
+    if (!supportsTreeSitter(language)) {
+      return {
+        status: 'error',
+        message:
+          `No Tree-sitter grammar for '${language}'; ` +
+          'outlines are not available for this file.'
+      };
+    }
+    extraction = await extractTreeSitterSymbols(content, language);
+  }
+
+  if (!extraction) {
+    return {
+      status: 'error',
+      message: `Could not parse ${file}; the file may be too large or too slow to parse.`
+    };
+  }
+
+  const depth =
+    options.depth !== undefined && Number.isFinite(options.depth) && options.depth > 0
+      ? Math.floor(options.depth)
+      : Infinity;
+
+  return {
+    status: 'success',
+    file: relPath.replace(/\\/g, '/'),
+    language,
+    symbolCount: extraction.symbols.length,
+    outline: toOutlineNodes(buildSymbolTree(extraction.symbols), depth, {
+      includeDocs: options.includeDocs === true,
+      signaturesOnly: options.signaturesOnly === true,
+      includeSource: options.includeSource === true
+    }),
+    ...(extraction.errorRegions ? { errorRegions: extraction.errorRegions } : {})
+  };
+}
@@ -1,22 +1,31 @@
 import chokidar from 'chokidar';
 import path from 'path';
-import { getSupportedExtensions } from '../utils/language-detection.js';
+import { getSupportedExtensions, TEXT_ONLY_EXTENSIONS } from '../utils/language-detection.js';
+import { DIRECTORY_CONFIG_FILENAME } from '../utils/directory-config.js';
+
+/** Quiet time after the last change before `onChanged` runs, unless configured otherwise. */
+export const DEFAULT_DEBOUNCE_MS = 200;
 
 export interface FileWatcherOptions {
   rootPath: string;
-  /** ms after last change before triggering. Default: 2000 */
+  /** ms after last change before triggering. Default: `DEFAULT_DEBOUNCE_MS` (200) */
   debounceMs?: number;
   /** Called once chokidar finishes initial scan and starts emitting change events */
   onReady?: () => void;
-  /** Called once the debounce window expires after the last detected change */
-  onChanged: () => void;
+  /**
+   * Called once the debounce window expires after the last detected change, with the
+   * absolute paths of every tracked file that changed during the window. Files created and
+   * removed again within one window (temp files of atomic saves) are left out, and a window
+   * holding only those does not call it at all.
+   */
+  onChanged: (changedPaths: string[]) => void;
 }
 
 const TRACKED_EXTENSIONS = new Set(
-  getSupportedExtensions().map((extension) => extension.toLowerCase())
+  [...getSupportedExtensions(), ...TEXT_ONLY_EXTENSIONS].map((extension) => extension.toLowerCase())
 );
 
-const TRACKED_METADATA_FILES = new Set(['.gitignore']);
+const TRACKED_METADATA_FILES = new Set(['.gitignore', DIRECTORY_CONFIG_FILENAME]);
 
 function isTrackedSourcePath(filePath: string): boolean {
   const basename = path.basename(filePath).toLowerCase();
@@ -25,20 +34,44 @@ function isTrackedSourcePath(filePath: string): boolean {
   return extension.length > 0 && TRACKED_EXTENSIONS.has(extension);
 }
 
+type WatchEvent = 'add' | 'change' | 'unlink';
+
+/**
+ * The debounce to watch with: `CODEBASE_CONTEXT_DEBOUNCE_MS` when it is a non-negative integer,
+ * else `debounce_ms` from the root's `[watch]` config, else `DEFAULT_DEBOUNCE_MS`.
+ */
+export function resolveDebounceMs(
+  envValue: string | undefined = process.env.CODEBASE_CONTEXT_DEBOUNCE_MS,
+  configured?: number
+): number {
+  const fromEnv = Number.parseInt(envValue ?? '', 10);
+  if (Number.isFinite(fromEnv) && fromEnv >= 0) return fromEnv;
+  return configured ?? DEFAULT_DEBOUNCE_MS;
+}
+
 /**
  * Watch rootPath for source file changes and call onChanged (debounced).
  * Returns a stop() function that cancels the debounce timer and closes the watcher.
  */
 export function startFileWatcher(opts: FileWatcherOptions): () => void {
-  const { rootPath, debounceMs = 2000, onReady, onChanged } = opts;
+  const { rootPath, debounceMs = DEFAULT_DEBOUNCE_MS, onReady, onChanged } = opts;
   let debounceTimer: ReturnType<typeof setTimeout> | undefined;
+  // First and latest event per path in the current window
+  const pendingPaths = new Map<string, { first: WatchEvent; last: WatchEvent }>();
 
-  const trigger = (filePath: string) => {
+  const trigger = (event: WatchEvent, filePath: string) => {
     if (!isTrackedSourcePath(filePath)) return;
+    const absolutePath = path.resolve(rootPath, filePath);
+    const pending = pendingPaths.get(absolutePath);
+    pendingPaths.set(absolutePath, { first: pending?.first ?? event, last: event });
     if (debounceTimer !== undefined) clearTimeout(debounceTimer);
     debounceTimer = setTimeout(() => {
       debounceTimer = undefined;
-      onChanged();
+      const changedPaths = Array.from(pendingPaths)
+        .filter(([, { first, last }]) => !(first === 'add' && last === 'unlink'))
+        .map(([changedPath]) => changedPath);
+      pendingPaths.clear();
+      if (changedPaths.length > 0) onChanged(changedPaths);
     }, debounceMs);
   };
 
@@ -62,9 +95,9 @@ export function startFileWatcher(opts: FileWatcherOptions): () => void {
 
   watcher
     .on('ready', () => onReady?.())
-    .on('add', trigger)
-    .on('change', trigger)
-    .on('unlink', trigger)
+    .on('add', (filePath: string) => trigger('add', filePath))
+    .on('change', (filePath: string) => trigger('change', filePath))
+    .on('unlink', (filePath: string) => trigger('unlink', filePath))
     .on('error', (err: unknown) => console.error('[file-watcher] error:', err));
 
   return () => {
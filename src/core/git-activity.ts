@@ -0,0 +1,53 @@
+/**
+ * Search ranking signal from git history: files changed recently and often are more likely to
+ * be live code than files nobody has touched in years. Outside a git repository, or for files
+ * without history, the boost is 0 and ranking is unchanged.
+ */
+
+import type { FileChangeStats } from '../utils/git-dates.js';
+
+/** Largest boost a file can receive; search scores are multiplied by `1 + boost`. */
+export const MAX_GIT_ACTIVITY_BOOST = 0.12;
+
+/** Share of the boost that comes from recency; the rest comes from change frequency. */
+const RECENCY_SHARE = 2 / 3;
+const RECENCY_HALF_LIFE_DAYS = 90;
+const MS_PER_DAY = 1000 * 60 * 60 * 24;
+
+/**
+ * Normalize change stats into per-file boosts in [0, MAX_GIT_ACTIVITY_BOOST].
+ * Recency halves every 90 days, measured from the newest commit in the repository rather than
+ * from now, so an idle checkout ranks the same tomorrow as today. Frequency is log-scaled
+ * against the most-changed file so a few hot files do not flatten everyone else.
+ */
+export function computeGitActivityBoosts(
+  stats: Map<string, FileChangeStats>
+): Map<string, number> {
+  const boosts = new Map<string, number>();
+  if (stats.size === 0) return boosts;
+
+  let newestCommit = 0;
+  let maxCommitCount = 0;
+  for (const { lastCommit, commitCount } of stats.values()) {
+    newestCommit = Math.max(newestCommit, lastCommit.getTime());
+    maxCommitCount = Math.max(maxCommitCount, commitCount);
+  }
+
+  for (const [file, { lastCommit, commitCount }] of stats) {
+    const ageDays = Math.max(0, (newestCommit - lastCommit.getTime()) / MS_PER_DAY);
+    const recency = Math.pow(0.5, ageDays / RECENCY_HALF_LIFE_DAYS);
+    const frequency = maxCommitCount > 1 ? Math.log1p(commitCount) / Math.log1p(maxCommitCount) : 0;
+    boosts.set(
+      file,
+      MAX_GIT_ACTIVITY_BOOST * (RECENCY_SHARE * recency + (1 - RECENCY_SHARE) * frequency)
+    );
+  }
+
+  return boosts;
+}
+
+/** `CODEBASE_CONTEXT_GIT_BOOST=false` turns the boost off by default for reproducible ranking. */
+export function isGitActivityBoostDisabledByEnv(): boolean {
+  const normalized = process.env.CODEBASE_CONTEXT_GIT_BOOST?.trim().toLowerCase();
+  return normalized === '0' || normalized === 'false' || normalized === 'no';
+}
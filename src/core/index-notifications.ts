@@ -0,0 +1,71 @@
+/**
+ * Push side of watch mode. Each debounce window of the file watcher becomes one `IndexChange`
+ * (the files it touched plus the index version they produced), handed to every subscriber so
+ * clients can drop what they cached for those files instead of polling. With no subscribers
+ * nothing is built or sent.
+ */
+
+import path from 'path';
+
+export interface IndexChange {
+  /** Result-cache index version after the change; results fetched earlier are stale */
+  indexVersion: number;
+  /** Workspace root the files belong to */
+  root: string;
+  /** Changed, added or removed files relative to `root`, sorted */
+  files: string[];
+}
+
+export type IndexChangeListener = (change: IndexChange) => void;
+
+export function describeIndexChange(
+  rootPath: string,
+  changedPaths: string[],
+  indexVersion: number
+): IndexChange {
+  const root = path.resolve(rootPath);
+  const files = Array.from(
+    new Set(
+      changedPaths.map((changedPath) =>
+        path.relative(root, path.resolve(root, changedPath)).replace(/\\/g, '/')
+      )
+    )
+  ).sort();
+  return { indexVersion, root, files };
+}
+
+export class IndexChangeNotifier {
+  private readonly listeners = new Set<IndexChangeListener>();
+
+  get subscriberCount(): number {
+    return this.listeners.size;
+  }
+
+  /** Returns the matching unsubscribe; calling it more than once is harmless. */
+  subscribe(listener: IndexChangeListener): () => void {
+    this.listeners.add(listener);
+    return () => {
+      this.listeners.delete(listener);
+    };
+  }
+
+  /**
+   * Deliver one change to every subscriber. `build` only runs when someone is subscribed, so an
+   * unwatched server pays nothing per change. Returns whether anything was delivered.
+   */
+  publish(build: () => IndexChange): boolean {
+    if (this.listeners.size === 0) return false;
+    const change = build();
+    for (const listener of [...this.listeners]) {
+      try {
+        listener(change);
+      } catch (error) {
+        // One broken subscriber must not starve the rest
+        if (process.env.CODEBASE_CONTEXT_DEBUG) {
+          console.error('[index-notifications] subscriber failed:', error);
+        }
+      }
+    }
+    return true;
+  }
+}
@@ -0,0 +1,80 @@
+/**
+ * Index health counters. The indexer tallies them while it folds parsed files and writes them
+ * next to the index, so reading them back never walks the codebase or the chunks.
+ */
+
+import { promises as fs } from 'fs';
+import path from 'path';
+import {
+  CODEBASE_CONTEXT_DIRNAME,
+  INDEXING_STATS_FILENAME
+} from '../constants/codebase-context.js';
+import type { FileSkipReason, SkippedFile } from '../types/index.js';
+
+/** Skipped files listed by name; the rest are only counted. */
+export const SKIPPED_FILES_SHOWN = 10;
+
+/** Contents of `indexing-stats.json`. Fields after `generatedAt` are absent in older indexes. */
+export interface PersistedIndexingStats {
+  indexedFiles: number;
+  totalChunks: number;
+  totalFiles: number;
+  generatedAt: string;
+  durationMs?: number;
+  unanalyzedFiles?: number; // scanned, but no analyzer produced a result
+  parseErrors?: number; // files whose read or analysis threw
+  skipped?: SkippedFile[];
+  filesByLanguage?: Record<string, number>;
+  symbolsByKind?: Record<string, number>;
+  indexedBytes?: number; // UTF-8 size of the indexed file contents
+  recoveredParseErrors?: number; // files indexed with symbols extracted around syntax errors
+}
+
+export interface SkippedSummary {
+  total: number;
+  byReason: Partial<Record<FileSkipReason, number>>;
+  files: SkippedFile[];
+  truncated: boolean;
+}
+
+/** Counts per reason plus the first few files; undefined when nothing was skipped. */
+export function summarizeSkipped(skipped: SkippedFile[] | undefined): SkippedSummary | undefined {
+  if (!skipped || skipped.length === 0) return undefined;
+  const byReason: Partial<Record<FileSkipReason, number>> = {};
+  for (const { reason } of skipped) {
+    byReason[reason] = (byReason[reason] ?? 0) + 1;
+  }
+  return {
+    total: skipped.length,
+    byReason,
+    files: skipped.slice(0, SKIPPED_FILES_SHOWN),
+    truncated: skipped.length > SKIPPED_FILES_SHOWN
+  };
+}
+
+/** Add one to `key` in a plain counter record. */
+export function incrementCount(counts: Record<string, number>, key: string): void {
+  counts[key] = (counts[key] ?? 0) + 1;
+}
+
+/** Read the stats the last index run wrote, or null when there is no index yet. */
+export async function readIndexingStats(rootPath: string): Promise<PersistedIndexingStats | null> {
+  try {
+    const parsed = JSON.parse(
+      await fs.readFile(
+        path.join(rootPath, CODEBASE_CONTEXT_DIRNAME, INDEXING_STATS_FILENAME),
+        'utf-8'
+      )
+    ) as Partial<PersistedIndexingStats>;
+    if (
+      typeof parsed.indexedFiles !== 'number' ||
+      typeof parsed.totalChunks !== 'number' ||
+      typeof parsed.totalFiles !== 'number'
+    ) {
+      return null;
+    }
+    return parsed as PersistedIndexingStats;
+  } catch {
+    return null;
+  }
+}
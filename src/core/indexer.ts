@@ -6,9 +6,16 @@
 import { randomUUID } from 'crypto';
 import { promises as fs } from 'fs';
 import path from 'path';
-import { glob } from 'glob';
-import ignore from 'ignore';
 import {
+  glob,
+  hasMagic,
+  Ignore,
+  type GlobOptionsWithFileTypesTrue,
+  type IgnoreLike,
+  type Path
+} from 'glob';
+import {
+  AnalysisResult,
   CodebaseMetadata,
   CodeChunk,
   IndexingProgress,
@@ -17,10 +24,19 @@ import {
   CodebaseConfig,
   Dependency,
   ArchitecturalLayer,
-  IntelligenceData
+  IntelligenceData,
+  SkippedFile
 } from '../types/index.js';
 import { analyzerRegistry } from './analyzer-registry.js';
-import { isCodeFile, isBinaryFile } from '../utils/language-detection.js';
+import {
+  isBinaryFile,
+  isTestFile,
+  detectLanguage,
+  setLanguageOverride,
+  sniffFile,
+  TEXT_ONLY_EXTENSIONS
+} from '../utils/language-detection.js';
+import { loadDirectoryConfigs } from '../utils/directory-config.js';
 import { getEmbeddingProvider, DEFAULT_MODEL } from '../embeddings/index.js';
 import { getStorageProvider, CodeChunkWithEmbedding } from '../storage/index.js';
 import {
@@ -31,7 +47,20 @@ import {
   FileExport
 } from '../utils/usage-tracker.js';
 import { mergeSmallChunks } from '../utils/chunking.js';
-import { getFileCommitDates } from '../utils/git-dates.js';
+import { clearFileCommitDatesCache, getFileCommitDates } from '../utils/git-dates.js';
+import { gitignoreWalkIgnore, loadGitignoreMatcher } from '../utils/gitignore.js';
+import { SymlinkResolver } from '../utils/symlinks.js';
+import {
+  setSymbolExtractionCache,
+  setSymbolParsePool,
+  type ImportSite,
+  type SymbolExtractionCache,
+  type TreeSitterSymbolExtraction
+} from '../utils/tree-sitter.js';
+import { mapWithConcurrency, parseConcurrencyFromEnv } from '../utils/concurrency.js';
+import { MIN_FILES_FOR_PARSE_WORKERS, ParsePool } from '../utils/parse-pool.js';
+import { decodeSourceBytes, isUndecodable } from '../utils/text-decoding.js';
+import { tracingEnabled, withSpan } from '../utils/tracing.js';
 import {
   CODEBASE_CONTEXT_DIRNAME,
   INDEX_FORMAT_VERSION,
@@ -42,6 +71,7 @@ import {
   KEYWORD_INDEX_FILENAME,
   MANIFEST_FILENAME,
   RELATIONSHIPS_FILENAME,
+  SYMBOL_CACHE_DIRNAME,
   VECTOR_DB_DIRNAME
 } from '../constants/codebase-context.js';
 
@@ -56,9 +86,57 @@ import {
   type FileManifest,
   type ManifestDiff
 } from './manifest.js';
+import { DiskSymbolCache } from './symbol-cache.js';
+import type { SymbolIndex } from './symbol-index.js';
+import { DiskEmbeddingCache, embeddingModelKey } from './embedding-cache.js';
+import { collectFileCallEdges, type CallEdge } from './call-graph.js';
+import { buildDependencyGraph, collectFileImports, type FileImports } from './dependency-graph.js';
+import { incrementCount, type PersistedIndexingStats } from './index-stats.js';
 
 let cachedToolVersion: string | null = null;
 
+function isDisabledByEnv(value: string | undefined): boolean {
+  const normalized = value?.trim().toLowerCase();
+  return normalized === '0' || normalized === 'false' || normalized === 'no';
+}
+
+function isEnabledByEnv(value: string | undefined): boolean {
+  const normalized = value?.trim().toLowerCase();
+  return normalized === '1' || normalized === 'true' || normalized === 'yes';
+}
+
+/** False for extensionless files and `.in` templates, whose language only content can tell. */
+function hasExplicitExtension(filePath: string): boolean {
+  const ext = path.extname(filePath).toLowerCase();
+  return ext !== '' && ext !== '.in';
+}
+
+/** Comma-separated globs from an env var such as `CODEBASE_CONTEXT_IGNORE`. */
+function globListFromEnv(value: string | undefined): string[] {
+  return (value ?? '')
+    .split(',')
+    .map((pattern) => pattern.trim())
+    .filter(Boolean);
+}
+
+/** Leading segments of a glob up to its first wildcard, e.g. `vendor/lib` for `vendor/lib/*.ts`. */
+function literalPatternPrefix(pattern: string): string {
+  const literal: string[] = [];
+  for (const segment of pattern.replace(/^\.\//, '').split('/')) {
+    if (hasMagic(segment)) break;
+    literal.push(segment);
+  }
+  return literal.join('/');
+}
+
+const DEFAULT_MAX_FILE_SIZE = 1048576;
+
+/** `CODEBASE_CONTEXT_MAX_FILE_SIZE` when it is a positive integer, else 1 MiB. */
+function maxFileSizeFromEnv(value = process.env.CODEBASE_CONTEXT_MAX_FILE_SIZE): number {
+  const parsed = value ? Number.parseInt(value.trim(), 10) : NaN;
+  return Number.isFinite(parsed) && parsed > 0 ? parsed : DEFAULT_MAX_FILE_SIZE;
+}
+
 async function getToolVersion(): Promise<string> {
   if (cachedToolVersion) return cachedToolVersion;
 
@@ -205,14 +283,27 @@ export interface IndexerOptions {
   config?: Partial<CodebaseConfig>;
   onProgress?: (progress: IndexingProgress) => void;
   incrementalOnly?: boolean;
+  /**
+   * In-memory index to keep in sync with this root: every run leaves it holding the symbols of
+   * exactly the indexed files, and its entries are reused instead of reparsing those files.
+   */
+  symbolIndex?: SymbolIndex;
 }
 
-interface PersistedIndexingStats {
-  indexedFiles: number;
-  totalChunks: number;
-  totalFiles: number;
-  generatedAt: string;
-}
+/** Output of the concurrent read-and-parse step for one file. */
+type ParsedFile =
+  | { file: string; error: unknown }
+  | {
+      file: string;
+      content: string;
+      result: AnalysisResult | null;
+      language: string;
+      relativeFile: string;
+      callEdges: CallEdge[];
+      importSites: ImportSite[];
+      /** Set when the bytes are not decodable text; `result` is then null */
+      skipped?: SkippedFile;
+    };
 
 export class CodebaseIndexer {
   private rootPath: string;
@@ -220,12 +311,14 @@ export class CodebaseIndexer {
   private progress: IndexingProgress;
   private onProgressCallback?: (progress: IndexingProgress) => void;
   private incrementalOnly: boolean;
+  private symbolIndex?: SymbolIndex;
 
   constructor(options: IndexerOptions) {
     this.rootPath = path.resolve(options.rootPath);
     this.config = this.mergeConfig(options.config);
     this.onProgressCallback = options.onProgress;
     this.incrementalOnly = options.incrementalOnly ?? false;
+    this.symbolIndex = options.symbolIndex;
 
     this.progress = {
       phase: 'initializing',
@@ -246,16 +339,39 @@ export class CodebaseIndexer {
         vue: { enabled: false, priority: 90 },
         generic: { enabled: true, priority: 10 }
       },
-      include: ['**/*.{ts,tsx,js,jsx,html,css,scss,sass,less}'],
-      exclude: ['node_modules/**', 'dist/**', 'build/**', '.git/**', 'coverage/**'],
-      respectGitignore: true,
+      // Extensionless files and `.in` templates are kept only when sniffing finds a language.
+      // SQL, shell and config files have no grammar and are indexed as text only.
+      include: [
+        '**/*.{ts,tsx,js,jsx,py,pyi,java,html,css,scss,sass,less}',
+        '**/!(*.*)',
+        '**/*.in',
+        `**/*.{${TEXT_ONLY_EXTENSIONS.map((ext) => ext.slice(1)).join(',')}}`
+      ],
+      exclude: [
+        'node_modules/**',
+        'dist/**',
+        'build/**',
+        '.git/**',
+        'coverage/**',
+        '**/pnpm-lock.yaml'
+      ],
+      respectGitignore: !isDisabledByEnv(process.env.CODEBASE_CONTEXT_RESPECT_GITIGNORE),
+      ignore: globListFromEnv(process.env.CODEBASE_CONTEXT_IGNORE),
+      includeOverride: globListFromEnv(process.env.CODEBASE_CONTEXT_INCLUDE_OVERRIDE),
+      followSymlinks: isEnabledByEnv(process.env.CODEBASE_CONTEXT_FOLLOW_SYMLINKS),
       parsing: {
-        maxFileSize: 1048576,
+        maxFileSize: maxFileSizeFromEnv(),
+        skipMinified: isEnabledByEnv(process.env.CODEBASE_CONTEXT_SKIP_MINIFIED),
+        concurrency: parseConcurrencyFromEnv(),
         chunkSize: 50,
         chunkOverlap: 0,
         parseTests: true,
         parseNodeModules: false
       },
+      symbolCache: {
+        enabled: !isDisabledByEnv(process.env.CODEBASE_CONTEXT_SYMBOL_CACHE),
+        dir: path.join(CODEBASE_CONTEXT_DIRNAME, SYMBOL_CACHE_DIRNAME)
+      },
       styleGuides: {
         autoDetect: true,
         paths: ['STYLE_GUIDE.md', 'docs/style-guide.md', 'ARCHITECTURE.md'],
@@ -283,6 +399,7 @@ export class CodebaseIndexer {
       ...userConfig,
       analyzers: { ...defaultConfig.analyzers, ...userConfig?.analyzers },
       parsing: { ...defaultConfig.parsing, ...userConfig?.parsing },
+      symbolCache: { ...defaultConfig.symbolCache, ...userConfig?.symbolCache },
       styleGuides: { ...defaultConfig.styleGuides, ...userConfig?.styleGuides },
       documentation: {
         ...defaultConfig.documentation,
@@ -295,6 +412,9 @@ export class CodebaseIndexer {
 
   async index(): Promise<IndexingStats> {
     const startTime = Date.now();
+    // Health counters for the `stats` tool, tallied as files are folded in
+    const filesByLanguage: Record<string, number> = {};
+    const symbolsByKind: Record<string, number> = {};
     const stats: IndexingStats = {
       totalFiles: 0,
       indexedFiles: 0,
@@ -316,10 +436,15 @@ export class CodebaseIndexer {
         unknown: 0
       },
       errors: [],
-      startedAt: new Date()
+      startedAt: new Date(),
+      filesByLanguage,
+      symbolsByKind,
+      indexedBytes: 0,
+      recoveredParseErrors: 0
     };
 
     let stagingDir: string | null = null;
+    let parsePool: ParsePool | null = null;
 
     try {
       // Ensure there is at least a generic fallback analyzer registered when the indexer
@@ -335,7 +460,16 @@ export class CodebaseIndexer {
 
       // Phase 1: Scanning
       this.updateProgress('scanning', 0);
-      let files = await this.scanFiles();
+      const skipped: SkippedFile[] = [];
+      let files = await withSpan('index.scan', { root: this.rootPath }, async (span) => {
+        const scanned = await this.scanFiles(skipped);
+        span.record({ files: scanned.length, skipped: skipped.length });
+        return scanned;
+      });
+      stats.skipped = skipped;
+      if (skipped.length > 0) {
+        console.error(`Skipped ${skipped.length} files by size, minified or binary content`);
+      }
 
       // Memory safety: limit total files to prevent heap exhaustion
       const MAX_FILES = 10000;
@@ -385,6 +519,7 @@ export class CodebaseIndexer {
         // Short-circuit: nothing changed
         if (diff.added.length === 0 && diff.changed.length === 0 && diff.deleted.length === 0) {
           console.error('No files changed - skipping re-index.');
+          await this.syncSymbolIndex(files);
           this.updateProgress('complete', 100);
           stats.duration = Date.now() - startTime;
           stats.completedAt = new Date();
@@ -404,6 +539,10 @@ export class CodebaseIndexer {
               stats.indexedFiles = persisted.indexedFiles;
               stats.totalChunks = persisted.totalChunks;
               stats.totalFiles = persisted.totalFiles;
+              stats.indexedBytes = persisted.indexedBytes ?? 0;
+              stats.recoveredParseErrors = persisted.recoveredParseErrors ?? 0;
+              Object.assign(filesByLanguage, persisted.filesByLanguage);
+              Object.assign(symbolsByKind, persisted.symbolsByKind);
               restoredFromPersistedStats = true;
             }
           } catch {
@@ -462,29 +601,92 @@ export class CodebaseIndexer {
       const patternDetector = new PatternDetector();
       const importGraph = new ImportGraph();
       const internalFileGraph = new InternalFileGraph(this.rootPath);
+      const callEdges: CallEdge[] = [];
+      const importsByFile = new Map<string, FileImports>();
 
-      // Fetch git commit dates for pattern momentum analysis
+      // Fetch git commit dates for pattern momentum analysis. Re-read history on every run so
+      // search ranking picks up commits made since the last index.
+      clearFileCommitDatesCache(this.rootPath);
       const fileDates = await getFileCommitDates(this.rootPath);
 
       // When incremental, track which files need embedding
       const filesToProcessSet = diff ? new Set(filesToProcess.map((f) => f)) : null;
 
-      for (let i = 0; i < files.length; i++) {
-        const file = files[i];
+      // Unchanged file contents reuse their symbols from the previous run instead of re-parsing
+      const symbolCache = await this.loadSymbolCache();
+      setSymbolExtractionCache(this.extractionCache(symbolCache));
+      // Cache misses are parsed on worker threads, one Parser each, when the run is big enough
+      parsePool = this.openParsePool(files.length);
+      setSymbolParsePool(parsePool);
+
+      // Reading and parsing runs on a bounded pool; results are folded in file order below, so
+      // the output does not depend on the concurrency level
+      const parseFile = (file: string): Promise<ParsedFile> =>
+        withSpan('index.file', { file }, async (span) => {
+          try {
+            // Decoded to UTF-8 without BOM and with \n line endings, so offsets and line numbers
+            // match across platforms; content that is not text is skipped with the reason
+            const bytes = await fs.readFile(file);
+            if (tracingEnabled()) span.record({ bytes: bytes.length });
+            const relativeFile = path.relative(this.rootPath, file).replace(/\\/g, '/');
+            const source = decodeSourceBytes(bytes);
+            if (isUndecodable(source)) {
+              span.record({ skipped: source.reason });
+              return {
+                file,
+                content: '',
+                result: null,
+                language: 'plaintext',
+                relativeFile,
+                callEdges: [],
+                importSites: [],
+                skipped: { file: relativeFile, reason: source.reason, size: bytes.length }
+              };
+            }
+            const content = source.text;
+            const result = await analyzerRegistry.analyzeFile(file, content);
+            const language = detectLanguage(file, content);
+            span.record({ language, chunks: result?.chunks.length ?? 0 });
+            const parsed = { file, content, result, language, relativeFile };
+            if (!result) return { ...parsed, callEdges: [], importSites: [] };
+
+            // Name-based call edges and import sites (parses with Tree-sitter where supported)
+            const [fileCallEdges, importSites] = await Promise.all([
+              collectFileCallEdges(relativeFile, content, language),
+              collectFileImports(content, language)
+            ]);
+            return { ...parsed, callEdges: fileCallEdges, importSites };
+          } catch (error) {
+            return { file, error };
+          }
+        });
+
+      let filesSeen = 0;
+      for await (const parsed of mapWithConcurrency(
+        files,
+        this.config.parsing.concurrency ?? parseConcurrencyFromEnv(),
+        parseFile
+      )) {
+        const { file } = parsed;
+        filesSeen++;
         this.progress.currentFile = file;
-        this.progress.filesProcessed = i + 1;
-        this.progress.percentage = Math.round(((i + 1) / files.length) * 100);
+        this.progress.filesProcessed = filesSeen;
+        this.progress.percentage = Math.round((filesSeen / files.length) * 100);
 
         try {
-          // Normalize line endings to \n for consistent cross-platform output
-          const rawContent = await fs.readFile(file, 'utf-8');
-          const content = rawContent.replace(/\r\n/g, '\n');
-          const result = await analyzerRegistry.analyzeFile(file, content);
+          if ('error' in parsed) throw parsed.error;
+          const { content, result } = parsed;
+          if (parsed.skipped) skipped.push(parsed.skipped);
 
           if (result) {
             const isFileChanged = !filesToProcessSet || filesToProcessSet.has(file);
 
             const mergedChunks = mergeSmallChunks(result.chunks, 15);
+            if (isTestFile(parsed.relativeFile)) {
+              for (const chunk of mergedChunks) {
+                chunk.metadata = { ...chunk.metadata, isTest: true };
+              }
+            }
 
             allChunks.push(...mergedChunks);
             if (isFileChanged) {
@@ -492,6 +694,11 @@ export class CodebaseIndexer {
             }
             stats.indexedFiles++;
             stats.totalLines += content.split('\n').length;
+            stats.indexedBytes = (stats.indexedBytes ?? 0) + Buffer.byteLength(content, 'utf8');
+            incrementCount(filesByLanguage, parsed.language);
+            if (result.metadata?.hasParseErrors === true) {
+              stats.recoveredParseErrors = (stats.recoveredParseErrors ?? 0) + 1;
+            }
 
             // Track library usage AND import graph from imports
             for (const imp of result.imports) {
@@ -528,6 +735,17 @@ export class CodebaseIndexer {
               internalFileGraph.trackExports(file, fileExports);
             }
 
+            // Name-based call edges for the call graph
+            callEdges.push(...parsed.callEdges);
+
+            // Import/use statements, resolved to files once every file has been seen
+            if (parsed.importSites.length > 0) {
+              importsByFile.set(parsed.relativeFile, {
+                language: parsed.language,
+                sites: parsed.importSites
+              });
+            }
+
             // Detect generic patterns from code
             patternDetector.detectFromCode(content, file);
 
@@ -599,6 +817,7 @@ export class CodebaseIndexer {
 
             // Update component statistics
             for (const component of result.components) {
+              incrementCount(symbolsByKind, component.type);
               if (component.componentType) {
                 stats.componentsByType[component.componentType] =
                   (stats.componentsByType[component.componentType] || 0) + 1;
@@ -625,6 +844,24 @@ export class CodebaseIndexer {
         }
       }
 
+      // Still inside the extraction cache, so files parsed above are not parsed again
+      await this.syncSymbolIndexFiles(files);
+      setSymbolExtractionCache(null);
+      setSymbolParsePool(null);
+      if (parsePool) {
+        stats.parseWorkers = { workers: parsePool.size, parsedFiles: parsePool.parsed };
+        await parsePool.close();
+      }
+
+      if (symbolCache) {
+        stats.symbolCache = { hits: symbolCache.hits, misses: symbolCache.misses };
+        try {
+          await symbolCache.save();
+        } catch (error) {
+          console.warn('Failed to write symbol cache:', error);
+        }
+      }
+
       stats.totalChunks = allChunks.length;
       stats.avgChunkSize =
         allChunks.length > 0
@@ -657,6 +894,7 @@ export class CodebaseIndexer {
 
         // Initialize embedding provider
         const embeddingProvider = await getEmbeddingProvider(this.config.embedding);
+        const embeddingCache = await this.loadEmbeddingCache(embeddingModelKey(embeddingProvider));
 
         // Generate embeddings for all chunks
         // Outer batch size controls how many chunks we collect before calling embedBatch.
@@ -683,12 +921,20 @@ export class CodebaseIndexer {
             return prefix + chunk.content;
           });
 
-          const embeddings = await embeddingProvider.embedBatch(texts);
+          const embeddings = texts.map((text) => embeddingCache?.get(text));
+          const missing = texts.flatMap((text, j) => (embeddings[j] ? [] : [j]));
+          if (missing.length > 0) {
+            const fresh = await embeddingProvider.embedBatch(missing.map((j) => texts[j]));
+            missing.forEach((j, k) => {
+              embeddings[j] = fresh[k];
+              embeddingCache?.set(texts[j], fresh[k]);
+            });
+          }
 
           for (let j = 0; j < batch.length; j++) {
             chunksWithEmbeddings.push({
               ...batch[j],
-              embedding: embeddings[j]
+              embedding: embeddings[j] as number[]
             });
           }
 
@@ -704,6 +950,15 @@ export class CodebaseIndexer {
             );
           }
         }
+
+        if (embeddingCache) {
+          stats.embeddingCache = { hits: embeddingCache.hits, misses: embeddingCache.misses };
+          try {
+            await embeddingCache.save({ prune: !diff });
+          } catch (error) {
+            console.warn('Failed to write embedding cache:', error);
+          }
+        }
       } else if (this.config.skipEmbedding) {
         console.error('Skipping embedding generation (skipEmbedding=true)');
       } else if (chunksToEmbed.length === 0 && diff) {
@@ -863,6 +1118,13 @@ export class CodebaseIndexer {
         symbols: {
           exportedBy
         },
+        callGraph: {
+          edges: callEdges
+        },
+        dependencyGraph: buildDependencyGraph(
+          importsByFile,
+          files.map((file) => path.relative(this.rootPath, file).replace(/\\/g, '/'))
+        ),
         stats: graphData.stats || internalFileGraph.getStats()
       };
       await fs.writeFile(relationshipsPath, JSON.stringify(relationships, null, 2));
@@ -877,11 +1139,20 @@ export class CodebaseIndexer {
       };
       await writeManifest(activeManifestPath, manifest);
 
+      const parseErrors = stats.errors.filter((error) => error.phase === 'analyzing').length;
       const persistedStats: PersistedIndexingStats = {
         indexedFiles: stats.indexedFiles,
         totalChunks: stats.totalChunks,
         totalFiles: stats.totalFiles,
-        generatedAt
+        generatedAt,
+        durationMs: Date.now() - startTime,
+        unanalyzedFiles: stats.skippedFiles - parseErrors,
+        parseErrors,
+        skipped: stats.skipped,
+        filesByLanguage,
+        symbolsByKind,
+        indexedBytes: stats.indexedBytes,
+        recoveredParseErrors: stats.recoveredParseErrors
       };
       const activeIndexingStatsPath = path.join(activeContextDir, INDEXING_STATS_FILENAME);
       await fs.writeFile(activeIndexingStatsPath, JSON.stringify(persistedStats, null, 2));
@@ -936,6 +1207,9 @@ export class CodebaseIndexer {
 
       return stats;
     } catch (error) {
+      setSymbolExtractionCache(null);
+      setSymbolParsePool(null);
+      await parsePool?.close();
       this.progress.phase = 'error';
       stats.errors.push({
         filePath: this.rootPath,
@@ -952,35 +1226,175 @@ export class CodebaseIndexer {
     }
   }
 
-  private async scanFiles(): Promise<string[]> {
-    const files: string[] = [];
-    const seen = new Set<string>();
-
-    // Read .gitignore if respecting it
-    let ig: ReturnType<typeof ignore.default> | null = null;
-    if (this.config.respectGitignore) {
+  /**
+   * Bring the `symbolIndex` option's entries for this root in line with `files` (by default
+   * the files of the last run's manifest): files no longer indexed are dropped and the rest are
+   * synced with disk, their symbols read from the symbol cache where it has them.
+   */
+  async syncSymbolIndex(files?: string[]): Promise<void> {
+    if (!this.symbolIndex) return;
+    let indexedFiles = files;
+    if (!indexedFiles) {
+      const manifestPath = path.join(this.rootPath, CODEBASE_CONTEXT_DIRNAME, MANIFEST_FILENAME);
+      const manifest = await readManifest(manifestPath);
+      indexedFiles = Object.keys(manifest?.files ?? {}).map((file) =>
+        path.join(this.rootPath, file)
+      );
+    }
+    const symbolCache = await this.loadSymbolCache();
+    setSymbolExtractionCache(this.extractionCache(symbolCache));
+    const parsePool = this.openParsePool(indexedFiles.length);
+    setSymbolParsePool(parsePool);
+    try {
+      await this.syncSymbolIndexFiles(indexedFiles);
+    } finally {
+      setSymbolExtractionCache(null);
+      setSymbolParsePool(null);
+      await parsePool?.close();
+    }
+    if (symbolCache) {
       try {
-        const gitignorePath = path.join(this.rootPath, '.gitignore');
-        const gitignoreContent = await fs.readFile(gitignorePath, 'utf-8');
-        ig = ignore.default().add(gitignoreContent);
-      } catch (_error) {
-        // No .gitignore or couldn't read it
+        await symbolCache.save();
+      } catch (error) {
+        console.warn('Failed to write symbol cache:', error);
+      }
+    }
+  }
+
+  private async syncSymbolIndexFiles(files: string[]): Promise<void> {
+    const symbolIndex = this.symbolIndex;
+    if (!symbolIndex) return;
+    const indexed = new Set(files.map((file) => path.resolve(file)));
+    for (const file of symbolIndex.files(this.rootPath)) {
+      const absolutePath = path.join(this.rootPath, file);
+      if (!indexed.has(absolutePath)) symbolIndex.removeFile(absolutePath);
+    }
+    try {
+      await symbolIndex.refreshFiles(
+        Array.from(indexed),
+        this.config.parsing.concurrency ?? parseConcurrencyFromEnv()
+      );
+    } catch (error) {
+      // Files that cannot be read now are synced again on their next change or lookup
+      if (process.env.CODEBASE_CONTEXT_DEBUG) {
+        console.error('[symbol-index] sync failed:', error);
       }
     }
+  }
+
+  /**
+   * Worker threads for a run over `fileCount` files: `parsing.workers` of them, or by default
+   * one per parse slot once the run reaches `MIN_FILES_FOR_PARSE_WORKERS` files. Null keeps
+   * parsing on this thread.
+   */
+  private openParsePool(fileCount: number): ParsePool | null {
+    const { workers, concurrency } = this.config.parsing;
+    const size =
+      workers ??
+      (fileCount >= MIN_FILES_FOR_PARSE_WORKERS ? (concurrency ?? parseConcurrencyFromEnv()) : 0);
+    return size > 0 && fileCount > 0 ? new ParsePool(size) : null;
+  }
+
+  /**
+   * Symbols already held by the `symbolIndex` option come first, then the disk cache. With the
+   * disk cache disabled, extractions are still shared for the length of the run, so a file's
+   * call and import sites come from the parse its analyzer made.
+   */
+  private extractionCache(symbolCache: DiskSymbolCache | null): SymbolExtractionCache {
+    const fallback = symbolCache ?? new Map<string, TreeSitterSymbolExtraction>();
+    return this.symbolIndex ? this.symbolIndex.extractionCache(fallback) : fallback;
+  }
+
+  private async loadSymbolCache(): Promise<DiskSymbolCache | null> {
+    const { enabled, dir } = this.config.symbolCache ?? {};
+    if (enabled === false) {
+      return null;
+    }
+    const cacheDir = path.resolve(
+      this.rootPath,
+      dir ?? path.join(CODEBASE_CONTEXT_DIRNAME, SYMBOL_CACHE_DIRNAME)
+    );
+    return DiskSymbolCache.load(cacheDir);
+  }
+
+  /** The embedding cache shares the symbol cache's directory and on/off switch. */
+  private async loadEmbeddingCache(model: string): Promise<DiskEmbeddingCache | null> {
+    const { enabled, dir } = this.config.symbolCache ?? {};
+    if (enabled === false) {
+      return null;
+    }
+    const cacheDir = path.resolve(
+      this.rootPath,
+      dir ?? path.join(CODEBASE_CONTEXT_DIRNAME, SYMBOL_CACHE_DIRNAME)
+    );
+    return DiskEmbeddingCache.load(cacheDir, model);
+  }
+
+  /** Files to index; size and minified skips are appended to `skipped` with their reason. */
+  private async scanFiles(skipped: SkippedFile[] = []): Promise<string[]> {
+    const files: string[] = [];
+    const seen = new Set<string>();
+
+    // Nested .gitignore files, .git/info/exclude and the global excludes file
+    const gitignore = this.config.respectGitignore
+      ? await loadGitignoreMatcher(this.rootPath)
+      : null;
+
+    // Nested .codebase-context.toml files: skipped subtrees, ignores, size limits, languages
+    const directoryConfigs = await loadDirectoryConfigs(this.rootPath);
+    setLanguageOverride(
+      this.rootPath,
+      directoryConfigs.sources.length > 0
+        ? (absolutePath) => directoryConfigs.languageFor(path.relative(this.rootPath, absolutePath))
+        : null
+    );
 
     // Scan with glob
     const includePatterns = this.config.include || ['**/*'];
     const excludePatterns = this.config.exclude || [];
 
+    // Directory symlinks are crawled only with followSymlinks, and never twice or in a cycle
+    const symlinks = new SymlinkResolver(this.rootPath);
+    const excluded = this.config.followSymlinks
+      ? symlinks.followIgnore(excludePatterns)
+      : new Ignore(excludePatterns, { platform: process.platform });
+
+    // Config-level ignores sit on top of .gitignore; an override re-includes past both. They
+    // are matched against the paths of the one walk
+    const ignoredByConfig = new Ignore(this.config.ignore ?? [], { platform: process.platform });
+    const overridden = new Ignore(this.config.includeOverride ?? [], {
+      platform: process.platform
+    });
+    const overrideRoots = (this.config.includeOverride ?? []).map(literalPatternPrefix);
+    const reachedByOverride = (relativeDir: string): boolean =>
+      overrideRoots.some(
+        (root) =>
+          !root || root.startsWith(`${relativeDir}/`) || `${relativeDir}/`.startsWith(`${root}/`)
+      );
+
+    // Ignored directories are not entered, unless an override may re-include a file below them
+    const pruned: IgnoreLike = {
+      ignored: (p: Path) => excluded.ignored?.(p) ?? false,
+      childrenIgnored: (p: Path) =>
+        Boolean(excluded.childrenIgnored?.(p)) ||
+        (ignoredByConfig.childrenIgnored(p) &&
+          !reachedByOverride(path.relative(this.rootPath, p.fullpath()).replace(/\\/g, '/')))
+    };
+    const globOptions: GlobOptionsWithFileTypesTrue = {
+      cwd: this.rootPath,
+      nodir: true,
+      withFileTypes: true,
+      follow: Boolean(this.config.followSymlinks),
+      ignore: gitignore
+        ? gitignoreWalkIgnore(this.rootPath, gitignore, pruned, reachedByOverride)
+        : pruned
+    };
+
     for (const pattern of includePatterns) {
-      const matches = await glob(pattern, {
-        cwd: this.rootPath,
-        absolute: true,
-        ignore: excludePatterns,
-        nodir: true
-      });
+      const matches = await glob(pattern, globOptions);
 
-      for (const file of matches) {
+      for (const entry of matches) {
+        const file = entry.fullpath();
         const normalizedFile = file.replace(/\\/g, '/');
         if (seen.has(normalizedFile)) {
           continue;
@@ -989,21 +1403,56 @@ export class CodebaseIndexer {
 
         const relativePath = path.relative(this.rootPath, file);
 
-        // Check gitignore
-        if (ig && ig.ignores(relativePath)) {
+        // `includeOverride` beats .gitignore and `ignore`; directory configs (`skip = true`
+        // included) and `exclude` still apply
+        if (
+          !overridden.ignored(entry) &&
+          (gitignore?.ignores(relativePath) || ignoredByConfig.ignored(entry))
+        ) {
+          continue;
+        }
+        if (directoryConfigs.ignores(relativePath)) {
+          continue;
+        }
+
+        if (isBinaryFile(file)) {
           continue;
         }
 
-        // Check if it's a code file
-        if (!isCodeFile(file) || isBinaryFile(file)) {
+        // A symlinked file is indexed once: under its target when that is inside the root,
+        // else under the first link reaching it
+        if (!(await symlinks.acceptsFile(file))) {
           continue;
         }
 
-        // Check file size
+        // Check file size before anything reads the file
+        let size: number;
         try {
-          const stats = await fs.stat(file);
-          if (stats.size > (this.config.parsing?.maxFileSize || 1048576)) {
-            console.warn(`Skipping large file: ${file} (${stats.size} bytes)`);
+          size = (await fs.stat(file)).size;
+        } catch (_error) {
+          continue;
+        }
+        const maxFileSize =
+          directoryConfigs.resolve(relativePath).maxFileSize ??
+          (this.config.parsing?.maxFileSize || DEFAULT_MAX_FILE_SIZE);
+        if (size > maxFileSize) {
+          console.warn(`Skipping large file: ${file} (${size} bytes)`);
+          skipped.push({ file: relativePath.replace(/\\/g, '/'), reason: 'too_large', size });
+          continue;
+        }
+
+        // Missing extensions (and `.in` templates) are kept only when the content names a
+        // language (shebang, modeline); other included text files are indexed as text only.
+        // Anything with NUL bytes in its first KB (UTF-16 aside) is binary and skipped.
+        try {
+          const sniffed = await sniffFile(file);
+          if (sniffed.binary) {
+            skipped.push({ file: relativePath.replace(/\\/g, '/'), reason: 'binary', size });
+            continue;
+          }
+          if (!hasExplicitExtension(file) && sniffed.language === 'plaintext') continue;
+          if (sniffed.minified && this.config.parsing?.skipMinified) {
+            skipped.push({ file: relativePath.replace(/\\/g, '/'), reason: 'minified', size });
             continue;
           }
         } catch (_error) {
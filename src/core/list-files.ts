@@ -0,0 +1,112 @@
+/**
+ * Paged listing of the files in the index manifest, filtered by glob and detected language.
+ * Globs are expanded with the same `glob` the indexer scans with, from the project root, so a
+ * pattern matches exactly the root-relative paths the manifest stores.
+ */
+
+import path from 'path';
+import { glob } from 'glob';
+import { readIndexedFiles } from './chunk-export.js';
+import { detectLanguage, sniffFile } from '../utils/language-detection.js';
+import { readSourceText } from '../utils/text-decoding.js';
+import { extractTreeSitterSymbols, supportsTreeSitter } from '../utils/tree-sitter.js';
+
+export const DEFAULT_LIST_FILES_LIMIT = 50;
+export const MAX_LIST_FILES_LIMIT = 200;
+
+export interface ListFilesOptions {
+  /** Root-relative glob, e.g. `src/**\/*.rs` */
+  glob?: string;
+  /** Detected language, e.g. `rust` */
+  language?: string;
+  limit?: number;
+  /** `nextCursor` of the previous page: listing resumes after that path */
+  cursor?: string;
+}
+
+export interface ListedFile {
+  /** Path relative to the project root, forward slashes */
+  file: string;
+  language: string;
+  /** Tree-sitter symbols in the file; 0 for languages without a grammar */
+  symbolCount: number;
+}
+
+export interface ListFilesResult {
+  files: ListedFile[];
+  /** Indexed files matching the filters, across all pages */
+  totalMatches: number;
+  /** Pass as `cursor` for the next page; absent on the last page */
+  nextCursor?: string;
+}
+
+/** Language from the path alone when it is conclusive; otherwise sniffed from the content. */
+async function indexedFileLanguage(absolutePath: string): Promise<string> {
+  const byPath = detectLanguage(absolutePath);
+  if (byPath !== 'plaintext' && path.extname(absolutePath).toLowerCase() !== '.h') return byPath;
+  try {
+    return (await sniffFile(absolutePath)).language;
+  } catch {
+    return byPath;
+  }
+}
+
+async function describeFile(rootPath: string, file: string): Promise<ListedFile> {
+  const absolutePath = path.join(rootPath, file);
+  let content: string;
+  try {
+    content = await readSourceText(absolutePath);
+  } catch {
+    // Deleted since the last index run
+    return { file, language: await indexedFileLanguage(absolutePath), symbolCount: 0 };
+  }
+  const language = detectLanguage(absolutePath, content);
+  const extraction = supportsTreeSitter(language)
+    ? await extractTreeSitterSymbols(content, language)
+    : null;
+  return { file, language, symbolCount: extraction?.symbols.length ?? 0 };
+}
+
+/** Null when the project has not been indexed. */
+export async function listIndexedFiles(
+  rootPath: string,
+  options: ListFilesOptions = {}
+): Promise<ListFilesResult | null> {
+  const resolvedRoot = path.resolve(rootPath);
+  const indexedFiles = await readIndexedFiles(resolvedRoot);
+  if (!indexedFiles) return null;
+
+  let candidates = indexedFiles;
+  if (options.glob) {
+    const matched = new Set(
+      (await glob(options.glob, { cwd: resolvedRoot, nodir: true })).map((file) =>
+        file.replace(/\\/g, '/')
+      )
+    );
+    candidates = candidates.filter((file) => matched.has(file));
+  }
+
+  const language = options.language?.trim().toLowerCase();
+  if (language) {
+    const languages = await Promise.all(
+      candidates.map((file) => indexedFileLanguage(path.join(resolvedRoot, file)))
+    );
+    candidates = candidates.filter((_, index) => languages[index] === language);
+  }
+
+  const limit = Math.min(
+    MAX_LIST_FILES_LIMIT,
+    Math.max(1, Math.floor(options.limit ?? DEFAULT_LIST_FILES_LIMIT))
+  );
+  // Paths are sorted, so resuming after the cursor path stays stable as files come and go
+  const cursor = options.cursor;
+  const start = cursor ? candidates.findIndex((file) => file > cursor) : 0;
+  const page = start < 0 ? [] : candidates.slice(start, start + limit);
+  const hasMore = start >= 0 && start + limit < candidates.length;
+
+  return {
+    files: await Promise.all(page.map((file) => describeFile(resolvedRoot, file))),
+    totalMatches: candidates.length,
+    ...(hasMore ? { nextCursor: page[page.length - 1] } : {})
+  };
+}
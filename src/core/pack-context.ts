@@ -0,0 +1,277 @@
+/**
+ * Prompt packaging: render snippet and symbol references as one ready-to-send block with a table
+ * of contents, a header per file and language-tagged fences.
+ *
+ * References are resolved and offered to the budget in the order given, so earlier ones win when
+ * space runs out; the packed block itself is ordered by file, then line, so the same set of
+ * references always renders the same text. Templates use `{placeholder}` substitution; unknown
+ * placeholders are left as they are.
+ */
+
+import path from 'path';
+import { findDefinitions } from './symbol-definition.js';
+import { DEFAULT_TOKEN_ESTIMATOR, type TokenEstimator } from './token-budget.js';
+import { detectLanguage } from '../utils/language-detection.js';
+import { readSourceText } from '../utils/text-decoding.js';
+import { relativeInside } from '../utils/symlinks.js';
+
+export const DEFAULT_PACK_MAX_TOKENS = 800;
+
+/** A file range (whole file when the lines are omitted) or a symbol's definitions. */
+export interface PackReference {
+  file?: string;
+  startLine?: number;
+  endLine?: number;
+  /** Packs every definition; narrow it with `file` */
+  symbol?: string;
+}
+
+export interface PackTemplates {
+  /** Top of the block; `{toc}`, `{count}` (snippets), `{files}` (file count) */
+  header: string;
+  /** One table-of-contents line: `{file}`, `{startLine}`, `{endLine}`, `{symbol}` */
+  tocEntry: string;
+  /** Before a file's first snippet: `{file}`, `{language}` */
+  file: string;
+  /** One snippet: `{file}`, `{language}`, `{startLine}`, `{endLine}`, `{symbol}`, `{code}` */
+  snippet: string;
+}
+
+export const DEFAULT_PACK_TEMPLATES: PackTemplates = {
+  header: '# Context ({count} snippets from {files} files)\n\n{toc}\n',
+  tocEntry: '- {file}:{startLine}-{endLine} {symbol}',
+  file: '\n## {file}\n',
+  snippet: '\nLines {startLine}-{endLine}:\n```{language}\n{code}\n```\n'
+};
+
+export interface PackedEntry {
+  file: string;
+  startLine: number;
+  endLine: number;
+  symbol?: string;
+  tokens: number;
+}
+
+export type PackExclusionReason = 'budget' | 'not_found' | 'duplicate';
+
+export interface ExcludedReference extends PackReference {
+  reason: PackExclusionReason;
+  message?: string;
+}
+
+export interface PackContextOptions {
+  maxTokens?: number;
+  templates?: Partial<PackTemplates>;
+  estimator?: TokenEstimator;
+}
+
+export interface PackContextResult {
+  status: 'success';
+  packed: string;
+  tokensUsed: number;
+  maxTokens: number;
+  /** In packed order: by file, then line */
+  included: PackedEntry[];
+  /** In reference order */
+  excluded: ExcludedReference[];
+}
+
+interface ResolvedSnippet {
+  file: string;
+  language: string;
+  startLine: number;
+  endLine: number;
+  symbol?: string;
+  code: string;
+}
+
+export function fillTemplate(template: string, values: Record<string, string | number>): string {
+  return template.replace(/\{(\w+)\}/g, (placeholder, key: string) =>
+    Object.hasOwn(values, key) ? String(values[key]) : placeholder
+  );
+}
+
+function fenceLanguage(language: string): string {
+  return language === 'plaintext' || language === 'unknown' ? '' : language;
+}
+
+async function readProjectFile(
+  rootPath: string,
+  file: string
+): Promise<{ relPath: string; lines: string[]; language: string } | string> {
+  const absPath = path.resolve(rootPath, file);
+  const relPath = relativeInside(rootPath, absPath);
+  if (!relPath) {
+    return `File is outside the project root: ${file}`;
+  }
+  let content: string;
+  try {
+    content = await readSourceText(absPath);
+  } catch {
+    return `File not found: ${file}`;
+  }
+  const lines = content.split('\n');
+  if (lines.length > 1 && lines[lines.length - 1] === '') lines.pop();
+  return {
+    relPath: relPath.replace(/\\/g, '/'),
+    lines,
+    language: fenceLanguage(detectLanguage(absPath, content))
+  };
+}
+
+/** Snippets for one reference, or why it could not be resolved. */
+async function resolveReference(
+  rootPath: string,
+  reference: PackReference
+): Promise<ResolvedSnippet[] | string> {
+  const ranges: Array<PackReference & { file: string }> = [];
+  if (reference.symbol) {
+    const lookup = await findDefinitions(rootPath, reference.symbol, {
+      ...(reference.file ? { file: reference.file } : {})
+    });
+    if (lookup.status === 'error') return lookup.message;
+    if (lookup.definitions.length === 0) return `No definition found for ${reference.symbol}`;
+    for (const definition of lookup.definitions) {
+      ranges.push({
+        file: definition.file,
+        startLine: definition.startLine,
+        endLine: definition.endLine,
+        symbol: reference.symbol
+      });
+    }
+  } else if (reference.file) {
+    ranges.push({
+      file: reference.file,
+      startLine: reference.startLine,
+      endLine: reference.endLine
+    });
+  } else {
+    return "Each reference needs a 'file' or a 'symbol'.";
+  }
+
+  const snippets: ResolvedSnippet[] = [];
+  for (const range of ranges) {
+    const read = await readProjectFile(rootPath, range.file);
+    if (typeof read === 'string') return read;
+    const startLine = Math.max(1, Math.floor(range.startLine ?? 1));
+    const endLine = Math.min(read.lines.length, Math.floor(range.endLine ?? read.lines.length));
+    if (startLine > endLine) {
+      const total = read.lines.length;
+      return `Lines ${startLine}-${endLine} are outside ${read.relPath}, which has ${total} lines.`;
+    }
+    snippets.push({
+      file: read.relPath,
+      language: read.language,
+      startLine,
+      endLine,
+      ...(range.symbol ? { symbol: range.symbol } : {}),
+      code: read.lines.slice(startLine - 1, endLine).join('\n')
+    });
+  }
+  return snippets;
+}
+
+function compareSnippets(a: ResolvedSnippet, b: ResolvedSnippet): number {
+  if (a.file !== b.file) return a.file < b.file ? -1 : 1;
+  return a.startLine - b.startLine || a.endLine - b.endLine;
+}
+
+function snippetValues(snippet: ResolvedSnippet): Record<string, string | number> {
+  return {
+    file: snippet.file,
+    language: snippet.language,
+    startLine: snippet.startLine,
+    endLine: snippet.endLine,
+    symbol: snippet.symbol ?? '',
+    code: snippet.code
+  };
+}
+
+function render(snippets: ResolvedSnippet[], templates: PackTemplates): string {
+  const sorted = [...snippets].sort(compareSnippets);
+  const toc = sorted
+    .map((snippet) => fillTemplate(templates.tocEntry, snippetValues(snippet)).trimEnd())
+    .join('\n');
+  const parts = [
+    fillTemplate(templates.header, {
+      toc,
+      count: sorted.length,
+      files: new Set(sorted.map((snippet) => snippet.file)).size
+    })
+  ];
+  let currentFile: string | null = null;
+  for (const snippet of sorted) {
+    if (snippet.file !== currentFile) {
+      currentFile = snippet.file;
+      parts.push(fillTemplate(templates.file, snippetValues(snippet)));
+    }
+    parts.push(fillTemplate(templates.snippet, snippetValues(snippet)));
+  }
+  return parts.join('');
+}
+
+/**
+ * Resolve `references` against `rootPath` and pack as many as fit in `maxTokens`, counting the
+ * whole rendered block (headers and table of contents included).
+ */
+export async function packContext(
+  rootPath: string,
+  references: PackReference[],
+  options: PackContextOptions = {}
+): Promise<PackContextResult> {
+  const resolvedRoot = path.resolve(rootPath);
+  const maxTokens =
+    options.maxTokens !== undefined && options.maxTokens > 0
+      ? Math.floor(options.maxTokens)
+      : DEFAULT_PACK_MAX_TOKENS;
+  const templates = { ...DEFAULT_PACK_TEMPLATES, ...options.templates };
+  const estimator = options.estimator ?? DEFAULT_TOKEN_ESTIMATOR;
+
+  const kept: ResolvedSnippet[] = [];
+  const excluded: ExcludedReference[] = [];
+  const seen = new Set<string>();
+
+  for (const reference of references) {
+    const resolved = await resolveReference(resolvedRoot, reference);
+    if (typeof resolved === 'string') {
+      excluded.push({ ...reference, reason: 'not_found', message: resolved });
+      continue;
+    }
+    for (const snippet of resolved) {
+      const key = `${snippet.file}:${snippet.startLine}:${snippet.endLine}`;
+      const excludedRef = {
+        file: snippet.file,
+        startLine: snippet.startLine,
+        endLine: snippet.endLine,
+        ...(snippet.symbol ? { symbol: snippet.symbol } : {})
+      };
+      if (seen.has(key)) {
+        excluded.push({ ...excludedRef, reason: 'duplicate' });
+        continue;
+      }
+      seen.add(key);
+      // Re-render with the candidate so headers and the table of contents count too
+      if (estimator.estimate(render([...kept, snippet], templates)) > maxTokens) {
+        excluded.push({ ...excludedRef, reason: 'budget' });
+        continue;
+      }
+      kept.push(snippet);
+    }
+  }
+
+  const packed = kept.length > 0 ? render(kept, templates) : '';
+  return {
+    status: 'success',
+    packed,
+    tokensUsed: packed ? estimator.estimate(packed) : 0,
+    maxTokens,
+    included: [...kept].sort(compareSnippets).map((snippet) => ({
+      file: snippet.file,
+      startLine: snippet.startLine,
+      endLine: snippet.endLine,
+      ...(snippet.symbol ? { symbol: snippet.symbol } : {}),
+      tokens: estimator.estimate(fillTemplate(templates.snippet, snippetValues(snippet)))
+    })),
+    excluded
+  };
+}
@@ -0,0 +1,241 @@
+/**
+ * Related-file suggestions: the indexed files most likely relevant when working on one file.
+ *
+ * Each candidate gets a score in [0, 1] per signal, and the suggestion score is their weighted
+ * sum. Signals:
+ *   - imports: the two files import one another directly
+ *   - sharedImports: overlap (Jaccard) of what both files import, workspace files and
+ *     third-party modules alike
+ *   - directory: 1 in the same directory, 1/2 one level apart, 1/3 two levels apart
+ *   - coChange: share of the file's commits that also touched the candidate
+ * Signals whose source is missing (no dependency graph, not a git repository) score 0 and are
+ * reported as unavailable, so the ranking degrades instead of failing.
+ */
+
+import path from 'path';
+import { readIndexedFiles } from './chunk-export.js';
+import { DependencyGraph } from './dependency-graph.js';
+import { getCoChangeCounts } from '../utils/git-dates.js';
+
+export const RELATED_SIGNALS = ['imports', 'sharedImports', 'directory', 'coChange'] as const;
+export type RelatedSignal = (typeof RELATED_SIGNALS)[number];
+export type RelatedWeights = Record<RelatedSignal, number>;
+
+export const DEFAULT_RELATED_WEIGHTS: RelatedWeights = {
+  imports: 1,
+  sharedImports: 0.6,
+  directory: 0.3,
+  coChange: 0.8
+};
+export const DEFAULT_RELATED_FILES_LIMIT = 10;
+export const MAX_RELATED_FILES_LIMIT = 50;
+
+/** Directory levels between two files beyond which proximity no longer counts */
+const MAX_DIRECTORY_DISTANCE = 2;
+const SHARED_IMPORTS_SHOWN = 3;
+
+export interface RelatedFilesOptions {
+  limit?: number;
+  /** Per-signal overrides of `DEFAULT_RELATED_WEIGHTS`; 0 turns a signal off */
+  weights?: Partial<RelatedWeights>;
+}
+
+export interface RelatedFile {
+  file: string;
+  score: number;
+  /** Why the file was suggested, strongest contribution first */
+  reasons: string[];
+}
+
+export interface RelatedFilesResult {
+  status: 'success';
+  file: string;
+  weights: RelatedWeights;
+  related: RelatedFile[];
+  /** Signals that could not be computed, e.g. coChange outside a git repository */
+  unavailable?: RelatedSignal[];
+}
+
+interface Contribution {
+  signal: RelatedSignal;
+  value: number;
+  reason: string;
+}
+
+/** Weights from `CODEBASE_CONTEXT_RELATED_WEIGHTS`, e.g. `imports=1,coChange=0`. */
+export function relatedWeightsFromEnv(
+  value: string | undefined = process.env.CODEBASE_CONTEXT_RELATED_WEIGHTS
+): Partial<RelatedWeights> {
+  const weights: Partial<RelatedWeights> = {};
+  for (const entry of (value ?? '').split(',')) {
+    const [name, raw] = entry.split('=').map((part) => part.trim());
+    const weight = Number.parseFloat(raw ?? '');
+    if ((RELATED_SIGNALS as readonly string[]).includes(name) && Number.isFinite(weight)) {
+      weights[name as RelatedSignal] = Math.max(0, weight);
+    }
+  }
+  return weights;
+}
+
+function directoryOf(file: string): string[] {
+  const dir = path.posix.dirname(file);
+  return dir === '.' ? [] : dir.split('/');
+}
+
+/** Levels up from one file's directory to the common ancestor and back down to the other's. */
+export function directoryDistance(a: string, b: string): number {
+  const left = directoryOf(a);
+  const right = directoryOf(b);
+  let common = 0;
+  while (common < left.length && common < right.length && left[common] === right[common]) {
+    common++;
+  }
+  return left.length - common + (right.length - common);
+}
+
+function importsOf(graph: DependencyGraph, file: string): Set<string> {
+  return new Set([
+    ...graph.dependenciesOf(file).map((edge) => edge.to),
+    ...(graph.unresolved[file] ?? [])
+  ]);
+}
+
+function round(value: number): number {
+  return Math.round(value * 1000) / 1000;
+}
+
+/** Rank indexed files by how related they are to `file`, a root-relative path. */
+export async function findRelatedFiles(
+  rootPath: string,
+  file: string,
+  options: RelatedFilesOptions = {}
+): Promise<RelatedFilesResult | { status: 'error'; message: string }> {
+  const resolvedRoot = path.resolve(rootPath);
+  const indexedFiles = await readIndexedFiles(resolvedRoot);
+  if (!indexedFiles) {
+    return { status: 'error', message: 'Index manifest not found. Run refresh_index first.' };
+  }
+  if (!indexedFiles.includes(file)) {
+    return { status: 'error', message: `File is not in the index: ${file}` };
+  }
+
+  const weights: RelatedWeights = { ...DEFAULT_RELATED_WEIGHTS, ...options.weights };
+  const limit = Math.min(
+    MAX_RELATED_FILES_LIMIT,
+    Math.max(1, Math.floor(options.limit ?? DEFAULT_RELATED_FILES_LIMIT))
+  );
+  const indexed = new Set(indexedFiles);
+  const contributions = new Map<string, Contribution[]>();
+  const unavailable: RelatedSignal[] = [];
+  const add = (candidate: string, signal: RelatedSignal, value: number, reason: string) => {
+    if (candidate === file || !indexed.has(candidate) || value <= 0 || weights[signal] <= 0) {
+      return;
+    }
+    const list = contributions.get(candidate) ?? [];
+    list.push({ signal, value, reason });
+    contributions.set(candidate, list);
+  };
+
+  const graph =
+    weights.imports > 0 || weights.sharedImports > 0
+      ? await DependencyGraph.load(resolvedRoot)
+      : null;
+  if (graph) {
+    const dependencies = graph.dependenciesOf(file).map((edge) => edge.to);
+    const dependents = graph.dependentsOf(file).map((edge) => edge.from);
+    for (const candidate of new Set([...dependencies, ...dependents])) {
+      const imported = dependencies.includes(candidate);
+      const importing = dependents.includes(candidate);
+      const reason =
+        imported && importing
+          ? 'imports this file and is imported by it'
+          : imported
+            ? 'imported by this file'
+            : 'imports this file';
+      add(candidate, 'imports', 1, reason);
+    }
+
+    const ownImports = importsOf(graph, file);
+    if (ownImports.size > 0 && weights.sharedImports > 0) {
+      // Only files importing at least one of the same things can share imports
+      const candidates = new Set<string>();
+      for (const imported of ownImports) {
+        for (const edge of graph.dependentsOf(imported)) candidates.add(edge.from);
+      }
+      for (const [candidate, modules] of Object.entries(graph.unresolved)) {
+        if (modules.some((module) => ownImports.has(module))) candidates.add(candidate);
+      }
+      for (const candidate of candidates) {
+        // A direct import between the two is counted by the imports signal instead
+        const mine = new Set(ownImports);
+        mine.delete(candidate);
+        const theirs = importsOf(graph, candidate);
+        theirs.delete(file);
+        const shared = [...theirs].filter((imported) => mine.has(imported)).sort();
+        if (shared.length === 0) continue;
+        const union = new Set([...mine, ...theirs]).size;
+        const listed = shared.slice(0, SHARED_IMPORTS_SHOWN).join(', ');
+        const more = shared.length > SHARED_IMPORTS_SHOWN ? ', …' : '';
+        add(
+          candidate,
+          'sharedImports',
+          shared.length / union,
+          `shares ${shared.length} import${shared.length === 1 ? '' : 's'} (${listed}${more})`
+        );
+      }
+    }
+  } else if (weights.imports > 0 || weights.sharedImports > 0) {
+    if (weights.imports > 0) unavailable.push('imports');
+    if (weights.sharedImports > 0) unavailable.push('sharedImports');
+  }
+
+  if (weights.directory > 0) {
+    for (const candidate of indexedFiles) {
+      const distance = directoryDistance(file, candidate);
+      if (distance > MAX_DIRECTORY_DISTANCE) continue;
+      const reason =
+        distance === 0
+          ? 'same directory'
+          : `${distance} director${distance === 1 ? 'y' : 'ies'} away`;
+      add(candidate, 'directory', 1 / (1 + distance), reason);
+    }
+  }
+
+  if (weights.coChange > 0) {
+    const coChange = await getCoChangeCounts(resolvedRoot, file);
+    if (!coChange) {
+      unavailable.push('coChange');
+    } else {
+      for (const [candidate, count] of coChange.counts) {
+        add(
+          candidate,
+          'coChange',
+          count / coChange.commits,
+          `changed together in ${count} of ${coChange.commits} commits`
+        );
+      }
+    }
+  }
+
+  const related = [...contributions]
+    .map(([candidate, list]) => {
+      const weighted = list
+        .map((entry) => ({ ...entry, weighted: entry.value * weights[entry.signal] }))
+        .sort((a, b) => b.weighted - a.weighted);
+      return {
+        file: candidate,
+        score: round(weighted.reduce((sum, entry) => sum + entry.weighted, 0)),
+        reasons: weighted.map((entry) => entry.reason)
+      };
+    })
+    .sort((a, b) => b.score - a.score || a.file.localeCompare(b.file))
+    .slice(0, limit);
+
+  return {
+    status: 'success',
+    file,
+    weights,
+    related,
+    ...(unavailable.length > 0 ? { unavailable } : {})
+  };
+}
@@ -0,0 +1,136 @@
+/**
+ * Qualified-path lookup: `geometry::Circle::area` or `com.example.calc.Calculator.add` to the one
+ * symbol it names. Either separator style matches. A path that is a trailing part of exactly one
+ * qualified path (`Circle::area`) resolves too, flagged as partial; anything that still names
+ * several symbols, such as Java overloads, comes back as ambiguous with every candidate.
+ */
+
+import path from 'path';
+import { detectLanguage } from '../utils/language-detection.js';
+import { readSourceText } from '../utils/text-decoding.js';
+import { extractTreeSitterSymbols, supportsTreeSitter } from '../utils/tree-sitter.js';
+import { qualifiedPathSegments, qualifiedSymbolPath } from '../utils/qualified-path.js';
+import { findCandidateFiles } from './symbol-references.js';
+import { relativeInside } from '../utils/symlinks.js';
+
+const MAX_AMBIGUOUS_CANDIDATES = 20;
+
+export interface ResolvedSymbol {
+  qualifiedPath: string;
+  /** File path relative to the project root */
+  file: string;
+  name: string;
+  kind: string;
+  startLine: number;
+  endLine: number;
+  signature?: string;
+  /** Java parameter list; tells overloads sharing a path apart */
+  parameters?: string;
+}
+
+export interface ResolvePathOptions {
+  /** Only look in this file (relative to the root); skips the keyword index entirely. */
+  file?: string;
+}
+
+export type ResolvePathResult =
+  | { status: 'success'; path: string; symbol: ResolvedSymbol; partial?: boolean }
+  | { status: 'ambiguous'; path: string; candidateCount: number; candidates: ResolvedSymbol[] }
+  | { status: 'error'; message: string };
+
+function sameSegments(a: string[], b: string[]): boolean {
+  return a.length === b.length && a.every((segment, index) => segment === b[index]);
+}
+
+function endsWithSegments(full: string[], suffix: string[]): boolean {
+  return (
+    suffix.length < full.length &&
+    sameSegments(full.slice(full.length - suffix.length), suffix)
+  );
+}
+
+async function fileSymbols(absPath: string, file: string): Promise<ResolvedSymbol[]> {
+  let content: string;
+  try {
+    content = await readSourceText(absPath);
+  } catch {
+    return [];
+  }
+
+  const language = detectLanguage(absPath, content);
+  if (!supportsTreeSitter(language)) return [];
+  const extraction = await extractTreeSitterSymbols(content, language);
+  if (!extraction) return [];
+
+  return extraction.symbols.map((symbol) => ({
+    qualifiedPath: qualifiedSymbolPath(symbol, language, file),
+    file,
+    name: symbol.name,
+    kind: symbol.kind,
+    startLine: symbol.startLine,
+    endLine: symbol.endLine,
+    ...(symbol.signature ? { signature: symbol.signature } : {}),
+    ...(symbol.parameters ? { parameters: symbol.parameters } : {})
+  }));
+}
+
+/** Resolve a qualified path in one root. Candidate files come from the keyword index. */
+export async function resolveQualifiedPath(
+  rootPath: string,
+  qualifiedPath: string,
+  options: ResolvePathOptions = {}
+): Promise<ResolvePathResult> {
+  const normalizedPath = qualifiedPath.trim();
+  const wanted = qualifiedPathSegments(normalizedPath);
+  if (wanted.length === 0) {
+    return { status: 'error', message: 'Path is required' };
+  }
+
+  const resolvedRoot = path.resolve(rootPath);
+  let candidates: Array<{ relPath: string; absPath: string }>;
+  if (options.file) {
+    const absPath = path.resolve(resolvedRoot, options.file);
+    const relPath = relativeInside(resolvedRoot, absPath);
+    if (!relPath) {
+      return { status: 'error', message: `File must be inside the project root: ${options.file}` };
+    }
+    candidates = [{ relPath: relPath.replace(/\\/g, '/'), absPath }];
+  } else {
+    candidates = await findCandidateFiles(resolvedRoot, wanted[wanted.length - 1]);
+  }
+
+  const exact: ResolvedSymbol[] = [];
+  const partial: ResolvedSymbol[] = [];
+  candidates.sort((a, b) => a.relPath.localeCompare(b.relPath));
+  for (const { relPath, absPath } of candidates) {
+    for (const symbol of await fileSymbols(absPath, relPath)) {
+      const segments = qualifiedPathSegments(symbol.qualifiedPath);
+      if (sameSegments(segments, wanted)) exact.push(symbol);
+      else if (endsWithSegments(segments, wanted)) partial.push(symbol);
+    }
+  }
+
+  const matches = exact.length > 0 ? exact : partial;
+  if (matches.length === 0) {
+    return {
+      status: 'error',
+      message:
+        `No symbol has the qualified path ${normalizedPath}. ` +
+        'Search for the bare name to find its qualified path.'
+    };
+  }
+  if (matches.length === 1) {
+    return {
+      status: 'success',
+      path: normalizedPath,
+      symbol: matches[0],
+      ...(exact.length === 0 ? { partial: true } : {})
+    };
+  }
+  return {
+    status: 'ambiguous',
+    path: normalizedPath,
+    candidateCount: matches.length,
+    candidates: matches.slice(0, MAX_AMBIGUOUS_CANDIDATES)
+  };
+}
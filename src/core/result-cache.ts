@@ -0,0 +1,146 @@
+/**
+ * In-memory LRU cache of tool results for repeated calls within a session.
+ *
+ * Keys combine the tool name, its arguments (object keys sorted, unset values dropped) and an
+ * index version. Anything that can change what a tool returns (a reindex, watched file changes,
+ * new memories) calls `invalidate()`, which advances the version, so an entry computed before
+ * the change can never be served after it. Values are stored serialized: every hit hands out a
+ * fresh copy that callers may mutate, and the byte bound measures what is actually held.
+ */
+
+export const DEFAULT_RESULT_CACHE_MAX_ENTRIES = 256;
+export const DEFAULT_RESULT_CACHE_MAX_BYTES = 8 * 1024 * 1024;
+
+export interface ResultCacheOptions {
+  /** Entries kept before the least recently used is evicted; 0 disables the cache */
+  maxEntries?: number;
+  /** Serialized size (UTF-16 code units) kept before evicting; 0 disables the cache */
+  maxBytes?: number;
+}
+
+export interface ResultCacheStats {
+  version: number;
+  entries: number;
+  bytes: number;
+  hits: number;
+  misses: number;
+}
+
+/** JSON with object keys sorted and `undefined`/`null` members dropped. */
+export function normalizeArgs(value: unknown): string {
+  return JSON.stringify(value ?? {}, (_key, member: unknown) => {
+    if (!member || typeof member !== 'object' || Array.isArray(member)) return member;
+    const sorted: Record<string, unknown> = {};
+    for (const key of Object.keys(member).sort()) {
+      const entry = (member as Record<string, unknown>)[key];
+      if (entry !== undefined && entry !== null) sorted[key] = entry;
+    }
+    return sorted;
+  });
+}
+
+function nonNegative(value: number | undefined, fallback: number): number {
+  return value !== undefined && Number.isFinite(value) ? Math.max(0, Math.floor(value)) : fallback;
+}
+
+export class ToolResultCache<T> {
+  readonly maxEntries: number;
+  readonly maxBytes: number;
+  hits = 0;
+  misses = 0;
+
+  private version = 0;
+  private bytes = 0;
+  /** Insertion order is recency order: the first key is the next to evict */
+  private readonly entries = new Map<string, string>();
+
+  constructor(options: ResultCacheOptions = {}) {
+    this.maxEntries = nonNegative(options.maxEntries, DEFAULT_RESULT_CACHE_MAX_ENTRIES);
+    this.maxBytes = nonNegative(options.maxBytes, DEFAULT_RESULT_CACHE_MAX_BYTES);
+  }
+
+  get enabled(): boolean {
+    return this.maxEntries > 0 && this.maxBytes > 0;
+  }
+
+  get indexVersion(): number {
+    return this.version;
+  }
+
+  private key(tool: string, args: unknown): string {
+    return `${this.version}\0${tool}\0${normalizeArgs(args)}`;
+  }
+
+  get(tool: string, args: unknown): T | undefined {
+    if (!this.enabled) return undefined;
+    const key = this.key(tool, args);
+    const stored = this.entries.get(key);
+    if (stored === undefined) {
+      this.misses++;
+      return undefined;
+    }
+    this.entries.delete(key);
+    this.entries.set(key, stored);
+    this.hits++;
+    return JSON.parse(stored) as T;
+  }
+
+  set(tool: string, args: unknown, value: T): void {
+    if (!this.enabled) return;
+    const key = this.key(tool, args);
+    const serialized = JSON.stringify(value);
+    // Single results larger than the whole budget are not worth evicting everything for
+    if (serialized.length > this.maxBytes) return;
+
+    this.remove(key);
+    this.entries.set(key, serialized);
+    this.bytes += serialized.length;
+    while (this.entries.size > this.maxEntries || this.bytes > this.maxBytes) {
+      const oldest = this.entries.keys().next().value;
+      if (oldest === undefined) break;
+      this.remove(oldest);
+    }
+  }
+
+  /** Advance the index version; every result cached so far becomes unreachable and is freed. */
+  invalidate(): void {
+    this.version++;
+    this.entries.clear();
+    this.bytes = 0;
+  }
+
+  stats(): ResultCacheStats {
+    return {
+      version: this.version,
+      entries: this.entries.size,
+      bytes: this.bytes,
+      hits: this.hits,
+      misses: this.misses
+    };
+  }
+
+  private remove(key: string): void {
+    const stored = this.entries.get(key);
+    if (stored === undefined) return;
+    this.bytes -= stored.length;
+    this.entries.delete(key);
+  }
+}
+
+function envCount(value: string | undefined, fallback: number): number {
+  const parsed = Number.parseInt(value ?? '', 10);
+  return Number.isFinite(parsed) && parsed >= 0 ? parsed : fallback;
+}
+
+/**
+ * Options from `CODEBASE_CONTEXT_RESULT_CACHE_SIZE` and `CODEBASE_CONTEXT_RESULT_CACHE_BYTES`;
+ * either set to 0 turns caching off.
+ */
+export function resultCacheOptionsFromEnv(
+  env: NodeJS.ProcessEnv = process.env
+): ResultCacheOptions {
+  return {
+    maxEntries: envCount(env.CODEBASE_CONTEXT_RESULT_CACHE_SIZE, DEFAULT_RESULT_CACHE_MAX_ENTRIES),
+    maxBytes: envCount(env.CODEBASE_CONTEXT_RESULT_CACHE_BYTES, DEFAULT_RESULT_CACHE_MAX_BYTES)
+  };
+}
@@ -5,7 +5,13 @@
 import Fuse from 'fuse.js';
 import path from 'path';
 import { promises as fs } from 'fs';
-import { CodeChunk, SearchResult, SearchFilters, IntelligenceData } from '../types/index.js';
+import {
+  CodeChunk,
+  SearchResult,
+  SearchFilters,
+  IntelligenceData,
+  LineRange
+} from '../types/index.js';
 import { EmbeddingProvider, getEmbeddingProvider } from '../embeddings/index.js';
 import { VectorStorageProvider, getStorageProvider } from '../storage/index.js';
 import { analyzerRegistry } from './analyzer-registry.js';
@@ -14,6 +20,12 @@ import { isTestingRelatedQuery } from '../preflight/query-scope.js';
 import { assessSearchQuality } from './search-quality.js';
 import { rerank } from './reranker.js';
 import { type IndexMeta, readIndexMeta, validateIndexArtifacts } from './index-meta.js';
+import { computeGitActivityBoosts, isGitActivityBoostDisabledByEnv } from './git-activity.js';
+import { getFileChangeStats } from '../utils/git-dates.js';
+import { chunkHasSymbolKind } from '../utils/ast-chunker.js';
+import { snippetDedupKey, type SnippetDedupMode } from './snippet-dedup.js';
+import { isTestChunk, matchesTestFilter } from './test-filter.js';
+import { withSpan } from '../utils/tracing.js';
 import {
   CODEBASE_CONTEXT_DIRNAME,
   INTELLIGENCE_FILENAME,
@@ -32,6 +44,16 @@ export interface SearchOptions {
   candidateFloor?: number;
   /** Enable stage-2 cross-encoder reranking when top scores are ambiguous. Default: true. */
   enableReranker?: boolean;
+  /**
+   * Boost recently and frequently changed files using git history. Default: true.
+   * Off when `CODEBASE_CONTEXT_GIT_BOOST=false`; disable for reproducible rankings.
+   */
+  useGitActivity?: boolean;
+  /**
+   * Collapse results whose chunk text is identical after normalization into one representative
+   * listing the other locations in `duplicates`. Off by default.
+   */
+  dedupSnippets?: SnippetDedupMode;
 }
 
 export type SearchIntentProfile = 'explore' | 'edit' | 'refactor' | 'migrate';
@@ -58,7 +80,8 @@ const DEFAULT_SEARCH_OPTIONS: SearchOptions = {
   enableQueryExpansion: true,
   enableLowConfidenceRescue: true,
   candidateFloor: 30,
-  enableReranker: true
+  enableReranker: true,
+  useGitActivity: true
 };
 
 const QUERY_EXPANSION_HINTS: Array<{ pattern: RegExp; terms: string[] }> = [
@@ -108,6 +131,17 @@ const QUERY_STOP_WORDS = new Set([
   'before'
 ]);
 
+/** Whether `chunk` overlaps any of the ranges listed for its file. */
+export function chunkOverlapsLineRanges(
+  chunk: Pick<CodeChunk, 'relativePath' | 'startLine' | 'endLine'>,
+  lineRanges: Record<string, LineRange[]>
+): boolean {
+  const ranges = lineRanges[chunk.relativePath.replace(/\\/g, '/')] ?? [];
+  return ranges.some(
+    (range) => range.startLine <= chunk.endLine && range.endLine >= chunk.startLine
+  );
+}
+
 export class CodebaseSearcher {
   private rootPath: string;
   private storagePath: string;
@@ -131,6 +165,8 @@ export class CodebaseSearcher {
 
   private importCentrality: Map<string, number> | null = null;
 
+  private gitActivityBoosts: Map<string, number> | null = null;
+
   constructor(rootPath: string) {
     this.rootPath = rootPath;
     this.storagePath = path.join(rootPath, CODEBASE_CONTEXT_DIRNAME, VECTOR_DB_DIRNAME);
@@ -327,6 +363,19 @@ export class CodebaseSearcher {
     return { trend: 'Stable' };
   }
 
+  /** Loaded on first use so searches with the boost disabled never shell out to git. */
+  private async loadGitActivityBoosts(): Promise<Map<string, number>> {
+    if (!this.gitActivityBoosts) {
+      this.gitActivityBoosts = computeGitActivityBoosts(await getFileChangeStats(this.rootPath));
+    }
+    return this.gitActivityBoosts;
+  }
+
+  private toRootRelativePath(filePath: string): string {
+    const normalizedRoot = this.rootPath.replace(/\\/g, '/').replace(/\/?$/, '/');
+    return filePath.replace(/\\/g, '/').replace(normalizedRoot, '');
+  }
+
   private isTestFile(filePath: string): boolean {
     const normalized = filePath.toLowerCase().replace(/\\/g, '/');
     return (
@@ -513,7 +562,10 @@ export class CodebaseSearcher {
     },
     profile: SearchIntentProfile,
     intent: QueryIntent,
-    totalVariantWeight: number
+    totalVariantWeight: number,
+    gitActivityBoosts: Map<string, number> | null,
+    dedupSnippets?: SnippetDedupMode,
+    testsOnly = false
   ): SearchResult[] {
     const likelyWiringQuery = this.isLikelyWiringOrFlowQuery(query);
     const actionQuery = this.isActionOrHowQuery(query);
@@ -563,8 +615,9 @@ export class CodebaseSearcher {
     const theoreticalMaxRrf = totalVariantWeight / (RRF_K + 0);
     const maxRrfScore = Math.max(theoreticalMaxRrf, 0.01);
 
-    // Separate test files from implementation files before scoring
-    const isNonTestQuery = !isTestingRelatedQuery(query);
+    // Separate test files from implementation files before scoring; a search restricted to
+    // tests keeps them all
+    const isNonTestQuery = !testsOnly && !isTestingRelatedQuery(query);
     const implementationChunks: Array<[string, CodeChunk]> = [];
     const testChunks: Array<[string, CodeChunk]> = [];
 
@@ -579,6 +632,7 @@ export class CodebaseSearcher {
     // For non-test queries: filter test files from candidate pool, keep max 1 test file only if < 3 implementation matches
     const chunksToScore = isNonTestQuery ? implementationChunks : Array.from(allChunks.entries());
 
+    const contentByResult = new Map<SearchResult, string>();
     const scoredResults = chunksToScore
       .map(([id, chunk]) => {
         // RRF score normalized to [0,1] range. Boosts below are unclamped
@@ -658,9 +712,7 @@ export class CodebaseSearcher {
         }
 
         if (this.importCentrality) {
-          const normalizedRoot = this.rootPath.replace(/\\/g, '/').replace(/\/?$/, '/');
-          const normalizedPath = chunk.filePath.replace(/\\/g, '/').replace(normalizedRoot, '');
-          const centrality = this.importCentrality.get(normalizedPath);
+          const centrality = this.importCentrality.get(this.toRootRelativePath(chunk.filePath));
           if (centrality !== undefined && centrality > 0.1) {
             // Boost files with high centrality (many imports)
             const centralityBoost = 1.0 + centrality * 0.15; // Up to +15% for max centrality
@@ -668,6 +720,14 @@ export class CodebaseSearcher {
           }
         }
 
+        // Actively developed files outrank ones untouched for a long time
+        if (gitActivityBoosts) {
+          const activityBoost = gitActivityBoosts.get(this.toRootRelativePath(chunk.filePath));
+          if (activityBoost) {
+            combinedScore *= 1 + activityBoost;
+          }
+        }
+
         // Detect pattern trend and apply momentum boost
         const { trend, warning } = this.detectChunkTrend(chunk);
         if (trend === 'Rising') {
@@ -679,9 +739,10 @@ export class CodebaseSearcher {
         const summary = this.generateSummary(chunk);
         const snippet = this.generateSnippet(chunk.content ?? '');
 
-        return {
+        const result = {
           summary,
           snippet,
+          fullContent: chunk.content,
           filePath: chunk.filePath,
           startLine: chunk.startLine,
           endLine: chunk.endLine,
@@ -695,6 +756,8 @@ export class CodebaseSearcher {
           trend,
           patternWarning: warning
         } as SearchResult;
+        contentByResult.set(result, chunk.content ?? '');
+        return result;
       })
       .sort((a, b) => b.score - a.score);
 
@@ -712,30 +775,51 @@ export class CodebaseSearcher {
       scoredResults.sort((a, b) => b.score - a.score);
     }
 
-    // File-level deduplication
+    // File-level deduplication; with dedupSnippets, identical code in other files collapses into
+    // the best-scoring copy before it can take a slot
     const seenFiles = new Set<string>();
+    const representatives = new Map<string, SearchResult>();
     const deduped: SearchResult[] = [];
     for (const result of scoredResults) {
       const normalizedPath = result.filePath.toLowerCase().replace(/\\/g, '/');
       if (seenFiles.has(normalizedPath)) continue;
+      const contentKey = dedupSnippets
+        ? snippetDedupKey(contentByResult.get(result) ?? '', dedupSnippets)
+        : null;
+      const representative = contentKey ? representatives.get(contentKey) : undefined;
+      if (representative) {
+        representative.duplicates = [
+          ...(representative.duplicates ?? []),
+          { filePath: result.filePath, startLine: result.startLine, endLine: result.endLine }
+        ];
+        continue;
+      }
+      // Past the limit, keep scanning only to list the remaining copies of kept results
+      if (deduped.length >= limit) {
+        if (!dedupSnippets) break;
+        continue;
+      }
       seenFiles.add(normalizedPath);
+      if (contentKey) representatives.set(contentKey, result);
       deduped.push(result);
-      if (deduped.length >= limit) break;
     }
 
     // SEARCH-01: Symbol-level deduplication
-    // Within each symbol group (symbolPath), keep only the highest-scoring chunk
+    // Within each symbol group (qualified path or symbolPath), keep only the highest-scoring chunk
     const seenSymbols = new Map<string, SearchResult>();
     const symbolDeduped: SearchResult[] = [];
     for (const result of deduped) {
-      const symbolPath = result.metadata?.symbolPath;
+      const symbolPath = result.metadata?.qualifiedPath ?? result.metadata?.symbolPath;
       if (!symbolPath) {
         // No symbol info, keep as-is
         symbolDeduped.push(result);
         continue;
       }
 
-      const symbolPathKey = Array.isArray(symbolPath) ? symbolPath.join('.') : String(symbolPath);
+      // Overloads share a path and differ only in their parameters
+      const symbolPathKey =
+        (Array.isArray(symbolPath) ? symbolPath.join('.') : String(symbolPath)) +
+        (result.metadata?.symbolParameters ?? '');
       const existing = seenSymbols.get(symbolPathKey);
       if (!existing || result.score > existing.score) {
         if (existing) {
@@ -776,6 +860,7 @@ export class CodebaseSearcher {
         finalResults.push({
           summary,
           snippet,
+          fullContent: bestTestChunk.chunk.content,
           filePath: bestTestChunk.chunk.filePath,
           startLine: bestTestChunk.chunk.startLine,
           endLine: bestTestChunk.chunk.endLine,
@@ -844,7 +929,15 @@ export class CodebaseSearcher {
     if (useSemanticSearch && this.embeddingProvider && this.storageProvider) {
       try {
         for (const variant of queryVariants) {
-          const vectorResults = await this.semanticSearch(variant.query, candidateLimit, filters);
+          const vectorResults = await withSpan(
+            'search.semantic',
+            { limit: candidateLimit },
+            async (span) => {
+              const found = await this.semanticSearch(variant.query, candidateLimit, filters);
+              span.record({ results: found.length });
+              return found;
+            }
+          );
 
           // Assign ranks based on retrieval order (0-indexed)
           vectorResults.forEach((result, index) => {
@@ -874,7 +967,15 @@ export class CodebaseSearcher {
     if (useKeywordSearch && this.fuseIndex) {
       try {
         for (const variant of queryVariants) {
-          const keywordResults = await this.keywordSearch(variant.query, candidateLimit, filters);
+          const keywordResults = await withSpan(
+            'search.keyword',
+            { limit: candidateLimit },
+            async (span) => {
+              const found = await this.keywordSearch(variant.query, candidateLimit, filters);
+              span.record({ results: found.length });
+              return found;
+            }
+          );
 
           // Assign ranks based on retrieval order (0-indexed)
           keywordResults.forEach((result, index) => {
@@ -906,6 +1007,19 @@ export class CodebaseSearcher {
     limit: number = 5,
     filters?: SearchFilters,
     options: SearchOptions = DEFAULT_SEARCH_OPTIONS
+  ): Promise<SearchResult[]> {
+    return withSpan('search.query', { limit, profile: options.profile }, async (span) => {
+      const results = await this.searchUntraced(query, limit, filters, options);
+      span.record({ results: results.length });
+      return results;
+    });
+  }
+
+  private async searchUntraced(
+    query: string,
+    limit: number,
+    filters: SearchFilters | undefined,
+    options: SearchOptions
   ): Promise<SearchResult[]> {
     if (!this.initialized) {
       await this.initialize();
@@ -922,7 +1036,9 @@ export class CodebaseSearcher {
       enableQueryExpansion,
       enableLowConfidenceRescue,
       candidateFloor,
-      enableReranker
+      enableReranker,
+      useGitActivity,
+      dedupSnippets
     } = merged;
 
     const { intent, weights: intentWeights } = this.classifyQueryIntent(query);
@@ -930,6 +1046,11 @@ export class CodebaseSearcher {
     const finalSemanticWeight = merged.semanticWeight ?? intentWeights.semantic;
     const finalKeywordWeight = merged.keywordWeight ?? intentWeights.keyword;
 
+    const gitActivityBoosts =
+      useGitActivity && !isGitActivityBoostDisabledByEnv()
+        ? await this.loadGitActivityBoosts()
+        : null;
+
     const candidateLimit = Math.max(limit * 2, candidateFloor || 30);
     const primaryVariants = this.buildQueryVariants(query, enableQueryExpansion ? 1 : 0);
 
@@ -952,7 +1073,10 @@ export class CodebaseSearcher {
       primaryMatches,
       (profile || 'explore') as SearchIntentProfile,
       intent,
-      primaryTotalWeight
+      primaryTotalWeight,
+      gitActivityBoosts,
+      dedupSnippets,
+      filters?.tests === 'only'
     );
 
     let bestResults = primaryResults;
@@ -985,7 +1109,10 @@ export class CodebaseSearcher {
             rescueMatches,
             (profile || 'explore') as SearchIntentProfile,
             intent,
-            rescueTotalWeight
+            rescueTotalWeight,
+            gitActivityBoosts,
+            dedupSnippets,
+            filters?.tests === 'only'
           );
 
           bestResults = this.pickBetterResultSet(query, primaryResults, rescueResults);
@@ -1078,8 +1205,17 @@ export class CodebaseSearcher {
     const queryVector = await this.embeddingProvider.embed(query);
 
     const results = await this.storageProvider.search(queryVector, limit, filters);
+    // The storage prefilters on kinds and files can over-match; the exact checks happen here
+    const kinds = filters?.kinds;
+    const lineRanges = filters?.lineRanges;
+    const matching = results.filter(
+      (r) =>
+        (!kinds || kinds.length === 0 || chunkHasSymbolKind(r.chunk, kinds)) &&
+        (!lineRanges || chunkOverlapsLineRanges(r.chunk, lineRanges)) &&
+        matchesTestFilter(isTestChunk(r.chunk), filters?.tests)
+    );
 
-    return results.map((r) => ({
+    return matching.map((r) => ({
       chunk: r.chunk,
       score: r.score
     }));
@@ -1118,6 +1254,16 @@ export class CodebaseSearcher {
             return false;
           }
         }
+        const kinds = filters.kinds;
+        if (kinds && kinds.length > 0 && !chunkHasSymbolKind(chunk, kinds)) {
+          return false;
+        }
+        if (filters.lineRanges && !chunkOverlapsLineRanges(chunk, filters.lineRanges)) {
+          return false;
+        }
+        if (!matchesTestFilter(isTestChunk(chunk), filters.tests)) {
+          return false;
+        }
 
         return true;
       });
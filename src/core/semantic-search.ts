@@ -0,0 +1,87 @@
+/**
+ * Pure vector search: embed the query with the active provider and return the nearest stored
+ * chunks by cosine similarity, with no keyword blending, reranking or boosts. Scores are raw
+ * similarities, so callers can mix them with their own lexical scores.
+ */
+
+import path from 'path';
+import { CODEBASE_CONTEXT_DIRNAME, VECTOR_DB_DIRNAME } from '../constants/codebase-context.js';
+import { getEmbeddingProvider } from '../embeddings/index.js';
+import { getStorageProvider } from '../storage/index.js';
+import { readIndexMeta, validateIndexArtifacts } from './index-meta.js';
+import { isTestChunk, matchesTestFilter } from './test-filter.js';
+import type { TestFilter } from '../types/index.js';
+
+export const DEFAULT_SEMANTIC_K = 10;
+export const MAX_SEMANTIC_K = 50;
+
+export interface SemanticMatch {
+  /** File path relative to the project root */
+  file: string;
+  startLine: number;
+  endLine: number;
+  /** Cosine similarity in [0, 1] */
+  score: number;
+  language: string;
+  componentType?: string;
+  layer?: string;
+  symbolName?: string;
+  symbolKind?: string;
+  /** Set for files indexed as plain lines, which have no symbols */
+  textOnly?: boolean;
+  /** Text of the chunk, present when requested */
+  content?: string;
+}
+
+export interface SemanticSearchResult {
+  /** `provider:model` that embedded the query */
+  model: string;
+  matches: SemanticMatch[];
+}
+
+/**
+ * Top-`k` chunks for `query`. Throws IndexCorruptedError when the index is missing or from an
+ * incompatible version; an index built with `skipEmbedding` has no vectors and yields no matches.
+ */
+export async function semanticSearch(
+  rootPath: string,
+  query: string,
+  options: { k?: number; includeContent?: boolean; tests?: TestFilter } = {}
+): Promise<SemanticSearchResult> {
+  const k = Math.min(Math.max(1, Math.floor(options.k ?? DEFAULT_SEMANTIC_K)), MAX_SEMANTIC_K);
+  const { tests } = options;
+  await validateIndexArtifacts(rootPath, await readIndexMeta(rootPath));
+
+  const provider = await getEmbeddingProvider();
+  const storage = await getStorageProvider({
+    path: path.join(rootPath, CODEBASE_CONTEXT_DIRNAME, VECTOR_DB_DIRNAME)
+  });
+  // The store prefilters on the recorded test flag; test files by path are dropped here, so a
+  // filtered search asks for extra candidates
+  const results = await storage.search(
+    await provider.embed(query),
+    tests ? k * 4 : k,
+    tests ? { tests } : undefined
+  );
+
+  return {
+    model: `${provider.name}:${provider.modelName}`,
+    matches: results
+      .filter(({ chunk }) => matchesTestFilter(isTestChunk(chunk), tests))
+      .sort((a, b) => b.score - a.score)
+      .slice(0, k)
+      .map(({ chunk, score }) => ({
+        file: chunk.relativePath.replace(/\\/g, '/'),
+        startLine: chunk.startLine,
+        endLine: chunk.endLine,
+        score: Math.round(score * 1000) / 1000,
+        language: chunk.language,
+        ...(chunk.componentType ? { componentType: chunk.componentType } : {}),
+        ...(chunk.layer ? { layer: chunk.layer } : {}),
+        ...(chunk.metadata?.symbolName ? { symbolName: chunk.metadata.symbolName } : {}),
+        ...(chunk.metadata?.symbolKind ? { symbolKind: chunk.metadata.symbolKind } : {}),
+        ...(chunk.metadata?.textOnly ? { textOnly: true } : {}),
+        ...(options.includeContent ? { content: chunk.content } : {})
+      }))
+  };
+}
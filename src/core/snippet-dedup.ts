@@ -0,0 +1,31 @@
+/**
+ * Keys for collapsing byte-identical code (generated files, copy-pasted boilerplate) in search
+ * results. Opt-in through `SearchOptions.dedupSnippets`.
+ */
+
+import { createHash } from 'crypto';
+
+/**
+ * `exact` ignores line endings, trailing whitespace and surrounding blank lines; `whitespace`
+ * also ignores indentation and any other whitespace differences.
+ */
+export type SnippetDedupMode = 'exact' | 'whitespace';
+
+export const SNIPPET_DEDUP_MODES: readonly SnippetDedupMode[] = ['exact', 'whitespace'];
+
+export function isSnippetDedupMode(value: unknown): value is SnippetDedupMode {
+  return typeof value === 'string' && (SNIPPET_DEDUP_MODES as readonly string[]).includes(value);
+}
+
+export function normalizeSnippet(content: string, mode: SnippetDedupMode): string {
+  const lines = content.split(/\r?\n/).map((line) => line.trimEnd());
+  const text = lines.join('\n').trim();
+  return mode === 'whitespace' ? text.replace(/\s+/g, ' ') : text;
+}
+
+/** Hash of the normalized text; empty content gets no key and is never collapsed. */
+export function snippetDedupKey(content: string, mode: SnippetDedupMode): string | null {
+  const normalized = normalizeSnippet(content, mode);
+  if (!normalized) return null;
+  return createHash('sha256').update(normalized).digest('hex').slice(0, 16);
+}
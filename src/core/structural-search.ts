@@ -0,0 +1,133 @@
+/**
+ * Structural search: run one tree-sitter query over every indexed file of a language and collect
+ * the captured nodes. Matches and wall time are capped, so a query that matches everything or
+ * backtracks badly returns partial results instead of stalling the server.
+ */
+
+import path from 'path';
+import { detectLanguage } from '../utils/language-detection.js';
+import { readSourceText } from '../utils/text-decoding.js';
+import { compileTreeSitterQuery, runTreeSitterQuery } from '../utils/tree-sitter.js';
+
+export const DEFAULT_STRUCTURAL_MATCH_LIMIT = 50;
+export const MAX_STRUCTURAL_MATCH_LIMIT = 200;
+export const DEFAULT_STRUCTURAL_TIMEOUT_MS = 2000;
+
+/** Captured text longer than this is cut, keeping responses small when a capture is a whole body */
+const MAX_CAPTURE_TEXT_CHARS = 240;
+
+export interface StructuralMatch {
+  /** File path relative to the project root */
+  file: string;
+  /** Capture name without the leading `@` */
+  capture: string;
+  startLine: number;
+  endLine: number;
+  startColumn: number;
+  text: string;
+}
+
+export interface StructuralSearchOptions {
+  /** S-expression query in tree-sitter syntax */
+  query: string;
+  language: string;
+  limit?: number;
+  timeoutMs?: number;
+}
+
+export interface StructuralSearchResult {
+  matches: StructuralMatch[];
+  filesSearched: number;
+  /** Set when more captures existed than `limit` allowed */
+  truncated: boolean;
+  /** Set when the time budget ran out before every file was searched */
+  timedOut: boolean;
+}
+
+/**
+ * Content only changes the detected language for `.h` headers (C or C++) and files without a
+ * known extension, so every other file of the wrong language is ruled out before it is read.
+ */
+function mayBeLanguage(pathLanguage: string, language: string): boolean {
+  return (
+    pathLanguage === language ||
+    pathLanguage === 'plaintext' ||
+    (pathLanguage === 'c' && language === 'cpp')
+  );
+}
+
+function clipText(text: string): string {
+  return text.length > MAX_CAPTURE_TEXT_CHARS
+    ? `${text.slice(0, MAX_CAPTURE_TEXT_CHARS)}...`
+    : text;
+}
+
+/**
+ * Search `files` (relative to `rootPath`) with a tree-sitter query. Throws TreeSitterQueryError
+ * when the query does not compile for the language's grammar.
+ */
+export async function structuralSearch(
+  rootPath: string,
+  files: string[],
+  options: StructuralSearchOptions
+): Promise<StructuralSearchResult> {
+  const limit = Math.min(
+    Math.max(1, Math.floor(options.limit ?? DEFAULT_STRUCTURAL_MATCH_LIMIT)),
+    MAX_STRUCTURAL_MATCH_LIMIT
+  );
+  const deadline = Date.now() + (options.timeoutMs ?? DEFAULT_STRUCTURAL_TIMEOUT_MS);
+  const compiled = await compileTreeSitterQuery(options.language, options.query);
+  const matches: StructuralMatch[] = [];
+  let filesSearched = 0;
+  let truncated = false;
+  let timedOut = false;
+
+  try {
+    for (const file of files) {
+      if (Date.now() > deadline) {
+        timedOut = true;
+        break;
+      }
+
+      const absolutePath = path.resolve(rootPath, file);
+      if (!mayBeLanguage(detectLanguage(absolutePath), options.language)) continue;
+
+      let content: string;
+      try {
+        content = await readSourceText(absolutePath);
+      } catch {
+        continue;
+      }
+      if (detectLanguage(absolutePath, content) !== options.language) continue;
+
+      const result = await runTreeSitterQuery(content, compiled, { deadline });
+      if (!result) continue;
+      filesSearched++;
+
+      for (const capture of result.captures) {
+        if (matches.length >= limit) {
+          truncated = true;
+          break;
+        }
+        matches.push({
+          file: file.replace(/\\/g, '/'),
+          capture: capture.name,
+          startLine: capture.startLine,
+          endLine: capture.endLine,
+          startColumn: capture.startColumn,
+          text: clipText(capture.text)
+        });
+      }
+
+      if (result.timedOut) {
+        timedOut = true;
+        break;
+      }
+      if (truncated) break;
+    }
+  } finally {
+    compiled.dispose();
+  }
+
+  return { matches, filesSearched, truncated, timedOut };
+}
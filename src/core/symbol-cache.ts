@@ -0,0 +1,88 @@
+/**
+ * On-disk cache of tree-sitter symbol extractions.
+ * Entries are keyed by a hash of (language, file contents), so an unchanged file never gets
+ * re-parsed — even after a checkout that bumps every mtime — and an edited file always does.
+ */
+
+import { promises as fs } from 'fs';
+import path from 'path';
+import { SYMBOL_CACHE_FILENAME } from '../constants/codebase-context.js';
+import type { SymbolExtractionCache, TreeSitterSymbolExtraction } from '../utils/tree-sitter.js';
+
+/**
+ * Bump whenever extraction output changes shape or content for the same input
+ * (new symbol kinds, different ranges, extra fields). A mismatch discards the whole cache.
+ */
+export const SYMBOL_CACHE_VERSION = 12;
+
+interface SymbolCacheFile {
+  version: number;
+  entries: Record<string, TreeSitterSymbolExtraction>;
+}
+
+export class DiskSymbolCache implements SymbolExtractionCache {
+  readonly cachePath: string;
+  hits = 0;
+  misses = 0;
+
+  private readonly stored: Map<string, TreeSitterSymbolExtraction>;
+  private readonly used = new Map<string, TreeSitterSymbolExtraction>();
+
+  private constructor(cachePath: string, stored: Map<string, TreeSitterSymbolExtraction>) {
+    this.cachePath = cachePath;
+    this.stored = stored;
+  }
+
+  /** Load the cache in `cacheDir`. Missing, corrupt or outdated caches start empty. */
+  static async load(cacheDir: string): Promise<DiskSymbolCache> {
+    const cachePath = path.join(cacheDir, SYMBOL_CACHE_FILENAME);
+    const stored = new Map<string, TreeSitterSymbolExtraction>();
+
+    try {
+      const parsed = JSON.parse(await fs.readFile(cachePath, 'utf-8')) as Partial<SymbolCacheFile>;
+      if (parsed.version === SYMBOL_CACHE_VERSION && parsed.entries) {
+        for (const [key, extraction] of Object.entries(parsed.entries)) {
+          if (extraction && Array.isArray(extraction.symbols)) {
+            stored.set(key, extraction);
+          }
+        }
+      }
+    } catch {
+      // Cold cache
+    }
+
+    return new DiskSymbolCache(cachePath, stored);
+  }
+
+  get size(): number {
+    return this.stored.size;
+  }
+
+  get(key: string): TreeSitterSymbolExtraction | undefined {
+    const extraction = this.used.get(key) ?? this.stored.get(key);
+    if (extraction) {
+      this.hits++;
+      this.used.set(key, extraction);
+    } else {
+      this.misses++;
+    }
+    return extraction;
+  }
+
+  set(key: string, extraction: TreeSitterSymbolExtraction): void {
+    this.used.set(key, extraction);
+  }
+
+  /**
+   * Persist the entries touched during this run. Entries for content that no longer exists
+   * anywhere in the tree are dropped, so the cache does not grow without bound.
+   */
+  async save(): Promise<void> {
+    const file: SymbolCacheFile = {
+      version: SYMBOL_CACHE_VERSION,
+      entries: Object.fromEntries(this.used)
+    };
+    await fs.mkdir(path.dirname(this.cachePath), { recursive: true });
+    await fs.writeFile(this.cachePath, JSON.stringify(file));
+  }
+}
@@ -0,0 +1,352 @@
+/**
+ * Budgeted context assembly around a symbol: its definition, then the places that use it,
+ * then the import statements that pull it in, packed until the token budget runs out.
+ * Snippets are produced lazily in that order and streamed out as soon as they are packed.
+ */
+
+import path from 'path';
+import { detectLanguage } from '../utils/language-detection.js';
+import { readSourceText } from '../utils/text-decoding.js';
+import { extractTreeSitterSymbols } from '../utils/tree-sitter.js';
+import { findScopedReferencesInRoots, type ReferenceLocation } from './symbol-references.js';
+import { matchesTestFilter, testLineRanges } from './test-filter.js';
+import { withSpan } from '../utils/tracing.js';
+import type { LineRange, TestFilter } from '../types/index.js';
+import type { DetailLevel } from './detail-level.js';
+import {
+  DEFAULT_TOKEN_ESTIMATOR,
+  packSnippetStream,
+  SnippetBudget,
+  type PackedSnippet,
+  type SnippetRole,
+  type TokenEstimator
+} from './token-budget.js';
+
+export const DEFAULT_CONTEXT_MAX_TOKENS = 800;
+
+const REFERENCE_WINDOW_LINES = 1;
+const MAX_REFERENCE_CANDIDATES = 50;
+// References are grouped by file, so a few open files are enough to avoid re-reads
+const FILE_CACHE_LIMIT = 4;
+const IMPORT_LINE_PATTERN =
+  /^\s*(import\b|from\s+\S+\s+import\b|export\s+.*\bfrom\b|use\s|#include\b)|\brequire\(/;
+
+export interface ContextSnippet {
+  role: SnippetRole;
+  file: string;
+  /** Workspace root of `file`, when context spans several roots */
+  root?: string;
+  startLine: number;
+  endLine: number;
+  text: string;
+}
+
+export interface SymbolContextOptions {
+  maxTokens?: number;
+  estimator?: TokenEstimator;
+  /** Start definition snippets at the doc comment written above them */
+  includeDocs?: boolean;
+  /** Cut definition snippets down to the signature, dropping the body */
+  signaturesOnly?: boolean;
+  /** Leave out test code, or use nothing but test code, for every snippet */
+  tests?: TestFilter;
+  /**
+   * `names` cuts every snippet to its own line (a definition's first line, a usage's line),
+   * `signatures` cuts definitions to their signature and `full` keeps whole definitions from
+   * their doc comment. Overrides `includeDocs` and `signaturesOnly` when set.
+   */
+  detail?: DetailLevel;
+}
+
+export interface SymbolContextResult {
+  status: 'success';
+  symbol: string;
+  maxTokens: number;
+  tokensUsed: number;
+  droppedSnippets: number;
+  snippets: Array<PackedSnippet<ContextSnippet>>;
+}
+
+/** Result of a call whose snippets went out as progress notifications: totals only. */
+export interface StreamedSymbolContextResult extends Omit<SymbolContextResult, 'snippets'> {
+  streamedSnippets: number;
+}
+
+export interface SymbolContextStream {
+  status: 'success';
+  symbol: string;
+  maxTokens: number;
+  /** Packed snippets in priority order, produced on demand */
+  snippets: AsyncGenerator<PackedSnippet<ContextSnippet>>;
+  /** Budget totals; final once `snippets` is exhausted */
+  totals(): { tokensUsed: number; droppedSnippets: number };
+}
+
+interface DefinitionOptions {
+  includeDocs: boolean;
+  signaturesOnly: boolean;
+  /** One line per snippet: where each definition starts and each usage is */
+  linesOnly: boolean;
+  tests?: TestFilter;
+}
+
+async function readLines(
+  cache: Map<string, string[] | null>,
+  absPath: string
+): Promise<string[] | null> {
+  if (cache.has(absPath)) return cache.get(absPath) ?? null;
+
+  let lines: string[] | null;
+  try {
+    lines = (await readSourceText(absPath)).split('\n');
+  } catch {
+    lines = null;
+  }
+  if (cache.size >= FILE_CACHE_LIMIT) {
+    const oldest = cache.keys().next().value;
+    if (oldest !== undefined) cache.delete(oldest);
+  }
+  cache.set(absPath, lines);
+  return lines;
+}
+
+function windowSnippet(
+  role: SnippetRole,
+  location: ReferenceLocation,
+  lines: string[],
+  radius: number
+): ContextSnippet {
+  const { file, root, line } = location;
+  const startLine = Math.max(1, line - radius);
+  const endLine = Math.min(lines.length, line + radius);
+  return {
+    role,
+    file,
+    ...(root ? { root } : {}),
+    startLine,
+    endLine,
+    text: lines.slice(startLine - 1, endLine).join('\n')
+  };
+}
+
+async function definitionSnippet(
+  absPath: string,
+  symbol: string,
+  location: ReferenceLocation,
+  lines: string[],
+  { includeDocs, signaturesOnly, linesOnly }: DefinitionOptions
+): Promise<ContextSnippet> {
+  const content = lines.join('\n');
+  const extraction = await extractTreeSitterSymbols(content, detectLanguage(absPath, content));
+  const match = extraction?.symbols
+    .filter((s) => s.name === symbol && s.startLine <= location.line && s.endLine >= location.line)
+    .sort((a, b) => a.endLine - a.startLine - (b.endLine - b.startLine))[0];
+
+  if (!match) {
+    return windowSnippet('definition', location, lines, linesOnly ? 0 : 2);
+  }
+  if (linesOnly) {
+    return windowSnippet('definition', { ...location, line: match.startLine }, lines, 0);
+  }
+
+  // Python docstrings sit inside the body, so only comments above the symbol widen the range
+  const startLine = includeDocs && match.docStartLine ? match.docStartLine : match.startLine;
+  if (signaturesOnly && match.signature) {
+    // The signature starts at the symbol, so it gets the first line's indentation back
+    const indent = lines[match.startLine - 1]?.match(/^\s*/)?.[0] ?? '';
+    const docLines = lines.slice(startLine - 1, match.startLine - 1);
+    return {
+      role: 'definition',
+      file: location.file,
+      ...(location.root ? { root: location.root } : {}),
+      startLine,
+      endLine: match.startLine + match.signature.split('\n').length - 1,
+      text: [...docLines, indent + match.signature].join('\n')
+    };
+  }
+  return {
+    role: 'definition',
+    file: location.file,
+    ...(location.root ? { root: location.root } : {}),
+    startLine,
+    endLine: match.endLine,
+    text: lines.slice(startLine - 1, match.endLine).join('\n')
+  };
+}
+
+/**
+ * Candidate snippets in packing order: definitions, then references, then import lines.
+ * Only line ranges of earlier candidates are kept (to skip locations they already cover), so
+ * memory stays bounded by the few cached files rather than the size of the context.
+ */
+async function* contextCandidates(
+  roots: string[],
+  symbol: string,
+  definitions: ReferenceLocation[],
+  references: ReferenceLocation[],
+  definitionOptions: DefinitionOptions
+): AsyncGenerator<ContextSnippet> {
+  const fileCache = new Map<string, string[] | null>();
+  const ranges: Array<{ file: string; root?: string; startLine: number; endLine: number }> = [];
+  const absolutePathOf = (location: ReferenceLocation) =>
+    path.join(location.root ?? roots[0], location.file);
+  const covered = (location: ReferenceLocation) =>
+    ranges.some(
+      (r) =>
+        r.file === location.file &&
+        r.root === location.root &&
+        r.startLine <= location.line &&
+        r.endLine >= location.line
+    );
+  const record = (snippet: ContextSnippet) => {
+    const { file, root, startLine, endLine } = snippet;
+    ranges.push({ file, root, startLine, endLine });
+    return snippet;
+  };
+  // Test ranges are looked up once per file, and only when filtering
+  const { tests } = definitionOptions;
+  const testRanges = new Map<string, Promise<LineRange[]>>();
+  const passesTestFilter = async (location: ReferenceLocation, lines: string[]) => {
+    if (!tests) return true;
+    const absPath = absolutePathOf(location);
+    let fileRanges = testRanges.get(absPath);
+    if (!fileRanges) {
+      fileRanges = testLineRanges(location.file, lines.join('\n'));
+      testRanges.set(absPath, fileRanges);
+    }
+    const isTest = (await fileRanges).some(
+      (r) => r.startLine <= location.line && r.endLine >= location.line
+    );
+    return matchesTestFilter(isTest, tests);
+  };
+
+  for (const location of definitions) {
+    const absPath = absolutePathOf(location);
+    const lines = await readLines(fileCache, absPath);
+    if (!lines || !(await passesTestFilter(location, lines))) continue;
+    yield record(await definitionSnippet(absPath, symbol, location, lines, definitionOptions));
+  }
+
+  const imports: ReferenceLocation[] = [];
+  for (const location of references) {
+    if (covered(location)) continue;
+    const lines = await readLines(fileCache, absolutePathOf(location));
+    if (!lines || !(await passesTestFilter(location, lines))) continue;
+
+    if (IMPORT_LINE_PATTERN.test(lines[location.line - 1] ?? '')) {
+      imports.push(location);
+    } else {
+      const radius = definitionOptions.linesOnly ? 0 : REFERENCE_WINDOW_LINES;
+      yield record(windowSnippet('reference', location, lines, radius));
+    }
+  }
+
+  for (const location of imports) {
+    const lines = await readLines(fileCache, absolutePathOf(location));
+    if (!lines) continue;
+    yield record(windowSnippet('import', location, lines, 0));
+  }
+}
+
+function definitionOptionsFor(options: SymbolContextOptions): DefinitionOptions {
+  switch (options.detail) {
+    case 'names':
+      return { includeDocs: false, signaturesOnly: false, linesOnly: true };
+    case 'signatures':
+      return { includeDocs: false, signaturesOnly: true, linesOnly: false };
+    case 'full':
+      return { includeDocs: true, signaturesOnly: false, linesOnly: false };
+    default:
+      return {
+        includeDocs: options.includeDocs === true,
+        signaturesOnly: options.signaturesOnly === true,
+        linesOnly: false
+      };
+  }
+}
+
+/**
+ * Streaming form of `buildSymbolContext`. Snippets are read, packed and handed out one at a
+ * time as the consumer pulls them, so a slow consumer never makes the assembler buffer ahead.
+ * `rootPath` may list several workspace roots; each snippet then carries its root.
+ */
+export async function streamSymbolContext(
+  rootPath: string | string[],
+  symbol: string,
+  options: SymbolContextOptions = {}
+): Promise<SymbolContextStream | { status: 'error'; message: string }> {
+  const maxTokens =
+    options.maxTokens !== undefined && Number.isFinite(options.maxTokens) && options.maxTokens > 0
+      ? Math.floor(options.maxTokens)
+      : DEFAULT_CONTEXT_MAX_TOKENS;
+  const estimator = options.estimator ?? DEFAULT_TOKEN_ESTIMATOR;
+
+  const roots = Array.isArray(rootPath) ? rootPath : [rootPath];
+  const refs = await withSpan('context.references', { symbol }, async (span) => {
+    const found = await findScopedReferencesInRoots(roots, symbol, {
+      limit: MAX_REFERENCE_CANDIDATES
+    });
+    if (found.status === 'success') {
+      span.record({ references: found.referenceCount, definitions: found.definitions.length });
+    }
+    return found;
+  });
+  if (refs.status === 'error') {
+    return refs;
+  }
+
+  const budget = new SnippetBudget<ContextSnippet>(maxTokens, estimator);
+  // References beyond the lookup limit were never candidates; count them as dropped too.
+  const unseenReferences = Math.max(0, refs.referenceCount - refs.references.length);
+
+  return {
+    status: 'success',
+    symbol: refs.symbol,
+    maxTokens,
+    snippets: packSnippetStream(
+      contextCandidates(
+        roots,
+        refs.symbol,
+        refs.definitions,
+        refs.references,
+        {
+          ...definitionOptionsFor(options),
+          ...(options.tests ? { tests: options.tests } : {})
+        }
+      ),
+      budget
+    ),
+    totals: () => ({
+      tokensUsed: budget.tokensUsed,
+      droppedSnippets: budget.droppedSnippets + unseenReferences
+    })
+  };
+}
+
+/**
+ * `rootPath` may list several workspace roots; context is then gathered across all of them
+ * and each snippet carries its root.
+ */
+export async function buildSymbolContext(
+  rootPath: string | string[],
+  symbol: string,
+  options: SymbolContextOptions = {}
+): Promise<SymbolContextResult | { status: 'error'; message: string }> {
+  const stream = await streamSymbolContext(rootPath, symbol, options);
+  if (stream.status === 'error') {
+    return stream;
+  }
+
+  const snippets: SymbolContextResult['snippets'] = [];
+  for await (const snippet of stream.snippets) {
+    snippets.push(snippet);
+  }
+
+  return {
+    status: 'success',
+    symbol: stream.symbol,
+    maxTokens: stream.maxTokens,
+    ...stream.totals(),
+    snippets
+  };
+}
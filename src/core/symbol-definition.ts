@@ -0,0 +1,267 @@
+/**
+ * Exact definitions: the full Tree-sitter span of every symbol with a given name, as source text
+ * plus byte and line ranges. Nothing is guessed; when several symbols share the name, all of
+ * them are returned. A C/C++ header prototype resolves to the definition in its sibling source
+ * file, which then records where it was declared.
+ */
+
+import path from 'path';
+import { detectLanguage } from '../utils/language-detection.js';
+import { readSourceText } from '../utils/text-decoding.js';
+import {
+  extractTreeSitterSymbols,
+  supportsTreeSitter,
+  type TreeSitterSymbol
+} from '../utils/tree-sitter.js';
+import { findCandidateFiles } from './symbol-references.js';
+import type { SymbolIndex } from './symbol-index.js';
+import { relativeInside } from '../utils/symlinks.js';
+
+export const DEFAULT_DEFINITION_MAX_LINES = 80;
+
+const C_HEADER_EXTENSIONS = new Set(['.h', '.hpp']);
+const C_SOURCE_EXTENSIONS = ['.c', '.cpp', '.cc', '.cxx'];
+
+export interface SymbolDefinition {
+  /** File path relative to the project root */
+  file: string;
+  /** Workspace root that `file` is relative to; set when a lookup spans several roots. */
+  root?: string;
+  name: string;
+  kind: string;
+  qualifier?: string;
+  startLine: number;
+  endLine: number;
+  /** UTF-8 byte offsets of the definition node, end exclusive */
+  startByte: number;
+  endByte: number;
+  /** Source of the definition, dedented so its first line starts at column 0 */
+  text: string;
+  /** True when `text` was cut at `maxLines`; the ranges still cover the whole definition */
+  truncated?: boolean;
+  /** C/C++ prototype whose definition was not found next to it */
+  declaration?: boolean;
+  /** Header prototype of this C/C++ definition */
+  declaredIn?: { file: string; startLine: number; endLine: number };
+}
+
+export interface DefinitionLookupOptions {
+  /** Only look in this file (relative to the root); skips the keyword index entirely. */
+  file?: string;
+  maxLines?: number;
+  /** Take symbols from here for files it has current, instead of parsing them */
+  symbolIndex?: SymbolIndex;
+}
+
+export type DefinitionLookupResult =
+  | { status: 'success'; symbol: string; definitionCount: number; definitions: SymbolDefinition[] }
+  | { status: 'error'; message: string };
+
+/** Remove the indentation shared by every non-blank line. */
+export function dedentLines(lines: string[]): string[] {
+  let common: number | null = null;
+  for (const line of lines) {
+    if (!line.trim()) continue;
+    const indent = line.length - line.trimStart().length;
+    common = common === null ? indent : Math.min(common, indent);
+  }
+  const shared = common ?? 0;
+  if (shared === 0) return lines;
+  return lines.map((line) => (line.trim() ? line.slice(shared) : ''));
+}
+
+interface DefinitionSearch {
+  symbol: string;
+  maxLines: number;
+  symbolIndex?: SymbolIndex;
+}
+
+/** A file's text and symbols, from the symbol index when it has them current. */
+async function readFileSymbols(
+  absPath: string,
+  symbolIndex: SymbolIndex | undefined
+): Promise<{ content: string; symbols: TreeSitterSymbol[] } | null> {
+  const indexed = await symbolIndex?.readIndexed(absPath);
+  if (indexed) return { content: indexed.text, symbols: indexed.symbols };
+
+  let content: string;
+  try {
+    content = await readSourceText(absPath);
+  } catch {
+    return null;
+  }
+
+  const language = detectLanguage(absPath, content);
+  if (!supportsTreeSitter(language)) return null;
+
+  const extraction = await extractTreeSitterSymbols(content, language);
+  return extraction ? { content, symbols: extraction.symbols } : null;
+}
+
+async function fileDefinitions(
+  absPath: string,
+  file: string,
+  { symbol, maxLines, symbolIndex }: DefinitionSearch
+): Promise<SymbolDefinition[]> {
+  const parsed = await readFileSymbols(absPath, symbolIndex);
+  if (!parsed) return [];
+  const { content, symbols } = parsed;
+
+  const lines = content.split('\n');
+  const lineOffsets: number[] = [0];
+  for (const line of lines) {
+    lineOffsets.push(lineOffsets[lineOffsets.length - 1] + line.length + 1);
+  }
+
+  const definitions: SymbolDefinition[] = [];
+  for (const match of symbols) {
+    const qualifiedName = match.qualifier ? `${match.qualifier}.${match.name}` : null;
+    if (match.name !== symbol && qualifiedName !== symbol) continue;
+
+    // Tree-sitter indices may be bytes or UTF-16 units depending on the binding, so the span is
+    // located in the text directly and converted to bytes here.
+    const lineStart = lineOffsets[match.startLine - 1] ?? 0;
+    const found = content.indexOf(match.content, lineStart);
+    const start = found >= 0 ? found : lineStart;
+    const startByte = Buffer.byteLength(content.slice(0, start), 'utf8');
+
+    // Keep the first line's indentation so it dedents along with the rest of the body.
+    const firstLine = lines[match.startLine - 1] ?? '';
+    const indent = firstLine.slice(0, firstLine.length - firstLine.trimStart().length);
+    const spanLines = dedentLines(`${indent}${match.content}`.split('\n'));
+    const truncated = spanLines.length > maxLines;
+
+    definitions.push({
+      file,
+      name: match.name,
+      kind: match.kind,
+      ...(match.qualifier ? { qualifier: match.qualifier } : {}),
+      startLine: match.startLine,
+      endLine: match.endLine,
+      startByte,
+      endByte: startByte + Buffer.byteLength(match.content, 'utf8'),
+      text: spanLines.slice(0, maxLines).join('\n'),
+      ...(truncated ? { truncated } : {}),
+      ...(match.declaration ? { declaration: true } : {})
+    });
+  }
+  return definitions;
+}
+
+/** Source files beside a header with the same base name: `src/calc.h` gives `src/calc.c`, ... */
+function siblingSources(header: string): string[] {
+  const base = header.slice(0, header.length - path.extname(header).length);
+  return C_SOURCE_EXTENSIONS.map((extension) => `${base}${extension}`);
+}
+
+function isImplementationOf(candidate: SymbolDefinition, declaration: SymbolDefinition): boolean {
+  return (
+    !candidate.declaration &&
+    candidate.name === declaration.name &&
+    candidate.qualifier === declaration.qualifier &&
+    (candidate.kind === 'function' || candidate.kind === 'method')
+  );
+}
+
+async function findSiblingImplementation(
+  rootPath: string,
+  declaration: SymbolDefinition,
+  found: SymbolDefinition[],
+  search: DefinitionSearch
+): Promise<SymbolDefinition | null> {
+  for (const source of siblingSources(declaration.file)) {
+    const inSource = found.filter((candidate) => candidate.file === source);
+    const candidates = inSource.length
+      ? inSource
+      : await fileDefinitions(path.join(rootPath, source), source, search);
+    const implementation = candidates.find((candidate) =>
+      isImplementationOf(candidate, declaration)
+    );
+    if (implementation) return implementation;
+  }
+  return null;
+}
+
+/**
+ * Swap each header prototype for the definition in its sibling source file, noting the header
+ * on the definition. Prototypes without one stay in the results, marked as declarations.
+ */
+async function linkHeaderDeclarations(
+  rootPath: string,
+  found: SymbolDefinition[],
+  search: DefinitionSearch
+): Promise<SymbolDefinition[]> {
+  const linked: SymbolDefinition[] = [];
+  for (const definition of found) {
+    if (!definition.declaration || !C_HEADER_EXTENSIONS.has(path.extname(definition.file))) {
+      linked.push(definition);
+      continue;
+    }
+    const implementation = await findSiblingImplementation(rootPath, definition, found, search);
+    if (!implementation) {
+      linked.push(definition);
+      continue;
+    }
+    implementation.declaredIn = {
+      file: definition.file,
+      startLine: definition.startLine,
+      endLine: definition.endLine
+    };
+    // An implementation already among the results is listed once, at its own position
+    if (!found.includes(implementation)) linked.push(implementation);
+  }
+  return linked;
+}
+
+/**
+ * Definitions of `symbol` in one root. Candidate files come from the keyword index unless
+ * `options.file` names the file to look in. `symbol` may be qualified, e.g. `Calculator.Add`.
+ */
+export async function findDefinitions(
+  rootPath: string,
+  symbol: string,
+  options: DefinitionLookupOptions = {}
+): Promise<DefinitionLookupResult> {
+  const normalizedSymbol = symbol.trim();
+  if (!normalizedSymbol) {
+    return { status: 'error', message: 'Symbol is required' };
+  }
+
+  const resolvedRoot = path.resolve(rootPath);
+  const maxLines =
+    options.maxLines !== undefined && options.maxLines > 0
+      ? Math.floor(options.maxLines)
+      : DEFAULT_DEFINITION_MAX_LINES;
+
+  let candidates: Array<{ relPath: string; absPath: string }>;
+  if (options.file) {
+    const absPath = path.resolve(resolvedRoot, options.file);
+    const relPath = relativeInside(resolvedRoot, absPath);
+    if (!relPath) {
+      return { status: 'error', message: `File must be inside the project root: ${options.file}` };
+    }
+    candidates = [{ relPath: relPath.replace(/\\/g, '/'), absPath }];
+  } else {
+    const bareName = normalizedSymbol.split('.').pop() ?? normalizedSymbol;
+    candidates = await findCandidateFiles(resolvedRoot, bareName);
+  }
+
+  const search: DefinitionSearch = {
+    symbol: normalizedSymbol,
+    maxLines,
+    symbolIndex: options.symbolIndex
+  };
+  const found: SymbolDefinition[] = [];
+  candidates.sort((a, b) => a.relPath.localeCompare(b.relPath));
+  for (const { relPath, absPath } of candidates) {
+    found.push(...(await fileDefinitions(absPath, relPath, search)));
+  }
+  const definitions = await linkHeaderDeclarations(resolvedRoot, found, search);
+
+  return {
+    status: 'success',
+    symbol: normalizedSymbol,
+    definitionCount: definitions.length,
+    definitions
+  };
+}
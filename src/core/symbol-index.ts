@@ -0,0 +1,661 @@
+/**
+ * In-memory symbol index kept alive for the lifetime of the MCP server.
+ * Files are reparsed one at a time and their symbols spliced in by path, so a
+ * single edit never requires walking or reparsing the rest of the workspace.
+ *
+ * Files that change after they were first indexed keep their parse tree, so the next change
+ * reparses incrementally and tree-sitter reuses every subtree it did not touch; the edit is
+ * computed from the old and new contents when the caller does not describe it. Kept trees are
+ * the heavy part of the index, so they share a memory budget (`maxTreeBytes`, weighed by the
+ * size of the source each was parsed from): past it, the least recently used trees are freed,
+ * and `withTree` reparses a file from disk when it needs one again. Symbol records always
+ * stay resident, but without their text: each keeps the range it spans, and its `content` is
+ * sliced from the file's text whenever symbols leave the index. A tree in use by `withTree` is
+ * never freed under it.
+ *
+ * One index can span several workspace roots. Entries are keyed by absolute path so two
+ * roots may both contain `src/main.rs`; every entry remembers the root it belongs to.
+ *
+ * Batches of files are synced concurrently up to a bound, and files parsed from scratch go to
+ * the parse worker pool when the indexer has installed one (`setSymbolParsePool`), so entries
+ * arrive in completion order; lookups sort their output and never depend on it.
+ *
+ * The server fills the index from each indexing run and keeps it current from the file watcher;
+ * outline, definition and reference lookups read symbols and trees from it (`readIndexed`,
+ * `withTree`) instead of parsing the file per call. Indexing runs look extractions up here
+ * first (`extractionCache`), so a file the watcher just reparsed is not parsed a second time.
+ */
+
+import { promises as fs } from 'fs';
+import path from 'path';
+import { detectLanguage } from '../utils/language-detection.js';
+import { isUndecodable, normalizeSourceText, readSource } from '../utils/text-decoding.js';
+import {
+  cachedTreeSitterSymbols,
+  computeTreeSitterEdit,
+  extractTreeSitterSymbols,
+  extractTreeSitterSymbolsIncremental,
+  supportsTreeSitter,
+  symbolExtractionCacheKey,
+  type RetainedParseTree,
+  type SymbolExtractionCache,
+  type TreeSitterEdit,
+  type TreeSitterErrorRegion,
+  type TreeSitterSymbol,
+  type TreeSitterSymbolExtraction
+} from '../utils/tree-sitter.js';
+import { mapWithConcurrency, parseConcurrencyFromEnv } from '../utils/concurrency.js';
+import type { Tree } from 'web-tree-sitter';
+import { hashFileContent } from './manifest.js';
+import { findContainingRoot } from './workspace-roots.js';
+
+/**
+ * A symbol as the index keeps it: instead of `content`, the code-unit range of that text in the
+ * contents the file was indexed from. Parents such as classes would otherwise hold their
+ * children's text a second time in memory.
+ */
+export type IndexedSymbol = Omit<TreeSitterSymbol, 'content'> & {
+  contentStart: number;
+  contentEnd: number;
+};
+
+export interface SymbolIndexEntry {
+  root: string;
+  absolutePath: string;
+  /** Path relative to `root`, forward slashes */
+  relativePath: string;
+  language: string;
+  contentHash: string;
+  mtimeMs?: number;
+  grammarFile: string;
+  symbols: IndexedSymbol[];
+  /** Lines that failed to parse; `symbols` were extracted around them */
+  errorRegions?: TreeSitterErrorRegion[];
+}
+
+export interface IndexedSource {
+  entry: SymbolIndexEntry;
+  /** Current contents of the file as parser input; `entry` was extracted from exactly this */
+  text: string;
+  /** `entry.symbols` with their content sliced from `text` */
+  symbols: TreeSitterSymbol[];
+}
+
+export type SymbolIndexUpdateStatus =
+  | 'updated'
+  | 'unchanged'
+  | 'removed'
+  | 'kept-previous'
+  | 'unsupported';
+
+export interface SymbolIndexUpdate {
+  root: string | null;
+  relativePath: string;
+  status: SymbolIndexUpdateStatus;
+  symbolCount: number;
+}
+
+export const DEFAULT_TREE_CACHE_BYTES = 32 * 1024 * 1024;
+
+export interface SymbolIndexOptions {
+  /**
+   * Source bytes whose parse trees may stay cached (default 32 MiB); least recently used trees
+   * beyond it are freed. 0 keeps no tree once its caller is done, so edits always full-parse.
+   */
+  maxTreeBytes?: number;
+}
+
+export interface TreeCacheStats {
+  trees: number;
+  /** Source bytes of the cached trees, pinned ones included */
+  bytes: number;
+  maxBytes: number;
+  evictions: number;
+  /** Trees `withTree` had to parse because none was cached */
+  reparses: number;
+}
+
+/** Options from `CODEBASE_CONTEXT_TREE_CACHE_BYTES`; 0 keeps no parse trees around. */
+export function treeCacheOptionsFromEnv(env: NodeJS.ProcessEnv = process.env): SymbolIndexOptions {
+  const parsed = Number.parseInt(env.CODEBASE_CONTEXT_TREE_CACHE_BYTES ?? '', 10);
+  const valid = Number.isFinite(parsed) && parsed >= 0;
+  return { maxTreeBytes: valid ? parsed : DEFAULT_TREE_CACHE_BYTES };
+}
+
+interface PendingTree {
+  /** `withTree` calls waiting for this parse; each gets one pin on the result */
+  waiters: number;
+  tree: Promise<CachedTree | null>;
+}
+
+interface CachedTree {
+  retained: RetainedParseTree;
+  /** Text the tree was parsed from */
+  source: string;
+  bytes: number;
+  /** `withTree` callers using the tree right now; it is only freed once this is 0 */
+  pins: number;
+  /** Out of the cache already; freed when the last pin is released */
+  released: boolean;
+}
+
+export interface SymbolIndexMatch {
+  root: string;
+  absolutePath: string;
+  relativePath: string;
+  symbol: IndexedSymbol;
+}
+
+const symbolIdentity = (symbol: Pick<TreeSitterSymbol, 'kind' | 'qualifier' | 'name'>) =>
+  `${symbol.kind}:${symbol.qualifier ?? ''}:${symbol.name}`;
+
+/** Whether a parse with syntax errors dropped any symbol `previous` had. */
+function losesSymbols(previous: SymbolIndexEntry, extraction: TreeSitterSymbolExtraction): boolean {
+  if (!extraction.errorRegions?.length) return false;
+  const recovered = new Set(extraction.symbols.map(symbolIdentity));
+  return previous.symbols.some((symbol) => !recovered.has(symbolIdentity(symbol)));
+}
+
+/** Swap each symbol's text for its range in `text`, the contents it was extracted from. */
+function toIndexedSymbols(symbols: TreeSitterSymbol[], text: string): IndexedSymbol[] {
+  return symbols.map(({ content, ...symbol }) => {
+    // `startIndex` counts code units or UTF-8 bytes depending on the binding, and never fewer
+    // code units than precede the text; any occurrence of the same text slices back the same
+    let contentStart = text.lastIndexOf(content, symbol.startIndex);
+    if (contentStart < 0) contentStart = Math.max(0, text.indexOf(content));
+    return { ...symbol, contentStart, contentEnd: contentStart + content.length };
+  });
+}
+
+function withContent(symbols: IndexedSymbol[], text: string): TreeSitterSymbol[] {
+  return symbols.map(({ contentStart, contentEnd, ...symbol }) => ({
+    ...symbol,
+    content: text.slice(contentStart, contentEnd)
+  }));
+}
+
+export class SymbolIndex {
+  /** Workspace roots, primary first. Relative paths resolve against the primary root. */
+  readonly roots: readonly string[];
+  private readonly entries = new Map<string, SymbolIndexEntry>();
+  /** Extraction cache key of each entry's contents, and the entry holding each key */
+  private readonly extractionKeys = new Map<string, string>();
+  private readonly entriesByExtractionKey = new Map<string, SymbolIndexEntry>();
+  readonly maxTreeBytes: number;
+  /** Cached parse trees keyed like `entries`; insertion order is recency, oldest first */
+  private readonly trees = new Map<string, CachedTree>();
+  /** Reparses in flight, so concurrent `withTree` calls for one file parse it once */
+  private readonly pendingTrees = new Map<string, PendingTree>();
+  private treeBytes = 0;
+  private evictions = 0;
+  private reparses = 0;
+
+  constructor(roots: string | string[], options: SymbolIndexOptions = {}) {
+    const list = (Array.isArray(roots) ? roots : [roots]).map((root) => path.resolve(root));
+    if (list.length === 0) {
+      throw new Error('SymbolIndex requires at least one root');
+    }
+    this.roots = Array.from(new Set(list));
+    const maxTreeBytes = options.maxTreeBytes ?? DEFAULT_TREE_CACHE_BYTES;
+    this.maxTreeBytes = Number.isFinite(maxTreeBytes)
+      ? Math.max(0, Math.floor(maxTreeBytes))
+      : DEFAULT_TREE_CACHE_BYTES;
+  }
+
+  /** Absolute key for an absolute or primary-root-relative path. */
+  toAbsolutePath(filePath: string): string {
+    return path.isAbsolute(filePath) ? path.resolve(filePath) : path.join(this.roots[0], filePath);
+  }
+
+  /** The innermost root containing `filePath`, or null when it is outside every root. */
+  rootOf(filePath: string): string | null {
+    return findContainingRoot(this.roots, this.toAbsolutePath(filePath));
+  }
+
+  /** Path relative to the file's own root, forward slashes. */
+  toRelativePath(filePath: string): string {
+    const absolute = this.toAbsolutePath(filePath);
+    const root = this.rootOf(absolute) ?? this.roots[0];
+    return path.relative(root, absolute).replace(/\\/g, '/');
+  }
+
+  get size(): number {
+    return this.entries.size;
+  }
+
+  /** Indexed files of one root (the primary root by default), relative to that root. */
+  files(root: string = this.roots[0]): string[] {
+    const resolvedRoot = path.resolve(root);
+    return Array.from(this.entries.values())
+      .filter((entry) => entry.root === resolvedRoot)
+      .map((entry) => entry.relativePath)
+      .sort();
+  }
+
+  getEntry(filePath: string): SymbolIndexEntry | undefined {
+    return this.entries.get(this.toAbsolutePath(filePath));
+  }
+
+  /** The file's symbols as kept in memory, without content; `readIndexed` adds it. */
+  getSymbols(filePath: string): IndexedSymbol[] {
+    return this.getEntry(filePath)?.symbols ?? [];
+  }
+
+  /**
+   * The file's current text with its indexed symbols, content included, after syncing the
+   * entry with disk (`refreshFile`), so callers need not parse it. Null when the file is not
+   * indexed, or when the entry does not describe what is on disk now (a torn read, or an edit
+   * that kept the previous symbols); callers then parse the file themselves.
+   */
+  async readIndexed(filePath: string): Promise<IndexedSource | null> {
+    const absolutePath = this.toAbsolutePath(filePath);
+    try {
+      await this.refreshFile(absolutePath);
+      const entry = this.entries.get(absolutePath);
+      if (!entry) return null;
+      const source = await readSource(absolutePath);
+      if (isUndecodable(source) || hashFileContent(source.text) !== entry.contentHash) return null;
+      return { entry, text: source.text, symbols: withContent(entry.symbols, source.text) };
+    } catch {
+      return null;
+    }
+  }
+
+  /**
+   * An extraction cache answering from this index before `fallback`, so an indexing run reuses
+   * the symbols of files the watcher already reparsed. Hits are copied into `fallback` so they
+   * are persisted with the rest; writes go to `fallback` only. Only lookups that pass the
+   * contents can hit here, since entries keep no symbol text of their own.
+   */
+  extractionCache(fallback: SymbolExtractionCache | null): SymbolExtractionCache {
+    return {
+      get: (key, content) => {
+        const entry = this.entriesByExtractionKey.get(key);
+        if (!entry || content === undefined) return fallback?.get(key, content);
+        const extraction: TreeSitterSymbolExtraction = {
+          grammarFile: entry.grammarFile,
+          symbols: withContent(entry.symbols, content),
+          ...(entry.errorRegions ? { errorRegions: entry.errorRegions } : {})
+        };
+        fallback?.set(key, extraction);
+        return extraction;
+      },
+      set: (key, extraction) => fallback?.set(key, extraction)
+    };
+  }
+
+  /**
+   * Symbols named `name` across all roots, or only within `options.root`, ordered by root, path
+   * and position. Qualified symbols also match as `Qualifier.name`, e.g. the Go method
+   * `Calculator.Add`.
+   */
+  findByName(name: string, options: { root?: string } = {}): SymbolIndexMatch[] {
+    const root = options.root ? path.resolve(options.root) : null;
+    const matches: SymbolIndexMatch[] = [];
+    for (const entry of this.entries.values()) {
+      if (root && entry.root !== root) continue;
+      for (const symbol of entry.symbols) {
+        const qualifiedName = symbol.qualifier ? `${symbol.qualifier}.${symbol.name}` : null;
+        if (symbol.name === name || qualifiedName === name) {
+          matches.push({
+            root: entry.root,
+            absolutePath: entry.absolutePath,
+            relativePath: entry.relativePath,
+            symbol
+          });
+        }
+      }
+    }
+    return matches.sort(
+      (a, b) =>
+        this.roots.indexOf(a.root) - this.roots.indexOf(b.root) ||
+        (a.relativePath < b.relativePath ? -1 : a.relativePath > b.relativePath ? 1 : 0) ||
+        a.symbol.startLine - b.symbol.startLine ||
+        a.symbol.startIndex - b.symbol.startIndex
+    );
+  }
+
+  /**
+   * Reparse a single file and replace its symbols.
+   * When the new contents fail to parse, or parse only around errors that lose symbols the file
+   * had, the previous symbols are kept rather than dropped: such contents are usually mid-edit.
+   * A recovered parse that keeps every symbol replaces them, with its `errorRegions`.
+   *
+   * A file that was indexed before keeps its parse tree (until evicted past `maxTreeBytes`), so
+   * its next change reparses incrementally. `edit` describes the change from the previously
+   * indexed contents; without one, or with contents that need line-ending normalization, it is
+   * computed by diffing the kept tree's source against `contents`. Files seen for the first
+   * time are parsed from scratch through the shared extraction cache.
+   */
+  async updateFile(
+    filePath: string,
+    contents: string,
+    mtimeMs?: number,
+    edit?: TreeSitterEdit
+  ): Promise<SymbolIndexUpdate> {
+    const absolutePath = this.toAbsolutePath(filePath);
+    const root = this.rootOf(absolutePath);
+    const relativePath = this.toRelativePath(absolutePath);
+    const normalized = normalizeSourceText(contents);
+    const language = detectLanguage(absolutePath, normalized);
+    const previous = this.entries.get(absolutePath);
+
+    if (!root || !supportsTreeSitter(language)) {
+      return { root, relativePath, status: 'unsupported', symbolCount: 0 };
+    }
+
+    const contentHash = hashFileContent(normalized);
+    if (previous && previous.contentHash === contentHash) {
+      previous.mtimeMs = mtimeMs ?? previous.mtimeMs;
+      return { root, relativePath, status: 'unchanged', symbolCount: previous.symbols.length };
+    }
+
+    // Edit offsets refer to the raw contents, which line-ending normalization would shift
+    const usableEdit = normalized === contents ? edit : undefined;
+    if (!normalized.trim()) {
+      this.releaseTree(absolutePath);
+    }
+    const extraction = normalized.trim()
+      ? await this.extract(absolutePath, normalized, language, usableEdit, Boolean(previous))
+      : { grammarFile: language, symbols: [] };
+
+    if (!extraction || (previous && losesSymbols(previous, extraction))) {
+      if (process.env.CODEBASE_CONTEXT_DEBUG) {
+        console.error(`[symbol-index] Failed to parse ${relativePath}; keeping previous symbols`);
+      }
+      return {
+        root,
+        relativePath,
+        status: 'kept-previous',
+        symbolCount: previous?.symbols.length ?? 0
+      };
+    }
+
+    this.setEntry(
+      {
+        root,
+        absolutePath,
+        relativePath,
+        language,
+        contentHash,
+        mtimeMs,
+        grammarFile: extraction.grammarFile,
+        symbols: toIndexedSymbols(extraction.symbols, normalized),
+        ...(extraction.errorRegions ? { errorRegions: extraction.errorRegions } : {})
+      },
+      symbolExtractionCacheKey(normalized, language)
+    );
+
+    return { root, relativePath, status: 'updated', symbolCount: extraction.symbols.length };
+  }
+
+  /**
+   * Parse incrementally for files being edited (`retain`, or a given edit or kept tree), through
+   * the shared extraction cache otherwise.
+   */
+  private async extract(
+    absolutePath: string,
+    contents: string,
+    language: string,
+    edit: TreeSitterEdit | undefined,
+    retain: boolean
+  ): Promise<TreeSitterSymbolExtraction | null> {
+    const cached = this.trees.get(absolutePath);
+    if (!edit && !cached) {
+      if (!retain) return extractTreeSitterSymbols(contents, language);
+      // With no tree to reuse, an extraction the running index already made is as good
+      const known = cachedTreeSitterSymbols(contents, language);
+      if (known) return known;
+    }
+
+    // The incremental parse consumes the old tree, which a `withTree` caller may still be using;
+    // such a tree is left to its caller and the file is parsed from scratch instead
+    const previous = cached && cached.pins === 0 ? this.takeTree(absolutePath) : undefined;
+    this.releaseTree(absolutePath);
+    const treeEdit =
+      previous && cached ? (edit ?? computeTreeSitterEdit(cached.source, contents)) : undefined;
+    const { extraction, retained } = await extractTreeSitterSymbolsIncremental(
+      contents,
+      language,
+      previous,
+      treeEdit ?? undefined
+    );
+    if (retained) {
+      this.releaseTree(absolutePath);
+      this.cacheTree(absolutePath, retained, contents);
+      this.evictTrees();
+    }
+    return extraction;
+  }
+
+  /**
+   * Run `fn` with the file's parse tree and the text it was parsed from, reparsing the file from
+   * disk when its tree was evicted or never kept. The tree stays valid until `fn` settles,
+   * however much is evicted meanwhile; do not keep it past that. Null when the file is not
+   * indexed or does not parse.
+   */
+  async withTree<T>(
+    filePath: string,
+    fn: (tree: Tree, language: string, source: string) => T | Promise<T>
+  ): Promise<T | null> {
+    const absolutePath = this.toAbsolutePath(filePath);
+    if (!this.entries.has(absolutePath)) return null;
+
+    const cached = await this.acquireTree(absolutePath);
+    if (!cached) return null;
+    try {
+      return await fn(cached.retained.tree, cached.retained.language, cached.source);
+    } finally {
+      cached.pins--;
+      if (cached.released && cached.pins === 0) cached.retained.tree.delete();
+      this.evictTrees();
+    }
+  }
+
+  treeCacheStats(): TreeCacheStats {
+    return {
+      trees: this.trees.size,
+      bytes: this.treeBytes,
+      maxBytes: this.maxTreeBytes,
+      evictions: this.evictions,
+      reparses: this.reparses
+    };
+  }
+
+  /**
+   * The file's tree, pinned once for this caller: taken from the cache and marked most recently
+   * used, or parsed from disk. Concurrent callers share one parse, and the tree is pinned for
+   * all of them before any resumes, so no eviction can free it in between.
+   */
+  private acquireTree(absolutePath: string): Promise<CachedTree | null> {
+    const cached = this.trees.get(absolutePath);
+    if (cached) {
+      cached.pins++;
+      this.trees.delete(absolutePath);
+      this.trees.set(absolutePath, cached);
+      return Promise.resolve(cached);
+    }
+
+    let pending = this.pendingTrees.get(absolutePath);
+    if (!pending) {
+      const created: PendingTree = { waiters: 0, tree: Promise.resolve(null) };
+      created.tree = this.reparseTree(absolutePath, created);
+      pending = created;
+      this.pendingTrees.set(absolutePath, pending);
+    }
+    pending.waiters++;
+    return pending.tree;
+  }
+
+  private async reparseTree(
+    absolutePath: string,
+    pending: PendingTree
+  ): Promise<CachedTree | null> {
+    let tree: CachedTree | null = null;
+    try {
+      const source = await readSource(absolutePath).catch(() => null);
+      if (!source || isUndecodable(source)) return null;
+      const language = detectLanguage(absolutePath, source.text);
+      if (!supportsTreeSitter(language)) return null;
+      const { retained } = await extractTreeSitterSymbolsIncremental(source.text, language);
+      if (!retained) return null;
+      this.reparses++;
+      // An edit may have cached a newer tree, or removed the file, while this one was parsing
+      const current = this.trees.get(absolutePath);
+      if (current || !this.entries.has(absolutePath)) {
+        retained.tree.delete();
+        tree = current ?? null;
+      } else {
+        tree = this.cacheTree(absolutePath, retained, source.text);
+      }
+      return tree;
+    } finally {
+      // Same synchronous step as caching: nobody joins after the pins are counted
+      this.pendingTrees.delete(absolutePath);
+      if (tree) tree.pins += pending.waiters;
+    }
+  }
+
+  private cacheTree(absolutePath: string, retained: RetainedParseTree, source: string): CachedTree {
+    const cached: CachedTree = { retained, source, bytes: source.length, pins: 0, released: false };
+    this.trees.set(absolutePath, cached);
+    this.treeBytes += cached.bytes;
+    return cached;
+  }
+
+  /** Remove a tree from the cache and hand it over; the caller now owns (and frees) it. */
+  private takeTree(absolutePath: string): RetainedParseTree | undefined {
+    const cached = this.trees.get(absolutePath);
+    if (!cached) return undefined;
+    this.trees.delete(absolutePath);
+    this.treeBytes -= cached.bytes;
+    cached.released = true;
+    return cached.retained;
+  }
+
+  /** Drop a file's tree from the cache; it is freed now, or by the last `withTree` using it. */
+  private releaseTree(absolutePath: string): void {
+    const cached = this.trees.get(absolutePath);
+    if (!cached) return;
+    this.takeTree(absolutePath);
+    if (cached.pins === 0) cached.retained.tree.delete();
+  }
+
+  /** Free least recently used trees until the cache fits its budget; pinned trees are skipped. */
+  private evictTrees(): void {
+    if (this.treeBytes <= this.maxTreeBytes) return;
+    for (const [absolutePath, cached] of this.trees) {
+      if (this.treeBytes <= this.maxTreeBytes) break;
+      if (cached.pins > 0) continue;
+      this.releaseTree(absolutePath);
+      this.evictions++;
+    }
+  }
+
+  private setEntry(entry: SymbolIndexEntry, extractionKey: string): void {
+    this.deleteEntry(entry.absolutePath);
+    this.entries.set(entry.absolutePath, entry);
+    this.extractionKeys.set(entry.absolutePath, extractionKey);
+    this.entriesByExtractionKey.set(extractionKey, entry);
+  }
+
+  private deleteEntry(absolutePath: string): boolean {
+    const entry = this.entries.get(absolutePath);
+    const key = this.extractionKeys.get(absolutePath);
+    // Files with identical contents share a key; only the entry that holds it releases it
+    if (key !== undefined && this.entriesByExtractionKey.get(key) === entry) {
+      this.entriesByExtractionKey.delete(key);
+    }
+    this.extractionKeys.delete(absolutePath);
+    return this.entries.delete(absolutePath);
+  }
+
+  /** Purge every symbol recorded for a file. Returns false when the file was not indexed. */
+  removeFile(filePath: string): boolean {
+    const absolutePath = this.toAbsolutePath(filePath);
+    this.releaseTree(absolutePath);
+    return this.deleteEntry(absolutePath);
+  }
+
+  /**
+   * Sync one file with disk: skip when its mtime is unchanged, reparse when it moved,
+   * purge when it no longer exists. A file modified while being read counts as a failed
+   * parse and keeps its previous symbols.
+   */
+  async refreshFile(filePath: string): Promise<SymbolIndexUpdate> {
+    const absolutePath = this.toAbsolutePath(filePath);
+    const root = this.rootOf(absolutePath);
+    const relativePath = this.toRelativePath(absolutePath);
+
+    let stat: Awaited<ReturnType<typeof fs.stat>>;
+    try {
+      stat = await fs.stat(absolutePath);
+    } catch {
+      const removed = this.removeFile(absolutePath);
+      return { root, relativePath, status: removed ? 'removed' : 'unchanged', symbolCount: 0 };
+    }
+
+    const previous = this.entries.get(absolutePath);
+    if (previous && previous.mtimeMs === stat.mtimeMs) {
+      return { root, relativePath, status: 'unchanged', symbolCount: previous.symbols.length };
+    }
+
+    const source = await readSource(absolutePath);
+    if (isUndecodable(source)) {
+      this.removeFile(absolutePath);
+      return { root, relativePath, status: 'unsupported', symbolCount: 0 };
+    }
+    // A write landing mid-read can leave a torn mix of old and new contents; keep the previous
+    // symbols and let the watcher event for that write trigger the next refresh
+    const after = await fs.stat(absolutePath).catch(() => null);
+    if (!after || after.mtimeMs !== stat.mtimeMs || after.size !== stat.size) {
+      return {
+        root,
+        relativePath,
+        status: 'kept-previous',
+        symbolCount: previous?.symbols.length ?? 0
+      };
+    }
+    return this.updateFile(absolutePath, source.text, stat.mtimeMs);
+  }
+
+  /**
+   * Sync many files with disk, with up to `concurrency` files in flight (default: one per core,
+   * or `CODEBASE_CONTEXT_PARSE_CONCURRENCY`); their parses run in parallel only on an active
+   * parse worker pool. Updates come back in input order, one per distinct path; the first
+   * failure is rethrown once every other file has been synced.
+   */
+  async refreshFiles(
+    filePaths: string[],
+    concurrency: number = parseConcurrencyFromEnv()
+  ): Promise<SymbolIndexUpdate[]> {
+    const uniquePaths = Array.from(
+      new Set(filePaths.map((filePath) => this.toAbsolutePath(filePath)))
+    );
+    const updates: SymbolIndexUpdate[] = [];
+    let failure: { error: unknown } | undefined;
+    for await (const outcome of mapWithConcurrency(uniquePaths, concurrency, (filePath) =>
+      this.refreshFile(filePath).then(
+        (update) => ({ update }),
+        (error: unknown) => ({ error })
+      )
+    )) {
+      if ('error' in outcome) {
+        failure ??= outcome;
+      } else {
+        updates.push(outcome.update);
+      }
+    }
+    if (failure) throw failure.error;
+    return updates;
+  }
+
+  clear(): void {
+    for (const absolutePath of Array.from(this.trees.keys())) {
+      this.releaseTree(absolutePath);
+    }
+    this.entries.clear();
+    this.extractionKeys.clear();
+    this.entriesByExtractionKey.clear();
+  }
+}
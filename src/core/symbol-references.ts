@@ -4,13 +4,30 @@ import { CODEBASE_CONTEXT_DIRNAME, KEYWORD_INDEX_FILENAME } from '../constants/c
 import { IndexCorruptedError } from '../errors/index.js';
 import type { UsageLocation } from '../types/index.js';
 import { detectLanguage } from '../utils/language-detection.js';
-import { findIdentifierOccurrences } from '../utils/tree-sitter.js';
+import {
+  compareFuzzyMatches,
+  fuzzyMatch,
+  type FuzzyMatch,
+  type FuzzyMatchTier
+} from '../utils/fuzzy-match.js';
+import {
+  findIdentifierOccurrences,
+  findScopedIdentifierOccurrences,
+  findScopedIdentifierOccurrencesInTree,
+  type ByteSpan,
+  type ScopedIdentifierOccurrence
+} from '../utils/tree-sitter.js';
+import { findContainingRoot } from './workspace-roots.js';
+import type { SymbolIndex } from './symbol-index.js';
+import { readSourceText } from '../utils/text-decoding.js';
+import { relativeInside } from '../utils/symlinks.js';
 
 interface IndexedChunk {
   content?: unknown;
   startLine?: unknown;
   relativePath?: unknown;
   filePath?: unknown;
+  metadata?: unknown;
 }
 
 export interface SymbolUsage extends UsageLocation {
@@ -43,8 +60,8 @@ function getUsageFile(rootPath: string, chunk: IndexedChunk): string {
   }
 
   if (typeof chunk.filePath === 'string' && chunk.filePath.trim()) {
-    const relativePath = path.relative(rootPath, chunk.filePath);
-    if (!relativePath || relativePath.startsWith('..')) {
+    const relativePath = relativeInside(rootPath, chunk.filePath);
+    if (!relativePath) {
       return path.basename(chunk.filePath);
     }
     return relativePath.replace(/\\/g, '/');
@@ -69,11 +86,8 @@ function buildPreviewFromFileLines(lines: string[], line: number): string {
 
 function resolveAbsoluteChunkPath(rootPath: string, chunk: IndexedChunk): string | null {
   const resolvedRoot = path.resolve(rootPath);
-  const isWithinRoot = (candidate: string): boolean => {
-    const resolvedCandidate = path.resolve(candidate);
-    const relative = path.relative(resolvedRoot, resolvedCandidate);
-    return Boolean(relative) && !relative.startsWith('..') && !path.isAbsolute(relative);
-  };
+  const isWithinRoot = (candidate: string): boolean =>
+    relativeInside(resolvedRoot, path.resolve(candidate)) !== null;
 
   if (typeof chunk.filePath === 'string' && chunk.filePath.trim()) {
     const raw = chunk.filePath.trim();
@@ -101,21 +115,7 @@ async function fileExists(targetPath: string): Promise<boolean> {
   }
 }
 
-export async function findSymbolReferences(
-  rootPath: string,
-  symbol: string,
-  limit = 10
-): Promise<SymbolReferencesResult> {
-  const normalizedSymbol = symbol.trim();
-  const normalizedLimit = Number.isFinite(limit) && limit > 0 ? Math.floor(limit) : 10;
-
-  if (!normalizedSymbol) {
-    return {
-      status: 'error',
-      message: 'Symbol is required'
-    };
-  }
-
+async function readIndexedChunks(rootPath: string): Promise<unknown[]> {
   const indexPath = path.join(rootPath, CODEBASE_CONTEXT_DIRNAME, KEYWORD_INDEX_FILENAME);
 
   let chunksRaw: unknown;
@@ -148,19 +148,21 @@ export async function findSymbolReferences(
     throw new IndexCorruptedError('Keyword index corrupted: expected { header, chunks }');
   }
 
-  const usages: SymbolUsage[] = [];
-  let usageCount = 0;
+  return chunks;
+}
 
-  const escapedSymbol = escapeRegex(normalizedSymbol);
-  const prefilter = new RegExp(`\\b${escapedSymbol}\\b`);
-  const matcher = new RegExp(`\\b${escapedSymbol}\\b`, 'g');
+interface CandidateFile {
+  relPath: string;
+  absPath: string | null;
+  chunks: IndexedChunk[];
+}
 
-  // Prefilter candidate files from the keyword index. We do not trust chunk contents for
-  // exact reference counting when Tree-sitter is available; chunks only guide which files to scan.
-  const chunksByFile = new Map<
-    string,
-    { relPath: string; absPath: string | null; chunks: IndexedChunk[] }
-  >();
+function groupCandidateChunksByFile(
+  rootPath: string,
+  chunks: unknown[],
+  prefilter: RegExp
+): Map<string, CandidateFile> {
+  const chunksByFile = new Map<string, CandidateFile>();
 
   for (const chunkRaw of chunks) {
     const chunk = chunkRaw as IndexedChunk;
@@ -182,6 +184,55 @@ export async function findSymbolReferences(
     }
   }
 
+  return chunksByFile;
+}
+
+/**
+ * Indexed files whose chunks mention `symbol` as a whole word. A cheap prefilter: callers still
+ * parse each file to confirm what the word actually is.
+ */
+export async function findCandidateFiles(
+  rootPath: string,
+  symbol: string
+): Promise<Array<{ relPath: string; absPath: string }>> {
+  const chunks = await readIndexedChunks(rootPath);
+  const prefilter = new RegExp(`\\b${escapeRegex(symbol)}\\b`);
+  const candidates: Array<{ relPath: string; absPath: string }> = [];
+  const grouped = groupCandidateChunksByFile(rootPath, chunks, prefilter);
+  for (const { relPath, absPath } of grouped.values()) {
+    if (absPath) candidates.push({ relPath, absPath });
+  }
+  return candidates;
+}
+
+export async function findSymbolReferences(
+  rootPath: string,
+  symbol: string,
+  limit = 10
+): Promise<SymbolReferencesResult> {
+  const normalizedSymbol = symbol.trim();
+  const normalizedLimit = Number.isFinite(limit) && limit > 0 ? Math.floor(limit) : 10;
+
+  if (!normalizedSymbol) {
+    return {
+      status: 'error',
+      message: 'Symbol is required'
+    };
+  }
+
+  const chunks = await readIndexedChunks(rootPath);
+
+  const usages: SymbolUsage[] = [];
+  let usageCount = 0;
+
+  const escapedSymbol = escapeRegex(normalizedSymbol);
+  const prefilter = new RegExp(`\\b${escapedSymbol}\\b`);
+  const matcher = new RegExp(`\\b${escapedSymbol}\\b`, 'g');
+
+  // Prefilter candidate files from the keyword index. We do not trust chunk contents for
+  // exact reference counting when Tree-sitter is available; chunks only guide which files to scan.
+  const chunksByFile = groupCandidateChunksByFile(rootPath, chunks, prefilter);
+
   for (const entry of chunksByFile.values()) {
     const relPath = entry.relPath;
     const absPath = entry.absPath;
@@ -189,9 +240,8 @@ export async function findSymbolReferences(
     // Preferred: Tree-sitter identifier walk on the real file content.
     if (absPath && (await fileExists(absPath))) {
       try {
-        const raw = await fs.readFile(absPath, 'utf-8');
-        const content = raw.replace(/\r\n/g, '\n');
-        const language = detectLanguage(absPath);
+        const content = await readSourceText(absPath);
+        const language = detectLanguage(absPath, content);
         const occurrences = await findIdentifierOccurrences(content, language, normalizedSymbol);
 
         if (occurrences) {
@@ -253,3 +303,426 @@ export async function findSymbolReferences(
     isComplete: usageCount < normalizedLimit
   };
 }
+
+export const DEFAULT_FUZZY_MIN_SCORE = 0.3;
+
+export interface SymbolNameMatch {
+  name: string;
+  kind?: string;
+  file: string;
+  line: number;
+  match: FuzzyMatchTier;
+  score: number;
+}
+
+export interface FuzzySymbolSearchOptions {
+  limit?: number;
+  /** Fuzzy matches scoring below this (0-1) are dropped; exact and prefix matches always pass */
+  minScore?: number;
+}
+
+interface FuzzySymbolSearchSuccess {
+  status: 'success';
+  mode: 'fuzzy';
+  query: string;
+  minScore: number;
+  totalMatches: number;
+  matches: SymbolNameMatch[];
+}
+
+export type FuzzySymbolSearchResult = FuzzySymbolSearchSuccess | SymbolReferencesError;
+
+const IDENTIFIER_PATTERN = /^[A-Za-z_$][\w$]*$/;
+
+/** Symbol names a chunk defines, taken from the analyzer's chunk metadata. */
+function chunkSymbolNames(chunk: IndexedChunk): Array<{ name: string; kind?: string }> {
+  if (typeof chunk.metadata !== 'object' || chunk.metadata === null) return [];
+  const metadata = chunk.metadata as Record<string, unknown>;
+  const kind = typeof metadata.symbolKind === 'string' ? metadata.symbolKind : undefined;
+  const names: Array<{ name: string; kind?: string }> = [];
+
+  if (Array.isArray(metadata.symbolPath)) {
+    const symbolPath = metadata.symbolPath.filter(
+      (part): part is string => typeof part === 'string'
+    );
+    symbolPath.forEach((name, index) => {
+      names.push(index === symbolPath.length - 1 ? { name, kind } : { name });
+    });
+  }
+  if (typeof metadata.symbolName === 'string') {
+    // Merged chunks join names with '+'; split chunks carry a ':<n>' suffix.
+    for (const part of metadata.symbolName.split('+')) {
+      names.push({ name: part.replace(/:\d+$/, '') });
+    }
+  }
+  for (const key of ['className', 'functionName', 'componentName']) {
+    const value = metadata[key];
+    if (typeof value === 'string') names.push({ name: value });
+  }
+
+  return names.filter((entry) => IDENTIFIER_PATTERN.test(entry.name));
+}
+
+function lineOfName(chunk: IndexedChunk, name: string): number {
+  const startLine = typeof chunk.startLine === 'number' ? chunk.startLine : 1;
+  if (typeof chunk.content !== 'string') return startLine;
+  const match = new RegExp(`\\b${escapeRegex(name)}\\b`).exec(chunk.content);
+  if (!match) return startLine;
+  return startLine + chunk.content.slice(0, match.index).split('\n').length - 1;
+}
+
+/**
+ * Rank indexed symbol names against a half-remembered `query` (`newCalc` finds
+ * `new_calculator`). Exact and prefix matches always outrank fuzzy ones.
+ */
+export async function findFuzzySymbols(
+  rootPath: string,
+  query: string,
+  options: FuzzySymbolSearchOptions = {}
+): Promise<FuzzySymbolSearchResult> {
+  const normalizedQuery = query.trim();
+  if (!normalizedQuery) {
+    return { status: 'error', message: 'Symbol is required' };
+  }
+  const limit =
+    options.limit !== undefined && Number.isFinite(options.limit) && options.limit > 0
+      ? Math.floor(options.limit)
+      : 10;
+  const minScore =
+    options.minScore !== undefined && Number.isFinite(options.minScore)
+      ? Math.max(0, Math.min(1, options.minScore))
+      : DEFAULT_FUZZY_MIN_SCORE;
+
+  const chunks = await readIndexedChunks(rootPath);
+  const seen = new Map<string, { entry: SymbolNameMatch; match: FuzzyMatch }>();
+
+  for (const chunkRaw of chunks) {
+    const chunk = chunkRaw as IndexedChunk;
+    const file = getUsageFile(rootPath, chunk);
+    for (const { name, kind } of chunkSymbolNames(chunk)) {
+      const key = `${file}\0${name}`;
+      const existing = seen.get(key);
+      if (existing) {
+        if (!existing.entry.kind && kind) existing.entry.kind = kind;
+        continue;
+      }
+
+      const match = fuzzyMatch(normalizedQuery, name);
+      if (!match || (match.tier === 'fuzzy' && match.score < minScore)) continue;
+      const entry: SymbolNameMatch = {
+        name,
+        ...(kind ? { kind } : {}),
+        file,
+        line: lineOfName(chunk, name),
+        match: match.tier,
+        score: Math.round(match.score * 100) / 100
+      };
+      seen.set(key, { entry, match });
+    }
+  }
+
+  const ranked = Array.from(seen.values())
+    .sort(
+      (a, b) =>
+        compareFuzzyMatches(
+          { name: a.entry.name, match: a.match },
+          { name: b.entry.name, match: b.match }
+        ) ||
+        a.entry.file.localeCompare(b.entry.file) ||
+        a.entry.line - b.entry.line
+    )
+    .map(({ entry }) => entry);
+
+  return {
+    status: 'success',
+    mode: 'fuzzy',
+    query: normalizedQuery,
+    minScore,
+    totalMatches: ranked.length,
+    matches: ranked.slice(0, limit)
+  };
+}
+
+export interface ReferenceLocation extends UsageLocation {
+  column: number;
+  kind: 'definition' | 'reference';
+  preview: string;
+  /** Workspace root that `file` is relative to; set when a query spans several roots. */
+  root?: string;
+}
+
+export interface FindReferencesOptions {
+  /** File containing the occurrence to disambiguate (absolute or root-relative). */
+  file?: string;
+  /** 1-based line of the occurrence inside `file`. */
+  line?: number;
+  limit?: number;
+  /** Scan the parse trees kept here for files it has current, instead of parsing them */
+  symbolIndex?: SymbolIndex;
+}
+
+interface FindReferencesSuccess {
+  status: 'success';
+  symbol: string;
+  /** 'module': module-level binding across files. 'local': one enclosing block in one file. */
+  scope: 'module' | 'local';
+  definitions: ReferenceLocation[];
+  references: ReferenceLocation[];
+  referenceCount: number;
+  isComplete: boolean;
+  /** Roots skipped because their index is missing or unreadable */
+  unavailableRoots?: string[];
+}
+
+export type FindReferencesResult = FindReferencesSuccess | SymbolReferencesError;
+
+function toReferenceLocation(
+  file: string,
+  lines: string[],
+  occ: { line: number; column: number },
+  kind: ReferenceLocation['kind']
+): ReferenceLocation {
+  return {
+    file,
+    line: occ.line,
+    column: occ.column,
+    kind,
+    preview: (lines[occ.line - 1] ?? '').trim()
+  };
+}
+
+function sameSpan(a: ByteSpan | null, b: ByteSpan | null): boolean {
+  if (!a || !b) return a === b;
+  return a.startIndex === b.startIndex && a.endIndex === b.endIndex;
+}
+
+function normalizeReferenceLimit(limit: number | undefined): number {
+  return limit !== undefined && Number.isFinite(limit) && limit > 0 ? Math.floor(limit) : 20;
+}
+
+async function readSourceFile(absPath: string): Promise<string | null> {
+  if (!(await fileExists(absPath))) return null;
+  return readSourceText(absPath);
+}
+
+interface ScannedFile {
+  content: string;
+  /** Null when the file has no grammar or does not parse cleanly */
+  occurrences: ScopedIdentifierOccurrence[] | null;
+}
+
+/**
+ * A file's text and scoped occurrences of `symbol`, scanned on the symbol index's parse tree
+ * when it has the file current. Null when the file cannot be read.
+ */
+async function scanFile(
+  absPath: string,
+  symbol: string,
+  symbolIndex: SymbolIndex | undefined
+): Promise<ScannedFile | null> {
+  const indexed = await symbolIndex?.readIndexed(absPath);
+  if (symbolIndex && indexed) {
+    const occurrences = await symbolIndex.withTree(absPath, (tree, language, source) =>
+      source === indexed.text ? findScopedIdentifierOccurrencesInTree(tree, language, symbol) : null
+    );
+    if (occurrences) return { content: indexed.text, occurrences };
+  }
+
+  const content = indexed?.text ?? (await readSourceFile(absPath).catch(() => null));
+  if (content === null) return null;
+  const language = detectLanguage(absPath, content);
+  return { content, occurrences: await findScopedIdentifierOccurrences(content, language, symbol) };
+}
+
+/**
+ * Find definitions and uses of a symbol, resolved by lexical scope.
+ *
+ * Without a position, only module-level bindings are reported, so locals and parameters that
+ * shadow the name are left out. With `file` + `line`, the occurrence on that line decides the
+ * binding: a local binding limits results to its enclosing block in that file.
+ */
+export async function findScopedReferences(
+  rootPath: string,
+  symbol: string,
+  options: FindReferencesOptions = {}
+): Promise<FindReferencesResult> {
+  const normalizedSymbol = symbol.trim();
+  const limit = normalizeReferenceLimit(options.limit);
+
+  if (!normalizedSymbol) {
+    return { status: 'error', message: 'Symbol is required' };
+  }
+
+  const resolvedRoot = path.resolve(rootPath);
+  const definitions: ReferenceLocation[] = [];
+  const references: ReferenceLocation[] = [];
+  let referenceCount = 0;
+
+  const record = (location: ReferenceLocation) => {
+    if (location.kind === 'definition') {
+      if (definitions.length < limit) definitions.push(location);
+      return;
+    }
+    referenceCount += 1;
+    if (references.length < limit) references.push(location);
+  };
+
+  // Resolve the binding targeted by an explicit position, if any.
+  let targetFile: string | null = null;
+  let targetScope: ByteSpan | null = null;
+  let targetOccurrences: ScopedIdentifierOccurrence[] | null = null;
+  let targetLines: string[] = [];
+
+  if (options.file) {
+    const absPath = path.resolve(resolvedRoot, options.file);
+    const relPath = relativeInside(resolvedRoot, absPath);
+    if (!relPath) {
+      return { status: 'error', message: `File must be inside the project root: ${options.file}` };
+    }
+    targetFile = relPath.replace(/\\/g, '/');
+    const scanned = await scanFile(absPath, normalizedSymbol, options.symbolIndex);
+    if (scanned) {
+      targetLines = scanned.content.split('\n');
+      targetOccurrences = scanned.occurrences;
+      const atLine =
+        options.line !== undefined
+          ? targetOccurrences?.find((occ) => occ.line === options.line)
+          : undefined;
+      targetScope = atLine?.bindingScope ?? null;
+    }
+  }
+
+  if (targetFile && targetScope && targetOccurrences) {
+    for (const occ of targetOccurrences) {
+      if (!sameSpan(occ.bindingScope, targetScope)) continue;
+      const kind = occ.isDefinition ? 'definition' : 'reference';
+      record(toReferenceLocation(targetFile, targetLines, occ, kind));
+    }
+
+    return {
+      status: 'success',
+      symbol: normalizedSymbol,
+      scope: 'local',
+      definitions,
+      references,
+      referenceCount,
+      isComplete: referenceCount <= references.length
+    };
+  }
+
+  const chunks = await readIndexedChunks(rootPath);
+  const prefilter = new RegExp(`\\b${escapeRegex(normalizedSymbol)}\\b`);
+  const candidates = groupCandidateChunksByFile(rootPath, chunks, prefilter);
+  if (targetFile && !candidates.has(targetFile)) {
+    candidates.set(targetFile, {
+      relPath: targetFile,
+      absPath: path.resolve(resolvedRoot, targetFile),
+      chunks: []
+    });
+  }
+
+  for (const entry of candidates.values()) {
+    if (!entry.absPath) continue;
+
+    const scanned =
+      entry.relPath === targetFile && targetOccurrences
+        ? { content: targetLines.join('\n'), occurrences: targetOccurrences }
+        : await scanFile(entry.absPath, normalizedSymbol, options.symbolIndex);
+    if (!scanned) continue;
+
+    const lines = scanned.content.split('\n');
+    const { occurrences } = scanned;
+
+    if (occurrences) {
+      for (const occ of occurrences) {
+        if (occ.bindingScope !== null) continue; // shadowed by a local or parameter
+        const kind = occ.isDefinition ? 'definition' : 'reference';
+        record(toReferenceLocation(entry.relPath, lines, occ, kind));
+      }
+      continue;
+    }
+
+    // No grammar for this file: every whole-word match counts as a plain reference.
+    const matcher = new RegExp(`\\b${escapeRegex(normalizedSymbol)}\\b`, 'g');
+    lines.forEach((text, index) => {
+      matcher.lastIndex = 0;
+      let match: RegExpExecArray | null;
+      while ((match = matcher.exec(text)) !== null) {
+        record(
+          toReferenceLocation(
+            entry.relPath,
+            lines,
+            { line: index + 1, column: match.index + 1 },
+            'reference'
+          )
+        );
+      }
+    });
+  }
+
+  return {
+    status: 'success',
+    symbol: normalizedSymbol,
+    scope: 'module',
+    definitions,
+    references,
+    referenceCount,
+    isComplete: referenceCount <= references.length
+  };
+}
+
+/**
+ * `findScopedReferences` over several workspace roots, merged into one result.
+ * A position (`file` + `line`) belongs to a single root and is resolved there only.
+ * Every location is tagged with its root so equal relative paths stay distinguishable.
+ */
+export async function findScopedReferencesInRoots(
+  roots: string[],
+  symbol: string,
+  options: FindReferencesOptions = {}
+): Promise<FindReferencesResult> {
+  const fileRoot =
+    options.file && path.isAbsolute(options.file) ? findContainingRoot(roots, options.file) : null;
+  // Relative positions resolve against the primary root, like single-root lookups.
+  const searchRoots = options.file ? [fileRoot ?? roots[0]] : roots;
+  if (roots.length <= 1) {
+    return findScopedReferences(searchRoots[0], symbol, options);
+  }
+
+  const limit = normalizeReferenceLimit(options.limit);
+  const merged: FindReferencesSuccess = {
+    status: 'success',
+    symbol: symbol.trim(),
+    scope: 'module',
+    definitions: [],
+    references: [],
+    referenceCount: 0,
+    isComplete: true
+  };
+  const unavailableRoots: string[] = [];
+
+  for (const root of searchRoots) {
+    let result: FindReferencesResult;
+    try {
+      result = await findScopedReferences(root, symbol, { ...options, limit });
+    } catch (error) {
+      // Only the primary root's index is guaranteed; other roots may still be indexing.
+      if (root === roots[0]) throw error;
+      unavailableRoots.push(root);
+      continue;
+    }
+    if (result.status === 'error') return result;
+
+    const tag = (location: ReferenceLocation): ReferenceLocation => ({ ...location, root });
+    merged.scope = result.scope;
+    merged.definitions.push(...result.definitions.map(tag));
+    merged.references.push(...result.references.map(tag));
+    merged.referenceCount += result.referenceCount;
+  }
+
+  merged.definitions = merged.definitions.slice(0, limit);
+  merged.references = merged.references.slice(0, limit);
+  merged.isComplete = merged.referenceCount <= merged.references.length;
+  if (unavailableRoots.length > 0) merged.unavailableRoots = unavailableRoots;
+  return merged;
+}
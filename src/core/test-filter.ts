@@ -0,0 +1,48 @@
+/**
+ * Test code filtering for search and context tools. Code counts as a test when its file follows
+ * a test path convention (`isTestFile`) or sits in a symbol that extraction flags as test code,
+ * such as a function inside a Rust `#[cfg(test)]` module.
+ */
+
+import type { CodeChunk, LineRange, TestFilter } from '../types/index.js';
+import { detectLanguage, isTestFile } from '../utils/language-detection.js';
+import { extractTreeSitterSymbols, supportsTreeSitter } from '../utils/tree-sitter.js';
+
+/**
+ * Filter for the `includeTests` / `testsOnly` tool arguments: undefined when tests are simply
+ * included, null when the two contradict each other.
+ */
+export function testFilterFromArgs(
+  includeTests: unknown,
+  testsOnly: unknown
+): TestFilter | null | undefined {
+  if (testsOnly === true) return includeTests === false ? null : 'only';
+  return includeTests === false ? 'exclude' : undefined;
+}
+
+export function matchesTestFilter(isTest: boolean, filter: TestFilter | undefined): boolean {
+  if (filter === 'exclude') return !isTest;
+  if (filter === 'only') return isTest;
+  return true;
+}
+
+/** Indexes built before chunks recorded `isTest` still match by path. */
+export function isTestChunk(chunk: Pick<CodeChunk, 'relativePath' | 'metadata'>): boolean {
+  return chunk.metadata?.isTest === true || isTestFile(chunk.relativePath);
+}
+
+/**
+ * Line ranges of test code in a file with text `content`: all of it for a test file by path,
+ * otherwise its inline test symbols.
+ */
+export async function testLineRanges(relativePath: string, content: string): Promise<LineRange[]> {
+  if (isTestFile(relativePath)) {
+    return [{ startLine: 1, endLine: content.split('\n').length }];
+  }
+  const language = detectLanguage(relativePath, content);
+  if (!supportsTreeSitter(language)) return [];
+  const extraction = await extractTreeSitterSymbols(content, language);
+  return (extraction?.symbols ?? [])
+    .filter((symbol) => symbol.test)
+    .map(({ startLine, endLine }) => ({ startLine, endLine }));
+}
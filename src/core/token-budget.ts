@@ -0,0 +1,168 @@
+/**
+ * Token-budget packing for context assembly.
+ * Snippets are added greedily in priority order until the budget is spent; snippets that do
+ * not fit are dropped whole so responses never end mid-line.
+ */
+
+export interface TokenEstimator {
+  /** Estimated token count for `text` */
+  estimate(text: string): number;
+}
+
+/** Rough default: ~4 characters per token, which is close enough for code in most tokenizers. */
+export class CharRatioTokenEstimator implements TokenEstimator {
+  private readonly charsPerToken: number;
+
+  constructor(charsPerToken = 4) {
+    this.charsPerToken = charsPerToken > 0 ? charsPerToken : 4;
+  }
+
+  estimate(text: string): number {
+    return Math.ceil(text.length / this.charsPerToken);
+  }
+}
+
+export const DEFAULT_TOKEN_ESTIMATOR: TokenEstimator = new CharRatioTokenEstimator();
+
+export type SnippetRole = 'definition' | 'reference' | 'import';
+
+/** Lower packs first: the definition, then its uses, then import context. */
+const ROLE_PRIORITY: Record<SnippetRole, number> = {
+  definition: 0,
+  reference: 1,
+  import: 2
+};
+
+export interface BudgetCandidate {
+  role: SnippetRole;
+  text: string;
+}
+
+export type PackedSnippet<T extends BudgetCandidate> = T & {
+  tokens: number;
+  /** True when the snippet was cut at a line boundary to fit the budget */
+  truncated?: boolean;
+};
+
+export interface PackResult<T extends BudgetCandidate> {
+  snippets: Array<PackedSnippet<T>>;
+  tokensUsed: number;
+  maxTokens: number;
+  droppedSnippets: number;
+}
+
+/**
+ * Keep whole leading lines of `text` while the estimate stays within `maxTokens`. The cut is
+ * binary-searched over line ends, so a long snippet takes a logarithmic number of estimates
+ * instead of one per line; estimates are assumed not to shrink as the prefix grows.
+ */
+function truncateAtLineBoundary(
+  text: string,
+  maxTokens: number,
+  estimator: TokenEstimator
+): string {
+  const lineEnds: number[] = [];
+  for (let index = text.indexOf('\n'); index !== -1; index = text.indexOf('\n', index + 1)) {
+    lineEnds.push(index);
+  }
+  lineEnds.push(text.length);
+
+  // Largest count of leading lines that fits
+  let low = 0;
+  let high = lineEnds.length;
+  while (low < high) {
+    const mid = Math.ceil((low + high) / 2);
+    if (estimator.estimate(text.slice(0, lineEnds[mid - 1])) <= maxTokens) low = mid;
+    else high = mid - 1;
+  }
+  return low > 0 ? text.slice(0, lineEnds[low - 1]) : '';
+}
+
+/**
+ * Incremental packer for candidates that arrive already in priority order.
+ * Each candidate is kept or dropped as it is offered, so callers can stream snippets out without
+ * holding the candidate list; the first candidate is cut at a line boundary when it alone
+ * exceeds the budget, so the caller always gets something.
+ */
+export class SnippetBudget<T extends BudgetCandidate> {
+  readonly maxTokens: number;
+  tokensUsed = 0;
+  droppedSnippets = 0;
+  private packedCount = 0;
+  private readonly estimator: TokenEstimator;
+
+  constructor(maxTokens: number, estimator: TokenEstimator = DEFAULT_TOKEN_ESTIMATOR) {
+    this.maxTokens = maxTokens;
+    this.estimator = estimator;
+  }
+
+  /** The candidate with its token count when it fits, otherwise null (and counted as dropped). */
+  offer(candidate: T): PackedSnippet<T> | null {
+    const tokens = this.estimator.estimate(candidate.text);
+    if (this.tokensUsed + tokens <= this.maxTokens) {
+      this.tokensUsed += tokens;
+      this.packedCount++;
+      return { ...candidate, tokens };
+    }
+
+    if (this.packedCount === 0) {
+      const text = truncateAtLineBoundary(candidate.text, this.maxTokens, this.estimator);
+      if (text) {
+        const truncatedTokens = this.estimator.estimate(text);
+        this.tokensUsed += truncatedTokens;
+        this.packedCount++;
+        return { ...candidate, text, tokens: truncatedTokens, truncated: true };
+      }
+    }
+
+    this.droppedSnippets++;
+    return null;
+  }
+}
+
+/**
+ * Yield the candidates that fit `budget` as they are produced. Candidates are pulled one at a
+ * time, so a slow consumer holds back the producer instead of letting snippets pile up.
+ */
+export async function* packSnippetStream<T extends BudgetCandidate>(
+  candidates: AsyncIterable<T> | Iterable<T>,
+  budget: SnippetBudget<T>
+): AsyncGenerator<PackedSnippet<T>> {
+  for await (const candidate of candidates) {
+    const packed = budget.offer(candidate);
+    if (packed) yield packed;
+  }
+}
+
+/**
+ * Greedily pack candidates into `maxTokens`.
+ * Candidates keep their relative (relevance) order within each role. When not even the first
+ * candidate fits, it is truncated at a line boundary so the caller always gets something.
+ */
+export function packSnippetsWithinBudget<T extends BudgetCandidate>(
+  candidates: T[],
+  maxTokens: number,
+  estimator: TokenEstimator = DEFAULT_TOKEN_ESTIMATOR
+): PackResult<T> {
+  const ordered = candidates
+    .map((candidate, index) => ({ candidate, index }))
+    .sort(
+      (a, b) =>
+        ROLE_PRIORITY[a.candidate.role] - ROLE_PRIORITY[b.candidate.role] || a.index - b.index
+    )
+    .map(({ candidate }) => candidate);
+
+  const budget = new SnippetBudget<T>(maxTokens, estimator);
+  const snippets: Array<PackedSnippet<T>> = [];
+  for (const candidate of ordered) {
+    const packed = budget.offer(candidate);
+    if (packed) snippets.push(packed);
+  }
+
+  return {
+    snippets,
+    tokensUsed: budget.tokensUsed,
+    maxTokens,
+    droppedSnippets: budget.droppedSnippets
+  };
+}
@@ -0,0 +1,55 @@
+/**
+ * Workspace roots: one server instance can serve several project directories.
+ * Each root keeps its own `.codebase-context/` index; tools fan out over the roots and tag
+ * results with the root they came from.
+ */
+
+import path from 'path';
+import { relativeInside } from '../utils/symlinks.js';
+
+/** Split a `path.delimiter`-separated list (as in `CODEBASE_CONTEXT_EXTRA_ROOTS`). */
+export function parseWorkspaceRootList(value: string | undefined): string[] {
+  if (!value) return [];
+  return value
+    .split(path.delimiter)
+    .map((entry) => entry.trim())
+    .filter(Boolean);
+}
+
+/** Absolute, de-duplicated roots with the primary root first. */
+export function resolveWorkspaceRoots(primaryRoot: string, extraRoots: string[] = []): string[] {
+  const roots = [primaryRoot, ...extraRoots].map((root) => path.resolve(root));
+  return Array.from(new Set(roots));
+}
+
+/** Innermost root containing the absolute `filePath`, or null when it is outside every root. */
+export function findContainingRoot(roots: string[], filePath: string): string | null {
+  let best: string | null = null;
+  for (const root of roots) {
+    if (relativeInside(root, filePath) !== null) {
+      if (!best || root.length > best.length) best = root;
+    }
+  }
+  return best;
+}
+
+/**
+ * Resolve an optional `root` selector to the roots a query should cover.
+ * Accepts an absolute path, a path relative to the primary root, or a root's directory name.
+ * Returns null when the selector matches no root.
+ */
+export function selectWorkspaceRoots(roots: string[], selector?: string): string[] | null {
+  const trimmed = selector?.trim();
+  if (!trimmed) return roots;
+
+  const resolved = path.resolve(roots[0], trimmed);
+  const byPath = roots.find((root) => root === resolved);
+  if (byPath) return [byPath];
+
+  const byName = roots.filter((root) => path.basename(root) === trimmed);
+  return byName.length === 1 ? byName : null;
+}
+
+export function describeUnknownRoot(roots: string[], selector: string): string {
+  return `Unknown root '${selector}'. Available roots: ${roots.join(', ')}`;
+}
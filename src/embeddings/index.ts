@@ -1,15 +1,34 @@
 export * from './types.js';
 export * from './transformers.js';
+export * from './stub.js';
 
 import { EmbeddingProvider, EmbeddingConfig, DEFAULT_EMBEDDING_CONFIG } from './types.js';
 import { TransformersEmbeddingProvider } from './transformers.js';
+import { StubEmbeddingProvider } from './stub.js';
 
 let cachedProvider: EmbeddingProvider | null = null;
 let cachedProviderType: string | null = null;
+let registeredProvider: EmbeddingProvider | null = null;
+
+/**
+ * Use `provider` for every embedding call in this process, indexing and search alike, instead
+ * of the configured one (e.g. a client for a local embedding server). Pass null to go back to
+ * configuration. Changing providers means re-indexing: stored vectors come from the old one.
+ */
+export function registerEmbeddingProvider(provider: EmbeddingProvider | null): void {
+  registeredProvider = provider;
+  cachedProvider = null;
+  cachedProviderType = null;
+}
 
 export async function getEmbeddingProvider(
   config: Partial<EmbeddingConfig> = {}
 ): Promise<EmbeddingProvider> {
+  if (registeredProvider) {
+    if (!registeredProvider.isReady()) await registeredProvider.initialize();
+    return registeredProvider;
+  }
+
   const mergedConfig = { ...DEFAULT_EMBEDDING_CONFIG, ...config };
   const providerKey = `${mergedConfig.provider}:${mergedConfig.model}`;
 
@@ -31,7 +50,16 @@ export async function getEmbeddingProvider(
   }
 
   if (mergedConfig.provider === 'custom') {
-    throw new Error("Custom provider not implemented. Use 'openai' or 'transformers'.");
+    throw new Error(
+      'No custom embedding provider registered. Call registerEmbeddingProvider() or use ' +
+        "'openai', 'transformers' or 'stub'."
+    );
+  }
+
+  if (mergedConfig.provider === 'stub') {
+    cachedProvider = new StubEmbeddingProvider();
+    cachedProviderType = providerKey;
+    return cachedProvider;
   }
 
   if (mergedConfig.provider === 'ollama') {
@@ -0,0 +1,36 @@
+import { createHash } from 'crypto';
+import { EmbeddingProvider } from './types.js';
+
+const STUB_DIMENSIONS = 256;
+
+/**
+ * Model-free provider: hashes identifier-like tokens into a fixed-size bag-of-words vector.
+ * Similarity is lexical, not conceptual, but it needs no download or network call, so the
+ * semantic pipeline runs end to end before a real provider is configured or registered.
+ */
+export class StubEmbeddingProvider implements EmbeddingProvider {
+  readonly name = 'stub';
+  readonly modelName = 'token-hash';
+  readonly dimensions = STUB_DIMENSIONS;
+
+  async initialize(): Promise<void> {}
+
+  isReady(): boolean {
+    return true;
+  }
+
+  async embed(text: string): Promise<number[]> {
+    const vector = new Array<number>(this.dimensions).fill(0);
+    for (const token of text.toLowerCase().match(/[a-z0-9_]+/g) ?? []) {
+      const bucket = createHash('md5').update(token).digest().readUInt32LE(0) % this.dimensions;
+      vector[bucket] += 1;
+    }
+    // An all-zero vector has no cosine similarity; nudge one bucket so empty text still ranks
+    if (vector.every((value) => value === 0)) vector[0] = 1;
+    return vector;
+  }
+
+  async embedBatch(texts: string[]): Promise<number[][]> {
+    return Promise.all(texts.map((text) => this.embed(text)));
+  }
+}
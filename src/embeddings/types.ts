@@ -10,7 +10,7 @@ export interface EmbeddingProvider {
 }
 
 export interface EmbeddingConfig {
-  provider: 'transformers' | 'ollama' | 'openai' | 'custom';
+  provider: 'transformers' | 'ollama' | 'openai' | 'stub' | 'custom';
   model?: string;
   batchSize?: number;
   maxRetries?: number;
@@ -123,8 +123,11 @@ export async function evaluateFixture({
 }: EvaluateFixtureParams): Promise<EvalSummary> {
   const results: EvalResult[] = [];
 
+  // Git history moves with every commit; frozen fixtures must rank the same on every run.
+  const evalSearchOptions = { useGitActivity: false, ...searchOptions };
+
   for (const query of fixture.queries) {
-    const searchResults = await searcher.search(query.query, limit, undefined, searchOptions);
+    const searchResults = await searcher.search(query.query, limit, undefined, evalSearchOptions);
     results.push(evaluateQuery(query, searchResults));
   }
 
@@ -15,6 +15,8 @@ import {
   ListToolsRequestSchema,
   ListResourcesRequestSchema,
   ReadResourceRequestSchema,
+  SubscribeRequestSchema,
+  UnsubscribeRequestSchema,
   Resource
 } from '@modelcontextprotocol/sdk/types.js';
 import { CodebaseIndexer } from './core/indexer.js';
@@ -38,23 +40,37 @@ import {
 } from './constants/codebase-context.js';
 import { appendMemoryFile } from './memory/store.js';
 import { handleCliCommand } from './cli.js';
-import { startFileWatcher } from './core/file-watcher.js';
+import { resolveDebounceMs, startFileWatcher } from './core/file-watcher.js';
+import { loadRootDirectoryConfig } from './utils/directory-config.js';
 import { createAutoRefreshController } from './core/auto-refresh.js';
+import { SymbolIndex, treeCacheOptionsFromEnv } from './core/symbol-index.js';
+import { ToolResultCache, resultCacheOptionsFromEnv } from './core/result-cache.js';
+import { IndexChangeNotifier, describeIndexChange } from './core/index-notifications.js';
+import { setSpanSink, spanSinkFromEnv } from './utils/tracing.js';
+import { describeCustomQueryError, installCustomSymbolQueries } from './core/custom-queries.js';
+import { parseWorkspaceRootList, resolveWorkspaceRoots } from './core/workspace-roots.js';
 import { parseGitLogLineToMemory } from './memory/git-memory.js';
 import {
   isComplementaryPatternCategory,
   shouldSkipLegacyTestingFrameworkCategory
 } from './patterns/semantics.js';
-import { CONTEXT_RESOURCE_URI, isContextResourceUri } from './resources/uri.js';
+import {
+  CONTEXT_RESOURCE_URI,
+  INDEX_RESOURCE_URI,
+  isContextResourceUri,
+  isIndexResourceUri
+} from './resources/uri.js';
 import { readIndexMeta, validateIndexArtifacts } from './core/index-meta.js';
-import { TOOLS, dispatchTool, type ToolContext } from './tools/index.js';
+import { TOOLS, dispatchTool, type ToolContext, type ToolResponse } from './tools/index.js';
+import { parseServerArgs } from './transports/args.js';
+import { startHttpTransport, type HttpTransportHandle } from './transports/http.js';
 
 analyzerRegistry.register(new AngularAnalyzer());
 analyzerRegistry.register(new GenericAnalyzer());
 
 // Resolve root path with validation
 function resolveRootPath(): string {
-  const arg = process.argv[2];
+  const arg = parseServerArgs(process.argv.slice(2)).roots[0];
   const envPath = process.env.CODEBASE_ROOT;
 
   // Priority: CLI arg > env var > cwd
@@ -71,6 +87,13 @@ function resolveRootPath(): string {
 }
 
 const ROOT_PATH = resolveRootPath();
+const SERVER_ARGS = parseServerArgs(process.argv.slice(2));
+
+// Extra workspace roots: further positional args, then CODEBASE_CONTEXT_EXTRA_ROOTS
+const WORKSPACE_ROOTS = resolveWorkspaceRoots(ROOT_PATH, [
+  ...SERVER_ARGS.roots.slice(1),
+  ...parseWorkspaceRootList(process.env.CODEBASE_CONTEXT_EXTRA_ROOTS)
+]);
 
 // File paths (new structure)
 const PATHS = {
@@ -90,9 +113,21 @@ const LEGACY_PATHS = {
 export const INDEX_CONSUMING_TOOL_NAMES = [
   'search_codebase',
   'get_symbol_references',
+  'find_references',
   'detect_circular_dependencies',
   'get_team_patterns',
-  'get_codebase_metadata'
+  'get_codebase_metadata',
+  'get_symbol_context',
+  'call_graph',
+  'export_chunks',
+  'get_definition',
+  'dependency_graph',
+  'structural_search',
+  'semantic_search',
+  'list_files',
+  'pack_context',
+  'related_files',
+  'resolve_path'
 ] as const;
 
 export const INDEX_CONSUMING_RESOURCE_NAMES = ['Codebase Intelligence'] as const;
@@ -254,23 +289,11 @@ const indexState: IndexState = {
 };
 
 const autoRefresh = createAutoRefreshController();
-
-const server: Server = new Server(
-  {
-    name: 'codebase-context',
-    version: PKG_VERSION
-  },
-  {
-    capabilities: {
-      tools: {},
-      resources: {}
-    }
-  }
-);
-
-server.setRequestHandler(ListToolsRequestSchema, async () => {
-  return { tools: TOOLS };
-});
+const symbolIndex = new SymbolIndex(WORKSPACE_ROOTS, treeCacheOptionsFromEnv());
+// Repeated tool calls are answered from here until the index or the watched files change
+const resultCache = new ToolResultCache<ToolResponse>(resultCacheOptionsFromEnv());
+// Sessions subscribed to INDEX_RESOURCE_URI, told which files each watcher window changed
+const indexChanges = new IndexChangeNotifier();
 
 // MCP Resources - Proactive context injection
 const RESOURCES: Resource[] = [
@@ -281,13 +304,17 @@ const RESOURCES: Resource[] = [
       'Automatic codebase context: libraries used, team patterns, and conventions. ' +
       'Read this BEFORE generating code to follow team standards.',
     mimeType: 'text/plain'
+  },
+  {
+    uri: INDEX_RESOURCE_URI,
+    name: 'Index Version',
+    description:
+      'Current index version and status. Subscribe to be notified (resources/updated, with ' +
+      'the changed files and new version) whenever watched files change.',
+    mimeType: 'application/json'
   }
 ];
 
-server.setRequestHandler(ListResourcesRequestSchema, async () => {
-  return { resources: RESOURCES };
-});
-
 async function generateCodebaseContext(): Promise<string> {
   const intelligencePath = PATHS.intelligence;
 
@@ -456,26 +483,6 @@ async function generateCodebaseContext(): Promise<string> {
   }
 }
 
-server.setRequestHandler(ReadResourceRequestSchema, async (request) => {
-  const uri = request.params.uri;
-
-  if (isContextResourceUri(uri)) {
-    const content = await generateCodebaseContext();
-
-    return {
-      contents: [
-        {
-          uri: CONTEXT_RESOURCE_URI,
-          mimeType: 'text/plain',
-          text: content
-        }
-      ]
-    };
-  }
-
-  throw new Error(`Unknown resource: ${uri}`);
-});
-
 /**
  * Extract memories from conventional git commits (refactor:, migrate:, fix:, revert:).
  * Scans last 90 days. Deduplicates via content hash. Zero friction alternative to manual memory.
@@ -525,6 +532,7 @@ async function performIndexingOnce(incrementalOnly?: boolean): Promise<void> {
     const indexer = new CodebaseIndexer({
       rootPath: ROOT_PATH,
       incrementalOnly,
+      symbolIndex,
       onProgress: (progress) => {
         // Only log when phase or percentage actually changes (prevents duplicate logs)
         const shouldLog =
@@ -566,10 +574,23 @@ async function performIndexingOnce(incrementalOnly?: boolean): Promise<void> {
     indexState.status = 'error';
     indexState.error = error instanceof Error ? error.message : String(error);
     console.error('Indexing failed:', indexState.error);
+  } finally {
+    resultCache.invalidate();
   }
 }
 
-async function performIndexing(incrementalOnly?: boolean): Promise<void> {
+/** The running `performIndexing` call, which also runs any refresh queued while it works. */
+let activeIndexing: Promise<void> | null = null;
+
+function performIndexing(incrementalOnly?: boolean): Promise<void> {
+  const run = performQueuedIndexing(incrementalOnly).finally(() => {
+    if (activeIndexing === run) activeIndexing = null;
+  });
+  activeIndexing = run;
+  return run;
+}
+
+async function performQueuedIndexing(incrementalOnly?: boolean): Promise<void> {
   let nextMode = incrementalOnly;
   for (;;) {
     await performIndexingOnce(nextMode);
@@ -594,93 +615,305 @@ async function shouldReindex(): Promise<boolean> {
   }
 }
 
-server.setRequestHandler(CallToolRequestSchema, async (request) => {
-  const { name, arguments: args } = request.params;
-
-  try {
-    // Gate INDEX_CONSUMING tools on a valid, healthy index
-    let indexSignal: IndexSignal | undefined;
-    if ((INDEX_CONSUMING_TOOL_NAMES as readonly string[]).includes(name)) {
-      if (indexState.status === 'indexing') {
-        return {
-          content: [
-            {
-              type: 'text',
-              text: JSON.stringify({
-                status: 'indexing',
-                message: 'Index build in progress — please retry shortly'
-              })
-            }
-          ]
-        };
+/**
+ * Build an MCP server wired to the shared tool handlers. Stdio serves one; the HTTP transport
+ * builds one per session, so every connection dispatches to the same code.
+ */
+function createServer(): Server {
+  const instance = new Server(
+    {
+      name: 'codebase-context',
+      version: PKG_VERSION
+    },
+    {
+      capabilities: {
+        tools: {},
+        resources: { subscribe: true }
       }
-      if (indexState.status === 'error') {
-        return {
-          content: [
-            {
-              type: 'text',
-              text: JSON.stringify({
-                status: 'error',
-                message: `Indexer error: ${indexState.error}`
-              })
-            }
-          ]
-        };
+    }
+  );
+
+  instance.setRequestHandler(ListToolsRequestSchema, async () => {
+    return { tools: TOOLS };
+  });
+
+  instance.setRequestHandler(ListResourcesRequestSchema, async () => {
+    return { resources: RESOURCES };
+  });
+
+  instance.setRequestHandler(ReadResourceRequestSchema, async (request) => {
+    const uri = request.params.uri;
+
+    if (isContextResourceUri(uri)) {
+      const content = await generateCodebaseContext();
+
+      return {
+        contents: [
+          {
+            uri: CONTEXT_RESOURCE_URI,
+            mimeType: 'text/plain',
+            text: content
+          }
+        ]
+      };
+    }
+
+    if (isIndexResourceUri(uri)) {
+      return {
+        contents: [
+          {
+            uri: INDEX_RESOURCE_URI,
+            mimeType: 'application/json',
+            text: JSON.stringify({
+              indexVersion: resultCache.indexVersion,
+              status: indexState.status,
+              lastIndexed: indexState.lastIndexed?.toISOString()
+            })
+          }
+        ]
+      };
+    }
+
+    throw new Error(`Unknown resource: ${uri}`);
+  });
+
+  // One subscription per session; closing the session ends it
+  let unsubscribeIndex: (() => void) | undefined;
+  instance.setRequestHandler(SubscribeRequestSchema, async (request) => {
+    if (!isIndexResourceUri(request.params.uri)) {
+      throw new Error(`Resource does not support subscriptions: ${request.params.uri}`);
+    }
+    unsubscribeIndex ??= indexChanges.subscribe((change) => {
+      instance.sendResourceUpdated({ uri: INDEX_RESOURCE_URI, ...change }).catch(() => {
+        // The session went away between the change and the send
+      });
+    });
+    return {};
+  });
+  instance.setRequestHandler(UnsubscribeRequestSchema, async (request) => {
+    if (isIndexResourceUri(request.params.uri)) {
+      unsubscribeIndex?.();
+      unsubscribeIndex = undefined;
+    }
+    return {};
+  });
+  instance.onclose = () => {
+    unsubscribeIndex?.();
+    unsubscribeIndex = undefined;
+  };
+
+  instance.setRequestHandler(CallToolRequestSchema, async (request, extra) => {
+    const { name, arguments: args } = request.params;
+    const progressToken = request.params._meta?.progressToken;
+
+    try {
+      // Gate INDEX_CONSUMING tools on a valid, healthy index
+      let indexSignal: IndexSignal | undefined;
+      if ((INDEX_CONSUMING_TOOL_NAMES as readonly string[]).includes(name)) {
+        if (indexState.status === 'indexing') {
+          return {
+            content: [
+              {
+                type: 'text',
+                text: JSON.stringify({
+                  status: 'indexing',
+                  message: 'Index build in progress — please retry shortly'
+                })
+              }
+            ]
+          };
+        }
+        if (indexState.status === 'error') {
+          return {
+            content: [
+              {
+                type: 'text',
+                text: JSON.stringify({
+                  status: 'error',
+                  message: `Indexer error: ${indexState.error}`
+                })
+              }
+            ]
+          };
+        }
+        indexSignal = await ensureValidIndexOrAutoHeal();
+        if (indexSignal.action === 'rebuild-failed') {
+          return {
+            content: [
+              {
+                type: 'text',
+                text: JSON.stringify({
+                  error: 'Index is corrupt and could not be rebuilt automatically.',
+                  index: indexSignal
+                })
+              }
+            ],
+            isError: true
+          };
+        }
       }
-      indexSignal = await ensureValidIndexOrAutoHeal();
-      if (indexSignal.action === 'rebuild-failed') {
-        return {
-          content: [
-            {
-              type: 'text',
-              text: JSON.stringify({
-                error: 'Index is corrupt and could not be rebuilt automatically.',
-                index: indexSignal
-              })
+
+      const ctx: ToolContext = {
+        indexState,
+        paths: PATHS,
+        rootPath: ROOT_PATH,
+        workspaceRoots: WORKSPACE_ROOTS,
+        performIndexing,
+        resultCache,
+        symbolIndex,
+        ...(progressToken !== undefined
+          ? {
+              reportProgress: (progress: number, message?: string) =>
+                extra.sendNotification({
+                  method: 'notifications/progress',
+                  params: { progressToken, progress, ...(message ? { message } : {}) }
+                })
             }
-          ],
-          isError: true
-        };
+          : {})
+      };
+
+      const result = await dispatchTool(name, args ?? {}, ctx);
+
+      // Inject IndexSignal into response so callers can inspect index health
+      if (indexSignal !== undefined && result.content?.[0]) {
+        try {
+          const parsed = JSON.parse(result.content[0].text);
+          result.content[0] = {
+            type: 'text',
+            text: JSON.stringify({ ...parsed, index: indexSignal })
+          };
+        } catch {
+          /* response wasn't JSON, skip injection */
+        }
       }
+
+      return result;
+    } catch (error) {
+      return {
+        content: [
+          {
+            type: 'text',
+            text: `Unexpected error: ${error instanceof Error ? error.message : String(error)}`
+          }
+        ],
+        isError: true
+      };
     }
+  });
 
-    const ctx: ToolContext = {
-      indexState,
-      paths: PATHS,
-      rootPath: ROOT_PATH,
-      performIndexing
-    };
+  return instance;
+}
 
-    const result = await dispatchTool(name, args ?? {}, ctx);
-
-    // Inject IndexSignal into response so callers can inspect index health
-    if (indexSignal !== undefined && result.content?.[0]) {
-      try {
-        const parsed = JSON.parse(result.content[0].text);
-        result.content[0] = {
-          type: 'text',
-          text: JSON.stringify({ ...parsed, index: indexSignal })
-        };
-      } catch {
-        /* response wasn't JSON, skip injection */
-      }
+const server: Server = createServer();
+
+/**
+ * Watch one workspace root. Each debounce window drops cached results, splices the changed files
+ * into the in-memory symbol index, then calls `reindex` so on-disk artifacts catch up through the
+ * manifest-diffed incremental reindex. The reindex starts once the symbol index has the changes,
+ * so it reuses their symbols. Index subscribers hear which files changed once the promise
+ * `reindex` returns settles, with the index version that includes them.
+ */
+function watchRoot(
+  rootPath: string,
+  debounceMs: number,
+  reindex: () => Promise<void> | void,
+  onReady?: () => void
+): () => void {
+  return startFileWatcher({
+    rootPath,
+    debounceMs,
+    onReady,
+    onChanged: (changedPaths) => {
+      // Files tools read directly (outlines, definitions) changed: drop every cached result
+      resultCache.invalidate();
+      void symbolIndex
+        .refreshFiles(changedPaths)
+        .catch((error) => {
+          if (process.env.CODEBASE_CONTEXT_DEBUG) {
+            console.error('[symbol-index] refresh failed:', error);
+          }
+        })
+        .then(() => reindex())
+        .catch((error) => {
+          if (process.env.CODEBASE_CONTEXT_DEBUG) {
+            console.error('[file-watcher] reindex failed:', error);
+          }
+        })
+        // Sent once search sees the new files too, so the version covers every tool
+        .finally(() => {
+          indexChanges.publish(() =>
+            describeIndexChange(rootPath, changedPaths, resultCache.indexVersion)
+          );
+        });
     }
+  });
+}
 
-    return result;
+/** Load the symbols of an already indexed root into the symbol index, in the background. */
+async function syncSymbolIndex(rootPath: string): Promise<void> {
+  try {
+    await new CodebaseIndexer({ rootPath, symbolIndex }).syncSymbolIndex();
   } catch (error) {
-    return {
-      content: [
-        {
-          type: 'text',
-          text: `Unexpected error: ${error instanceof Error ? error.message : String(error)}`
-        }
-      ],
-      isError: true
-    };
+    if (process.env.CODEBASE_CONTEXT_DEBUG) {
+      console.error(`[symbol-index] Loading ${rootPath} failed:`, error);
+    }
   }
-});
+}
+
+/**
+ * Index an extra workspace root in the background (when it has no index yet) and keep it
+ * fresh like the primary root. Extra roots are not tracked in `indexState`; tools report a
+ * root as unavailable until its index exists.
+ */
+function startExtraRoot(extraRoot: string, debounceMs: number): () => void {
+  let running: Promise<void> | null = null;
+  let rerun = false;
+
+  // Settles once no run is left, including the rerun a call made mid-run queues
+  const reindex = (incrementalOnly: boolean): Promise<void> => {
+    if (running) {
+      rerun = true;
+      return running;
+    }
+    console.error(`Indexing (${incrementalOnly ? 'incremental' : 'full'}): ${extraRoot}`);
+    running = new CodebaseIndexer({ rootPath: extraRoot, incrementalOnly, symbolIndex })
+      .index()
+      .then(
+        () => undefined,
+        (error) => {
+          console.error(`[workspace-roots] Indexing failed for ${extraRoot}:`, error);
+        }
+      )
+      .then(() => {
+        running = null;
+        resultCache.invalidate();
+        if (rerun) {
+          rerun = false;
+          return reindex(true);
+        }
+      });
+    return running;
+  };
+
+  void fileExists(path.join(extraRoot, CODEBASE_CONTEXT_DIRNAME, KEYWORD_INDEX_FILENAME)).then(
+    (exists) => {
+      if (!exists) {
+        void reindex(false);
+      } else {
+        void syncSymbolIndex(extraRoot);
+      }
+    }
+  );
+
+  return watchRoot(extraRoot, debounceMs, () => reindex(true));
+}
 
 async function main() {
+  if (SERVER_ARGS.error) {
+    console.error(`ERROR: ${SERVER_ARGS.error}`);
+    process.exit(1);
+  }
+
   // Validate root path exists and is a directory
   try {
     const stats = await fs.stat(ROOT_PATH);
@@ -730,6 +963,11 @@ async function main() {
     }
   }
 
+  // Compile custom symbol queries before anything parses; a bad query file is only skipped
+  for (const error of await installCustomSymbolQueries(ROOT_PATH)) {
+    console.error(describeCustomQueryError(error));
+  }
+
   const needsIndex = await shouldReindex();
 
   if (needsIndex) {
@@ -739,47 +977,86 @@ async function main() {
     if (process.env.CODEBASE_CONTEXT_DEBUG) console.error('[DEBUG] Index found. Ready.');
     indexState.status = 'ready';
     indexState.lastIndexed = new Date();
+    void syncSymbolIndex(ROOT_PATH);
   }
 
-  const transport = new StdioServerTransport();
-  await server.connect(transport);
+  let httpTransport: HttpTransportHandle | null = null;
+  if (SERVER_ARGS.transport === 'http') {
+    const idleEnv = Number.parseInt(process.env.CODEBASE_CONTEXT_HTTP_IDLE_MS ?? '', 10);
+    httpTransport = await startHttpTransport({
+      host: SERVER_ARGS.host,
+      port: SERVER_ARGS.port,
+      createServer,
+      ...(Number.isFinite(idleEnv) && idleEnv >= 0 ? { sessionIdleTimeoutMs: idleEnv } : {})
+    });
+    console.error(`MCP server listening on ${httpTransport.url}`);
+  } else {
+    const transport = new StdioServerTransport();
+    await server.connect(transport);
+  }
 
   if (process.env.CODEBASE_CONTEXT_DEBUG) console.error('[DEBUG] Server ready');
 
   // Auto-refresh: watch for file changes and trigger incremental reindex
-  const debounceEnv = Number.parseInt(process.env.CODEBASE_CONTEXT_DEBOUNCE_MS ?? '', 10);
-  const debounceMs = Number.isFinite(debounceEnv) && debounceEnv >= 0 ? debounceEnv : 2000;
-  const stopWatcher = startFileWatcher({
-    rootPath: ROOT_PATH,
-    debounceMs,
-    onChanged: () => {
-      const shouldRunNow = autoRefresh.onFileChange(indexState.status === 'indexing');
-      if (!shouldRunNow) {
-        if (process.env.CODEBASE_CONTEXT_DEBUG) {
-          console.error('[file-watcher] Index in progress — queueing auto-refresh');
-        }
-        return;
-      }
+  const debounceMs = resolveDebounceMs(
+    process.env.CODEBASE_CONTEXT_DEBOUNCE_MS,
+    (await loadRootDirectoryConfig(ROOT_PATH)).watchDebounceMs
+  );
+  const stopWatcher = watchRoot(ROOT_PATH, debounceMs, () => {
+    const shouldRunNow = autoRefresh.onFileChange(indexState.status === 'indexing');
+    if (!shouldRunNow) {
       if (process.env.CODEBASE_CONTEXT_DEBUG) {
-        console.error('[file-watcher] Changes detected — incremental reindex starting');
+        console.error('[file-watcher] Index in progress — queueing auto-refresh');
       }
-      void performIndexing(true);
+      // The running call picks the queued refresh up before it settles
+      return activeIndexing ?? undefined;
     }
+    if (process.env.CODEBASE_CONTEXT_DEBUG) {
+      console.error('[file-watcher] Changes detected — incremental reindex starting');
+    }
+    return performIndexing(true);
   });
 
-  process.once('exit', stopWatcher);
-  process.once('SIGINT', () => {
-    stopWatcher();
-    process.exit(0);
-  });
-  process.once('SIGTERM', () => {
+  const extraWatchers: Array<() => void> = [];
+  for (const extraRoot of WORKSPACE_ROOTS.slice(1)) {
+    const isDirectory = await fs
+      .stat(extraRoot)
+      .then((stats) => stats.isDirectory())
+      .catch(() => false);
+    if (!isDirectory) {
+      console.error(`WARNING: Skipping workspace root that is not a directory: ${extraRoot}`);
+      continue;
+    }
+    extraWatchers.push(startExtraRoot(extraRoot, debounceMs));
+  }
+
+  const stopAllWatchers = () => {
     stopWatcher();
-    process.exit(0);
-  });
+    for (const stop of extraWatchers) stop();
+  };
+
+  // HTTP sessions finish their in-flight requests before the process exits
+  const shutdown = () => {
+    stopAllWatchers();
+    void (httpTransport?.close() ?? Promise.resolve()).finally(() => process.exit(0));
+  };
+
+  process.once('exit', stopAllWatchers);
+  process.once('SIGINT', shutdown);
+  process.once('SIGTERM', shutdown);
 }
 
 // Export server components for programmatic use
-export { server, performIndexing, resolveRootPath, shouldReindex, TOOLS };
+export {
+  server,
+  createServer,
+  performIndexing,
+  resolveRootPath,
+  shouldReindex,
+  symbolIndex,
+  TOOLS,
+  watchRoot
+};
 
 // Only auto-start when run directly as CLI (not when imported as module)
 // Check if this module is the entry point
@@ -796,10 +1073,27 @@ const CLI_SUBCOMMANDS = [
   'style-guide',
   'patterns',
   'refs',
-  'cycles'
+  'references',
+  'cycles',
+  'context',
+  'outline',
+  'calls',
+  'export-chunks',
+  'definition',
+  'deps',
+  'stats',
+  'structural',
+  'changed',
+  'semantic',
+  'around',
+  'files',
+  'pack',
+  'related',
+  'resolve'
 ];
 
 if (isDirectRun) {
+  setSpanSink(spanSinkFromEnv());
   const subcommand = process.argv[2];
   if (CLI_SUBCOMMANDS.includes(subcommand) || subcommand === '--help') {
     handleCliCommand(process.argv.slice(2)).catch((error) => {
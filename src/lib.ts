@@ -38,11 +38,34 @@ export { CodebaseSearcher, type SearchOptions } from './core/search.js';
 import { CodebaseSearcher } from './core/search.js';
 export { AnalyzerRegistry, analyzerRegistry } from './core/analyzer-registry.js';
 import { analyzerRegistry } from './core/analyzer-registry.js';
+export {
+  SymbolIndex,
+  DEFAULT_TREE_CACHE_BYTES,
+  treeCacheOptionsFromEnv,
+  type IndexedSymbol,
+  type SymbolIndexEntry,
+  type SymbolIndexMatch,
+  type SymbolIndexOptions,
+  type SymbolIndexUpdate,
+  type SymbolIndexUpdateStatus,
+  type TreeCacheStats
+} from './core/symbol-index.js';
+export { DiskSymbolCache, SYMBOL_CACHE_VERSION } from './core/symbol-cache.js';
+export {
+  exportSemanticChunks,
+  readIndexedFiles,
+  semanticChunkId,
+  DEFAULT_EXPORT_MAX_LINES,
+  type SemanticChunk,
+  type ChunkExportOptions
+} from './core/chunk-export.js';
 
 // Embedding providers
 export {
   getEmbeddingProvider,
+  registerEmbeddingProvider,
   TransformersEmbeddingProvider,
+  StubEmbeddingProvider,
   type EmbeddingProvider,
   type EmbeddingConfig,
   DEFAULT_EMBEDDING_CONFIG
@@ -69,10 +92,21 @@ export {
   isCodeFile,
   isBinaryFile,
   detectLanguage,
+  detectLanguageFromContent,
+  sniffFile,
   isTestFile,
   isDocumentationFile,
   getSupportedExtensions
 } from './utils/language-detection.js';
+export {
+  decodeSourceBytes,
+  readSource,
+  normalizeSourceText,
+  originalLineSpan,
+  type DecodedSource,
+  type UndecodableSource,
+  type SourceEncoding
+} from './utils/text-decoding.js';
 export {
   createChunksFromCode,
   calculateComplexity,
@@ -126,6 +160,8 @@ export type {
   IndexingPhase,
   IndexingError,
   IndexingStats,
+  FileSkipReason,
+  SkippedFile,
 
   // Configuration
   AnalyzerConfig,
@@ -1,9 +1,13 @@
 const CONTEXT_RESOURCE_URI = 'codebase://context';
+const INDEX_RESOURCE_URI = 'codebase://index';
+
+const KNOWN_RESOURCE_URIS = [CONTEXT_RESOURCE_URI, INDEX_RESOURCE_URI];
 
 export function normalizeResourceUri(uri: string): string {
   if (!uri) return uri;
-  if (uri === CONTEXT_RESOURCE_URI) return uri;
-  if (uri.endsWith(`/${CONTEXT_RESOURCE_URI}`)) return CONTEXT_RESOURCE_URI;
+  for (const known of KNOWN_RESOURCE_URIS) {
+    if (uri === known || uri.endsWith(`/${known}`)) return known;
+  }
   return uri;
 }
 
@@ -11,4 +15,8 @@ export function isContextResourceUri(uri: string): boolean {
   return normalizeResourceUri(uri) === CONTEXT_RESOURCE_URI;
 }
 
-export { CONTEXT_RESOURCE_URI };
+export function isIndexResourceUri(uri: string): boolean {
+  return normalizeResourceUri(uri) === INDEX_RESOURCE_URI;
+}
+
+export { CONTEXT_RESOURCE_URI, INDEX_RESOURCE_URI };
@@ -171,6 +171,27 @@ export class LanceDBStorageProvider implements VectorStorageProvider {
         if (filters.language) {
           whereConditions.push(`language = '${filters.language}'`);
         }
+        if (filters.kinds && filters.kinds.length > 0) {
+          // metadata is stored as JSON text; the searcher re-checks the kinds exactly
+          const kindConditions = filters.kinds.map(
+            (kind) =>
+              `metadata LIKE '%"symbolKind":"${kind}"%' OR ` +
+              `metadata LIKE '%"symbolKinds":[%"${kind}"%'`
+          );
+          whereConditions.push(`(${kindConditions.join(' OR ')})`);
+        }
+        if (filters.lineRanges) {
+          const files = Object.keys(filters.lineRanges).map((p) => `'${p.replace(/'/g, "''")}'`);
+          whereConditions.push(
+            files.length > 0 ? `"relativePath" IN (${files.join(', ')})` : 'false'
+          );
+        }
+        if (filters.tests) {
+          // Test files by path are re-checked by the searcher; indexes record the flag on chunks
+          whereConditions.push(
+            `metadata ${filters.tests === 'only' ? 'LIKE' : 'NOT LIKE'} '%"isTest":true%'`
+          );
+        }
 
         if (whereConditions.length > 0) {
           query = query.where(whereConditions.join(' AND '));
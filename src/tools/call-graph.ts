@@ -0,0 +1,131 @@
+import type { Tool } from '@modelcontextprotocol/sdk/types.js';
+import type { ToolContext, ToolResponse } from './types.js';
+import {
+  CallGraph,
+  DEFAULT_CALL_GRAPH_EDGE_LIMIT,
+  MAX_CALL_GRAPH_DEPTH,
+  type CallGraphDirection
+} from '../core/call-graph.js';
+
+export const definition: Tool = {
+  name: 'call_graph',
+  description:
+    'Trace who calls a function (callers) or what it calls (callees) as a flat list of ' +
+    'caller → callee edges with file and line. Matching is by name, so edges are candidates.',
+  inputSchema: {
+    type: 'object',
+    properties: {
+      symbol: {
+        type: 'string',
+        description: 'Function or method name (for example: add)'
+      },
+      direction: {
+        type: 'string',
+        enum: ['callers', 'callees'],
+        description: 'callers: functions that call symbol (default). callees: what symbol calls.',
+        default: 'callers'
+      },
+      depth: {
+        type: 'number',
+        description: `Hops to follow transitively (default: 1, max: ${MAX_CALL_GRAPH_DEPTH})`,
+        default: 1
+      },
+      limit: {
+        type: 'number',
+        description: `Maximum edges to return (default: ${DEFAULT_CALL_GRAPH_EDGE_LIMIT})`,
+        default: DEFAULT_CALL_GRAPH_EDGE_LIMIT
+      }
+    },
+    required: ['symbol']
+  }
+};
+
+export async function handle(
+  args: Record<string, unknown>,
+  ctx: ToolContext
+): Promise<ToolResponse> {
+  const { symbol, direction, depth, limit } = args as {
+    symbol?: unknown;
+    direction?: unknown;
+    depth?: unknown;
+    limit?: unknown;
+  };
+  const normalizedSymbol = typeof symbol === 'string' ? symbol.trim() : '';
+
+  if (!normalizedSymbol) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            {
+              status: 'error',
+              message: "Invalid params: 'symbol' is required and must be a non-empty string."
+            },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  const graph = await CallGraph.load(ctx.rootPath);
+  if (!graph) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            {
+              status: 'error',
+              message: 'Call graph not found in the index. Run refresh_index to build it.'
+            },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  const normalizedDirection: CallGraphDirection = direction === 'callees' ? 'callees' : 'callers';
+  const normalizedDepth =
+    typeof depth === 'number' && Number.isFinite(depth) && depth > 0
+      ? Math.min(MAX_CALL_GRAPH_DEPTH, Math.floor(depth))
+      : 1;
+  const normalizedLimit =
+    typeof limit === 'number' && Number.isFinite(limit) && limit > 0
+      ? Math.floor(limit)
+      : DEFAULT_CALL_GRAPH_EDGE_LIMIT;
+
+  const { edges, truncated } = graph.traverse(
+    normalizedSymbol,
+    normalizedDirection,
+    normalizedDepth,
+    normalizedLimit
+  );
+
+  return {
+    content: [
+      {
+        type: 'text',
+        text: JSON.stringify(
+          {
+            status: 'success',
+            symbol: normalizedSymbol,
+            direction: normalizedDirection,
+            depth: normalizedDepth,
+            edgeCount: edges.length,
+            truncated,
+            edges
+          },
+          null,
+          2
+        )
+      }
+    ]
+  };
+}
@@ -0,0 +1,127 @@
+import type { Tool } from '@modelcontextprotocol/sdk/types.js';
+import type { ToolContext, ToolResponse } from './types.js';
+import { findChangedSymbols } from '../core/changed-symbols.js';
+import { GitDiffError, getGitDiff } from '../utils/git-diff.js';
+import { isSymbolKind, SYMBOL_KINDS } from '../utils/tree-sitter.js';
+
+const DEFAULT_LIMIT = 50;
+
+export const definition: Tool = {
+  name: 'changed_symbols',
+  description:
+    'List the functions, classes and other symbols a git diff touched, with file and line range. ' +
+    'Defaults to the working tree (including untracked files) against HEAD; pass base and head ' +
+    'to compare two refs, e.g. base="main" head="HEAD" for a branch under review.',
+  inputSchema: {
+    type: 'object',
+    properties: {
+      base: {
+        type: 'string',
+        description: 'Ref to diff from (default: HEAD)'
+      },
+      head: {
+        type: 'string',
+        description: 'Ref to diff to (default: the working tree)'
+      },
+      kinds: {
+        type: 'array',
+        items: { type: 'string', enum: [...SYMBOL_KINDS] },
+        description: 'Only report symbols of these kinds, e.g. ["function", "method"]'
+      },
+      limit: {
+        type: 'number',
+        description: `Maximum symbols to return (default: ${DEFAULT_LIMIT})`,
+        default: DEFAULT_LIMIT
+      }
+    }
+  }
+};
+
+export async function handle(
+  args: Record<string, unknown>,
+  ctx: ToolContext
+): Promise<ToolResponse> {
+  const { base, head, kinds, limit } = args as {
+    base?: unknown;
+    head?: unknown;
+    kinds?: unknown;
+    limit?: unknown;
+  };
+
+  const kindList = Array.isArray(kinds) ? kinds : kinds === undefined ? [] : null;
+  const unknownKinds = (kindList ?? []).filter(
+    (kind) => typeof kind !== 'string' || !isSymbolKind(kind)
+  );
+  if (!kindList || unknownKinds.length > 0) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            {
+              status: 'error',
+              errorCode: 'invalid_params',
+              message: kindList
+                ? `Invalid params: unknown kinds ${JSON.stringify(unknownKinds)}.`
+                : "Invalid params: 'kinds' must be an array of symbol kinds.",
+              hint: `Allowed kinds: ${SYMBOL_KINDS.join(', ')}`
+            },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  let result: Awaited<ReturnType<typeof findChangedSymbols>>;
+  let diff: Awaited<ReturnType<typeof getGitDiff>>;
+  try {
+    diff = await getGitDiff(ctx.rootPath, {
+      base: typeof base === 'string' ? base : undefined,
+      head: typeof head === 'string' ? head : undefined
+    });
+    result = await findChangedSymbols(ctx.rootPath, diff, { kinds: kindList as string[] });
+  } catch (error) {
+    if (!(error instanceof GitDiffError)) throw error;
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            { status: 'error', errorCode: error.code, message: error.message },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  const maxSymbols = typeof limit === 'number' && limit > 0 ? Math.floor(limit) : DEFAULT_LIMIT;
+  const symbols = result.symbols.slice(0, maxSymbols);
+
+  return {
+    content: [
+      {
+        type: 'text',
+        text: JSON.stringify(
+          {
+            status: 'success',
+            base: diff.base,
+            head: diff.head ?? 'working tree',
+            fileCount: result.files.length,
+            files: result.files.slice(0, maxSymbols),
+            symbolCount: result.symbols.length,
+            symbols,
+            truncated: result.symbols.length > symbols.length || result.files.length > maxSymbols
+          },
+          null,
+          2
+        )
+      }
+    ]
+  };
+}
@@ -0,0 +1,95 @@
+import type { Tool } from '@modelcontextprotocol/sdk/types.js';
+import type { ToolContext, ToolResponse } from './types.js';
+import { getContextAround, MAX_CONTEXT_AROUND_LINES } from '../core/context-around.js';
+import { describeUnknownRoot, selectWorkspaceRoots } from '../core/workspace-roots.js';
+
+export const definition: Tool = {
+  name: 'context_around',
+  description:
+    'Get numbered source lines around one line of a file, clamped to the file boundaries. ' +
+    'Use after a search hit instead of reading the whole file; snapToSymbol widens the window ' +
+    `to the enclosing function or class. At most ${MAX_CONTEXT_AROUND_LINES} lines.`,
+  inputSchema: {
+    type: 'object',
+    properties: {
+      file: {
+        type: 'string',
+        description: 'File path relative to the project root (for example: src/core/indexer.ts)'
+      },
+      line: {
+        type: 'number',
+        description: '1-based line to center the window on'
+      },
+      before: {
+        type: 'number',
+        description: 'Lines to include before `line` (default: 5)',
+        default: 5
+      },
+      after: {
+        type: 'number',
+        description: 'Lines to include after `line` (default: 5)',
+        default: 5
+      },
+      snapToSymbol: {
+        type: 'boolean',
+        description:
+          'Widen the window to cover the innermost symbol containing `line`, e.g. the whole ' +
+          'function (default: false)',
+        default: false
+      },
+      root: {
+        type: 'string',
+        description: 'Workspace root (path or directory name) that `file` is relative to'
+      }
+    },
+    required: ['file', 'line']
+  }
+};
+
+function errorResponse(message: string): ToolResponse {
+  return {
+    content: [{ type: 'text', text: JSON.stringify({ status: 'error', message }, null, 2) }],
+    isError: true
+  };
+}
+
+export async function handle(
+  args: Record<string, unknown>,
+  ctx: ToolContext
+): Promise<ToolResponse> {
+  const { file, line, before, after, snapToSymbol, root } = args as {
+    file?: unknown;
+    line?: unknown;
+    before?: unknown;
+    after?: unknown;
+    snapToSymbol?: unknown;
+    root?: unknown;
+  };
+  const normalizedFile = typeof file === 'string' ? file.trim() : '';
+
+  if (!normalizedFile) {
+    return errorResponse("Invalid params: 'file' is required and must be a non-empty string.");
+  }
+  if (typeof line !== 'number' || !Number.isInteger(line) || line < 1) {
+    return errorResponse("Invalid params: 'line' is required and must be a positive integer.");
+  }
+
+  const workspaceRoots = ctx.workspaceRoots ?? [ctx.rootPath];
+  const rootSelector = typeof root === 'string' && root.trim() ? root : undefined;
+  const roots = selectWorkspaceRoots(workspaceRoots, rootSelector);
+  if (!roots) {
+    return errorResponse(describeUnknownRoot(workspaceRoots, rootSelector ?? ''));
+  }
+
+  // Without a selector every root matches; the file is then resolved against the primary one.
+  const result = await getContextAround(roots[0], normalizedFile, line, {
+    before: typeof before === 'number' ? before : undefined,
+    after: typeof after === 'number' ? after : undefined,
+    snapToSymbol: snapToSymbol === true
+  });
+
+  return {
+    content: [{ type: 'text', text: JSON.stringify(result, null, 2) }],
+    ...(result.status === 'error' ? { isError: true } : {})
+  };
+}
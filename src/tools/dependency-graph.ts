@@ -0,0 +1,141 @@
+import type { Tool } from '@modelcontextprotocol/sdk/types.js';
+import path from 'path';
+import type { ToolContext, ToolResponse } from './types.js';
+import {
+  DEFAULT_DEPENDENCY_EDGE_LIMIT,
+  DependencyGraph,
+  MAX_DEPENDENCY_GRAPH_DEPTH,
+  type DependencyDirection
+} from '../core/dependency-graph.js';
+
+export const definition: Tool = {
+  name: 'dependency_graph',
+  description:
+    'List which files a file imports (dependencies) and which files import it (dependents) as ' +
+    'from → to edges, optionally transitive. Third-party imports are returned as unresolved.',
+  inputSchema: {
+    type: 'object',
+    properties: {
+      file: {
+        type: 'string',
+        description: 'File relative to the project root (for example: src/core/search.ts)'
+      },
+      direction: {
+        type: 'string',
+        enum: ['dependencies', 'dependents', 'both'],
+        description: 'dependencies: what file imports. dependents: who imports it. Default: both.',
+        default: 'both'
+      },
+      depth: {
+        type: 'number',
+        description: `Hops to follow transitively (default: 1, max: ${MAX_DEPENDENCY_GRAPH_DEPTH})`,
+        default: 1
+      },
+      limit: {
+        type: 'number',
+        description: `Maximum edges to return (default: ${DEFAULT_DEPENDENCY_EDGE_LIMIT})`,
+        default: DEFAULT_DEPENDENCY_EDGE_LIMIT
+      }
+    },
+    required: ['file']
+  }
+};
+
+export async function handle(
+  args: Record<string, unknown>,
+  ctx: ToolContext
+): Promise<ToolResponse> {
+  const { file, direction, depth, limit } = args as {
+    file?: unknown;
+    direction?: unknown;
+    depth?: unknown;
+    limit?: unknown;
+  };
+  const rawFile = typeof file === 'string' ? file.trim() : '';
+
+  if (!rawFile) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            {
+              status: 'error',
+              message: "Invalid params: 'file' is required and must be a non-empty string."
+            },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  const graph = await DependencyGraph.load(ctx.rootPath);
+  if (!graph) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            {
+              status: 'error',
+              message: 'Dependency graph not found in the index. Run refresh_index to build it.'
+            },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  const resolvedRoot = path.resolve(ctx.rootPath);
+  const normalizedFile = path
+    .relative(resolvedRoot, path.resolve(resolvedRoot, rawFile))
+    .replace(/\\/g, '/');
+  const normalizedDirection: DependencyDirection =
+    direction === 'dependencies' || direction === 'dependents' ? direction : 'both';
+  const normalizedDepth =
+    typeof depth === 'number' && Number.isFinite(depth) && depth > 0
+      ? Math.min(MAX_DEPENDENCY_GRAPH_DEPTH, Math.floor(depth))
+      : 1;
+  const normalizedLimit =
+    typeof limit === 'number' && Number.isFinite(limit) && limit > 0
+      ? Math.floor(limit)
+      : DEFAULT_DEPENDENCY_EDGE_LIMIT;
+
+  const { edges, unresolved, truncated } = graph.subgraph(
+    normalizedFile,
+    normalizedDirection,
+    normalizedDepth,
+    normalizedLimit
+  );
+
+  return {
+    content: [
+      {
+        type: 'text',
+        text: JSON.stringify(
+          {
+            status: 'success',
+            file: normalizedFile,
+            direction: normalizedDirection,
+            depth: normalizedDepth,
+            edgeCount: edges.length,
+            truncated,
+            edges,
+            unresolved,
+            ...(graph.has(normalizedFile)
+              ? {}
+              : { hint: 'File has no recorded imports or importers in the current index.' })
+          },
+          null,
+          2
+        )
+      }
+    ]
+  };
+}
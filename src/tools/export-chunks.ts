@@ -0,0 +1,152 @@
+import type { Tool } from '@modelcontextprotocol/sdk/types.js';
+import { createWriteStream, promises as fs } from 'fs';
+import { once } from 'events';
+import path from 'path';
+import type { ToolContext, ToolResponse } from './types.js';
+import {
+  DEFAULT_EXPORT_MAX_LINES,
+  exportSemanticChunks,
+  readIndexedFiles
+} from '../core/chunk-export.js';
+import { CODEBASE_CONTEXT_DIRNAME } from '../constants/codebase-context.js';
+import { relativeInside } from '../utils/symlinks.js';
+
+const DEFAULT_OUTPUT = `${CODEBASE_CONTEXT_DIRNAME}/chunks.jsonl`;
+
+export const definition: Tool = {
+  name: 'export_chunks',
+  description:
+    'Write per-symbol chunks (source text, file, line range, kind, parent, stable id) as JSON ' +
+    'Lines for an external embedding pipeline. Returns counts and the output path, not the chunks.',
+  inputSchema: {
+    type: 'object',
+    properties: {
+      output: {
+        type: 'string',
+        description: `Output file inside the project (default: ${DEFAULT_OUTPUT})`
+      },
+      scope: {
+        type: 'string',
+        description: 'Only export files under this path prefix (for example: src/core)'
+      },
+      maxLines: {
+        type: 'number',
+        description:
+          'Symbols longer than this many lines are split at statement boundaries ' +
+          `(default: ${DEFAULT_EXPORT_MAX_LINES})`,
+        default: DEFAULT_EXPORT_MAX_LINES
+      },
+      overlapLines: {
+        type: 'number',
+        description: 'Lines shared between consecutive parts of a split symbol (default: 0)',
+        default: 0
+      }
+    }
+  }
+};
+
+export async function handle(
+  args: Record<string, unknown>,
+  ctx: ToolContext
+): Promise<ToolResponse> {
+  const { output, scope, maxLines, overlapLines } = args as {
+    output?: unknown;
+    scope?: unknown;
+    maxLines?: unknown;
+    overlapLines?: unknown;
+  };
+
+  const resolvedRoot = path.resolve(ctx.rootPath);
+  const outputFile = typeof output === 'string' && output.trim() ? output.trim() : DEFAULT_OUTPUT;
+  const outputPath = path.resolve(resolvedRoot, outputFile);
+  if (relativeInside(resolvedRoot, outputPath) === null) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            {
+              status: 'error',
+              message: `Output must be inside the project root: ${outputFile}`
+            },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  const indexedFiles = await readIndexedFiles(resolvedRoot);
+  if (!indexedFiles) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            {
+              status: 'error',
+              message: 'Index manifest not found. Run refresh_index first.'
+            },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  const scopePrefix =
+    typeof scope === 'string' && scope.trim()
+      ? scope.trim().replace(/\\/g, '/').replace(/^\.\//, '').replace(/\/$/, '')
+      : '';
+  const files = scopePrefix
+    ? indexedFiles.filter((file) => file === scopePrefix || file.startsWith(`${scopePrefix}/`))
+    : indexedFiles;
+
+  await fs.mkdir(path.dirname(outputPath), { recursive: true });
+  const stream = createWriteStream(outputPath, { encoding: 'utf-8' });
+  const exportedFiles = new Set<string>();
+  let chunkCount = 0;
+  let splitChunks = 0;
+
+  try {
+    for await (const chunk of exportSemanticChunks(resolvedRoot, files, {
+      ...(typeof maxLines === 'number' && maxLines > 0 ? { maxLines } : {}),
+      ...(typeof overlapLines === 'number' && overlapLines >= 0 ? { overlapLines } : {})
+    })) {
+      exportedFiles.add(chunk.file);
+      chunkCount++;
+      if (chunk.part) splitChunks++;
+      if (!stream.write(`${JSON.stringify(chunk)}\n`)) {
+        await once(stream, 'drain');
+      }
+    }
+  } finally {
+    stream.end();
+    await once(stream, 'finish');
+  }
+
+  return {
+    content: [
+      {
+        type: 'text',
+        text: JSON.stringify(
+          {
+            status: 'success',
+            output: relativeOutput.replace(/\\/g, '/'),
+            format: 'jsonl',
+            filesScanned: files.length,
+            filesExported: exportedFiles.size,
+            chunkCount,
+            splitChunks
+          },
+          null,
+          2
+        )
+      }
+    ]
+  };
+}
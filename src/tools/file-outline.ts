@@ -0,0 +1,132 @@
+import type { Tool } from '@modelcontextprotocol/sdk/types.js';
+import type { ToolContext, ToolResponse } from './types.js';
+import { getFileOutline } from '../core/file-outline.js';
+import { DETAIL_LEVEL_SCHEMA, invalidDetailMessage, isDetailLevel } from '../core/detail-level.js';
+import { describeUnknownRoot, selectWorkspaceRoots } from '../core/workspace-roots.js';
+
+export const definition: Tool = {
+  name: 'file_outline',
+  description:
+    'Get the structure of one file — symbols with kind, line range and nesting — without ' +
+    'source bodies. Use depth: 1 for top-level items only. Cheaper than reading the file.',
+  inputSchema: {
+    type: 'object',
+    properties: {
+      file: {
+        type: 'string',
+        description: 'File path relative to the project root (for example: src/core/indexer.ts)'
+      },
+      depth: {
+        type: 'number',
+        description: 'Nesting levels to include (1 = top-level only). Omit for the full tree.'
+      },
+      includeDocs: {
+        type: 'boolean',
+        description: "Include each symbol's doc comment or docstring as `doc` (default: false)",
+        default: false
+      },
+      signaturesOnly: {
+        type: 'boolean',
+        description:
+          "Include each symbol's signature as `signature`: its declaration up to the body " +
+          '(default: false)',
+        default: false
+      },
+      detail: {
+        ...DETAIL_LEVEL_SCHEMA,
+        description:
+          '"names" (kind and lines only), "signatures" (plus `signature`) or "full" (plus ' +
+          '`doc`, and `source` for symbols listed without children). Overrides includeDocs ' +
+          'and signaturesOnly.'
+      },
+      root: {
+        type: 'string',
+        description: 'Workspace root (path or directory name) that `file` is relative to'
+      }
+    },
+    required: ['file']
+  }
+};
+
+export async function handle(
+  args: Record<string, unknown>,
+  ctx: ToolContext
+): Promise<ToolResponse> {
+  const { file, depth, includeDocs, signaturesOnly, detail, root } = args as {
+    file?: unknown;
+    depth?: unknown;
+    includeDocs?: unknown;
+    signaturesOnly?: unknown;
+    detail?: unknown;
+    root?: unknown;
+  };
+  const normalizedFile = typeof file === 'string' ? file.trim() : '';
+
+  if (!normalizedFile) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            {
+              status: 'error',
+              message: "Invalid params: 'file' is required and must be a non-empty string."
+            },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  if (detail !== undefined && !isDetailLevel(detail)) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify({ status: 'error', message: invalidDetailMessage(detail) }, null, 2)
+        }
+      ],
+      isError: true
+    };
+  }
+
+  const workspaceRoots = ctx.workspaceRoots ?? [ctx.rootPath];
+  const rootSelector = typeof root === 'string' && root.trim() ? root : undefined;
+  const roots = selectWorkspaceRoots(workspaceRoots, rootSelector);
+  if (!roots) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            { status: 'error', message: describeUnknownRoot(workspaceRoots, rootSelector ?? '') },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  // Without a selector every root matches; the file is then resolved against the primary one.
+  const result = await getFileOutline(roots[0], normalizedFile, {
+    depth: typeof depth === 'number' ? depth : undefined,
+    symbolIndex: ctx.symbolIndex,
+    ...(detail === undefined
+      ? { includeDocs: includeDocs === true, signaturesOnly: signaturesOnly === true }
+      : {
+          includeDocs: detail === 'full',
+          signaturesOnly: detail !== 'names',
+          includeSource: detail === 'full'
+        })
+  });
+
+  return {
+    content: [{ type: 'text', text: JSON.stringify(result, null, 2) }],
+    ...(result.status === 'error' ? { isError: true } : {})
+  };
+}
@@ -0,0 +1,109 @@
+import type { Tool } from '@modelcontextprotocol/sdk/types.js';
+import type { ToolContext, ToolResponse } from './types.js';
+import { findScopedReferencesInRoots } from '../core/symbol-references.js';
+import { describeUnknownRoot, selectWorkspaceRoots } from '../core/workspace-roots.js';
+
+export const definition: Tool = {
+  name: 'find_references',
+  description:
+    'Find every place a symbol is used, with the definition reported separately from the uses. ' +
+    'Pass file + line to resolve a specific binding; shadowed locals are excluded. ' +
+    'Returns an empty list (not an error) when nothing references the symbol.',
+  inputSchema: {
+    type: 'object',
+    properties: {
+      symbol: {
+        type: 'string',
+        description: 'Identifier to look up (for example: parseConfig)'
+      },
+      file: {
+        type: 'string',
+        description: 'Optional file (relative to the project root) containing the occurrence'
+      },
+      line: {
+        type: 'number',
+        description: 'Optional 1-based line of the occurrence in `file`'
+      },
+      limit: {
+        type: 'number',
+        description: 'Maximum number of references to return (default: 20)',
+        default: 20
+      },
+      root: {
+        type: 'string',
+        description: 'Optional workspace root (path or directory name) to limit the lookup to'
+      }
+    },
+    required: ['symbol']
+  }
+};
+
+export async function handle(
+  args: Record<string, unknown>,
+  ctx: ToolContext
+): Promise<ToolResponse> {
+  const { symbol, file, line, limit, root } = args as {
+    symbol?: unknown;
+    file?: unknown;
+    line?: unknown;
+    limit?: unknown;
+    root?: unknown;
+  };
+  const normalizedSymbol = typeof symbol === 'string' ? symbol.trim() : '';
+
+  if (!normalizedSymbol) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            {
+              status: 'error',
+              message: "Invalid params: 'symbol' is required and must be a non-empty string."
+            },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  const normalizedFile = typeof file === 'string' && file.trim() ? file.trim() : undefined;
+  const normalizedLine =
+    typeof line === 'number' && Number.isFinite(line) && line > 0 ? Math.floor(line) : undefined;
+  const normalizedLimit =
+    typeof limit === 'number' && Number.isFinite(limit) && limit > 0 ? Math.floor(limit) : 20;
+
+  const workspaceRoots = ctx.workspaceRoots ?? [ctx.rootPath];
+  const rootSelector = typeof root === 'string' ? root : undefined;
+  const roots = selectWorkspaceRoots(workspaceRoots, rootSelector);
+  if (!roots) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            { status: 'error', message: describeUnknownRoot(workspaceRoots, rootSelector ?? '') },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  const result = await findScopedReferencesInRoots(roots, normalizedSymbol, {
+    file: normalizedFile,
+    line: normalizedLine,
+    limit: normalizedLimit,
+    symbolIndex: ctx.symbolIndex
+  });
+
+  return {
+    content: [{ type: 'text', text: JSON.stringify(result, null, 2) }],
+    ...(result.status === 'error' ? { isError: true } : {})
+  };
+}
@@ -0,0 +1,138 @@
+import type { Tool } from '@modelcontextprotocol/sdk/types.js';
+import type { ToolContext, ToolResponse } from './types.js';
+import {
+  DEFAULT_DEFINITION_MAX_LINES,
+  findDefinitions,
+  type SymbolDefinition
+} from '../core/symbol-definition.js';
+import { describeUnknownRoot, selectWorkspaceRoots } from '../core/workspace-roots.js';
+
+export const definition: Tool = {
+  name: 'get_definition',
+  description:
+    'Return the exact source of a symbol definition (the whole function, class or type node) ' +
+    'with its file, line range and byte range. Every symbol sharing the name is returned. ' +
+    'A C/C++ header prototype resolves to its definition in the sibling source file.',
+  inputSchema: {
+    type: 'object',
+    properties: {
+      symbol: {
+        type: 'string',
+        description: 'Symbol name, optionally qualified (for example: new_calculator, Calc.Add)'
+      },
+      file: {
+        type: 'string',
+        description: 'Optional file (relative to the project root) to look in'
+      },
+      maxLines: {
+        type: 'number',
+        description:
+          "Cut each definition's text after this many lines " +
+          `(default: ${DEFAULT_DEFINITION_MAX_LINES})`,
+        default: DEFAULT_DEFINITION_MAX_LINES
+      },
+      root: {
+        type: 'string',
+        description: 'Optional workspace root (path or directory name) to limit the lookup to'
+      }
+    },
+    required: ['symbol']
+  }
+};
+
+export async function handle(
+  args: Record<string, unknown>,
+  ctx: ToolContext
+): Promise<ToolResponse> {
+  const { symbol, file, maxLines, root } = args as {
+    symbol?: unknown;
+    file?: unknown;
+    maxLines?: unknown;
+    root?: unknown;
+  };
+  const normalizedSymbol = typeof symbol === 'string' ? symbol.trim() : '';
+
+  if (!normalizedSymbol) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            {
+              status: 'error',
+              message: "Invalid params: 'symbol' is required and must be a non-empty string."
+            },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  const normalizedFile = typeof file === 'string' && file.trim() ? file.trim() : undefined;
+  const normalizedMaxLines =
+    typeof maxLines === 'number' && Number.isFinite(maxLines) && maxLines > 0
+      ? Math.floor(maxLines)
+      : undefined;
+
+  const workspaceRoots = ctx.workspaceRoots ?? [ctx.rootPath];
+  const rootSelector = typeof root === 'string' ? root : undefined;
+  const roots = selectWorkspaceRoots(workspaceRoots, rootSelector);
+  if (!roots) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            { status: 'error', message: describeUnknownRoot(workspaceRoots, rootSelector ?? '') },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  // A relative file belongs to the primary selected root, like find_references positions.
+  const searchRoots = normalizedFile ? [roots[0]] : roots;
+  const definitions: SymbolDefinition[] = [];
+  for (const searchRoot of searchRoots) {
+    const result = await findDefinitions(searchRoot, normalizedSymbol, {
+      file: normalizedFile,
+      maxLines: normalizedMaxLines,
+      symbolIndex: ctx.symbolIndex
+    });
+    if (result.status === 'error') {
+      return {
+        content: [{ type: 'text', text: JSON.stringify(result, null, 2) }],
+        isError: true
+      };
+    }
+    const tagged =
+      searchRoots.length > 1
+        ? result.definitions.map((found) => ({ ...found, root: searchRoot }))
+        : result.definitions;
+    definitions.push(...tagged);
+  }
+
+  return {
+    content: [
+      {
+        type: 'text',
+        text: JSON.stringify(
+          {
+            status: 'success',
+            symbol: normalizedSymbol,
+            definitionCount: definitions.length,
+            definitions
+          },
+          null,
+          2
+        )
+      }
+    ]
+  };
+}
@@ -1,10 +1,12 @@
 import type { Tool } from '@modelcontextprotocol/sdk/types.js';
 import type { ToolContext, ToolResponse } from './types.js';
+import { summarizeSkipped } from '../core/index-stats.js';
 
 export const definition: Tool = {
   name: 'get_indexing_status',
   description:
-    'Get current indexing status: state, statistics, and progress. ' +
+    'Get current indexing status: state, statistics, progress, and files skipped by size or ' +
+    'minified content. ' +
     'Use refresh_index to manually trigger re-indexing when needed.',
   inputSchema: {
     type: 'object',
@@ -33,7 +35,8 @@ export async function handle(
                   indexedFiles: ctx.indexState.stats.indexedFiles,
                   totalChunks: ctx.indexState.stats.totalChunks,
                   duration: `${(ctx.indexState.stats.duration / 1000).toFixed(2)}s`,
-                  incremental: ctx.indexState.stats.incremental
+                  incremental: ctx.indexState.stats.incremental,
+                  skipped: summarizeSkipped(ctx.indexState.stats.skipped)
                 }
               : undefined,
             progress: progress
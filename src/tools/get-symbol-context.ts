@@ -0,0 +1,216 @@
+import type { Tool } from '@modelcontextprotocol/sdk/types.js';
+import type { ToolContext, ToolResponse } from './types.js';
+import {
+  DEFAULT_CONTEXT_MAX_TOKENS,
+  streamSymbolContext,
+  type StreamedSymbolContextResult,
+  type SymbolContextResult
+} from '../core/symbol-context.js';
+import { describeUnknownRoot, selectWorkspaceRoots } from '../core/workspace-roots.js';
+import { testFilterFromArgs } from '../core/test-filter.js';
+import { DETAIL_LEVEL_SCHEMA, invalidDetailMessage, isDetailLevel } from '../core/detail-level.js';
+import { withSpan } from '../utils/tracing.js';
+
+export const definition: Tool = {
+  name: 'get_symbol_context',
+  description:
+    'Assemble context for a symbol that is guaranteed to fit a token budget: definition first, ' +
+    'then usages, then imports. Reports tokensUsed and how many snippets were dropped. ' +
+    'With a progressToken, each snippet is sent once as a progress notification and the ' +
+    'result carries only the totals.',
+  inputSchema: {
+    type: 'object',
+    properties: {
+      symbol: {
+        type: 'string',
+        description: 'Symbol name to build context for (for example: parseConfig)'
+      },
+      maxTokens: {
+        type: 'number',
+        description: `Token budget for the returned snippets (default: ${DEFAULT_CONTEXT_MAX_TOKENS})`,
+        default: DEFAULT_CONTEXT_MAX_TOKENS
+      },
+      includeDocs: {
+        type: 'boolean',
+        description: 'Start the definition at its doc comment so the docs count toward the budget',
+        default: false
+      },
+      signaturesOnly: {
+        type: 'boolean',
+        description: 'Return only the signature of each definition, without its body',
+        default: false
+      },
+      detail: {
+        ...DETAIL_LEVEL_SCHEMA,
+        description:
+          '"names" (one line per definition and usage), "signatures" (definitions cut to ' +
+          'their signature) or "full" (whole definitions from their docs). Overrides ' +
+          'includeDocs and signaturesOnly.'
+      },
+      includeTests: {
+        type: 'boolean',
+        description: 'Set false to leave out usages in test files and inline test code',
+        default: true
+      },
+      testsOnly: {
+        type: 'boolean',
+        description: 'Only use test code, e.g. to see how a symbol is exercised',
+        default: false
+      },
+      root: {
+        type: 'string',
+        description: 'Optional workspace root (path or directory name) to limit the context to'
+      }
+    },
+    required: ['symbol']
+  }
+};
+
+export async function handle(
+  args: Record<string, unknown>,
+  ctx: ToolContext
+): Promise<ToolResponse> {
+  const { symbol, maxTokens, includeDocs, signaturesOnly, detail, includeTests, testsOnly, root } =
+    args as {
+      symbol?: unknown;
+      maxTokens?: unknown;
+      includeDocs?: unknown;
+      signaturesOnly?: unknown;
+      detail?: unknown;
+      includeTests?: unknown;
+      testsOnly?: unknown;
+      root?: unknown;
+    };
+  const normalizedSymbol = typeof symbol === 'string' ? symbol.trim() : '';
+
+  if (!normalizedSymbol) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            {
+              status: 'error',
+              message: "Invalid params: 'symbol' is required and must be a non-empty string."
+            },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  if (detail !== undefined && !isDetailLevel(detail)) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            { status: 'error', errorCode: 'invalid_params', message: invalidDetailMessage(detail) },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  const tests = testFilterFromArgs(includeTests, testsOnly);
+  if (tests === null) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            {
+              status: 'error',
+              errorCode: 'invalid_params',
+              message: 'Invalid params: testsOnly cannot be combined with includeTests: false.'
+            },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  const workspaceRoots = ctx.workspaceRoots ?? [ctx.rootPath];
+  const rootSelector = typeof root === 'string' ? root : undefined;
+  const roots = selectWorkspaceRoots(workspaceRoots, rootSelector);
+  if (!roots) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            { status: 'error', message: describeUnknownRoot(workspaceRoots, rootSelector ?? '') },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  return withSpan('context.assemble', { symbol: normalizedSymbol }, async (span) => {
+    const stream = await streamSymbolContext(roots, normalizedSymbol, {
+      maxTokens: typeof maxTokens === 'number' ? maxTokens : undefined,
+      includeDocs: includeDocs === true,
+      signaturesOnly: signaturesOnly === true,
+      ...(isDetailLevel(detail) ? { detail } : {}),
+      ...(tests ? { tests } : {})
+    });
+    if (stream.status === 'error') {
+      return {
+        content: [{ type: 'text', text: JSON.stringify(stream, null, 2) }],
+        isError: true
+      };
+    }
+
+    // A streaming call gets each snippet once, as progress, and none is held for the result
+    const reportProgress = ctx.reportProgress;
+    const snippets: SymbolContextResult['snippets'] = [];
+    let snippetCount = 0;
+    for await (const snippet of stream.snippets) {
+      snippetCount++;
+      if (reportProgress) {
+        // Awaited so a slow client paces the assembler instead of queueing notifications
+        await reportProgress(snippetCount, JSON.stringify(snippet));
+      } else {
+        snippets.push(snippet);
+      }
+    }
+
+    const totals = stream.totals();
+    const result: SymbolContextResult | StreamedSymbolContextResult = reportProgress
+      ? {
+          status: 'success',
+          symbol: stream.symbol,
+          maxTokens: stream.maxTokens,
+          ...totals,
+          streamedSnippets: snippetCount
+        }
+      : {
+          status: 'success',
+          symbol: stream.symbol,
+          maxTokens: stream.maxTokens,
+          ...totals,
+          snippets
+        };
+    span.record({
+      snippets: snippetCount,
+      tokensUsed: totals.tokensUsed,
+      droppedSnippets: totals.droppedSnippets
+    });
+
+    return {
+      content: [{ type: 'text', text: JSON.stringify(result, null, 2) }]
+    };
+  });
+}
@@ -1,11 +1,16 @@
 import type { Tool } from '@modelcontextprotocol/sdk/types.js';
 import type { ToolContext, ToolResponse } from './types.js';
-import { findSymbolReferences } from '../core/symbol-references.js';
+import {
+  DEFAULT_FUZZY_MIN_SCORE,
+  findFuzzySymbols,
+  findSymbolReferences
+} from '../core/symbol-references.js';
 
 export const definition: Tool = {
   name: 'get_symbol_references',
   description:
-    'Find concrete references to a symbol in indexed chunks. Returns total usageCount and top usage snippets.',
+    'Find concrete references to a symbol in indexed chunks. Returns total usageCount and top ' +
+    'usage snippets. Only half-remember the name? Pass mode "fuzzy" to get ranked symbol names.',
   inputSchema: {
     type: 'object',
     properties: {
@@ -15,8 +20,22 @@ export const definition: Tool = {
       },
       limit: {
         type: 'number',
-        description: 'Maximum number of usage snippets to return (default: 10)',
+        description: 'Maximum number of usage snippets (or fuzzy matches) to return (default: 10)',
         default: 10
+      },
+      mode: {
+        type: 'string',
+        enum: ['exact', 'fuzzy'],
+        description:
+          'exact (default): usages of this exact name. fuzzy: symbol names matching it as a ' +
+          'case-insensitive subsequence, ranked by score',
+        default: 'exact'
+      },
+      minScore: {
+        type: 'number',
+        description:
+          'Fuzzy mode only: drop fuzzy matches scoring below this, from 0 to 1 ' +
+          `(default: ${DEFAULT_FUZZY_MIN_SCORE})`
       }
     },
     required: ['symbol']
@@ -27,7 +46,12 @@ export async function handle(
   args: Record<string, unknown>,
   ctx: ToolContext
 ): Promise<ToolResponse> {
-  const { symbol, limit } = args as { symbol?: unknown; limit?: unknown };
+  const { symbol, limit, mode, minScore } = args as {
+    symbol?: unknown;
+    limit?: unknown;
+    mode?: unknown;
+    minScore?: unknown;
+  };
   const normalizedSymbol = typeof symbol === 'string' ? symbol.trim() : '';
   const normalizedLimit =
     typeof limit === 'number' && Number.isFinite(limit) && limit > 0 ? Math.floor(limit) : 10;
@@ -51,6 +75,17 @@ export async function handle(
     };
   }
 
+  if (mode === 'fuzzy') {
+    const fuzzyResult = await findFuzzySymbols(ctx.rootPath, normalizedSymbol, {
+      limit: normalizedLimit,
+      minScore: typeof minScore === 'number' ? minScore : undefined
+    });
+    return {
+      content: [{ type: 'text', text: JSON.stringify(fuzzyResult, null, 2) }],
+      ...(fuzzyResult.status === 'error' ? { isError: true } : {})
+    };
+  }
+
   const result = await findSymbolReferences(ctx.rootPath, normalizedSymbol, normalizedLimit);
 
   if (result.status === 'error') {
@@ -12,15 +12,120 @@ import { definition as d7, handle as h7 } from './get-symbol-references.js';
 import { definition as d8, handle as h8 } from './detect-circular-dependencies.js';
 import { definition as d9, handle as h9 } from './remember.js';
 import { definition as d10, handle as h10 } from './get-memory.js';
+import { definition as d11, handle as h11 } from './find-references.js';
+import { definition as d12, handle as h12 } from './get-symbol-context.js';
+import { definition as d13, handle as h13 } from './file-outline.js';
+import { definition as d14, handle as h14 } from './call-graph.js';
+import { definition as d15, handle as h15 } from './export-chunks.js';
+import { definition as d16, handle as h16 } from './get-definition.js';
+import { definition as d17, handle as h17 } from './dependency-graph.js';
+import { definition as d18, handle as h18 } from './stats.js';
+import { definition as d19, handle as h19 } from './structural-search.js';
+import { definition as d20, handle as h20 } from './changed-symbols.js';
+import { definition as d21, handle as h21 } from './semantic-search.js';
+import { definition as d22, handle as h22 } from './context-around.js';
+import { definition as d23, handle as h23 } from './list-files.js';
+import { definition as d24, handle as h24 } from './pack-context.js';
+import { definition as d25, handle as h25 } from './related-files.js';
+import { definition as d26, handle as h26 } from './resolve-path.js';
 
 import type { ToolContext, ToolResponse } from './types.js';
+import { gitStateKey, type GitDiffSpec } from '../utils/git-diff.js';
+import { withSpan } from '../utils/tracing.js';
 
-export const TOOLS: Tool[] = [d1, d2, d3, d4, d5, d6, d7, d8, d9, d10];
+export const TOOLS: Tool[] = [
+  d1, d2, d3, d4, d5, d6, d7, d8, d9, d10, d11, d12, d13, d14, d15, d16, d17, d18, d19, d20, d21,
+  d22, d23, d24, d25, d26
+];
+
+/**
+ * Tools whose results are cached (when the context has a `resultCache`): read-only, and
+ * determined by their arguments, the indexed files, team memory (`remember` invalidates) and,
+ * for `GIT_KEYED_TOOL_NAMES`, git state. Tools that write files or report live status are
+ * left out.
+ */
+export const CACHEABLE_TOOL_NAMES = [
+  'search_codebase',
+  'get_symbol_references',
+  'find_references',
+  'detect_circular_dependencies',
+  'get_symbol_context',
+  'file_outline',
+  'call_graph',
+  'get_definition',
+  'dependency_graph',
+  'structural_search',
+  'semantic_search',
+  'context_around',
+  'list_files',
+  'pack_context',
+  'resolve_path'
+] as const;
+
+/** Tools that change what other tools return, so cached results are dropped after them. */
+const INVALIDATING_TOOL_NAMES = new Set(['refresh_index', 'remember']);
+
+/**
+ * Cached tools that also read git (history ranking, the `diff` filter): their keys carry the
+ * commits HEAD and the `diff` refs resolve to, so a commit or checkout misses the cache.
+ */
+const GIT_KEYED_TOOL_NAMES = new Set(['search_codebase']);
+
+async function cacheKeyArgs(
+  name: string,
+  args: Record<string, unknown>,
+  ctx: ToolContext
+): Promise<Record<string, unknown>> {
+  if (!GIT_KEYED_TOOL_NAMES.has(name)) return args;
+  const diff =
+    typeof args.diff === 'object' && args.diff !== null ? (args.diff as GitDiffSpec) : null;
+  const roots = ctx.workspaceRoots ?? [ctx.rootPath];
+  const states = await Promise.all(
+    roots.map((root) => gitStateKey(root, root === ctx.rootPath ? diff : null))
+  );
+  // Not a tool parameter name, so it cannot collide with one
+  return { ...args, '@git': states };
+}
 
 export async function dispatchTool(
   name: string,
   args: Record<string, unknown>,
   ctx: ToolContext
+): Promise<ToolResponse> {
+  const cache = ctx.resultCache;
+  // Streaming calls report snippets as progress, which a cached response would skip
+  const cacheable =
+    cache !== undefined &&
+    ctx.reportProgress === undefined &&
+    ctx.indexState.status === 'ready' &&
+    (CACHEABLE_TOOL_NAMES as readonly string[]).includes(name);
+
+  return withSpan('tool.call', { tool: name }, async (span) => {
+    const keyArgs = cacheable ? await cacheKeyArgs(name, args, ctx) : args;
+    if (cache && cacheable) {
+      const cached = cache.get(name, keyArgs);
+      span.record({ cached: cached !== undefined });
+      if (cached) return cached;
+    }
+
+    const version = cache?.indexVersion;
+    const result = await runTool(name, args, ctx);
+    span.record({ isError: result.isError === true });
+    // Skip results computed while the index changed underneath: they may mix both versions
+    if (cache && cacheable && !result.isError && cache.indexVersion === version) {
+      cache.set(name, keyArgs, result);
+    }
+    if (cache && INVALIDATING_TOOL_NAMES.has(name)) {
+      cache.invalidate();
+    }
+    return result;
+  });
+}
+
+async function runTool(
+  name: string,
+  args: Record<string, unknown>,
+  ctx: ToolContext
 ): Promise<ToolResponse> {
   switch (name) {
     case 'search_codebase':
@@ -43,6 +148,38 @@ export async function dispatchTool(
       return h9(args, ctx);
     case 'get_memory':
       return h10(args, ctx);
+    case 'find_references':
+      return h11(args, ctx);
+    case 'get_symbol_context':
+      return h12(args, ctx);
+    case 'file_outline':
+      return h13(args, ctx);
+    case 'call_graph':
+      return h14(args, ctx);
+    case 'export_chunks':
+      return h15(args, ctx);
+    case 'get_definition':
+      return h16(args, ctx);
+    case 'dependency_graph':
+      return h17(args, ctx);
+    case 'stats':
+      return h18(args, ctx);
+    case 'structural_search':
+      return h19(args, ctx);
+    case 'changed_symbols':
+      return h20(args, ctx);
+    case 'semantic_search':
+      return h21(args, ctx);
+    case 'context_around':
+      return h22(args, ctx);
+    case 'list_files':
+      return h23(args, ctx);
+    case 'pack_context':
+      return h24(args, ctx);
+    case 'related_files':
+      return h25(args, ctx);
+    case 'resolve_path':
+      return h26(args, ctx);
     default:
       return {
         content: [{ type: 'text', text: JSON.stringify({ error: `Unknown tool: ${name}` }) }],
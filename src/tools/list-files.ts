@@ -0,0 +1,105 @@
+import type { Tool } from '@modelcontextprotocol/sdk/types.js';
+import type { ToolContext, ToolResponse } from './types.js';
+import {
+  DEFAULT_LIST_FILES_LIMIT,
+  MAX_LIST_FILES_LIMIT,
+  listIndexedFiles
+} from '../core/list-files.js';
+import { describeUnknownRoot, selectWorkspaceRoots } from '../core/workspace-roots.js';
+
+export const definition: Tool = {
+  name: 'list_files',
+  description:
+    'List indexed file paths with detected language and symbol count, filtered by a glob ' +
+    '(e.g. src/**/*.rs) and/or language. Paged: pass nextCursor back as cursor. Use ' +
+    'file_outline for the structure of one file.',
+  inputSchema: {
+    type: 'object',
+    properties: {
+      glob: {
+        type: 'string',
+        description: 'Glob over paths relative to the project root (for example: src/**/*.rs)'
+      },
+      language: {
+        type: 'string',
+        description: 'Only files of this detected language (for example: rust, typescript)'
+      },
+      limit: {
+        type: 'number',
+        description:
+          `Maximum files per page (default: ${DEFAULT_LIST_FILES_LIMIT}, ` +
+          `max: ${MAX_LIST_FILES_LIMIT})`,
+        default: DEFAULT_LIST_FILES_LIMIT
+      },
+      cursor: {
+        type: 'string',
+        description: 'nextCursor from the previous page'
+      },
+      root: {
+        type: 'string',
+        description: 'Workspace root (path or directory name) to list'
+      }
+    }
+  }
+};
+
+export async function handle(
+  args: Record<string, unknown>,
+  ctx: ToolContext
+): Promise<ToolResponse> {
+  const { glob, language, limit, cursor, root } = args as {
+    glob?: unknown;
+    language?: unknown;
+    limit?: unknown;
+    cursor?: unknown;
+    root?: unknown;
+  };
+
+  const workspaceRoots = ctx.workspaceRoots ?? [ctx.rootPath];
+  const rootSelector = typeof root === 'string' && root.trim() ? root : undefined;
+  const roots = selectWorkspaceRoots(workspaceRoots, rootSelector);
+  if (!roots) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            { status: 'error', message: describeUnknownRoot(workspaceRoots, rootSelector ?? '') },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  const result = await listIndexedFiles(roots[0], {
+    ...(typeof glob === 'string' && glob.trim() ? { glob: glob.trim() } : {}),
+    ...(typeof language === 'string' && language.trim() ? { language } : {}),
+    ...(typeof limit === 'number' && limit > 0 ? { limit } : {}),
+    ...(typeof cursor === 'string' && cursor ? { cursor } : {})
+  });
+  if (!result) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            {
+              status: 'error',
+              message: 'Index manifest not found. Run refresh_index first.'
+            },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  return {
+    content: [{ type: 'text', text: JSON.stringify({ status: 'success', ...result }, null, 2) }]
+  };
+}
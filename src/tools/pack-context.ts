@@ -0,0 +1,129 @@
+import type { Tool } from '@modelcontextprotocol/sdk/types.js';
+import type { ToolContext, ToolResponse } from './types.js';
+import {
+  DEFAULT_PACK_MAX_TOKENS,
+  DEFAULT_PACK_TEMPLATES,
+  packContext,
+  type PackReference,
+  type PackTemplates
+} from '../core/pack-context.js';
+import { describeUnknownRoot, selectWorkspaceRoots } from '../core/workspace-roots.js';
+
+const TEMPLATE_KEYS = Object.keys(DEFAULT_PACK_TEMPLATES) as Array<keyof PackTemplates>;
+
+export const definition: Tool = {
+  name: 'pack_context',
+  description:
+    'Pack file ranges and symbol definitions into one ready-to-send prompt block: table of ' +
+    'contents, a header per file and language-tagged code fences, ordered by file then line ' +
+    'and cut to maxTokens. Returns the block plus what was included and excluded.',
+  inputSchema: {
+    type: 'object',
+    properties: {
+      references: {
+        type: 'array',
+        description:
+          'Snippets to pack, most important first (they win when the budget runs out). Each is ' +
+          '{ file, startLine?, endLine? } (whole file without lines) or { symbol, file? } for ' +
+          'its definitions.',
+        items: {
+          type: 'object',
+          properties: {
+            file: { type: 'string', description: 'Path relative to the project root' },
+            startLine: { type: 'number' },
+            endLine: { type: 'number' },
+            symbol: { type: 'string', description: 'Symbol name; may be qualified' }
+          }
+        }
+      },
+      maxTokens: {
+        type: 'number',
+        description: `Token budget for the whole block (default: ${DEFAULT_PACK_MAX_TOKENS})`,
+        default: DEFAULT_PACK_MAX_TOKENS
+      },
+      templates: {
+        type: 'object',
+        description:
+          'Override any of header ({toc}, {count}, {files}), tocEntry, file and snippet ' +
+          '({file}, {language}, {startLine}, {endLine}, {symbol}, {code}).',
+        properties: {
+          header: { type: 'string', default: DEFAULT_PACK_TEMPLATES.header },
+          tocEntry: { type: 'string', default: DEFAULT_PACK_TEMPLATES.tocEntry },
+          file: { type: 'string', default: DEFAULT_PACK_TEMPLATES.file },
+          snippet: { type: 'string', default: DEFAULT_PACK_TEMPLATES.snippet }
+        }
+      },
+      root: {
+        type: 'string',
+        description: 'Workspace root (path or directory name) the references are relative to'
+      }
+    },
+    required: ['references']
+  }
+};
+
+function errorResponse(message: string): ToolResponse {
+  return {
+    content: [{ type: 'text', text: JSON.stringify({ status: 'error', message }, null, 2) }],
+    isError: true
+  };
+}
+
+function parseReference(value: unknown): PackReference | null {
+  if (!value || typeof value !== 'object') return null;
+  const { file, startLine, endLine, symbol } = value as Record<string, unknown>;
+  const reference: PackReference = {
+    ...(typeof file === 'string' && file.trim() ? { file: file.trim() } : {}),
+    ...(typeof startLine === 'number' ? { startLine } : {}),
+    ...(typeof endLine === 'number' ? { endLine } : {}),
+    ...(typeof symbol === 'string' && symbol.trim() ? { symbol: symbol.trim() } : {})
+  };
+  return reference.file || reference.symbol ? reference : null;
+}
+
+export async function handle(
+  args: Record<string, unknown>,
+  ctx: ToolContext
+): Promise<ToolResponse> {
+  const { references, maxTokens, templates, root } = args as {
+    references?: unknown;
+    maxTokens?: unknown;
+    templates?: unknown;
+    root?: unknown;
+  };
+
+  if (!Array.isArray(references) || references.length === 0) {
+    return errorResponse("Invalid params: 'references' must be a non-empty array.");
+  }
+  const parsed = references.map(parseReference);
+  const invalid = parsed.findIndex((reference) => reference === null);
+  if (invalid >= 0) {
+    return errorResponse(
+      `Invalid params: references[${invalid}] needs a non-empty 'file' or 'symbol'.`
+    );
+  }
+
+  const templateOverrides: Partial<PackTemplates> = {};
+  if (templates && typeof templates === 'object') {
+    for (const key of TEMPLATE_KEYS) {
+      const value = (templates as Record<string, unknown>)[key];
+      if (typeof value === 'string') templateOverrides[key] = value;
+    }
+  }
+
+  const workspaceRoots = ctx.workspaceRoots ?? [ctx.rootPath];
+  const rootSelector = typeof root === 'string' && root.trim() ? root : undefined;
+  const roots = selectWorkspaceRoots(workspaceRoots, rootSelector);
+  if (!roots) {
+    return errorResponse(describeUnknownRoot(workspaceRoots, rootSelector ?? ''));
+  }
+
+  const result = await packContext(roots[0], parsed as PackReference[], {
+    ...(typeof maxTokens === 'number' && maxTokens > 0 ? { maxTokens } : {}),
+    templates: templateOverrides
+  });
+
+  return {
+    content: [{ type: 'text', text: JSON.stringify(result, null, 2) }]
+  };
+}
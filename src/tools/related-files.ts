@@ -0,0 +1,110 @@
+import type { Tool } from '@modelcontextprotocol/sdk/types.js';
+import path from 'path';
+import type { ToolContext, ToolResponse } from './types.js';
+import {
+  DEFAULT_RELATED_FILES_LIMIT,
+  DEFAULT_RELATED_WEIGHTS,
+  MAX_RELATED_FILES_LIMIT,
+  RELATED_SIGNALS,
+  findRelatedFiles,
+  relatedWeightsFromEnv,
+  type RelatedWeights
+} from '../core/related-files.js';
+import { describeUnknownRoot, selectWorkspaceRoots } from '../core/workspace-roots.js';
+
+export const definition: Tool = {
+  name: 'related_files',
+  description:
+    'Suggest the files most likely relevant when working on a file, with the reasons: direct ' +
+    'imports, shared imports, directory proximity and git co-change. Works with fewer signals ' +
+    'outside a git repository.',
+  inputSchema: {
+    type: 'object',
+    properties: {
+      path: {
+        type: 'string',
+        description: 'File relative to the project root (for example: src/core/search.ts)'
+      },
+      limit: {
+        type: 'number',
+        description:
+          `Maximum files to return (default: ${DEFAULT_RELATED_FILES_LIMIT}, ` +
+          `max: ${MAX_RELATED_FILES_LIMIT})`,
+        default: DEFAULT_RELATED_FILES_LIMIT
+      },
+      weights: {
+        type: 'object',
+        description:
+          'Per-signal weights; 0 turns a signal off. Unset ones come from ' +
+          'CODEBASE_CONTEXT_RELATED_WEIGHTS, then the defaults.',
+        properties: Object.fromEntries(
+          RELATED_SIGNALS.map((signal) => [
+            signal,
+            { type: 'number', default: DEFAULT_RELATED_WEIGHTS[signal] }
+          ])
+        )
+      },
+      root: {
+        type: 'string',
+        description: 'Workspace root (path or directory name) the path is relative to'
+      }
+    },
+    required: ['path']
+  }
+};
+
+function errorResponse(message: string): ToolResponse {
+  return {
+    content: [{ type: 'text', text: JSON.stringify({ status: 'error', message }, null, 2) }],
+    isError: true
+  };
+}
+
+export async function handle(
+  args: Record<string, unknown>,
+  ctx: ToolContext
+): Promise<ToolResponse> {
+  const { path: file, limit, weights, root } = args as {
+    path?: unknown;
+    limit?: unknown;
+    weights?: unknown;
+    root?: unknown;
+  };
+  const rawFile = typeof file === 'string' ? file.trim() : '';
+  if (!rawFile) {
+    return errorResponse("Invalid params: 'path' is required and must be a non-empty string.");
+  }
+
+  const workspaceRoots = ctx.workspaceRoots ?? [ctx.rootPath];
+  const rootSelector = typeof root === 'string' && root.trim() ? root : undefined;
+  const roots = selectWorkspaceRoots(workspaceRoots, rootSelector);
+  if (!roots) {
+    return errorResponse(describeUnknownRoot(workspaceRoots, rootSelector ?? ''));
+  }
+
+  const overrides: Partial<RelatedWeights> = relatedWeightsFromEnv();
+  if (weights && typeof weights === 'object') {
+    for (const signal of RELATED_SIGNALS) {
+      const value = (weights as Record<string, unknown>)[signal];
+      if (typeof value === 'number' && Number.isFinite(value)) {
+        overrides[signal] = Math.max(0, value);
+      }
+    }
+  }
+
+  const resolvedRoot = path.resolve(roots[0]);
+  const normalizedFile = path
+    .relative(resolvedRoot, path.resolve(resolvedRoot, rawFile))
+    .replace(/\\/g, '/');
+  const result = await findRelatedFiles(resolvedRoot, normalizedFile, {
+    ...(typeof limit === 'number' && limit > 0 ? { limit } : {}),
+    weights: overrides
+  });
+  if (result.status === 'error') {
+    return errorResponse(result.message);
+  }
+
+  return {
+    content: [{ type: 'text', text: JSON.stringify(result, null, 2) }]
+  };
+}
@@ -0,0 +1,72 @@
+import type { Tool } from '@modelcontextprotocol/sdk/types.js';
+import path from 'path';
+import type { ToolContext, ToolResponse } from './types.js';
+import { resolveQualifiedPath } from '../core/resolve-path.js';
+import { describeUnknownRoot, selectWorkspaceRoots } from '../core/workspace-roots.js';
+
+export const definition: Tool = {
+  name: 'resolve_path',
+  description:
+    'Resolve a fully-qualified symbol path (geometry::Circle::area, com.example.Calc.add) to ' +
+    'the one symbol it names, with its file and line range. search_codebase results carry ' +
+    'these paths. Several matches come back as ambiguous candidates instead of a guess.',
+  inputSchema: {
+    type: 'object',
+    properties: {
+      path: {
+        type: 'string',
+        description:
+          'Qualified path; `::` and `.` both separate scopes, and leading modules may be omitted'
+      },
+      file: {
+        type: 'string',
+        description: 'Optional file (relative to the project root) to look in'
+      },
+      root: {
+        type: 'string',
+        description: 'Workspace root (path or directory name) to resolve in'
+      }
+    },
+    required: ['path']
+  }
+};
+
+function errorResponse(message: string): ToolResponse {
+  return {
+    content: [{ type: 'text', text: JSON.stringify({ status: 'error', message }, null, 2) }],
+    isError: true
+  };
+}
+
+export async function handle(
+  args: Record<string, unknown>,
+  ctx: ToolContext
+): Promise<ToolResponse> {
+  const { path: qualifiedPath, file, root } = args as {
+    path?: unknown;
+    file?: unknown;
+    root?: unknown;
+  };
+  const normalizedPath = typeof qualifiedPath === 'string' ? qualifiedPath.trim() : '';
+  if (!normalizedPath) {
+    return errorResponse("Invalid params: 'path' is required and must be a non-empty string.");
+  }
+
+  const workspaceRoots = ctx.workspaceRoots ?? [ctx.rootPath];
+  const rootSelector = typeof root === 'string' && root.trim() ? root : undefined;
+  const roots = selectWorkspaceRoots(workspaceRoots, rootSelector);
+  if (!roots) {
+    return errorResponse(describeUnknownRoot(workspaceRoots, rootSelector ?? ''));
+  }
+
+  const result = await resolveQualifiedPath(path.resolve(roots[0]), normalizedPath, {
+    ...(typeof file === 'string' && file.trim() ? { file: file.trim() } : {})
+  });
+  if (result.status === 'error') {
+    return errorResponse(result.message);
+  }
+
+  return {
+    content: [{ type: 'text', text: JSON.stringify(result, null, 2) }]
+  };
+}
@@ -22,7 +22,19 @@ import { assessSearchQuality } from '../core/search-quality.js';
 import { IndexCorruptedError } from '../errors/index.js';
 import { readMemoriesFile, withConfidence } from '../memory/store.js';
 import { InternalFileGraph } from '../utils/usage-tracker.js';
+import { isSymbolKind, SYMBOL_KINDS } from '../utils/tree-sitter.js';
+import { GitDiffError, getGitDiff, type GitDiffSpec } from '../utils/git-diff.js';
+import { diffLineRanges } from '../core/changed-symbols.js';
+import { isSnippetDedupMode, SNIPPET_DEDUP_MODES } from '../core/snippet-dedup.js';
+import { DETAIL_LEVEL_SCHEMA, invalidDetailMessage, isDetailLevel } from '../core/detail-level.js';
+import { testFilterFromArgs } from '../core/test-filter.js';
 import { RELATIONSHIPS_FILENAME } from '../constants/codebase-context.js';
+import {
+  describeUnknownRoot,
+  findContainingRoot,
+  selectWorkspaceRoots
+} from '../core/workspace-roots.js';
+import { relativeInside } from '../utils/symlinks.js';
 
 interface RelationshipsData {
   graph?: {
@@ -55,12 +67,68 @@ export const definition: Tool = {
         description: 'Maximum number of results to return (default: 5)',
         default: 5
       },
+      kinds: {
+        type: 'array',
+        items: { type: 'string', enum: [...SYMBOL_KINDS] },
+        description:
+          'Only return code holding a symbol of these kinds, e.g. ["function"]. ' +
+          'Applied before ranking and the limit. Omit or leave empty for all kinds.'
+      },
+      diff: {
+        type: 'object',
+        description:
+          'Only return code on lines changed in git: {} for the working tree against HEAD, ' +
+          '{"base": "main"} for the working tree against a ref, or base and head for two ' +
+          'refs. Searches the primary root only.',
+        properties: {
+          base: { type: 'string', description: 'Ref to diff from (default: HEAD)' },
+          head: { type: 'string', description: 'Ref to diff to (default: the working tree)' }
+        }
+      },
+      detail: {
+        ...DETAIL_LEVEL_SCHEMA,
+        description:
+          'Per-result detail: "names" (file, qualifiedPath, score), "signatures" (plus summary ' +
+          'and signature) or "full" (everything, with the whole chunk as source). Omit for the ' +
+          'default fields.'
+      },
       includeSnippets: {
         type: 'boolean',
         description:
           'Include code snippets in results (default: false). If you need code, prefer read_file instead.',
         default: false
       },
+      dedupSnippets: {
+        type: 'string',
+        enum: [...SNIPPET_DEDUP_MODES],
+        description:
+          'Collapse results with identical code in different files into one, listing the other ' +
+          'locations in `duplicates`. "exact" ignores trailing whitespace; "whitespace" ignores ' +
+          'all whitespace differences. Omit to return every copy.'
+      },
+      includeTests: {
+        type: 'boolean',
+        description:
+          'Set false to drop test code: test files by path (tests/, *.test.ts, *_test.go) and ' +
+          'inline tests such as Rust #[cfg(test)] modules (default: true)',
+        default: true
+      },
+      testsOnly: {
+        type: 'boolean',
+        description: 'Only return test code (default: false)',
+        default: false
+      },
+      root: {
+        type: 'string',
+        description: 'Optional workspace root (path or directory name) to limit the search to'
+      },
+      gitBoost: {
+        type: 'boolean',
+        description:
+          'Rank recently and frequently changed files higher using git history (default: true). ' +
+          'Set false for reproducible rankings.',
+        default: true
+      },
       filters: {
         type: 'object',
         description: 'Optional filters',
@@ -98,12 +166,34 @@ export async function handle(
   args: Record<string, unknown>,
   ctx: ToolContext
 ): Promise<ToolResponse> {
-  const { query, limit, filters, intent, includeSnippets } = args as {
+  const {
+    query,
+    limit,
+    kinds,
+    diff,
+    filters: rawFilters,
+    intent,
+    includeSnippets,
+    root,
+    gitBoost,
+    dedupSnippets,
+    includeTests,
+    testsOnly,
+    detail
+  } = args as {
     query?: unknown;
     limit?: number;
+    kinds?: unknown;
+    diff?: unknown;
     filters?: Record<string, unknown>;
     intent?: string;
     includeSnippets?: boolean;
+    root?: unknown;
+    gitBoost?: boolean;
+    dedupSnippets?: unknown;
+    includeTests?: unknown;
+    testsOnly?: unknown;
+    detail?: unknown;
   };
   const queryStr = typeof query === 'string' ? query.trim() : '';
 
@@ -128,6 +218,119 @@ export async function handle(
     };
   }
 
+  const kindList = Array.isArray(kinds) ? kinds : kinds === undefined ? [] : null;
+  const unknownKinds = (kindList ?? []).filter(
+    (kind) => typeof kind !== 'string' || !isSymbolKind(kind)
+  );
+  if (!kindList || unknownKinds.length > 0) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            {
+              status: 'error',
+              errorCode: 'invalid_params',
+              message: kindList
+                ? `Invalid params: unknown kinds ${JSON.stringify(unknownKinds)}.`
+                : "Invalid params: 'kinds' must be an array of symbol kinds.",
+              hint: `Allowed kinds: ${SYMBOL_KINDS.join(', ')}`
+            },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+  if (dedupSnippets !== undefined && !isSnippetDedupMode(dedupSnippets)) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            {
+              status: 'error',
+              errorCode: 'invalid_params',
+              message: `Invalid params: unknown dedupSnippets ${JSON.stringify(dedupSnippets)}.`,
+              hint: `Allowed values: ${SNIPPET_DEDUP_MODES.join(', ')}`
+            },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+  if (detail !== undefined && !isDetailLevel(detail)) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            { status: 'error', errorCode: 'invalid_params', message: invalidDetailMessage(detail) },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+  const detailLevel = isDetailLevel(detail) ? detail : undefined;
+  const tests = testFilterFromArgs(includeTests, testsOnly);
+  if (tests === null) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            {
+              status: 'error',
+              errorCode: 'invalid_params',
+              message: 'Invalid params: testsOnly cannot be combined with includeTests: false.'
+            },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+  let filters = kindList.length > 0 ? { ...rawFilters, kinds: kindList as string[] } : rawFilters;
+  if (tests) {
+    filters = { ...filters, tests };
+  }
+
+  const diffSpec = typeof diff === 'object' && diff !== null ? (diff as GitDiffSpec) : null;
+  if (diffSpec) {
+    try {
+      const gitDiff = await getGitDiff(ctx.rootPath, {
+        base: typeof diffSpec.base === 'string' ? diffSpec.base : undefined,
+        head: typeof diffSpec.head === 'string' ? diffSpec.head : undefined
+      });
+      filters = { ...filters, lineRanges: diffLineRanges(gitDiff) };
+    } catch (error) {
+      if (!(error instanceof GitDiffError)) throw error;
+      return {
+        content: [
+          {
+            type: 'text',
+            text: JSON.stringify(
+              { status: 'error', errorCode: error.code, message: error.message },
+              null,
+              2
+            )
+          }
+        ],
+        isError: true
+      };
+    }
+  }
+
   if (ctx.indexState.status === 'indexing') {
     return {
       content: [
@@ -165,16 +368,42 @@ export async function handle(
     };
   }
 
+  const workspaceRoots = ctx.workspaceRoots ?? [ctx.rootPath];
+  const rootSelector = typeof root === 'string' ? root : undefined;
+  const selectedRoots = selectWorkspaceRoots(workspaceRoots, rootSelector);
+  if (!selectedRoots) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            { status: 'error', message: describeUnknownRoot(workspaceRoots, rootSelector ?? '') },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+  const searchPrimary = selectedRoots.includes(workspaceRoots[0]);
+  const multiRoot = workspaceRoots.length > 1;
+
   const searcher = new CodebaseSearcher(ctx.rootPath);
   let results: SearchResult[];
   const searchProfile = (
     intent && ['explore', 'edit', 'refactor', 'migrate'].includes(intent) ? intent : 'explore'
   ) as SearchIntentProfile;
+  const searchOptions = {
+    profile: searchProfile,
+    useGitActivity: gitBoost !== false,
+    ...(isSnippetDedupMode(dedupSnippets) && { dedupSnippets })
+  };
 
   try {
-    results = await searcher.search(queryStr, limit || 5, filters, {
-      profile: searchProfile
-    });
+    results = searchPrimary
+      ? await searcher.search(queryStr, limit || 5, filters, searchOptions)
+      : [];
   } catch (error) {
     if (error instanceof IndexCorruptedError) {
       console.error('[Auto-Heal] Index corrupted. Triggering full re-index...');
@@ -185,9 +414,7 @@ export async function handle(
         console.error('[Auto-Heal] Success. Retrying search...');
         const freshSearcher = new CodebaseSearcher(ctx.rootPath);
         try {
-          results = await freshSearcher.search(queryStr, limit || 5, filters, {
-            profile: searchProfile
-          });
+          results = await freshSearcher.search(queryStr, limit || 5, filters, searchOptions);
         } catch (retryError) {
           return {
             content: [
@@ -230,6 +457,31 @@ export async function handle(
     }
   }
 
+  // Extra workspace roots keep their own indexes; merge their hits by score.
+  const unavailableRoots: string[] = [];
+  // Diff line ranges are relative to the primary root, so other roots cannot match them
+  const extraRoots = diffSpec ? [] : selectedRoots.filter((r) => r !== workspaceRoots[0]);
+  if (extraRoots.length > 0) {
+    const extraResults = await Promise.all(
+      extraRoots.map(async (extraRoot) => {
+        try {
+          return await new CodebaseSearcher(extraRoot).search(
+            queryStr,
+            limit || 5,
+            filters,
+            searchOptions
+          );
+        } catch {
+          unavailableRoots.push(extraRoot);
+          return [];
+        }
+      })
+    );
+    results = [...results, ...extraResults.flat()]
+      .sort((a, b) => b.score - a.score)
+      .slice(0, limit || 5);
+  }
+
   // Load memories for keyword matching, enriched with confidence
   const allMemories = await readMemoriesFile(ctx.paths.memory);
   const allMemoriesWithConf = withConfidence(allMemories);
@@ -298,9 +550,9 @@ export async function handle(
   function normalizeGraphPath(filePath: string): string {
     const normalized = filePath.replace(/\\/g, '/');
     if (path.isAbsolute(filePath)) {
-      const rel = path.relative(ctx.rootPath, filePath).replace(/\\/g, '/');
-      if (rel && !rel.startsWith('..')) {
-        return rel;
+      const rel = relativeInside(ctx.rootPath, filePath);
+      if (rel) {
+        return rel.replace(/\\/g, '/');
       }
     }
     return normalized.replace(/^\.\//, '');
@@ -434,6 +686,23 @@ export async function handle(
     };
   }
 
+  // One result as serialized; `detail` only decides which optional fields are filled
+  interface SearchResultPayload extends RelationshipHints {
+    file: string;
+    root?: string;
+    qualifiedPath?: string;
+    summary?: string;
+    score: number;
+    signature?: string;
+    type?: string;
+    textOnly?: boolean;
+    trend?: string;
+    patternWarning?: string;
+    duplicates?: string[];
+    snippet?: string;
+    source?: string;
+  }
+
   function buildRelationshipHints(result: SearchResult): RelationshipHints {
     const rPath = result.filePath;
     // Graph keys are relative paths with forward slashes; normalize for comparison
@@ -818,9 +1087,12 @@ export async function handle(
 
   // Helper: Build scope header for symbol-aware chunks (SEARCH-02)
   function buildScopeHeader(metadata: ChunkMetadata): string | null {
-    // Try symbolPath first (most reliable for AST-based symbols)
+    // Try the qualified path, then symbolPath (most reliable for AST-based symbols)
+    if (metadata?.qualifiedPath) {
+      return metadata.qualifiedPath + (metadata.symbolParameters ?? '');
+    }
     if (metadata?.symbolPath && Array.isArray(metadata.symbolPath)) {
-      return metadata.symbolPath.join('.');
+      return metadata.symbolPath.join('.') + (metadata.symbolParameters ?? '');
     }
     // Fallback: className + functionName
     if (metadata?.className && metadata?.functionName) {
@@ -842,9 +1114,8 @@ export async function handle(
   }
 
   function formatSnippetFallbackHeader(filePath: string, startLine: number): string {
-    const rel = path.relative(ctx.rootPath, filePath).replace(/\\/g, '/');
-    const displayPath =
-      rel && !rel.startsWith('..') && !path.isAbsolute(rel) ? rel : path.basename(filePath);
+    const rel = relativeInside(ctx.rootPath, filePath);
+    const displayPath = rel ? rel.replace(/\\/g, '/') : path.basename(filePath);
     return `${displayPath}:${startLine}`;
   }
 
@@ -882,29 +1153,57 @@ export async function handle(
                 })
             },
             ...(preflightPayload && { preflight: preflightPayload }),
-            results: results.map((r) => {
-              const relationshipsAndHints = buildRelationshipHints(r);
-              const enrichedSnippet = includeSnippets
-                ? enrichSnippetWithScope(r.snippet, r.metadata, r.filePath, r.startLine)
-                : undefined;
+            results: results.map((r): SearchResultPayload => {
+              const resultRoot = multiRoot
+                ? (findContainingRoot(workspaceRoots, r.filePath) ?? workspaceRoots[0])
+                : null;
+              const located = {
+                file: `${r.filePath}:${r.startLine}-${r.endLine}`,
+                ...(resultRoot && { root: resultRoot }),
+                ...(r.metadata?.qualifiedPath && { qualifiedPath: r.metadata.qualifiedPath })
+              };
+              const score = Math.round(r.score * 100) / 100;
+              const signature = r.metadata?.symbolSignature;
+              if (detailLevel === 'names') {
+                return { ...located, score };
+              }
+              if (detailLevel === 'signatures') {
+                return { ...located, summary: r.summary, score, ...(signature && { signature }) };
+              }
+
+              // The relationship graph only describes the primary root.
+              const relationshipsAndHints: RelationshipHints =
+                !resultRoot || resultRoot === workspaceRoots[0] ? buildRelationshipHints(r) : {};
+              // At full detail the whole chunk replaces the snippet
+              const enrichedSnippet =
+                includeSnippets && detailLevel !== 'full'
+                  ? enrichSnippetWithScope(r.snippet, r.metadata, r.filePath, r.startLine)
+                  : undefined;
 
               return {
-                file: `${r.filePath}:${r.startLine}-${r.endLine}`,
+                ...located,
                 summary: r.summary,
-                score: Math.round(r.score * 100) / 100,
+                score,
+                ...(detailLevel === 'full' && signature && { signature }),
                 ...(r.componentType &&
                   r.layer &&
                   r.layer !== 'unknown' && { type: `${r.componentType}:${r.layer}` }),
+                ...(r.metadata?.textOnly && { textOnly: true }),
                 ...(r.trend && r.trend !== 'Stable' && { trend: r.trend }),
                 ...(r.patternWarning && { patternWarning: r.patternWarning }),
+                ...(r.duplicates && {
+                  duplicates: r.duplicates.map((d) => `${d.filePath}:${d.startLine}-${d.endLine}`)
+                }),
                 ...(relationshipsAndHints.relationships && {
                   relationships: relationshipsAndHints.relationships
                 }),
                 ...(relationshipsAndHints.hints && { hints: relationshipsAndHints.hints }),
-                ...(enrichedSnippet && { snippet: enrichedSnippet })
+                ...(enrichedSnippet && { snippet: enrichedSnippet }),
+                ...(detailLevel === 'full' && r.fullContent && { source: r.fullContent })
               };
             }),
             totalResults: results.length,
+            ...(unavailableRoots.length > 0 && { unavailableRoots }),
             ...(relatedMemories.length > 0 && {
               relatedMemories: relatedMemories
                 .slice(0, 3)
@@ -0,0 +1,147 @@
+import type { Tool } from '@modelcontextprotocol/sdk/types.js';
+import type { ToolContext, ToolResponse } from './types.js';
+import { DEFAULT_SEMANTIC_K, MAX_SEMANTIC_K, semanticSearch } from '../core/semantic-search.js';
+import { IndexCorruptedError } from '../errors/index.js';
+import { testFilterFromArgs } from '../core/test-filter.js';
+
+export const definition: Tool = {
+  name: 'semantic_search',
+  description:
+    'Nearest chunks to a query by embedding cosine similarity alone, with raw scores and chunk ' +
+    'metadata (file, lines, symbol, kind). No keyword blending or reranking: use it to find ' +
+    'conceptually related code that shares no names, or to blend with your own lexical scores.',
+  inputSchema: {
+    type: 'object',
+    properties: {
+      query: {
+        type: 'string',
+        description: 'Natural language or code to find neighbours of'
+      },
+      k: {
+        type: 'number',
+        description: `Chunks to return (default: ${DEFAULT_SEMANTIC_K}, max: ${MAX_SEMANTIC_K})`,
+        default: DEFAULT_SEMANTIC_K
+      },
+      includeContent: {
+        type: 'boolean',
+        description: 'Include each chunk’s text (default: false)',
+        default: false
+      },
+      includeTests: {
+        type: 'boolean',
+        description: 'Set false to drop test files and inline test code (default: true)',
+        default: true
+      },
+      testsOnly: {
+        type: 'boolean',
+        description: 'Only return test code (default: false)',
+        default: false
+      }
+    },
+    required: ['query']
+  }
+};
+
+export async function handle(
+  args: Record<string, unknown>,
+  ctx: ToolContext
+): Promise<ToolResponse> {
+  const { query, k, includeContent, includeTests, testsOnly } = args as {
+    query?: unknown;
+    k?: unknown;
+    includeContent?: unknown;
+    includeTests?: unknown;
+    testsOnly?: unknown;
+  };
+  const queryStr = typeof query === 'string' ? query.trim() : '';
+
+  if (!queryStr) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            {
+              status: 'error',
+              errorCode: 'invalid_params',
+              message: "Invalid params: 'query' is required and must be a non-empty string."
+            },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  const tests = testFilterFromArgs(includeTests, testsOnly);
+  if (tests === null) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            {
+              status: 'error',
+              errorCode: 'invalid_params',
+              message: 'Invalid params: testsOnly cannot be combined with includeTests: false.'
+            },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  let result: Awaited<ReturnType<typeof semanticSearch>>;
+  try {
+    result = await semanticSearch(ctx.rootPath, queryStr, {
+      ...(typeof k === 'number' && k > 0 ? { k } : {}),
+      includeContent: includeContent === true,
+      ...(tests ? { tests } : {})
+    });
+  } catch (error) {
+    if (!(error instanceof IndexCorruptedError)) throw error;
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            {
+              status: 'error',
+              message: error.message,
+              hint: 'Run refresh_index to rebuild the index.'
+            },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  return {
+    content: [
+      {
+        type: 'text',
+        text: JSON.stringify(
+          {
+            status: 'success',
+            model: result.model,
+            matches: result.matches,
+            hint:
+              result.matches.length === 0
+                ? 'No stored embeddings. Re-index without skipEmbedding to enable semantic search.'
+                : undefined
+          },
+          null,
+          2
+        )
+      }
+    ]
+  };
+}
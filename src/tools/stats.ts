@@ -0,0 +1,75 @@
+import type { Tool } from '@modelcontextprotocol/sdk/types.js';
+import type { ToolContext, ToolResponse } from './types.js';
+import { readIndexingStats, summarizeSkipped } from '../core/index-stats.js';
+import { CURATED_LANGUAGE_TO_WASM } from '../grammars/manifest.js';
+import { loadedTreeSitterLanguages } from '../utils/tree-sitter.js';
+
+export const definition: Tool = {
+  name: 'stats',
+  description:
+    'Report index health as JSON: files indexed and skipped (with reasons), symbols by kind, ' +
+    'files per language, parse errors (fatal and recovered), memory, last index duration, ' +
+    'roots and grammars. Use it to find out why a file is missing from results.',
+  inputSchema: {
+    type: 'object',
+    properties: {}
+  }
+};
+
+function sumCounts(counts: Record<string, number> | undefined): number {
+  return Object.values(counts ?? {}).reduce((sum, count) => sum + count, 0);
+}
+
+export async function handle(
+  _args: Record<string, unknown>,
+  ctx: ToolContext
+): Promise<ToolResponse> {
+  const persisted = await readIndexingStats(ctx.rootPath);
+  const memory = process.memoryUsage();
+  const skipped = summarizeSkipped(persisted?.skipped);
+
+  return {
+    content: [
+      {
+        type: 'text',
+        text: JSON.stringify(
+          {
+            status: persisted ? 'success' : 'no_index',
+            indexState: ctx.indexState.status,
+            roots: ctx.workspaceRoots ?? [ctx.rootPath],
+            generatedAt: persisted?.generatedAt,
+            durationMs: persisted?.durationMs,
+            files: persisted
+              ? {
+                  scanned: persisted.totalFiles,
+                  indexed: persisted.indexedFiles,
+                  unanalyzed: persisted.unanalyzedFiles,
+                  skipped: skipped ?? { total: 0, byReason: {}, files: [], truncated: false },
+                  byLanguage: persisted.filesByLanguage
+                }
+              : undefined,
+            symbols: persisted?.symbolsByKind
+              ? { total: sumCounts(persisted.symbolsByKind), byKind: persisted.symbolsByKind }
+              : undefined,
+            chunks: persisted?.totalChunks,
+            parseErrors: persisted?.parseErrors,
+            recoveredParseErrors: persisted?.recoveredParseErrors,
+            memory: {
+              indexedBytes: persisted?.indexedBytes,
+              heapUsedBytes: memory.heapUsed,
+              rssBytes: memory.rss
+            },
+            grammars: {
+              available: Object.keys(CURATED_LANGUAGE_TO_WASM).sort(),
+              loaded: loadedTreeSitterLanguages()
+            },
+            error: ctx.indexState.error,
+            hint: persisted ? undefined : 'No index yet. Run refresh_index to build one.'
+          },
+          null,
+          2
+        )
+      }
+    ]
+  };
+}
@@ -0,0 +1,168 @@
+import type { Tool } from '@modelcontextprotocol/sdk/types.js';
+import path from 'path';
+import type { ToolContext, ToolResponse } from './types.js';
+import { readIndexedFiles } from '../core/chunk-export.js';
+import {
+  DEFAULT_STRUCTURAL_MATCH_LIMIT,
+  MAX_STRUCTURAL_MATCH_LIMIT,
+  structuralSearch
+} from '../core/structural-search.js';
+import { CURATED_LANGUAGE_TO_WASM } from '../grammars/manifest.js';
+import { TreeSitterQueryError, supportsTreeSitter } from '../utils/tree-sitter.js';
+
+export const definition: Tool = {
+  name: 'structural_search',
+  description:
+    'Match code by syntax tree shape with a tree-sitter S-expression query, e.g. ' +
+    '(parameter type: (reference_type (mutable_specifier))) @param for Rust. Runs over indexed ' +
+    'files of one language and returns each capture with file, line and text. Use ' +
+    'search_codebase for intent-level questions.',
+  inputSchema: {
+    type: 'object',
+    properties: {
+      query: {
+        type: 'string',
+        description:
+          'Tree-sitter query with at least one @capture. Predicates such as #eq? and #match? ' +
+          'are supported.'
+      },
+      language: {
+        type: 'string',
+        description: 'Grammar to compile the query for; only files of this language are searched',
+        enum: Object.keys(CURATED_LANGUAGE_TO_WASM).sort()
+      },
+      scope: {
+        type: 'string',
+        description: 'Only search files under this path prefix (for example: src/core)'
+      },
+      limit: {
+        type: 'number',
+        description:
+          `Maximum captures to return (default: ${DEFAULT_STRUCTURAL_MATCH_LIMIT}, ` +
+          `max: ${MAX_STRUCTURAL_MATCH_LIMIT})`,
+        default: DEFAULT_STRUCTURAL_MATCH_LIMIT
+      }
+    },
+    required: ['query', 'language']
+  }
+};
+
+export async function handle(
+  args: Record<string, unknown>,
+  ctx: ToolContext
+): Promise<ToolResponse> {
+  const { query, language, scope, limit } = args as {
+    query?: unknown;
+    language?: unknown;
+    scope?: unknown;
+    limit?: unknown;
+  };
+
+  const queryStr = typeof query === 'string' ? query.trim() : '';
+  const languageStr = typeof language === 'string' ? language.trim().toLowerCase() : '';
+  if (!queryStr || !supportsTreeSitter(languageStr)) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            {
+              status: 'error',
+              errorCode: 'invalid_params',
+              message: !queryStr
+                ? "Invalid params: 'query' is required and must be a non-empty string."
+                : `Invalid params: no tree-sitter grammar for language '${languageStr}'.`,
+              hint: `Languages: ${Object.keys(CURATED_LANGUAGE_TO_WASM).sort().join(', ')}`
+            },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  const resolvedRoot = path.resolve(ctx.rootPath);
+  const indexedFiles = await readIndexedFiles(resolvedRoot);
+  if (!indexedFiles) {
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            {
+              status: 'error',
+              message: 'Index manifest not found. Run refresh_index first.'
+            },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  const scopePrefix =
+    typeof scope === 'string' && scope.trim()
+      ? scope.trim().replace(/\\/g, '/').replace(/^\.\//, '').replace(/\/$/, '')
+      : '';
+  const files = scopePrefix
+    ? indexedFiles.filter((file) => file === scopePrefix || file.startsWith(`${scopePrefix}/`))
+    : indexedFiles;
+
+  let result: Awaited<ReturnType<typeof structuralSearch>>;
+  try {
+    result = await structuralSearch(resolvedRoot, files, {
+      query: queryStr,
+      language: languageStr,
+      ...(typeof limit === 'number' && limit > 0 ? { limit } : {})
+    });
+  } catch (error) {
+    if (!(error instanceof TreeSitterQueryError)) throw error;
+    return {
+      content: [
+        {
+          type: 'text',
+          text: JSON.stringify(
+            {
+              status: 'error',
+              errorCode: 'query_parse_error',
+              message: `Query does not compile for ${languageStr}: ${error.message}`,
+              offset: error.offset,
+              hint: 'Check node and field names against the grammar and balance the parentheses.'
+            },
+            null,
+            2
+          )
+        }
+      ],
+      isError: true
+    };
+  }
+
+  return {
+    content: [
+      {
+        type: 'text',
+        text: JSON.stringify(
+          {
+            status: 'success',
+            language: languageStr,
+            filesSearched: result.filesSearched,
+            matchCount: result.matches.length,
+            truncated: result.truncated,
+            timedOut: result.timedOut,
+            matches: result.matches,
+            hint: result.timedOut
+              ? 'Time budget ran out; narrow the query or pass scope to search fewer files.'
+              : undefined
+          },
+          null,
+          2
+        )
+      }
+    ]
+  };
+}
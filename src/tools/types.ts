@@ -1,4 +1,6 @@
 import type { CodebaseIndexer } from '../core/indexer.js';
+import type { ToolResultCache } from '../core/result-cache.js';
+import type { SymbolIndex } from '../core/symbol-index.js';
 import type { IndexingStats } from '../types/index.js';
 
 export interface DecisionCard {
@@ -38,7 +40,21 @@ export interface ToolContext {
   indexState: IndexState;
   paths: ToolPaths;
   rootPath: string;
+  /** Every workspace root served by this instance, `rootPath` first. Defaults to `[rootPath]`. */
+  workspaceRoots?: string[];
   performIndexing: (incrementalOnly?: boolean, reason?: string) => void;
+  /**
+   * Send an MCP progress notification; set only when the client passed a `progressToken`.
+   * Resolves once the transport accepted the message, so streaming tools can await it.
+   */
+  reportProgress?: (progress: number, message?: string) => Promise<void>;
+  /** Caches results of `CACHEABLE_TOOL_NAMES`; unset (no caching) for one-shot CLI calls */
+  resultCache?: ToolResultCache<ToolResponse>;
+  /**
+   * Symbols and parse trees kept current by the watcher; outline, definition and reference
+   * lookups read from it instead of parsing each file. Unset for one-shot CLI calls.
+   */
+  symbolIndex?: SymbolIndex;
 }
 
 export interface ToolResponse {
@@ -56,11 +72,13 @@ export interface SearchQuality {
 
 export interface SearchResultItem {
   file: string; // "path:startLine-endLine"
+  root?: string; // workspace root, when several are served
   summary: string;
   score: number;
   type?: string; // "componentType:layer"
   trend?: 'Rising' | 'Declining';
   patternWarning?: string;
+  duplicates?: string[]; // other "path:startLine-endLine" copies, with dedupSnippets
   relationships?: {
     importedByCount?: number;
     hasTests?: boolean;
@@ -79,6 +97,7 @@ export interface SearchResponse {
   preflight?: DecisionCard;
   results: SearchResultItem[];
   totalResults: number;
+  unavailableRoots?: string[];
   relatedMemories?: string[];
 }
 
@@ -214,3 +233,19 @@ export interface RefsResponse {
   confidence: string;
   usages: RefsUsage[];
 }
+
+export interface SymbolMatchItem {
+  name: string;
+  kind?: string;
+  file: string;
+  line: number;
+  match: 'exact' | 'prefix' | 'fuzzy';
+  score: number;
+}
+
+export interface FuzzyRefsResponse {
+  mode: 'fuzzy';
+  query: string;
+  totalMatches: number;
+  matches: SymbolMatchItem[];
+}
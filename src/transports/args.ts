@@ -0,0 +1,77 @@
+/**
+ * Server command line: positional project roots plus the transport flags
+ * `--transport stdio|http`, `--port <n>` and `--host <h>`. Unknown flags are ignored, as before.
+ */
+
+export const SERVER_TRANSPORTS = ['stdio', 'http'] as const;
+export type ServerTransport = (typeof SERVER_TRANSPORTS)[number];
+
+export const DEFAULT_HTTP_HOST = '127.0.0.1';
+export const DEFAULT_HTTP_PORT = 3000;
+
+export interface ServerArgs {
+  /** Positional arguments: the primary root, then extra workspace roots */
+  roots: string[];
+  transport: ServerTransport;
+  host: string;
+  port: number;
+  /** Set when a transport flag has a bad value; the server reports it and exits */
+  error?: string;
+}
+
+const VALUE_FLAGS = new Set(['transport', 'port', 'host']);
+
+function isServerTransport(value: string): value is ServerTransport {
+  return (SERVER_TRANSPORTS as readonly string[]).includes(value);
+}
+
+/** Parse `process.argv.slice(2)` for the server; never throws. */
+export function parseServerArgs(argv: string[]): ServerArgs {
+  const parsed: ServerArgs = {
+    roots: [],
+    transport: 'stdio',
+    host: DEFAULT_HTTP_HOST,
+    port: DEFAULT_HTTP_PORT
+  };
+  const values = new Map<string, string>();
+
+  for (let i = 0; i < argv.length; i++) {
+    const arg = argv[i];
+    if (!arg.startsWith('--')) {
+      if (!arg.startsWith('-')) parsed.roots.push(arg);
+      continue;
+    }
+    const [name, inline] = arg.slice(2).split(/=(.*)/s, 2);
+    if (!VALUE_FLAGS.has(name)) continue;
+    const value = inline ?? argv[i + 1];
+    if (inline === undefined) i++;
+    if (value === undefined || value === '') {
+      parsed.error ??= `--${name} needs a value`;
+      continue;
+    }
+    values.set(name, value);
+  }
+
+  const transport = values.get('transport');
+  if (transport !== undefined) {
+    if (isServerTransport(transport)) {
+      parsed.transport = transport;
+    } else {
+      const allowed = SERVER_TRANSPORTS.join(', ');
+      parsed.error ??= `invalid --transport "${transport}". Allowed: ${allowed}`;
+    }
+  }
+
+  const port = values.get('port');
+  if (port !== undefined) {
+    const value = Number(port);
+    if (Number.isInteger(value) && value >= 0 && value <= 65535) {
+      parsed.port = value;
+    } else {
+      parsed.error ??= `invalid --port "${port}": expected an integer from 0 to 65535`;
+    }
+  }
+
+  parsed.host = values.get('host') ?? parsed.host;
+  return parsed;
+}
@@ -0,0 +1,243 @@
+/**
+ * Streamable HTTP transport (the MCP HTTP transport: JSON-RPC over POST, Server-Sent Events for
+ * streamed responses and notifications). Each session gets its own MCP server from
+ * `createServer`, so HTTP clients run the same tool handlers as stdio.
+ */
+
+import { randomUUID } from 'crypto';
+import http, { type IncomingMessage, type ServerResponse } from 'http';
+import type { AddressInfo } from 'net';
+import type { Server } from '@modelcontextprotocol/sdk/server/index.js';
+import { StreamableHTTPServerTransport } from '@modelcontextprotocol/sdk/server/streamableHttp.js';
+import { isInitializeRequest } from '@modelcontextprotocol/sdk/types.js';
+
+export const MCP_HTTP_PATH = '/mcp';
+
+/** Largest request body accepted; tool arguments are small. */
+const MAX_BODY_BYTES = 4 * 1024 * 1024;
+
+/** How long `close()` waits for in-flight requests before dropping connections. */
+const DEFAULT_DRAIN_TIMEOUT_MS = 10_000;
+
+/** How long a session may sit without requests or an open stream before it is closed. */
+export const DEFAULT_SESSION_IDLE_TIMEOUT_MS = 30 * 60_000;
+
+/** Longest gap between sweeps for idle sessions. */
+const MAX_REAP_INTERVAL_MS = 60_000;
+
+const LOOPBACK_HOSTS = new Set(['localhost', '127.0.0.1', '::1']);
+
+export interface HttpTransportOptions {
+  host: string;
+  port: number;
+  createServer: () => Server;
+  drainTimeoutMs?: number;
+  /**
+   * Close sessions that had no request and no open stream for this long, for clients that go
+   * away without sending DELETE. 0 keeps sessions until DELETE or `close()`.
+   */
+  sessionIdleTimeoutMs?: number;
+}
+
+export interface HttpTransportHandle {
+  /** Endpoint clients connect to, e.g. `http://127.0.0.1:3000/mcp` */
+  url: string;
+  /** Stop accepting requests, let in-flight ones finish, then end every session. */
+  close: () => Promise<void>;
+}
+
+interface Session {
+  transport: StreamableHTTPServerTransport;
+  server: Server;
+  /** Requests and SSE streams of this session still open */
+  openRequests: number;
+  /** When the last of them closed */
+  lastActive: number;
+}
+
+function isLoopbackHost(host: string): boolean {
+  return LOOPBACK_HOSTS.has(host.replace(/^\[|\]$/g, '').toLowerCase());
+}
+
+/** Hostname part of a Host header, without the port. */
+function hostHeaderName(header: string | undefined): string {
+  if (!header) return '';
+  const bracketed = header.match(/^\[([^\]]+)\]/);
+  return bracketed ? bracketed[1] : header.replace(/:\d+$/, '');
+}
+
+function sendJsonRpcError(res: ServerResponse, status: number, message: string): void {
+  res.writeHead(status, { 'Content-Type': 'application/json' });
+  res.end(JSON.stringify({ jsonrpc: '2.0', error: { code: -32000, message }, id: null }));
+}
+
+async function readJsonBody(req: IncomingMessage): Promise<unknown> {
+  const chunks: Buffer[] = [];
+  let size = 0;
+  for await (const chunk of req) {
+    const buffer = chunk as Buffer;
+    size += buffer.length;
+    if (size > MAX_BODY_BYTES) throw new RangeError('Request body too large');
+    chunks.push(buffer);
+  }
+  return JSON.parse(Buffer.concat(chunks).toString('utf8'));
+}
+
+function formatUrl(address: AddressInfo): string {
+  const host = address.family === 'IPv6' ? `[${address.address}]` : address.address;
+  return `http://${host}:${address.port}${MCP_HTTP_PATH}`;
+}
+
+/**
+ * Serve MCP over HTTP on `host:port` (port 0 picks a free one). When bound to a loopback address,
+ * requests whose Host header names anything else are refused, which blocks DNS rebinding.
+ */
+export async function startHttpTransport(
+  options: HttpTransportOptions
+): Promise<HttpTransportHandle> {
+  const sessions = new Map<string, Session>();
+  const loopbackOnly = isLoopbackHost(options.host);
+  let closing = false;
+  let inFlight = 0;
+  let onDrained: (() => void) | null = null;
+
+  const finishRequest = (): void => {
+    inFlight--;
+    if (inFlight === 0) onDrained?.();
+  };
+
+  const openSession = async (): Promise<StreamableHTTPServerTransport> => {
+    const server = options.createServer();
+    const transport = new StreamableHTTPServerTransport({
+      sessionIdGenerator: () => randomUUID(),
+      onsessioninitialized: (sessionId) => {
+        sessions.set(sessionId, { transport, server, openRequests: 0, lastActive: Date.now() });
+      }
+    });
+    transport.onclose = () => {
+      if (transport.sessionId) sessions.delete(transport.sessionId);
+    };
+    await server.connect(transport);
+    return transport;
+  };
+
+  const handle = async (req: IncomingMessage, res: ServerResponse): Promise<void> => {
+    if (loopbackOnly && !isLoopbackHost(hostHeaderName(req.headers.host))) {
+      sendJsonRpcError(res, 403, 'Forbidden: Host header must name a loopback address');
+      return;
+    }
+    if (new URL(req.url ?? '/', 'http://localhost').pathname !== MCP_HTTP_PATH) {
+      sendJsonRpcError(res, 404, `Not found: MCP is served at ${MCP_HTTP_PATH}`);
+      return;
+    }
+    if (closing) {
+      sendJsonRpcError(res, 503, 'Server is shutting down');
+      return;
+    }
+
+    const sessionHeader = req.headers['mcp-session-id'];
+    const sessionId = Array.isArray(sessionHeader) ? sessionHeader[0] : sessionHeader;
+    const session = sessionId ? sessions.get(sessionId) : undefined;
+    if (session) {
+      session.openRequests++;
+      res.once('close', () => {
+        session.openRequests--;
+        session.lastActive = Date.now();
+      });
+    }
+
+    if (req.method === 'GET' || req.method === 'DELETE') {
+      if (!session) {
+        sendJsonRpcError(res, 404, 'Unknown or missing Mcp-Session-Id');
+        return;
+      }
+      await session.transport.handleRequest(req, res);
+      return;
+    }
+    if (req.method !== 'POST') {
+      res.writeHead(405, { Allow: 'GET, POST, DELETE' }).end();
+      return;
+    }
+
+    let body: unknown;
+    try {
+      body = await readJsonBody(req);
+    } catch (error) {
+      if (error instanceof RangeError) sendJsonRpcError(res, 413, error.message);
+      else sendJsonRpcError(res, 400, 'Invalid JSON body');
+      return;
+    }
+
+    if (session) {
+      await session.transport.handleRequest(req, res, body);
+    } else if (!sessionId && isInitializeRequest(body)) {
+      const transport = await openSession();
+      await transport.handleRequest(req, res, body);
+    } else {
+      sendJsonRpcError(res, sessionId ? 404 : 400, 'Unknown or missing Mcp-Session-Id');
+    }
+  };
+
+  const httpServer = http.createServer((req, res) => {
+    // SSE streams opened by GET stay open for the whole session; only requests count as in flight
+    if (req.method === 'POST') {
+      inFlight++;
+      res.once('close', finishRequest);
+    }
+    handle(req, res).catch((error) => {
+      console.error('[http-transport] request failed:', error);
+      if (!res.headersSent) sendJsonRpcError(res, 500, 'Internal server error');
+      else res.end();
+    });
+  });
+
+  await new Promise<void>((resolve, reject) => {
+    httpServer.once('error', reject);
+    httpServer.listen(options.port, options.host, () => {
+      httpServer.off('error', reject);
+      resolve();
+    });
+  });
+
+  const idleTimeoutMs = options.sessionIdleTimeoutMs ?? DEFAULT_SESSION_IDLE_TIMEOUT_MS;
+  const reapIdleSessions = (): void => {
+    const cutoff = Date.now() - idleTimeoutMs;
+    for (const [sessionId, session] of sessions) {
+      if (session.openRequests === 0 && session.lastActive <= cutoff) {
+        sessions.delete(sessionId);
+        void session.server.close().catch(() => undefined);
+      }
+    }
+  };
+  const reaper =
+    idleTimeoutMs > 0
+      ? setInterval(reapIdleSessions, Math.min(idleTimeoutMs, MAX_REAP_INTERVAL_MS))
+      : null;
+  reaper?.unref();
+
+  const close = async (): Promise<void> => {
+    if (closing) return;
+    closing = true;
+    if (reaper) clearInterval(reaper);
+    const stopped = new Promise<void>((resolve) => httpServer.close(() => resolve()));
+
+    if (inFlight > 0) {
+      await new Promise<void>((resolve) => {
+        const timer = setTimeout(resolve, options.drainTimeoutMs ?? DEFAULT_DRAIN_TIMEOUT_MS);
+        onDrained = () => {
+          clearTimeout(timer);
+          resolve();
+        };
+      });
+    }
+
+    await Promise.all(
+      [...sessions.values()].map(({ server }) => server.close().catch(() => undefined))
+    );
+    sessions.clear();
+    httpServer.closeAllConnections();
+    await stopped;
+  };
+
+  return { url: formatUrl(httpServer.address() as AddressInfo), close };
+}
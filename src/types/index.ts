@@ -147,9 +147,23 @@ export interface ChunkMetadata {
   symbolAware?: boolean;
   symbolName?: string;
   symbolKind?: string;
+  /** Kinds of every symbol inside the chunk, `symbolKind` included */
+  symbolKinds?: string[];
   symbolPath?: string[];
+  /** Enclosing scopes and the symbol name, e.g. `geometry::Circle::area`; see `resolve_path` */
+  qualifiedPath?: string;
+  /** Parameter list of a Java method, `(int a, int b)`; overloads share a `symbolPath` */
+  symbolParameters?: string;
+  /** Declaration of the chunk's symbol up to its body, `fn area(&self) -> f64` */
+  symbolSignature?: string;
   parentSymbol?: string;
   chunkStrategy?: string;
+  /** Line chunk of a file with no symbol extraction (SQL, shell, config); never has symbols */
+  textOnly?: boolean;
+  /** Test code: the file is a test by path convention, or the chunk is an inline test symbol */
+  isTest?: boolean;
+  /** The file has syntax errors; its symbols were extracted around them */
+  hasParseErrors?: boolean;
 
   // Framework-specific
   isStandalone?: boolean;
@@ -320,14 +334,29 @@ export interface SearchQuery {
   includeRelated?: boolean;
 }
 
+/** Inclusive, 1-based line range */
+export interface LineRange {
+  startLine: number;
+  endLine: number;
+}
+
+/** `exclude` drops test code, `only` keeps nothing else */
+export type TestFilter = 'exclude' | 'only';
+
 export interface SearchFilters {
   framework?: string;
   language?: string;
   componentType?: string;
   layer?: ArchitecturalLayer;
   tags?: string[];
+  /** Symbol kinds; a chunk matches when it holds a symbol of any of them */
+  kinds?: string[];
+  /** Project-relative path to line ranges; a chunk matches when it overlaps one of its file's */
+  lineRanges?: Record<string, LineRange[]>;
   filePaths?: string[];
   excludePaths?: string[];
+  /** Drop test code or keep only test code; see `isTestChunk` */
+  tests?: TestFilter;
   hasTests?: boolean;
   minComplexity?: number;
   maxComplexity?: number;
@@ -361,8 +390,11 @@ export interface SearchResult {
 
   relationships?: RelationshipData;
 
+  // Other locations holding the same code, when search collapsed duplicates (dedupSnippets)
+  duplicates?: Array<{ filePath: string; startLine: number; endLine: number }>;
+
   // Optional detailed context (for agent to request if needed)
-  fullContent?: string; // Only included if explicitly requested
+  fullContent?: string; // Whole chunk text; serialized only at detail: 'full'
   relatedChunks?: CodeChunk[];
   highlights?: TextHighlight[];
 }
@@ -420,6 +452,18 @@ export interface IndexingError {
   timestamp: Date;
 }
 
+/**
+ * Why a file was left out: the scanner's size, minified and binary checks, or content that did
+ * not decode as text (`undecodable`, e.g. an odd byte count after a UTF-16 BOM).
+ */
+export type FileSkipReason = 'too_large' | 'minified' | 'binary' | 'undecodable';
+
+export interface SkippedFile {
+  file: string; // relative to the project root
+  reason: FileSkipReason;
+  size: number; // bytes
+}
+
 export interface IndexingStats {
   totalFiles: number;
   indexedFiles: number;
@@ -439,6 +483,24 @@ export interface IndexingStats {
     deleted: number;
     unchanged: number;
   };
+  symbolCache?: {
+    hits: number;
+    misses: number;
+  };
+  /** Set when symbol extraction ran on worker threads */
+  parseWorkers?: {
+    workers: number;
+    parsedFiles: number;
+  };
+  embeddingCache?: {
+    hits: number;
+    misses: number;
+  };
+  skipped?: SkippedFile[]; // files left out by size, minified, binary or decoding checks
+  filesByLanguage?: Record<string, number>;
+  symbolsByKind?: Record<string, number>;
+  indexedBytes?: number; // UTF-8 size of the indexed file contents
+  recoveredParseErrors?: number; // files indexed with symbols extracted around syntax errors
 }
 
 // ============================================================================
@@ -465,16 +527,33 @@ export interface CodebaseConfig {
   include?: string[];
   exclude?: string[];
   respectGitignore?: boolean;
+  /** Root-relative globs skipped like `.gitignore` entries, without editing `.gitignore` */
+  ignore?: string[];
+  /** Root-relative globs indexed even when `.gitignore` or `ignore` would skip them */
+  includeOverride?: string[];
+  /** Crawl symlinked directories (default: false); cycles and repeated targets are skipped */
+  followSymlinks?: boolean;
 
   // Parsing options
   parsing: {
-    maxFileSize?: number; // bytes
+    maxFileSize?: number; // bytes; larger files are skipped before they are read
+    skipMinified?: boolean; // also skip files whose first KBs average very long lines
+    concurrency?: number; // files read and parsed at once; defaults to available parallelism
+    // Parse worker threads; 0 parses on the main thread. Default: `concurrency`, once a run
+    // has 200 or more files
+    workers?: number;
     chunkSize?: number; // lines
     chunkOverlap?: number; // lines
     parseTests?: boolean;
     parseNodeModules?: boolean;
   };
 
+  // Content-hash keyed caches of extracted symbols and chunk embeddings, reused across runs
+  symbolCache?: {
+    enabled?: boolean;
+    dir?: string; // relative to the project root; defaults to .codebase-context/cache
+  };
+
   // Style guides
   styleGuides?: {
     autoDetect?: boolean;
@@ -492,7 +571,7 @@ export interface CodebaseConfig {
 
   // Embedding
   embedding?: {
-    provider?: 'transformers' | 'openai' | 'ollama' | 'custom';
+    provider?: 'transformers' | 'openai' | 'ollama' | 'stub' | 'custom';
     model?: string;
     batchSize?: number;
   };
@@ -9,6 +9,7 @@
 
 import { v4 as uuidv4 } from 'uuid';
 import type { TreeSitterSymbol } from './tree-sitter.js';
+import { qualifiedSymbolPath } from './qualified-path.js';
 import type { CodeChunk, ChunkMetadata } from '../types/index.js';
 
 // ---------------------------------------------------------------------------
@@ -53,10 +54,14 @@ export const MAX_AST_CHUNK_FILE_LINES = 10_000; // 10K lines
  * - Top-level function: `// getData :: (id: string): Promise<User>`
  * - Method inside class: `// UserService > getData :: (id: string): Promise<User>`
  * - Standalone constant: `// MAX_RETRIES :: const`
+ * - Go method (receiver as qualifier): `// Calculator > Add :: (n int)`
  */
 export function generateScopePrefix(node: SymbolNode, ancestors: SymbolNode[]): string {
   // Build scope path: ancestor names joined with " > ", then current node
   const pathParts = ancestors.map((a) => a.symbol.name);
+  if (pathParts.length === 0 && node.symbol.qualifier) {
+    pathParts.push(node.symbol.qualifier);
+  }
   pathParts.push(node.symbol.name);
   const scopePath = pathParts.join(' > ');
 
@@ -217,11 +222,13 @@ function processNode(
   shouldPrefix: boolean
 ): CodeChunk[] {
   const sym = node.symbol;
-  const symbolPath = parentName ? [parentName, sym.name] : [sym.name];
+  const owner = parentName ?? sym.qualifier;
+  const symbolPath = owner ? [owner, sym.name] : [sym.name];
+  const qualifiedPath = qualifiedSymbolPath(sym, options.language, options.filePath);
 
   if (node.children.length === 0) {
     // Leaf symbol → single chunk
-    const chunk = makeSymbolChunk(sym, lines, options, symbolPath, parentName);
+    const chunk = makeSymbolChunk(sym, lines, options, symbolPath, qualifiedPath, parentName);
     if (shouldPrefix) {
       const prefix = generateScopePrefix(node, ancestors);
       chunk.content = prefix + '\n' + chunk.content;
@@ -253,6 +260,7 @@ function processNode(
         lines,
         options,
         symbolPath,
+        qualifiedPath,
         parentName,
         true // use provided content
       );
@@ -301,6 +309,7 @@ function processNode(
         lines,
         options,
         symbolPath,
+        qualifiedPath,
         parentName,
         true
       );
@@ -338,8 +347,17 @@ export function mergeSmallSymbolChunks(chunks: CodeChunk[], minLines: number): C
 
     const sameParent =
       (current.metadata?.parentSymbol ?? null) === (next.metadata?.parentSymbol ?? null);
-
-    if (curSymAware && nextSymAware && curLines < minLines && nextLines < minLines && sameParent) {
+    // Inline tests (Rust `#[cfg(test)]` modules) are not merged into production code
+    const sameTestStatus = Boolean(current.metadata?.isTest) === Boolean(next.metadata?.isTest);
+
+    if (
+      curSymAware &&
+      nextSymAware &&
+      curLines < minLines &&
+      nextLines < minLines &&
+      sameParent &&
+      sameTestStatus
+    ) {
       // Merge
       const mergedName = [current.metadata?.symbolName || '', next.metadata?.symbolName || '']
         .filter(Boolean)
@@ -503,6 +521,32 @@ export function createASTAlignedChunks(
   return final;
 }
 
+/**
+ * Record on each chunk the kinds of the symbols lying wholly inside it, so searches can filter
+ * by kind. `symbols` may include ones that were never chunk boundaries, such as class fields.
+ */
+export function annotateSymbolKinds(chunks: CodeChunk[], symbols: TreeSitterSymbol[]): void {
+  for (const chunk of chunks) {
+    const kinds = new Set<string>();
+    if (chunk.metadata?.symbolKind) kinds.add(chunk.metadata.symbolKind);
+    for (const symbol of symbols) {
+      if (symbol.startLine >= chunk.startLine && symbol.endLine <= chunk.endLine) {
+        kinds.add(symbol.kind);
+      }
+    }
+    if (kinds.size > 0) {
+      chunk.metadata = { ...chunk.metadata, symbolKinds: [...kinds].sort() };
+    }
+  }
+}
+
+/** True when the chunk holds a symbol of one of `kinds`; older indexes only have `symbolKind`. */
+export function chunkHasSymbolKind(chunk: CodeChunk, kinds: readonly string[]): boolean {
+  const metadata = chunk.metadata;
+  const chunkKinds = metadata?.symbolKinds ?? (metadata?.symbolKind ? [metadata.symbolKind] : []);
+  return kinds.some((kind) => chunkKinds.includes(kind));
+}
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
@@ -544,6 +588,7 @@ function makeSymbolChunk(
   lines: string[],
   options: ASTChunkOptions,
   symbolPath: string[],
+  qualifiedPath: string,
   parentName: string | null,
   useProvidedContent = false
 ): CodeChunk {
@@ -570,6 +615,10 @@ function makeSymbolChunk(
       symbolName: sym.name,
       symbolKind: sym.kind,
       symbolPath,
+      qualifiedPath,
+      ...(sym.parameters ? { symbolParameters: sym.parameters } : {}),
+      ...(sym.signature ? { symbolSignature: sym.signature } : {}),
+      ...(sym.test ? { isTest: true } : {}),
       parentSymbol: parentName ?? undefined,
       chunkStrategy: 'ast-aligned',
       componentName: sym.name
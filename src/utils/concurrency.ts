@@ -0,0 +1,54 @@
+/**
+ * Bounded concurrency for indexing work.
+ * Results come back in input order, and a finished result that has not been consumed yet still
+ * holds its slot, so memory is bounded by `limit` items plus the one being consumed.
+ */
+
+import os from 'os';
+
+export const PARSE_CONCURRENCY_ENV = 'CODEBASE_CONTEXT_PARSE_CONCURRENCY';
+
+/** One parse worker per core the process may use. */
+export function defaultParseConcurrency(): number {
+  const parallelism =
+    typeof os.availableParallelism === 'function' ? os.availableParallelism() : os.cpus().length;
+  return Math.max(1, parallelism);
+}
+
+/** `CODEBASE_CONTEXT_PARSE_CONCURRENCY` when it is a positive integer, else the default. */
+export function parseConcurrencyFromEnv(
+  value: string | undefined = process.env[PARSE_CONCURRENCY_ENV]
+): number {
+  const parsed = value ? Number.parseInt(value.trim(), 10) : NaN;
+  return Number.isFinite(parsed) && parsed > 0 ? parsed : defaultParseConcurrency();
+}
+
+/**
+ * Run `worker` over `items` with at most `limit` calls in flight, yielding results in input
+ * order. The next item only starts when the consumer takes the oldest result, so a slow
+ * consumer stalls the workers instead of letting results queue up. `worker` must not reject;
+ * turn failures into values instead.
+ */
+export async function* mapWithConcurrency<T, R>(
+  items: readonly T[],
+  limit: number,
+  worker: (item: T, index: number) => Promise<R>
+): AsyncGenerator<R> {
+  const slots = Math.max(1, Math.floor(limit) || 1);
+  const pending: Array<Promise<R>> = [];
+  let next = 0;
+
+  while (next < items.length && pending.length < slots) {
+    pending.push(worker(items[next], next));
+    next++;
+  }
+
+  while (pending.length > 0) {
+    const result = await pending.shift()!;
+    if (next < items.length) {
+      pending.push(worker(items[next], next));
+      next++;
+    }
+    yield result;
+  }
+}
@@ -0,0 +1,302 @@
+/**
+ * Per-directory indexing overrides for mixed monorepos.
+ * A `.codebase-context.toml` applies to its own directory and everything below it. Settings
+ * are resolved nearest-ancestor-wins: deeper files override scalar settings and individual
+ * language mappings, and their ignore patterns are evaluated after their ancestors', so a
+ * deeper `!pattern` can re-include what a parent ignored.
+ *
+ *   skip = true                      # exclude this subtree, regardless of .gitignore
+ *   max_file_size = 262144           # bytes
+ *   ignore = ["*.pb.go", "gen/**"]   # gitignore syntax, relative to this directory
+ *
+ *   [languages]
+ *   ".go" = "plaintext"              # extension -> language id; plaintext disables parsing
+ *   "*" = "plaintext"                # every file in the subtree
+ *
+ *   [watch]                          # root file only, read when the server starts
+ *   debounce_ms = 500                # quiet time after the last change before reindexing
+ */
+
+import { promises as fs } from 'fs';
+import path from 'path';
+import { glob } from 'glob';
+import ignore from 'ignore';
+
+type IgnoreInstance = ReturnType<typeof ignore.default>;
+
+export const DIRECTORY_CONFIG_FILENAME = '.codebase-context.toml';
+
+export interface DirectoryConfig {
+  skip?: boolean;
+  maxFileSize?: number;
+  ignore?: string[];
+  /** Extension (with dot) or `*` -> language id */
+  languages?: Record<string, string>;
+  /** File watcher debounce in ms; only the root file's is used */
+  watchDebounceMs?: number;
+}
+
+export interface EffectiveDirectoryConfig {
+  skip: boolean;
+  maxFileSize?: number;
+  languages: Record<string, string>;
+  /** Config files that contributed, root first, relative to the root */
+  sources: string[];
+}
+
+export interface DirectoryConfigResolver {
+  /** Config files that were loaded, relative to the root */
+  sources: string[];
+  /** Effective settings for a root-relative file path */
+  resolve(relativePath: string): EffectiveDirectoryConfig;
+  /** True when the file sits in a skipped subtree or matches an applicable ignore pattern */
+  ignores(relativePath: string): boolean;
+  /** Language forced by the nearest config mapping the file's extension (or `*`), if any */
+  languageFor(relativePath: string): string | undefined;
+}
+
+interface ScopedConfig {
+  /** Directory the file lives in, posix, '' for the root */
+  baseDir: string;
+  source: string;
+  config: DirectoryConfig;
+  matcher: IgnoreInstance | null;
+}
+
+type TomlValue = string | number | boolean | string[];
+
+// Never look for config files inside these, they are excluded by the walker anyway
+const DIRECTORY_CONFIG_SCAN_EXCLUDES = ['**/node_modules/**', '**/.git/**'];
+
+function toPosix(filePath: string): string {
+  return filePath.replace(/\\/g, '/');
+}
+
+function stripComment(line: string): string {
+  let quote: string | null = null;
+  for (let i = 0; i < line.length; i++) {
+    const char = line[i];
+    if (quote) {
+      if (char === '\\' && quote === '"') i++;
+      else if (char === quote) quote = null;
+    } else if (char === '"' || char === "'") {
+      quote = char;
+    } else if (char === '#') {
+      return line.slice(0, i);
+    }
+  }
+  return line;
+}
+
+function parseTomlString(raw: string): string | null {
+  if (raw.length >= 2 && raw.startsWith("'") && raw.endsWith("'")) return raw.slice(1, -1);
+  if (raw.length >= 2 && raw.startsWith('"') && raw.endsWith('"')) {
+    try {
+      return JSON.parse(raw) as string;
+    } catch {
+      return null;
+    }
+  }
+  return null;
+}
+
+function splitArrayItems(body: string): string[] {
+  const items: string[] = [];
+  let current = '';
+  let quote: string | null = null;
+  for (let i = 0; i < body.length; i++) {
+    const char = body[i];
+    if (quote) {
+      current += char;
+      if (char === '\\' && quote === '"') current += body[++i] ?? '';
+      else if (char === quote) quote = null;
+    } else if (char === '"' || char === "'") {
+      quote = char;
+      current += char;
+    } else if (char === ',') {
+      items.push(current.trim());
+      current = '';
+    } else {
+      current += char;
+    }
+  }
+  if (current.trim()) items.push(current.trim());
+  return items;
+}
+
+function parseTomlValue(raw: string): TomlValue | null {
+  if (raw === 'true') return true;
+  if (raw === 'false') return false;
+  if (/^[+-]?\d[\d_]*$/.test(raw)) return Number(raw.replace(/_/g, ''));
+  if (raw.startsWith('[') && raw.endsWith(']')) {
+    const items = splitArrayItems(raw.slice(1, -1)).map(parseTomlString);
+    return items.every((item): item is string => item !== null) ? items : null;
+  }
+  return parseTomlString(raw);
+}
+
+/**
+ * Parse the TOML subset used by directory configs: top-level keys, `[tables]` one level deep,
+ * and string, integer, boolean and string-array values (arrays may span lines).
+ */
+export function parseDirectoryConfig(text: string): DirectoryConfig {
+  const tables: Record<string, Record<string, TomlValue>> = { '': {} };
+  let table = '';
+  const lines = text.replace(/^\uFEFF/, '').split(/\r?\n/);
+
+  for (let i = 0; i < lines.length; i++) {
+    let line = stripComment(lines[i]).trim();
+    if (!line) continue;
+
+    const header = line.match(/^\[\s*([\w-]+)\s*\]$/);
+    if (header) {
+      table = header[1];
+      tables[table] ??= {};
+      continue;
+    }
+
+    const eq = line.indexOf('=');
+    if (eq <= 0) throw new Error(`line ${i + 1}: expected key = value`);
+    const rawKey = line.slice(0, eq).trim();
+    let rawValue = line.slice(eq + 1).trim();
+    while (rawValue.startsWith('[') && !rawValue.endsWith(']') && i + 1 < lines.length) {
+      line = stripComment(lines[++i]).trim();
+      rawValue += line;
+    }
+
+    const key = /^[\w-]+$/.test(rawKey) ? rawKey : parseTomlString(rawKey);
+    const value = parseTomlValue(rawValue);
+    if (!key || value === null) throw new Error(`line ${i + 1}: unsupported value for ${rawKey}`);
+    tables[table][key] = value;
+  }
+
+  const top = tables[''];
+  const config: DirectoryConfig = {};
+  if (typeof top.skip === 'boolean') config.skip = top.skip;
+  if (typeof top.max_file_size === 'number' && top.max_file_size > 0) {
+    config.maxFileSize = top.max_file_size;
+  }
+  if (Array.isArray(top.ignore)) config.ignore = top.ignore;
+  if (tables.languages) {
+    config.languages = {};
+    for (const [pattern, language] of Object.entries(tables.languages)) {
+      if (typeof language !== 'string') continue;
+      const key = pattern === '*' || pattern.startsWith('.') ? pattern : `.${pattern}`;
+      config.languages[key.toLowerCase()] = language;
+    }
+  }
+  const debounceMs = tables.watch?.debounce_ms;
+  if (typeof debounceMs === 'number' && debounceMs >= 0) config.watchDebounceMs = debounceMs;
+  return config;
+}
+
+function appliesTo(scoped: ScopedConfig, relativePath: string): boolean {
+  return !scoped.baseDir || relativePath.startsWith(`${scoped.baseDir}/`);
+}
+
+function scopedPathOf(scoped: ScopedConfig, relativePath: string): string {
+  return scoped.baseDir ? relativePath.slice(scoped.baseDir.length + 1) : relativePath;
+}
+
+export function createDirectoryConfigResolver(
+  configs: Array<{ baseDir: string; source: string; config: DirectoryConfig }>
+): DirectoryConfigResolver {
+  const scopedConfigs: ScopedConfig[] = configs
+    .map(({ baseDir, source, config }) => ({
+      baseDir: baseDir === '.' ? '' : toPosix(baseDir),
+      source,
+      config,
+      matcher: config.ignore?.length ? ignore.default().add(config.ignore) : null
+    }))
+    .sort((a, b) => {
+      const depth = (dir: string) => (dir ? dir.split('/').length : 0);
+      return depth(a.baseDir) - depth(b.baseDir) || a.baseDir.localeCompare(b.baseDir);
+    });
+
+  const applicable = (relativePath: string) =>
+    scopedConfigs.filter((scoped) => appliesTo(scoped, relativePath));
+
+  const resolve = (relativePath: string): EffectiveDirectoryConfig => {
+    const effective: EffectiveDirectoryConfig = { skip: false, languages: {}, sources: [] };
+    for (const { source, config } of applicable(toPosix(relativePath))) {
+      if (config.skip !== undefined) effective.skip = config.skip;
+      if (config.maxFileSize !== undefined) effective.maxFileSize = config.maxFileSize;
+      Object.assign(effective.languages, config.languages);
+      effective.sources.push(source);
+    }
+    return effective;
+  };
+
+  return {
+    sources: scopedConfigs.map((scoped) => scoped.source),
+    resolve,
+    ignores(relativePath: string): boolean {
+      const posixPath = toPosix(relativePath);
+      let skipped = false;
+      let ignored = false;
+      for (const scoped of applicable(posixPath)) {
+        if (scoped.config.skip !== undefined) skipped = scoped.config.skip;
+        if (!scoped.matcher) continue;
+        const result = scoped.matcher.test(scopedPathOf(scoped, posixPath));
+        if (result.ignored) ignored = true;
+        else if (result.unignored) ignored = false;
+      }
+      return skipped || ignored;
+    },
+    languageFor(relativePath: string): string | undefined {
+      const posixPath = toPosix(relativePath);
+      const ext = path.posix.extname(posixPath).toLowerCase();
+      // Deepest first, so a nested `*` outranks an ancestor's extension mapping
+      for (const { config } of applicable(posixPath).reverse()) {
+        const language = config.languages?.[ext] ?? config.languages?.['*'];
+        if (language) return language;
+      }
+      return undefined;
+    }
+  };
+}
+
+/** The root's own `.codebase-context.toml`; empty when it is missing or invalid. */
+export async function loadRootDirectoryConfig(rootPath: string): Promise<DirectoryConfig> {
+  let text: string;
+  try {
+    text = await fs.readFile(path.join(rootPath, DIRECTORY_CONFIG_FILENAME), 'utf-8');
+  } catch {
+    return {};
+  }
+  try {
+    return parseDirectoryConfig(text);
+  } catch (error) {
+    const message = error instanceof Error ? error.message : String(error);
+    console.warn(`Ignoring ${DIRECTORY_CONFIG_FILENAME}: ${message}`);
+    return {};
+  }
+}
+
+/** Find and parse every `.codebase-context.toml` under the root. Invalid files are skipped. */
+export async function loadDirectoryConfigs(rootPath: string): Promise<DirectoryConfigResolver> {
+  let files: string[] = [];
+  try {
+    files = await glob(`**/${DIRECTORY_CONFIG_FILENAME}`, {
+      cwd: rootPath,
+      dot: true,
+      nodir: true,
+      ignore: DIRECTORY_CONFIG_SCAN_EXCLUDES
+    });
+  } catch {
+    // Unreadable directories: no overrides
+  }
+
+  const configs: Array<{ baseDir: string; source: string; config: DirectoryConfig }> = [];
+  for (const file of files.map(toPosix).sort()) {
+    try {
+      const text = await fs.readFile(path.join(rootPath, file), 'utf-8');
+      const config = parseDirectoryConfig(text);
+      configs.push({ baseDir: path.posix.dirname(file), source: file, config });
+    } catch (error) {
+      console.warn(`Ignoring ${file}: ${error instanceof Error ? error.message : String(error)}`);
+    }
+  }
+
+  return createDirectoryConfigResolver(configs);
+}
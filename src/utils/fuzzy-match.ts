@@ -0,0 +1,122 @@
+/**
+ * Fuzzy identifier matching in the style of fzf/skim: the query must appear as a
+ * case-insensitive subsequence of the candidate, and alignments that hit word boundaries
+ * (`new_calc`, `newCalc`) or run consecutively score higher than scattered ones.
+ */
+
+export type FuzzyMatchTier = 'exact' | 'prefix' | 'fuzzy';
+
+export interface FuzzyMatch {
+  tier: FuzzyMatchTier;
+  /** Alignment quality in [0, 1]; exact matches are always 1 */
+  score: number;
+}
+
+const SCORE_MATCH = 16;
+const PENALTY_GAP_START = 3;
+const PENALTY_GAP_EXTENSION = 1;
+const BONUS_BOUNDARY = 8;
+const BONUS_CAMEL = 7;
+const BONUS_CONSECUTIVE = 4;
+const BONUS_FIRST_CHAR_MULTIPLIER = 2;
+
+/** Higher ranks first; a prefix match always outranks any fuzzy one. */
+const TIER_RANK: Record<FuzzyMatchTier, number> = { exact: 2, prefix: 1, fuzzy: 0 };
+
+function isWordChar(char: string): boolean {
+  return /[A-Za-z0-9]/.test(char);
+}
+
+function boundaryBonus(text: string, index: number): number {
+  if (index === 0) return BONUS_BOUNDARY;
+  const prev = text[index - 1];
+  const current = text[index];
+  if (!isWordChar(prev) && isWordChar(current)) return BONUS_BOUNDARY;
+  if (/[a-z]/.test(prev) && /[A-Z]/.test(current)) return BONUS_CAMEL;
+  if (/[A-Za-z]/.test(prev) && /[0-9]/.test(current)) return BONUS_CAMEL;
+  return 0;
+}
+
+/**
+ * Best subsequence alignment score of `query` in `text`, or null when `query` is not a
+ * subsequence. Dynamic programming over (query char, text position), O(query * text).
+ * As in fzf, a run of consecutive matches keeps the boundary bonus of the char that started it.
+ */
+function alignmentScore(query: string, text: string): number | null {
+  const q = query.toLowerCase();
+  const t = text.toLowerCase();
+  const bonuses = Array.from(text, (_, index) => boundaryBonus(text, index));
+
+  let previous: number[] = [];
+  let previousRunBonus: number[] = [];
+  for (let i = 0; i < q.length; i++) {
+    const current = new Array<number>(t.length).fill(-Infinity);
+    const runBonus = new Array<number>(t.length).fill(0);
+    // Best previous-row score that would reach column j through a gap of at least one char.
+    let carry = -Infinity;
+    for (let j = i; j < t.length; j++) {
+      if (i > 0 && j >= 2) {
+        carry = Math.max(carry - PENALTY_GAP_EXTENSION, previous[j - 2] - PENALTY_GAP_START);
+      }
+      if (q[i] !== t[j]) continue;
+
+      if (i === 0) {
+        current[j] = SCORE_MATCH + bonuses[j] * BONUS_FIRST_CHAR_MULTIPLIER;
+        runBonus[j] = bonuses[j];
+        continue;
+      }
+
+      const inheritedBonus = Math.max(bonuses[j], previousRunBonus[j - 1], BONUS_CONSECUTIVE);
+      const consecutive = previous[j - 1] + SCORE_MATCH + inheritedBonus;
+      const afterGap = carry + SCORE_MATCH + bonuses[j];
+      if (consecutive >= afterGap && consecutive > -Infinity) {
+        current[j] = consecutive;
+        runBonus[j] = inheritedBonus;
+      } else if (afterGap > -Infinity) {
+        current[j] = afterGap;
+        runBonus[j] = bonuses[j];
+      }
+    }
+    previous = current;
+    previousRunBonus = runBonus;
+  }
+
+  const best = previous.length > 0 ? Math.max(...previous) : -Infinity;
+  return best > -Infinity ? best : null;
+}
+
+/** Score of a query of this length matched as one boundary-aligned run. */
+function maxAlignmentScore(length: number): number {
+  return (
+    length * (SCORE_MATCH + BONUS_BOUNDARY) + BONUS_BOUNDARY * (BONUS_FIRST_CHAR_MULTIPLIER - 1)
+  );
+}
+
+/** Match `query` against one identifier. Returns null when it does not match at all. */
+export function fuzzyMatch(query: string, candidate: string): FuzzyMatch | null {
+  const q = query.trim();
+  if (!q || !candidate) return null;
+
+  const lowerQuery = q.toLowerCase();
+  const lowerCandidate = candidate.toLowerCase();
+  if (lowerCandidate === lowerQuery) return { tier: 'exact', score: 1 };
+
+  const raw = alignmentScore(q, candidate);
+  if (raw === null) return null;
+
+  const score = Math.max(0, Math.min(1, raw / maxAlignmentScore(q.length)));
+  return { tier: lowerCandidate.startsWith(lowerQuery) ? 'prefix' : 'fuzzy', score };
+}
+
+/** Sort comparator: tier first, then score, then shorter names, then alphabetically. */
+export function compareFuzzyMatches(
+  a: { name: string; match: FuzzyMatch },
+  b: { name: string; match: FuzzyMatch }
+): number {
+  return (
+    TIER_RANK[b.match.tier] - TIER_RANK[a.match.tier] ||
+    b.match.score - a.match.score ||
+    a.name.length - b.name.length ||
+    a.name.localeCompare(b.name)
+  );
+}
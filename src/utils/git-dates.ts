@@ -1,13 +1,23 @@
 /**
  * Git Date Utility
- * Extracts file commit dates from git history for pattern momentum analysis
+ * Extracts file commit dates, change counts and co-changed files from git history for pattern
+ * momentum analysis, search ranking and related-file suggestions
  */
 
-import { exec } from 'child_process';
+import { exec, execFile } from 'child_process';
 import { promisify } from 'util';
 
 const execAsync = promisify(exec);
-const commitDateCache = new Map<string, Map<string, Date>>();
+const execFileAsync = promisify(execFile);
+
+export interface FileChangeStats {
+  /** Date of the most recent commit touching the file */
+  lastCommit: Date;
+  /** Number of commits touching the file */
+  commitCount: number;
+}
+
+const changeStatsCache = new Map<string, Map<string, FileChangeStats>>();
 
 function normalizeRootPath(rootPath: string): string {
   return rootPath.replace(/\\/g, '/').toLowerCase();
@@ -15,23 +25,35 @@ function normalizeRootPath(rootPath: string): string {
 
 export function clearFileCommitDatesCache(rootPath?: string): void {
   if (rootPath) {
-    commitDateCache.delete(normalizeRootPath(rootPath));
+    changeStatsCache.delete(normalizeRootPath(rootPath));
     return;
   }
 
-  commitDateCache.clear();
+  changeStatsCache.clear();
 }
 
 /**
  * Get the last commit date for each file in the repository.
- * Uses a single git command to efficiently extract all file dates.
  *
  * @param rootPath - Root path of the git repository
  * @returns Map of relative file paths to their last commit date
  */
 export async function getFileCommitDates(rootPath: string): Promise<Map<string, Date>> {
+  const stats = await getFileChangeStats(rootPath);
+  return new Map(Array.from(stats, ([file, { lastCommit }]) => [file, lastCommit]));
+}
+
+/**
+ * Get the last commit date and commit count for each file in the repository.
+ * Uses a single git command for all files; results are cached per root until
+ * `clearFileCommitDatesCache` is called. Outside a git repository the map is empty.
+ *
+ * @param rootPath - Root path of the git repository
+ * @returns Map of relative file paths to their change stats
+ */
+export async function getFileChangeStats(rootPath: string): Promise<Map<string, FileChangeStats>> {
   const cacheKey = normalizeRootPath(rootPath);
-  const cached = commitDateCache.get(cacheKey);
+  const cached = changeStatsCache.get(cacheKey);
   if (cached) {
     if (process.env.CODEBASE_CONTEXT_DEBUG) {
       console.error(`[git-dates] Cache hit for ${cacheKey}`);
@@ -39,7 +61,7 @@ export async function getFileCommitDates(rootPath: string): Promise<Map<string,
     return new Map(cached);
   }
 
-  const fileDates = new Map<string, Date>();
+  const fileStats = new Map<string, FileChangeStats>();
 
   try {
     // Single git command to get all file dates
@@ -61,15 +83,18 @@ export async function getFileCommitDates(rootPath: string): Promise<Map<string,
         const dateStr = trimmed.slice(3);
         currentDate = new Date(dateStr);
       } else if (currentDate) {
-        // File path - only store if we don't already have a date (first occurrence = most recent)
+        // File path - the first occurrence is the most recent commit (git log is newest first)
         const normalizedPath = trimmed.replace(/\\/g, '/');
-        if (!fileDates.has(normalizedPath)) {
-          fileDates.set(normalizedPath, currentDate);
+        const existing = fileStats.get(normalizedPath);
+        if (existing) {
+          existing.commitCount++;
+        } else {
+          fileStats.set(normalizedPath, { lastCommit: currentDate, commitCount: 1 });
         }
       }
     }
 
-    console.error(`[git-dates] Loaded commit dates for ${fileDates.size} files`);
+    console.error(`[git-dates] Loaded commit dates for ${fileStats.size} files`);
   } catch (error) {
     // Not a git repo or git not available - graceful fallback
     const message = error instanceof Error ? error.message : String(error);
@@ -80,8 +105,8 @@ export async function getFileCommitDates(rootPath: string): Promise<Map<string,
     }
   }
 
-  commitDateCache.set(cacheKey, new Map(fileDates));
-  return fileDates;
+  changeStatsCache.set(cacheKey, new Map(fileStats));
+  return fileStats;
 }
 
 /**
@@ -102,3 +127,70 @@ export function calculateTrend(
   if (daysDiff >= 180) return 'Declining';
   return 'Stable';
 }
+
+export interface CoChangeCounts {
+  /** Commits that touched the file, up to the requested limit */
+  commits: number;
+  /** Other files, relative to the root, and how many of those commits also touched them */
+  counts: Map<string, number>;
+}
+
+/**
+ * Files committed together with `file` (relative to `rootPath`), newest commits first.
+ * Commits touching more than `maxFilesPerCommit` files (mass renames, formatting sweeps) say
+ * nothing about a relationship and are skipped. Returns null outside a git repository.
+ */
+export async function getCoChangeCounts(
+  rootPath: string,
+  file: string,
+  options: { maxCommits?: number; maxFilesPerCommit?: number } = {}
+): Promise<CoChangeCounts | null> {
+  const maxCommits = options.maxCommits ?? 500;
+  const maxFilesPerCommit = options.maxFilesPerCommit ?? 50;
+  let stdout: string;
+  try {
+    // --full-diff lists every file of the matching commits, not just the pathspec
+    ({ stdout } = await execFileAsync(
+      'git',
+      [
+        '-c',
+        'core.quotePath=false',
+        'log',
+        '--format=:::',
+        '--name-only',
+        '--relative',
+        '--full-diff',
+        `-n${maxCommits}`,
+        '--',
+        file
+      ],
+      { cwd: rootPath, maxBuffer: 50 * 1024 * 1024 }
+    ));
+  } catch {
+    return null;
+  }
+
+  const counts = new Map<string, number>();
+  let commits = 0;
+  const fold = (files: string[]) => {
+    if (files.length === 0) return;
+    commits++;
+    if (files.length > maxFilesPerCommit) return;
+    for (const other of files) {
+      if (other !== file) counts.set(other, (counts.get(other) ?? 0) + 1);
+    }
+  };
+
+  let current: string[] = [];
+  for (const line of stdout.split('\n')) {
+    const trimmed = line.trim();
+    if (trimmed === ':::') {
+      fold(current);
+      current = [];
+    } else if (trimmed) {
+      current.push(trimmed.replace(/\\/g, '/'));
+    }
+  }
+  fold(current);
+  return { commits, counts };
+}
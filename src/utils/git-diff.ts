@@ -0,0 +1,256 @@
+/**
+ * Changed files and line ranges from `git diff`, for scoping queries to what a branch or a
+ * working copy touched. Paths are relative to the root the diff runs in.
+ */
+
+import { execFile } from 'child_process';
+import { promises as fs } from 'fs';
+import path from 'path';
+import { promisify } from 'util';
+import { CODEBASE_CONTEXT_DIRNAME } from '../constants/codebase-context.js';
+import type { LineRange } from '../types/index.js';
+
+const execFileAsync = promisify(execFile);
+
+const GIT_MAX_BUFFER = 50 * 1024 * 1024;
+
+export type ChangedFileStatus = 'added' | 'modified' | 'deleted' | 'renamed';
+
+export interface ChangedFile {
+  /** Path on the head side (the old path for deleted files), relative to the root */
+  file: string;
+  status: ChangedFileStatus;
+  /** Old path of a renamed file */
+  previousFile?: string;
+  /**
+   * Changed lines on the head side. A pure deletion is recorded as the one line it happened
+   * after, so the symbol that lost lines still overlaps the diff. Empty for deleted files.
+   */
+  ranges: LineRange[];
+}
+
+export interface GitDiffSpec {
+  /** Ref to diff from (default: HEAD) */
+  base?: string;
+  /** Ref to diff to; omitted means the working tree, including untracked files */
+  head?: string;
+}
+
+export interface GitDiff {
+  base: string;
+  /** null when the head side is the working tree */
+  head: string | null;
+  files: ChangedFile[];
+}
+
+export type GitDiffErrorCode = 'not_a_git_repository' | 'invalid_ref' | 'git_failed';
+
+export class GitDiffError extends Error {
+  constructor(
+    readonly code: GitDiffErrorCode,
+    message: string
+  ) {
+    super(message);
+    this.name = 'GitDiffError';
+  }
+}
+
+async function git(rootPath: string, args: string[]): Promise<string> {
+  const { stdout } = await execFileAsync('git', ['-c', 'core.quotePath=false', ...args], {
+    cwd: rootPath,
+    maxBuffer: GIT_MAX_BUFFER
+  });
+  return stdout;
+}
+
+function gitErrorMessage(error: unknown): string {
+  const stderr = (error as { stderr?: unknown }).stderr;
+  if (typeof stderr === 'string' && stderr.trim()) return stderr.trim();
+  return error instanceof Error ? error.message : String(error);
+}
+
+async function ensureGitRepository(rootPath: string): Promise<void> {
+  try {
+    await git(rootPath, ['rev-parse', '--is-inside-work-tree']);
+  } catch (error) {
+    throw new GitDiffError(
+      'not_a_git_repository',
+      `Not a git repository: ${rootPath} (${gitErrorMessage(error)})`
+    );
+  }
+}
+
+async function verifyRef(rootPath: string, ref: string): Promise<void> {
+  // A leading dash would be read as an option, not a ref
+  if (!ref || ref.startsWith('-')) {
+    throw new GitDiffError('invalid_ref', `Invalid git ref: '${ref}'`);
+  }
+  try {
+    await git(rootPath, ['rev-parse', '--verify', '--quiet', `${ref}^{commit}`]);
+  } catch {
+    throw new GitDiffError('invalid_ref', `Unknown git ref: '${ref}'`);
+  }
+}
+
+async function commitOf(rootPath: string, ref: string): Promise<string | null> {
+  if (!ref || ref.startsWith('-')) return null;
+  try {
+    const commit = await git(rootPath, ['rev-parse', '--verify', '--quiet', `${ref}^{commit}`]);
+    return commit.trim() || null;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * The commits HEAD and the refs of `spec` resolve to, as one string for keying cached results:
+ * it changes on every commit, checkout or moved ref. Refs that do not resolve, and roots
+ * outside git, read as `-`.
+ */
+export async function gitStateKey(rootPath: string, spec?: GitDiffSpec | null): Promise<string> {
+  const refs = ['HEAD', ...(spec ? [spec.base?.trim() || 'HEAD', spec.head?.trim() ?? ''] : [])];
+  const commits = await Promise.all(refs.map((ref) => commitOf(rootPath, ref)));
+  return commits.map((commit) => commit ?? '-').join(':');
+}
+
+/** Undo git's C-style quoting of paths with control characters or quotes. */
+function unquoteGitPath(raw: string): string {
+  if (!raw.startsWith('"')) return raw;
+  try {
+    return JSON.parse(raw) as string;
+  } catch {
+    return raw.slice(1, -1);
+  }
+}
+
+function stripSidePrefix(raw: string): string | null {
+  const unquoted = unquoteGitPath(raw.trim());
+  if (unquoted === '/dev/null') return null;
+  return unquoted.replace(/^[ab]\//, '');
+}
+
+const HUNK_HEADER = /^@@ -\d+(?:,\d+)? \+(\d+)(?:,(\d+))? @@/;
+
+/** Parse `git diff --unified=0` output into changed files with head-side line ranges. */
+export function parseUnifiedDiff(diff: string): ChangedFile[] {
+  const files: ChangedFile[] = [];
+  let oldPath: string | null = null;
+  let renamedFrom: string | null = null;
+  let current: ChangedFile | null = null;
+
+  for (const line of diff.split('\n')) {
+    if (line.startsWith('diff --git ')) {
+      current = null;
+      oldPath = null;
+      renamedFrom = null;
+    } else if (line.startsWith('rename from ')) {
+      renamedFrom = unquoteGitPath(line.slice('rename from '.length));
+    } else if (line.startsWith('rename to ') && renamedFrom !== null) {
+      // Pure renames have no ---/+++ lines, so the entry is created here
+      const file = unquoteGitPath(line.slice('rename to '.length));
+      current = { file, status: 'renamed', previousFile: renamedFrom, ranges: [] };
+      files.push(current);
+    } else if (line.startsWith('--- ') && !current) {
+      oldPath = stripSidePrefix(line.slice(4));
+    } else if (line.startsWith('+++ ') && !current) {
+      const newPath = stripSidePrefix(line.slice(4));
+      if (newPath === null) {
+        current = oldPath ? { file: oldPath, status: 'deleted', ranges: [] } : null;
+      } else {
+        current = { file: newPath, status: oldPath === null ? 'added' : 'modified', ranges: [] };
+      }
+      if (current) files.push(current);
+    } else if (current && current.status !== 'deleted') {
+      const hunk = HUNK_HEADER.exec(line);
+      if (!hunk) continue;
+      const start = Number(hunk[1]);
+      const count = hunk[2] === undefined ? 1 : Number(hunk[2]);
+      current.ranges.push(
+        count === 0
+          ? { startLine: Math.max(1, start), endLine: Math.max(1, start) }
+          : { startLine: start, endLine: start + count - 1 }
+      );
+    }
+  }
+
+  return files;
+}
+
+function countLines(content: string): number {
+  if (!content) return 0;
+  const lines = content.split('\n').length;
+  return content.endsWith('\n') ? lines - 1 : lines;
+}
+
+/**
+ * Diff `base` (default HEAD) against `head`, or against the working tree when `head` is
+ * omitted. Working-tree diffs include untracked, non-ignored files as wholly added.
+ * Throws GitDiffError outside a git repository or for refs that do not name a commit.
+ */
+export async function getGitDiff(rootPath: string, spec: GitDiffSpec = {}): Promise<GitDiff> {
+  const base = spec.base?.trim() || 'HEAD';
+  const head = spec.head?.trim() || null;
+
+  await ensureGitRepository(rootPath);
+  await verifyRef(rootPath, base);
+  if (head) await verifyRef(rootPath, head);
+
+  let files: ChangedFile[];
+  try {
+    const output = await git(rootPath, [
+      'diff',
+      '--unified=0',
+      '--no-color',
+      '--no-ext-diff',
+      '--relative',
+      '-M',
+      base,
+      ...(head ? [head] : []),
+      '--'
+    ]);
+    files = parseUnifiedDiff(output);
+
+    if (!head) {
+      const untracked = await git(rootPath, ['ls-files', '--others', '--exclude-standard', '-z']);
+      for (const file of untracked.split('\0').filter(Boolean)) {
+        const content = await fs.readFile(path.join(rootPath, file), 'utf-8').catch(() => '');
+        const lines = countLines(content);
+        files.push({
+          file,
+          status: 'added',
+          ranges: lines > 0 ? [{ startLine: 1, endLine: lines }] : []
+        });
+      }
+    }
+  } catch (error) {
+    throw new GitDiffError('git_failed', `git diff failed: ${gitErrorMessage(error)}`);
+  }
+
+  // The index directory is usually untracked; it is never part of the change under review
+  const ownFiles = `${CODEBASE_CONTEXT_DIRNAME}/`;
+  return {
+    base,
+    head,
+    files: files
+      .filter((file) => !file.file.startsWith(ownFiles))
+      .sort((a, b) => a.file.localeCompare(b.file))
+  };
+}
+
+/**
+ * Contents of `file` on the head side of a diff: the working tree copy when `head` is null,
+ * otherwise the blob at that ref. Returns null when the file does not exist there.
+ */
+export async function readDiffHeadFile(
+  rootPath: string,
+  file: string,
+  head: string | null
+): Promise<string | null> {
+  try {
+    return head
+      ? await git(rootPath, ['show', `${head}:./${file}`])
+      : await fs.readFile(path.join(rootPath, file), 'utf-8');
+  } catch {
+    return null;
+  }
+}
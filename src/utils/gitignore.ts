@@ -0,0 +1,171 @@
+/**
+ * Git-style ignore matching for the file walker.
+ * Mirrors how git itself resolves ignores: the global excludes file (`core.excludesFile`, by
+ * default `$XDG_CONFIG_HOME/git/ignore`) and `.git/info/exclude` apply at the root, and every
+ * nested `.gitignore` applies to its own directory, with deeper files able to override (or
+ * negate) rules from their ancestors.
+ */
+
+import { execFile } from 'child_process';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { promisify } from 'util';
+import { glob, type IgnoreLike, type Path } from 'glob';
+import ignore from 'ignore';
+
+type IgnoreInstance = ReturnType<typeof ignore.default>;
+
+const execFileAsync = promisify(execFile);
+
+interface ScopedMatcher {
+  /** Directory the rules are relative to, posix, '' for the root */
+  baseDir: string;
+  matcher: IgnoreInstance;
+}
+
+export interface GitignoreMatcher {
+  /** Files whose rules were loaded, relative to the root (global excludes are absolute) */
+  sources: string[];
+  /** True when a root-relative path is ignored by any applicable ignore file */
+  ignores(relativePath: string): boolean;
+}
+
+// Never look for nested ignore files inside these, they are excluded by the walker anyway
+const NESTED_GITIGNORE_SCAN_EXCLUDES = ['**/node_modules/**', '**/.git/**'];
+
+function toPosix(filePath: string): string {
+  return filePath.replace(/\\/g, '/');
+}
+
+async function readIgnoreFile(filePath: string): Promise<string | null> {
+  try {
+    return await fs.readFile(filePath, 'utf-8');
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * The excludes file git reads for `rootPath`: `core.excludesFile` from any config scope, else
+ * the XDG default, which is also used when git is not installed.
+ */
+async function globalExcludesPath(rootPath: string): Promise<string> {
+  try {
+    const { stdout } = await execFileAsync(
+      'git',
+      ['config', '--path', '--get', 'core.excludesFile'],
+      { cwd: rootPath }
+    );
+    const configured = stdout.trim();
+    if (configured) return path.resolve(rootPath, configured);
+  } catch {
+    // Unset (git exits with status 1) or git unavailable
+  }
+  const xdgConfigHome = process.env.XDG_CONFIG_HOME || path.join(os.homedir(), '.config');
+  return path.join(xdgConfigHome, 'git', 'ignore');
+}
+
+function evaluate(matchers: ScopedMatcher[], relativePath: string): boolean {
+  let ignored = false;
+
+  for (const { baseDir, matcher } of matchers) {
+    let scopedPath: string;
+    if (!baseDir) {
+      scopedPath = relativePath;
+    } else if (relativePath.startsWith(`${baseDir}/`)) {
+      scopedPath = relativePath.slice(baseDir.length + 1);
+    } else {
+      continue;
+    }
+    if (!scopedPath) continue;
+
+    const result = matcher.test(scopedPath);
+    if (result.ignored) ignored = true;
+    else if (result.unignored) ignored = false;
+  }
+
+  return ignored;
+}
+
+export async function loadGitignoreMatcher(rootPath: string): Promise<GitignoreMatcher> {
+  const matchers: ScopedMatcher[] = [];
+  const sources: string[] = [];
+
+  const rootMatcher = ignore.default();
+  let hasRootRules = false;
+
+  const globalExcludes = await globalExcludesPath(rootPath);
+  const rootSources: Array<{ absolute: string; label: string }> = [
+    { absolute: globalExcludes, label: globalExcludes },
+    { absolute: path.join(rootPath, '.git', 'info', 'exclude'), label: '.git/info/exclude' },
+    { absolute: path.join(rootPath, '.gitignore'), label: '.gitignore' }
+  ];
+
+  for (const source of rootSources) {
+    const content = await readIgnoreFile(source.absolute);
+    if (content === null) continue;
+    rootMatcher.add(content);
+    sources.push(source.label);
+    hasRootRules = true;
+  }
+
+  if (hasRootRules) {
+    matchers.push({ baseDir: '', matcher: rootMatcher });
+  }
+
+  let nested: string[] = [];
+  try {
+    nested = await glob('**/.gitignore', {
+      cwd: rootPath,
+      dot: true,
+      nodir: true,
+      ignore: NESTED_GITIGNORE_SCAN_EXCLUDES
+    });
+  } catch {
+    // Unreadable directories: fall back to root-level rules only
+  }
+
+  const nestedByDepth = nested
+    .map(toPosix)
+    .filter((file) => file !== '.gitignore')
+    .sort((a, b) => a.split('/').length - b.split('/').length || a.localeCompare(b));
+
+  for (const file of nestedByDepth) {
+    const baseDir = path.posix.dirname(file);
+    // Git never reads ignore files inside directories that are already ignored
+    if (evaluate(matchers, `${baseDir}/`)) continue;
+
+    const content = await readIgnoreFile(path.join(rootPath, file));
+    if (content === null) continue;
+    matchers.push({ baseDir, matcher: ignore.default().add(content) });
+    sources.push(file);
+  }
+
+  return {
+    sources,
+    ignores: (relativePath: string) => evaluate(matchers, toPosix(relativePath))
+  };
+}
+
+/**
+ * glob `ignore` that applies `base` and also stops the walk at directories `matcher` ignores,
+ * so their contents are never read. `descend` keeps walking an ignored directory (root-relative,
+ * posix) when something below it may still be indexed.
+ */
+export function gitignoreWalkIgnore(
+  rootPath: string,
+  matcher: GitignoreMatcher,
+  base: IgnoreLike,
+  descend: (relativeDir: string) => boolean = () => false
+): IgnoreLike {
+  return {
+    ignored: (p: Path) => base.ignored?.(p) ?? false,
+    childrenIgnored: (p: Path) => {
+      if (base.childrenIgnored?.(p)) return true;
+      const relativeDir = toPosix(path.relative(rootPath, p.fullpath()));
+      if (!relativeDir || !matcher.ignores(`${relativeDir}/`)) return false;
+      return !descend(relativeDir);
+    }
+  };
+}
@@ -3,7 +3,9 @@
  * Determines file types and languages based on extension and content
  */
 
+import { promises as fs } from 'fs';
 import path from 'path';
+import { sniffEncoding } from './text-decoding.js';
 
 // Map of file extensions to languages
 const extensionToLanguage: Record<string, string> = {
@@ -152,12 +154,208 @@ const codeExtensions = new Set([
   '.hpp'
 ]);
 
+/** Extensions with no symbol extraction that the default scan still indexes as text only. */
+export const TEXT_ONLY_EXTENSIONS = [
+  '.sql',
+  '.sh',
+  '.bash',
+  '.zsh',
+  '.yaml',
+  '.yml',
+  '.toml',
+  '.ini',
+  '.cfg',
+  '.conf',
+  '.properties',
+  '.graphql',
+  '.gql',
+  '.proto'
+];
+
+// Shebang interpreters, version suffixes stripped (python3.11 -> python)
+const interpreterToLanguage: Record<string, string> = {
+  python: 'python',
+  node: 'javascript',
+  nodejs: 'javascript',
+  deno: 'typescript',
+  bun: 'typescript',
+  'ts-node': 'typescript',
+  tsx: 'typescript',
+  sh: 'shellscript',
+  bash: 'shellscript',
+  dash: 'shellscript',
+  ksh: 'shellscript',
+  zsh: 'shellscript',
+  ruby: 'ruby',
+  php: 'php',
+  pwsh: 'powershell'
+};
+
+// Editor modeline names that differ from our language ids
+const modelineAliases: Record<string, string> = {
+  py: 'python',
+  js: 'javascript',
+  ts: 'typescript',
+  sh: 'shellscript',
+  bash: 'shellscript',
+  zsh: 'shellscript',
+  rb: 'ruby',
+  rs: 'rust',
+  golang: 'go',
+  'c++': 'cpp'
+};
+
+const KNOWN_LANGUAGES = new Set(Object.values(extensionToLanguage));
+
+// Suffixes of template files that keep the real extension in front (config.h.in, setup.py.in)
+const TEMPLATE_SUFFIXES = new Set(['.in']);
+
+type LanguageOverride = (absolutePath: string) => string | undefined;
+
+// Per-root overrides (from directory configs), consulted before the extension
+const languageOverrides = new Map<string, LanguageOverride>();
+
+/** Force languages for absolute paths under `rootPath` until reset with `null`. */
+export function setLanguageOverride(rootPath: string, override: LanguageOverride | null): void {
+  const root = path.resolve(rootPath);
+  if (override) languageOverrides.set(root, override);
+  else languageOverrides.delete(root);
+}
+
+function overriddenLanguage(filePath: string): string | undefined {
+  if (languageOverrides.size === 0 || !path.isAbsolute(filePath)) return undefined;
+  const resolved = path.resolve(filePath);
+  let owner: string | null = null;
+  for (const root of languageOverrides.keys()) {
+    if (!resolved.startsWith(root + path.sep)) continue;
+    if (!owner || root.length > owner.length) owner = root;
+  }
+  return owner ? languageOverrides.get(owner)?.(resolved) : undefined;
+}
+
+/** Bytes read from the start of a file to sniff its interpreter and spot binary content. */
+export const CONTENT_SNIFF_BYTES = 1024;
+
+/** Bytes sampled to judge line lengths; a minified bundle fills them with one or two lines. */
+export const MINIFIED_SNIFF_BYTES = 4096;
+
+/** Average line length above which a sample reads as minified or generated output. */
+export const MINIFIED_AVERAGE_LINE_LENGTH = 500;
+
+function interpreterLanguage(shebang: string): string | null {
+  const words = shebang.slice(2).trim().split(/\s+/);
+  let command = path.posix.basename(words[0] ?? '');
+  if (command === 'env') {
+    command = words.slice(1).find((word) => !word.startsWith('-') && !word.includes('=')) ?? '';
+  }
+  const interpreter = command.toLowerCase().replace(/[\d.]+$/, '');
+  return interpreterToLanguage[interpreter] ?? null;
+}
+
+function modelineLanguage(line: string): string | null {
+  const match =
+    line.match(/-\*-\s*(?:mode:\s*)?([\w+-]+)\s*(?:;.*)?-\*-/i) ??
+    line.match(/\bvim?:.*\b(?:ft|filetype|syntax)=([\w+-]+)/i);
+  if (!match) return null;
+  const name = match[1].toLowerCase();
+  const language = modelineAliases[name] ?? name;
+  return KNOWN_LANGUAGES.has(language) ? language : null;
+}
+
 /**
- * Detect language from file path
+ * Guess the language of a file whose extension says nothing: a shebang first, then an editor
+ * modeline or an unmistakable opening tag. Returns null rather than guessing further.
  */
-export function detectLanguage(filePath: string): string {
-  const ext = path.extname(filePath).toLowerCase();
-  return extensionToLanguage[ext] || 'plaintext';
+export function detectLanguageFromContent(content: string): string | null {
+  const lines = content.replace(/^\uFEFF/, '').split('\n', 5);
+  const first = (lines[0] ?? '').trim();
+
+  if (first.startsWith('#!')) {
+    const language = interpreterLanguage(first);
+    if (language) return language;
+  }
+  if (/^<\?php\b/i.test(first)) return 'php';
+  if (/^<\?xml\b/i.test(first)) return 'xml';
+  if (/^<!doctype html\b|^<html\b/i.test(first)) return 'html';
+
+  for (const line of lines) {
+    const language = modelineLanguage(line);
+    if (language) return language;
+  }
+  return null;
+}
+
+/** True when the sample contains a NUL byte, which text files never do. */
+export function looksBinary(sample: Buffer): boolean {
+  return sample.subarray(0, CONTENT_SNIFF_BYTES).includes(0);
+}
+
+/**
+ * True when the sample averages more than `MINIFIED_AVERAGE_LINE_LENGTH` characters per line.
+ * Samples shorter than a KB are never judged minified, so small one-liners are kept.
+ */
+export function looksMinified(sample: string): boolean {
+  if (sample.length < CONTENT_SNIFF_BYTES) return false;
+  const lines = sample.split('\n').length;
+  return sample.length / lines > MINIFIED_AVERAGE_LINE_LENGTH;
+}
+
+/** C++-only syntax near the top of a `.h` file, which is otherwise read as C. */
+const CPP_HEADER_PATTERN = /^\s*(?:class|namespace|template\s*<)\b|^\s*(?:public|private|protected):/m;
+
+/**
+ * Detect language from file path. Directory-config overrides win; template suffixes like `.in`
+ * are looked through, and when the extension is unknown or missing, `content` (if given) is
+ * inspected instead. A `.h` header whose content shows C++ syntax is C++.
+ */
+export function detectLanguage(filePath: string, content?: string): string {
+  const overridden = overriddenLanguage(filePath);
+  if (overridden) return overridden;
+
+  let ext = path.extname(filePath).toLowerCase();
+  if (TEMPLATE_SUFFIXES.has(ext)) {
+    ext = path.extname(filePath.slice(0, -ext.length)).toLowerCase();
+  }
+  const byExtension = extensionToLanguage[ext];
+  if (ext === '.h' && content !== undefined && CPP_HEADER_PATTERN.test(content)) return 'cpp';
+  if (byExtension) return byExtension;
+  return (content !== undefined && detectLanguageFromContent(content)) || 'plaintext';
+}
+
+export interface FileSniff {
+  binary: boolean;
+  language: string;
+  minified: boolean;
+}
+
+/**
+ * Read the first few KB of a file and report whether it is binary (NUL in the first KB, unless
+ * it is UTF-16), whether it looks minified and, when it is text, the language its path and
+ * content suggest.
+ */
+export async function sniffFile(filePath: string): Promise<FileSniff> {
+  const handle = await fs.open(filePath, 'r');
+  try {
+    const sample = Buffer.alloc(MINIFIED_SNIFF_BYTES);
+    const { bytesRead } = await handle.read(sample, 0, MINIFIED_SNIFF_BYTES, 0);
+    const head = sample.subarray(0, bytesRead);
+    // UTF-16 text is full of zero bytes; only other encodings are judged by them
+    const encoding = sniffEncoding(head);
+    const utf16 = encoding === 'utf-16le' || encoding === 'utf-16be';
+    if (!utf16 && looksBinary(head)) {
+      return { binary: true, language: 'plaintext', minified: false };
+    }
+    const text = utf16
+      ? new TextDecoder(encoding).decode(head)
+      : head.toString('utf8').replace(/^\uFEFF/, '');
+    return {
+      binary: false,
+      language: detectLanguage(filePath, text.slice(0, CONTENT_SNIFF_BYTES)),
+      minified: looksMinified(text)
+    };
+  } finally {
+    await handle.close();
+  }
 }
 
 /**
@@ -177,16 +375,24 @@ export function isBinaryFile(filePath: string): boolean {
 }
 
 /**
- * Check if file is a test file
+ * Check if file is a test file by path convention: `*.test.*` / `*.spec.*`, a `test/`, `tests/`,
+ * `__tests__/` or `e2e/` directory, Go and Python `_test` suffixes, Python `test_` modules and
+ * JVM/.NET `*Test` / `*Tests` classes. Pass paths relative to the project root.
  */
 export function isTestFile(filePath: string): boolean {
-  const lowerPath = filePath.toLowerCase();
+  const normalized = `/${filePath.replace(/\\/g, '/')}`;
+  const lowerPath = normalized.toLowerCase();
+  const fileName = path.basename(normalized);
   return (
     lowerPath.includes('.spec.') ||
     lowerPath.includes('.test.') ||
     lowerPath.includes('__tests__') ||
     lowerPath.includes('/test/') ||
-    lowerPath.includes('/tests/')
+    lowerPath.includes('/tests/') ||
+    lowerPath.includes('/e2e/') ||
+    /_test\.(?:go|py)$/.test(fileName) ||
+    /^test_\w+\.py$/.test(fileName) ||
+    /\wTests?\.(?:java|kt|cs)$/.test(fileName)
   );
 }
 
@@ -0,0 +1,117 @@
+/**
+ * Worker threads that parse files for symbol extraction, so a cold index parses on every core
+ * instead of one. web-tree-sitter parsers and grammars live per isolate, so each worker loads
+ * its own. Files wait in one shared queue and go to whichever worker is idle first; workers
+ * start on demand, up to `size`.
+ *
+ * The pool never changes results, only where they are computed: when a worker cannot start or
+ * dies, every file it has not answered, and every later one, is handed back and parsed on the
+ * main thread.
+ */
+
+import { Worker } from 'worker_threads';
+import type { SymbolParsePool, TreeSitterSymbolExtraction } from './tree-sitter.js';
+
+/** Below this many files a cold index parses on the main thread: workers take time to start. */
+export const MIN_FILES_FOR_PARSE_WORKERS = 200;
+
+export interface ParseRequest {
+  content: string;
+  language: string;
+}
+
+export interface ParseResponse {
+  /** `undefined` when the worker could not parse the file and hands it back */
+  extraction: TreeSitterSymbolExtraction | null | undefined;
+}
+
+type ParseResult = TreeSitterSymbolExtraction | null | undefined;
+
+interface ParseTask extends ParseRequest {
+  resolve: (result: ParseResult) => void;
+}
+
+interface PoolWorker {
+  worker: Worker;
+  task: ParseTask | null;
+}
+
+// Running from source (tests, `pnpm dev`), the worker is TypeScript and needs tsx's loader
+const FROM_SOURCE = import.meta.url.endsWith('.ts');
+const WORKER_URL = new URL(
+  FROM_SOURCE ? './parse-worker.ts' : './parse-worker.js',
+  import.meta.url
+);
+const WORKER_EXEC_ARGV = FROM_SOURCE ? ['--import', 'tsx'] : [];
+
+export class ParsePool implements SymbolParsePool {
+  readonly size: number;
+  /** Files parsed by a worker */
+  parsed = 0;
+
+  private readonly workers: PoolWorker[] = [];
+  private readonly queue: ParseTask[] = [];
+  private stopped = false;
+
+  constructor(size: number) {
+    this.size = Math.max(1, Math.floor(size));
+  }
+
+  extract(content: string, language: string): Promise<ParseResult> {
+    if (this.stopped) return Promise.resolve(undefined);
+    return new Promise((resolve) => {
+      this.queue.push({ content, language, resolve });
+      this.dispatch();
+    });
+  }
+
+  private dispatch(): void {
+    while (this.queue.length > 0 && !this.stopped) {
+      let idle = this.workers.find((candidate) => candidate.task === null);
+      if (!idle && this.workers.length < this.size) idle = this.spawn();
+      if (!idle) return;
+
+      const task = this.queue.shift()!;
+      idle.task = task;
+      idle.worker.postMessage({
+        content: task.content,
+        language: task.language
+      } satisfies ParseRequest);
+    }
+  }
+
+  private spawn(): PoolWorker {
+    const slot: PoolWorker = {
+      worker: new Worker(WORKER_URL, { execArgv: WORKER_EXEC_ARGV }),
+      task: null
+    };
+    slot.worker.on('message', ({ extraction }: ParseResponse) => {
+      const task = slot.task;
+      slot.task = null;
+      if (extraction !== undefined) this.parsed++;
+      task?.resolve(extraction);
+      this.dispatch();
+    });
+    slot.worker.on('error', (error) => {
+      if (process.env.CODEBASE_CONTEXT_DEBUG) {
+        console.error('[parse-pool] worker failed; parsing on the main thread:', error);
+      }
+      void this.close();
+    });
+    slot.worker.on('exit', () => void this.close());
+    this.workers.push(slot);
+    return slot;
+  }
+
+  /** Stop every worker; files still queued or in flight are handed back. */
+  async close(): Promise<void> {
+    if (this.stopped) return;
+    this.stopped = true;
+    for (const task of this.queue.splice(0)) task.resolve(undefined);
+    for (const slot of this.workers) {
+      slot.task?.resolve(undefined);
+      slot.task = null;
+    }
+    await Promise.all(this.workers.map((slot) => slot.worker.terminate()));
+  }
+}
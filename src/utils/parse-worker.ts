@@ -0,0 +1,19 @@
+/**
+ * Worker thread entry for `ParsePool`: extracts the symbols of each file it is sent with its own
+ * web-tree-sitter Parser and grammars, which are per isolate.
+ */
+
+import { parentPort } from 'worker_threads';
+import { extractTreeSitterSymbols } from './tree-sitter.js';
+import type { ParseRequest, ParseResponse } from './parse-pool.js';
+
+const port = parentPort;
+if (port) {
+  port.on('message', ({ content, language }: ParseRequest) => {
+    extractTreeSitterSymbols(content, language).then(
+      (extraction) => port.postMessage({ extraction } satisfies ParseResponse),
+      // Unexpected failures hand the file back, so the main thread parses it instead
+      () => port.postMessage({ extraction: undefined } satisfies ParseResponse)
+    );
+  });
+}
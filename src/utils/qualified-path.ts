@@ -0,0 +1,43 @@
+/**
+ * Fully-qualified symbol paths: the enclosing scopes captured during extraction joined with the
+ * symbol name, e.g. `geometry::Circle::area` in Rust or `com.example.calc.Calculator.add` in
+ * Java. Rust module segments come from the file path (`src/geometry/mod.rs` is `geometry`);
+ * Java and Go packages come from the source. Other languages qualify within the file only.
+ */
+
+import path from 'path';
+import type { TreeSitterSymbol } from './tree-sitter.js';
+
+const DOUBLE_COLON_LANGUAGES = new Set(['c', 'cpp', 'rust']);
+const RUST_CRATE_ROOTS = new Set(['lib', 'main', 'mod']);
+
+export function qualifiedPathSeparator(language: string): string {
+  return DOUBLE_COLON_LANGUAGES.has(language) ? '::' : '.';
+}
+
+/** `src/geometry/shapes.rs` is `geometry::shapes`; `src/lib.rs` and `src/main.rs` are the crate. */
+export function rustModulePath(filePath: string): string[] {
+  const segments = filePath.replace(/\\/g, '/').split('/').filter(Boolean);
+  const srcIndex = segments.lastIndexOf('src');
+  const relative = srcIndex >= 0 ? segments.slice(srcIndex + 1) : segments.slice(-1);
+  const stem = path.posix.basename(relative.pop() ?? '', '.rs');
+  return RUST_CRATE_ROOTS.has(stem) ? relative : [...relative, stem];
+}
+
+export function qualifiedSymbolPath(
+  symbol: Pick<TreeSitterSymbol, 'name' | 'scope' | 'qualifier'>,
+  language: string,
+  filePath: string
+): string {
+  const scope = symbol.scope ?? (symbol.qualifier ? [symbol.qualifier] : []);
+  const modulePath = language === 'rust' ? rustModulePath(filePath) : [];
+  return [...modulePath, ...scope, symbol.name].join(qualifiedPathSeparator(language));
+}
+
+/** Segments of a qualified path, splitting on both `::` and `.` so either style matches. */
+export function qualifiedPathSegments(qualifiedPath: string): string[] {
+  return qualifiedPath
+    .split(/::|\./)
+    .map((segment) => segment.trim())
+    .filter(Boolean);
+}
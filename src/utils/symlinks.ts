@@ -0,0 +1,97 @@
+/**
+ * Symlink handling for the indexer's file scan.
+ *
+ * Directory symlinks are not followed by default (glob does not crawl them), which rules out
+ * cycles and double indexing. With `followSymlinks`, links are followed unless their target is
+ * inside the root (already indexed under its real path) or was already reached through another
+ * link, so self-referential links terminate. Symlinked files are deduplicated the same way.
+ */
+
+import { promises as fs, realpathSync } from 'fs';
+import path from 'path';
+import { Ignore, type IgnoreLike, type Path } from 'glob';
+
+/**
+ * `child` relative to `parent` when it lies strictly below it, else null (for `parent` itself
+ * too). Only a whole `..` segment steps up: `..foo/bar.ts` is a file inside.
+ */
+export function relativeInside(parent: string, child: string): string | null {
+  const relative = path.relative(parent, child);
+  if (!relative || path.isAbsolute(relative)) return null;
+  if (relative === '..' || relative.startsWith(`..${path.sep}`)) return null;
+  return relative;
+}
+
+/** True when `child` is `parent` or lies below it. */
+function isInside(parent: string, child: string): boolean {
+  return path.relative(parent, child) === '' || relativeInside(parent, child) !== null;
+}
+
+function realpathOr(filePath: string): string {
+  try {
+    return realpathSync(filePath);
+  } catch {
+    return filePath;
+  }
+}
+
+/**
+ * Paths under `rootPath` that resolve through a symlink, and where their target points.
+ * A path counts as linked when its real location differs from where it sits under the root,
+ * so a root that is itself a symlink (for example `/tmp` on macOS) does not count.
+ */
+export class SymlinkResolver {
+  private readonly rootPath: string;
+  private readonly realRoot: string;
+  /** First path each real location outside the root was reached through */
+  private readonly firstPathByTarget = new Map<string, string>();
+
+  constructor(rootPath: string) {
+    this.rootPath = path.resolve(rootPath);
+    this.realRoot = realpathOr(this.rootPath);
+    this.firstPathByTarget.set(this.realRoot, this.rootPath);
+  }
+
+  /**
+   * False for a path reached through a symlink whose target is inside the root or was first
+   * reached through a different path; true otherwise.
+   */
+  private accepts(fullPath: string, realPath: string): boolean {
+    const expected = path.join(this.realRoot, path.relative(this.rootPath, fullPath));
+    if (realPath === expected) return true;
+    if (isInside(this.realRoot, realPath)) return false;
+    const first = this.firstPathByTarget.get(realPath);
+    if (first === undefined) {
+      this.firstPathByTarget.set(realPath, fullPath);
+      return true;
+    }
+    return first === fullPath;
+  }
+
+  /** Whether a scanned file should be indexed under `filePath` (an absolute path). */
+  async acceptsFile(filePath: string): Promise<boolean> {
+    let realPath: string;
+    try {
+      realPath = await fs.realpath(filePath);
+    } catch {
+      return false;
+    }
+    return this.accepts(path.resolve(filePath), realPath);
+  }
+
+  /**
+   * glob `ignore` for `follow: true` scans: applies `exclude` and stops descending into
+   * directories whose target is indexed or crawled elsewhere.
+   */
+  followIgnore(exclude: string[]): IgnoreLike {
+    const excluded = new Ignore(exclude, { platform: process.platform });
+    return {
+      ignored: (p: Path) => excluded.ignored(p),
+      childrenIgnored: (p: Path) => {
+        if (excluded.childrenIgnored(p)) return true;
+        const realPath = p.realpathSync()?.fullpath() ?? p.fullpath();
+        return !this.accepts(p.fullpath(), realPath);
+      }
+    };
+  }
+}
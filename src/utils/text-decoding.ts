@@ -0,0 +1,180 @@
+/**
+ * Source bytes to parser input. Every file is decoded the same way before parsing or line
+ * counting: a BOM is stripped and picks the encoding (UTF-8, UTF-16 LE/BE), BOM-less UTF-16 is
+ * recognized by its zero bytes, invalid UTF-8 falls back to Windows-1252, and CRLF or lone CR
+ * line endings become LF. Line and column numbers are therefore the same whichever way a file
+ * was saved. The decoded text with its original line endings is kept alongside for callers
+ * that must reproduce a span exactly as written.
+ */
+
+import { promises as fs } from 'fs';
+
+export type SourceEncoding = 'utf-8' | 'utf-16le' | 'utf-16be' | 'windows-1252';
+export type LineEnding = 'lf' | 'crlf' | 'cr' | 'mixed' | 'none';
+
+export interface DecodedSource {
+  /** BOM stripped, line endings normalized to `\n`: what parsing, offsets and lines use */
+  text: string;
+  /** Decoded text as written (BOM stripped, line endings untouched) */
+  original: string;
+  encoding: SourceEncoding;
+  bom: boolean;
+  lineEnding: LineEnding;
+}
+
+export type UndecodableReason = 'binary' | 'undecodable';
+
+export interface UndecodableSource {
+  reason: UndecodableReason;
+  /** What gave it away, e.g. `odd byte count for utf-16le` */
+  detail: string;
+}
+
+/** Bytes inspected to recognize BOM-less UTF-16 */
+const UTF16_SNIFF_BYTES = 1024;
+/** Share of code units whose high byte is zero before BOM-less text counts as UTF-16 */
+const UTF16_ZERO_RATIO = 0.4;
+/** Most code units whose low byte may be zero too (CJK text has a few) */
+const UTF16_OPPOSITE_ZERO_RATIO = 0.05;
+
+const BOMS: Array<{ bytes: number[]; encoding: SourceEncoding }> = [
+  { bytes: [0xef, 0xbb, 0xbf], encoding: 'utf-8' },
+  { bytes: [0xff, 0xfe], encoding: 'utf-16le' },
+  { bytes: [0xfe, 0xff], encoding: 'utf-16be' }
+];
+
+function startsWith(bytes: Uint8Array, prefix: number[]): boolean {
+  return prefix.length <= bytes.length && prefix.every((byte, index) => bytes[index] === byte);
+}
+
+/** `utf-16le`/`utf-16be` when the sample's zero bytes sit in every other position. */
+function sniffUtf16(bytes: Uint8Array): SourceEncoding | null {
+  const length = Math.min(bytes.length, UTF16_SNIFF_BYTES) & ~1;
+  if (length < 2) return null;
+  let zeroEven = 0;
+  let zeroOdd = 0;
+  for (let i = 0; i < length; i += 2) {
+    if (bytes[i] === 0) zeroEven++;
+    if (bytes[i + 1] === 0) zeroOdd++;
+  }
+  const units = length / 2;
+  const even = zeroEven / units;
+  const odd = zeroOdd / units;
+  if (odd >= UTF16_ZERO_RATIO && even <= UTF16_OPPOSITE_ZERO_RATIO) return 'utf-16le';
+  if (even >= UTF16_ZERO_RATIO && odd <= UTF16_OPPOSITE_ZERO_RATIO) return 'utf-16be';
+  return null;
+}
+
+/**
+ * Encoding announced by a BOM or, for UTF-16, given away by its zero bytes; null for anything
+ * else, which is decoded as UTF-8 with a Windows-1252 fallback. Works on a file's first bytes.
+ */
+export function sniffEncoding(bytes: Uint8Array): SourceEncoding | null {
+  const bom = BOMS.find((candidate) => startsWith(bytes, candidate.bytes));
+  return bom?.encoding ?? sniffUtf16(bytes);
+}
+
+function detectLineEnding(text: string): LineEnding {
+  const crlf = text.match(/\r\n/g)?.length ?? 0;
+  const cr = (text.match(/\r/g)?.length ?? 0) - crlf;
+  const lf = (text.match(/\n/g)?.length ?? 0) - crlf;
+  const kinds = [crlf > 0, cr > 0, lf > 0].filter(Boolean).length;
+  if (kinds === 0) return 'none';
+  if (kinds > 1) return 'mixed';
+  return crlf > 0 ? 'crlf' : cr > 0 ? 'cr' : 'lf';
+}
+
+/** CRLF and lone CR to LF, so a line number means the same thing on every platform. */
+export function normalizeLineEndings(text: string): string {
+  return text.replace(/\r\n?/g, '\n');
+}
+
+/** Parser input from text that is already decoded: a leading BOM dropped, line endings to LF. */
+export function normalizeSourceText(text: string): string {
+  return normalizeLineEndings(text.startsWith('\uFEFF') ? text.slice(1) : text);
+}
+
+function decodeStrict(bytes: Uint8Array, encoding: SourceEncoding): string | null {
+  try {
+    return new TextDecoder(encoding, { fatal: true, ignoreBOM: true }).decode(bytes);
+  } catch {
+    return null;
+  }
+}
+
+function decoded(original: string, encoding: SourceEncoding, bom: boolean): DecodedSource {
+  return {
+    text: normalizeLineEndings(original),
+    original,
+    encoding,
+    bom,
+    lineEnding: detectLineEnding(original)
+  };
+}
+
+export function isUndecodable(
+  source: DecodedSource | UndecodableSource
+): source is UndecodableSource {
+  return 'reason' in source;
+}
+
+/** Decode a file's bytes, or say why they are not text. */
+export function decodeSourceBytes(bytes: Uint8Array): DecodedSource | UndecodableSource {
+  const bom = BOMS.find((candidate) => startsWith(bytes, candidate.bytes));
+  const encoding = bom?.encoding ?? sniffUtf16(bytes);
+  const body = bom ? bytes.subarray(bom.bytes.length) : bytes;
+
+  if (encoding === 'utf-16le' || encoding === 'utf-16be') {
+    if (body.length % 2 !== 0) {
+      return { reason: 'undecodable', detail: `odd byte count for ${encoding}` };
+    }
+    const text = decodeStrict(body, encoding);
+    if (text === null) {
+      return { reason: 'undecodable', detail: `invalid ${encoding} (unpaired surrogate)` };
+    }
+    if (text.includes('\0')) return { reason: 'binary', detail: `NUL character in ${encoding}` };
+    return decoded(text, encoding, Boolean(bom));
+  }
+
+  if (body.includes(0)) return { reason: 'binary', detail: 'NUL byte' };
+  const utf8 = decodeStrict(body, 'utf-8');
+  if (utf8 !== null) return decoded(utf8, 'utf-8', Boolean(bom));
+  // A UTF-8 BOM in front of invalid UTF-8 is corruption, not a legacy encoding
+  if (bom) return { reason: 'undecodable', detail: 'invalid utf-8 after a utf-8 BOM' };
+  const legacy = decodeStrict(body, 'windows-1252');
+  if (legacy === null) return { reason: 'undecodable', detail: 'neither utf-8 nor windows-1252' };
+  return decoded(legacy, 'windows-1252', false);
+}
+
+/** Read and decode a file; undecodable content comes back as the reason instead of text. */
+export async function readSource(absPath: string): Promise<DecodedSource | UndecodableSource> {
+  return decodeSourceBytes(await fs.readFile(absPath));
+}
+
+/**
+ * Read a file as parser input (`DecodedSource.text`). Throws when the file cannot be read or
+ * is not text, so callers keep treating it like a missing file.
+ */
+export async function readSourceText(absPath: string): Promise<string> {
+  const source = await readSource(absPath);
+  if (isUndecodable(source)) {
+    throw new Error(`Cannot decode ${absPath}: ${source.reason} (${source.detail})`);
+  }
+  return source.text;
+}
+
+/**
+ * Lines `startLine`..`endLine` (1-based, inclusive) exactly as written, CRLF and all. Line
+ * numbers are those of `text`, so spans found on the normalized text map back one to one.
+ */
+export function originalLineSpan(
+  source: DecodedSource,
+  startLine: number,
+  endLine: number
+): string {
+  // Odd entries are the terminators themselves
+  const parts = source.original.split(/(\r\n|\r|\n)/);
+  const first = Math.max(0, (startLine - 1) * 2);
+  const last = Math.min(parts.length - 1, (endLine - 1) * 2);
+  return parts.slice(first, last + 1).join('');
+}
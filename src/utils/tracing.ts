@@ -0,0 +1,114 @@
+/**
+ * Timing spans for the hot paths: file scan, per-file parse and extraction, search, context
+ * assembly and tool calls.
+ *
+ * Spans go to one sink at a time. With none set (the default), `withSpan` only calls its
+ * function, so instrumentation costs a null check. `CODEBASE_CONTEXT_TRACE` installs a sink that
+ * writes one JSON line per finished span to stderr: `1`/`true`/`*` for every span, or
+ * comma-separated name prefixes such as `tool,treesitter.parse`. Spans nest through async
+ * calls, so a slow tool call can be broken down by `parentId`.
+ */
+
+import { AsyncLocalStorage } from 'async_hooks';
+import { performance } from 'perf_hooks';
+
+export type SpanFieldValue = string | number | boolean;
+export type SpanFields = Record<string, SpanFieldValue | undefined>;
+
+export interface SpanRecord {
+  id: number;
+  /** Span this one ran inside, when there was one */
+  parentId?: number;
+  name: string;
+  /** Epoch milliseconds */
+  startTime: number;
+  durationMs: number;
+  fields: Record<string, SpanFieldValue>;
+  /** Set when the traced function threw */
+  error?: string;
+}
+
+export type SpanSink = (span: SpanRecord) => void;
+
+/** What a traced function can add to its own span, e.g. counts known only at the end. */
+export interface ActiveSpan {
+  record(fields: SpanFields): void;
+}
+
+const INACTIVE_SPAN: ActiveSpan = { record: () => undefined };
+
+let activeSink: SpanSink | null = null;
+let nextSpanId = 1;
+const currentSpan = new AsyncLocalStorage<number>();
+
+/** Send finished spans to `sink` until reset with `null`. */
+export function setSpanSink(sink: SpanSink | null): void {
+  activeSink = sink;
+}
+
+export function tracingEnabled(): boolean {
+  return activeSink !== null;
+}
+
+function definedFields(fields: SpanFields, into: Record<string, SpanFieldValue>): void {
+  for (const [key, value] of Object.entries(fields)) {
+    if (value !== undefined) into[key] = value;
+  }
+}
+
+/** Run `fn` inside a span named `name`; the span ends when the returned promise settles. */
+export async function withSpan<T>(
+  name: string,
+  fields: SpanFields,
+  fn: (span: ActiveSpan) => Promise<T> | T
+): Promise<T> {
+  const sink = activeSink;
+  if (!sink) return fn(INACTIVE_SPAN);
+
+  const id = nextSpanId++;
+  const parentId = currentSpan.getStore();
+  const recorded: Record<string, SpanFieldValue> = {};
+  definedFields(fields, recorded);
+  const span: ActiveSpan = { record: (more) => definedFields(more, recorded) };
+  const startTime = Date.now();
+  const start = performance.now();
+  let error: string | undefined;
+
+  try {
+    return await currentSpan.run(id, () => fn(span));
+  } catch (thrown) {
+    error = thrown instanceof Error ? thrown.message : String(thrown);
+    throw thrown;
+  } finally {
+    sink({
+      id,
+      ...(parentId !== undefined ? { parentId } : {}),
+      name,
+      startTime,
+      durationMs: Math.round((performance.now() - start) * 1000) / 1000,
+      fields: recorded,
+      ...(error !== undefined ? { error } : {})
+    });
+  }
+}
+
+/** Sink for `CODEBASE_CONTEXT_TRACE`, or null when the variable is unset or `0`/`false`. */
+export function spanSinkFromEnv(
+  value: string | undefined = process.env.CODEBASE_CONTEXT_TRACE,
+  write: (line: string) => void = (line) => process.stderr.write(`${line}\n`)
+): SpanSink | null {
+  const setting = value?.trim().toLowerCase();
+  if (!setting || setting === '0' || setting === 'false') return null;
+
+  const prefixes =
+    setting === '1' || setting === 'true' || setting === '*'
+      ? null
+      : setting
+          .split(',')
+          .map((prefix) => prefix.trim())
+          .filter(Boolean);
+  return (span) => {
+    if (prefixes && !prefixes.some((prefix) => span.name.startsWith(prefix))) return;
+    write(JSON.stringify(span));
+  };
+}
@@ -1,10 +1,12 @@
+import { createHash } from 'crypto';
 import { createRequire } from 'module';
-import { Language, Parser, type Node } from 'web-tree-sitter';
+import { Language, Parser, Query, type Node, type Tree } from 'web-tree-sitter';
 import {
   CURATED_LANGUAGE_TO_WASM,
   supportsCuratedTreeSitter,
   resolveGrammarPath
 } from '../grammars/manifest.js';
+import { withSpan } from './tracing.js';
 
 export interface TreeSitterSymbol {
   name: string;
@@ -15,11 +17,153 @@ export interface TreeSitterSymbol {
   endIndex: number;
   content: string;
   nodeType: string;
+  /** Type a symbol belongs to when nesting does not show it, e.g. a Go method's receiver */
+  qualifier?: string;
+  /**
+   * Named scopes enclosing the symbol in its file, outermost first: the Java or Go package,
+   * Rust modules and impl types, classes, namespaces and enclosing functions
+   */
+  scope?: string[];
+  /** Doc comment (`///`, `/** *\/`) or Python docstring, without comment markers */
+  doc?: string;
+  /** First line of a doc comment written above the symbol; unset for docstrings */
+  docStartLine?: number;
+  /** C/C++ prototype: the function is declared here and defined elsewhere */
+  declaration?: boolean;
+  /**
+   * Declaration up to where the body starts (`fn add(a: i64) -> i64`, `def f(x):`,
+   * `class Foo extends Bar`); the whole text of symbols without a body
+   */
+  signature?: string;
+  /** Java method or constructor parameter list, `(int a, int b)`; tells overloads apart */
+  parameters?: string;
+  /** Rust test code: a `#[test]` function or anything inside a `#[cfg(test)]` module */
+  test?: boolean;
+}
+
+/** Every `kind` symbol extraction produces. */
+export const SYMBOL_KINDS = [
+  'class',
+  'constant',
+  'enum',
+  'field',
+  'function',
+  'impl',
+  'interface',
+  'method',
+  'namespace',
+  'struct',
+  'trait',
+  'type',
+  'union',
+  'variable'
+] as const;
+export type SymbolKind = (typeof SYMBOL_KINDS)[number];
+
+export function isSymbolKind(value: string): value is SymbolKind {
+  return (SYMBOL_KINDS as readonly string[]).includes(value);
+}
+
+/** Lines tree-sitter could not parse, 1-based and inclusive */
+export interface TreeSitterErrorRegion {
+  startLine: number;
+  endLine: number;
 }
 
 export interface TreeSitterSymbolExtraction {
   grammarFile: string;
   symbols: TreeSitterSymbol[];
+  /**
+   * Set when the file parsed with syntax errors (in C/C++ usually macros). Symbols broken by
+   * these regions are missing; the rest of the file is still extracted.
+   */
+  errorRegions?: TreeSitterErrorRegion[];
+  /**
+   * Call expressions found in the same parse, in source order. Unset on extractions that did not
+   * collect them, such as incremental reparses.
+   */
+  callSites?: CallSite[];
+  /** Import and use statements found in the same parse; unset exactly when `callSites` is */
+  importSites?: ImportSite[];
+}
+
+/**
+ * Content-addressed store for extraction results. Keys cover the language and the exact file
+ * contents, so a hit never depends on paths or mtimes.
+ */
+export interface SymbolExtractionCache {
+  /** `content` is the text `key` was computed from, for stores that keep symbols without it */
+  get(key: string, content?: string): TreeSitterSymbolExtraction | undefined;
+  set(key: string, extraction: TreeSitterSymbolExtraction): void;
+}
+
+let activeExtractionCache: SymbolExtractionCache | null = null;
+
+/** Route `extractTreeSitterSymbols` through `cache` until reset with `null`. */
+export function setSymbolExtractionCache(cache: SymbolExtractionCache | null): void {
+  activeExtractionCache = cache;
+}
+
+/** The active cache's extraction of `content`, without parsing on a miss. */
+export function cachedTreeSitterSymbols(
+  content: string,
+  language: string
+): TreeSitterSymbolExtraction | undefined {
+  if (!activeExtractionCache || !supportsTreeSitter(language)) return undefined;
+  return activeExtractionCache.get(symbolExtractionCacheKey(content, language), content);
+}
+
+/**
+ * Parses extraction misses somewhere other than this thread. `undefined` means it could not
+ * take the file, which is then parsed here.
+ */
+export interface SymbolParsePool {
+  extract(
+    content: string,
+    language: string
+  ): Promise<TreeSitterSymbolExtraction | null | undefined>;
+}
+
+let activeParsePool: SymbolParsePool | null = null;
+
+/** Hand `extractTreeSitterSymbols` cache misses to `pool` until reset with `null`. */
+export function setSymbolParsePool(pool: SymbolParsePool | null): void {
+  activeParsePool = pool;
+}
+
+export function symbolExtractionCacheKey(content: string, language: string): string {
+  const hash = createHash('sha256').update(language).update('\0').update(content);
+  // Custom queries change what a file yields, so results cached without them must not hit
+  for (const query of customSymbolQueries.get(language) ?? []) {
+    hash.update('\0').update(query.fingerprint);
+  }
+  return hash.digest('hex');
+}
+
+/** A user query whose captures become extra symbols of the mapped kinds. */
+export interface CustomSymbolQuery {
+  /** Query file it was compiled from, for diagnostics */
+  source: string;
+  compiled: CompiledTreeSitterQuery;
+  /** Capture name (without `@`) -> kind of the symbol its node becomes */
+  kinds: Record<string, SymbolKind>;
+  /** Changes whenever the query text or kind mapping does */
+  fingerprint: string;
+}
+
+let customSymbolQueries = new Map<string, CustomSymbolQuery[]>();
+
+/**
+ * Merge the captures of `queries` into every later symbol extraction for their languages,
+ * replacing any queries set before. The caller keeps ownership of the compiled queries.
+ */
+export function setCustomSymbolQueries(queries: CustomSymbolQuery[]): void {
+  const byLanguage = new Map<string, CustomSymbolQuery[]>();
+  for (const query of queries) {
+    const language = query.compiled.language;
+    byLanguage.set(language, [...(byLanguage.get(language) ?? []), query]);
+  }
+  customSymbolQueries = byLanguage;
 }
 
 const require = createRequire(import.meta.url);
@@ -27,27 +171,38 @@ const require = createRequire(import.meta.url);
 const CORE_WASM_PATH = require.resolve('web-tree-sitter/tree-sitter.wasm');
 
 const SYMBOL_CANDIDATE_NODE_TYPES = [
+  'abstract_class_declaration',
   'class_declaration',
   'class_definition',
   'class_specifier',
   'constructor_declaration',
+  'declaration',
   'enum_declaration',
   'enum_item',
+  'enum_specifier',
+  'field_declaration',
+  'field_definition',
   'function_declaration',
   'function_definition',
   'function_item',
   'generator_function_declaration',
+  'impl_item',
   'interface_declaration',
   'lexical_declaration',
   'method',
   'method_declaration',
   'method_definition',
+  'namespace_definition',
+  'package_declaration',
+  'public_field_definition',
+  'record_declaration',
   'struct_item',
   'struct_specifier',
   'trait_item',
   'type_alias_declaration',
   'type_declaration',
   'type_spec',
+  'union_specifier',
   'variable_declarator'
 ] as const;
 
@@ -104,15 +259,16 @@ function sliceUtf8(content: string, startIndex: number, endIndex: number): strin
   return utf8.subarray(startIndex, endIndex).toString('utf8');
 }
 
-function extractNodeContent(node: Node, content: string): string {
+function extractNodeContent(node: Node, content: string, endIndex = node.endIndex): string {
   const byteSlice = sliceUtf8(content, node.startIndex, node.endIndex);
   const codeUnitSlice = content.slice(node.startIndex, node.endIndex);
 
+  // Indices count code units or bytes depending on the binding; the node's own text tells which
   if (node.text === codeUnitSlice && node.text !== byteSlice) {
-    return codeUnitSlice;
+    return endIndex === node.endIndex ? codeUnitSlice : content.slice(node.startIndex, endIndex);
   }
 
-  return byteSlice;
+  return endIndex === node.endIndex ? byteSlice : sliceUtf8(content, node.startIndex, endIndex);
 }
 
 function isTreeSitterDebugEnabled(): boolean {
@@ -123,6 +279,11 @@ export function supportsTreeSitter(language: string): boolean {
   return supportsCuratedTreeSitter(language);
 }
 
+/** Languages whose grammar this process has loaded (or is loading), sorted. */
+export function loadedTreeSitterLanguages(): string[] {
+  return [...languageCache.keys()].sort();
+}
+
 async function ensureParserInitialized(): Promise<void> {
   if (!initPromise) {
     initPromise = Parser.init({
@@ -181,11 +342,14 @@ async function getParserForLanguage(language: string): Promise<Parser> {
 }
 
 function getNodeKind(nodeType: string): string {
+  if (nodeType === 'package_declaration') return 'namespace';
+  if (nodeType === 'record_declaration') return 'class';
   if (nodeType.includes('class')) return 'class';
   if (nodeType.includes('interface')) return 'interface';
   if (nodeType.includes('enum')) return 'enum';
   if (nodeType.includes('struct')) return 'struct';
   if (nodeType.includes('trait')) return 'trait';
+  if (nodeType === 'impl_item') return 'impl';
   if (nodeType.includes('constructor')) return 'method';
   if (nodeType.includes('method')) return 'method';
   if (nodeType.includes('type_alias') || nodeType === 'type_spec') return 'type';
@@ -217,7 +381,24 @@ function maybeGetNameNode(node: Node): Node | null {
   return null;
 }
 
+/** `impl Calculator`, `impl Display for Calculator` */
+function extractRustImplName(node: Node): string | null {
+  const typeNode = node.childForFieldName('type');
+  if (!typeNode) {
+    return null;
+  }
+  const traitNode = node.childForFieldName('trait');
+  const typeName = normalizeSymbolName(typeNode.text);
+  return traitNode
+    ? `impl ${normalizeSymbolName(traitNode.text)} for ${typeName}`
+    : `impl ${typeName}`;
+}
+
 function extractNodeName(node: Node): string {
+  if (node.type === 'impl_item') {
+    return extractRustImplName(node) ?? 'anonymous';
+  }
+
   const nameNode = maybeGetNameNode(node);
   if (nameNode?.text) {
     const normalized = normalizeSymbolName(nameNode.text);
@@ -237,25 +418,41 @@ function extractNodeName(node: Node): string {
   return 'anonymous';
 }
 
-function isFunctionVariableDeclarator(node: Node): boolean {
-  if (node.type !== 'variable_declarator') {
-    return false;
-  }
-
+const JS_FAMILY_LANGUAGES = new Set([
+  'javascript',
+  'javascriptreact',
+  'typescript',
+  'typescriptreact'
+]);
+
+const FUNCTION_VALUE_NODE_TYPES = new Set([
+  'arrow_function',
+  'function_expression',
+  'function',
+  'generator_function'
+]);
+
+/** TS `public_field_definition`, JS `field_definition` */
+const CLASS_FIELD_NODE_TYPES = new Set(['public_field_definition', 'field_definition']);
+
+/** `const f = () => ...` and class fields holding a function (`onClick = () => ...`) */
+function hasFunctionValue(node: Node): boolean {
   const valueNode = node.childForFieldName('value');
-  if (!valueNode) {
-    return false;
-  }
-
-  return valueNode.type === 'arrow_function' || valueNode.type === 'function_expression';
+  return valueNode !== null && FUNCTION_VALUE_NODE_TYPES.has(valueNode.type);
 }
 
 function shouldSkipNode(language: string, node: Node): boolean {
-  if (node.type === 'variable_declarator') {
-    return (
-      !['javascript', 'javascriptreact', 'typescript', 'typescriptreact'].includes(language) ||
-      !isFunctionVariableDeclarator(node)
-    );
+  if (C_FAMILY_LANGUAGES.has(language)) {
+    return shouldSkipCNode(node);
+  }
+
+  if (node.type === 'declaration' || node.type === 'field_declaration') {
+    // Java and C# fields share these node types with C/C++ prototypes
+    return true;
+  }
+
+  if (node.type === 'variable_declarator' || CLASS_FIELD_NODE_TYPES.has(node.type)) {
+    return !JS_FAMILY_LANGUAGES.has(language) || !hasFunctionValue(node);
   }
 
   if (node.type === 'lexical_declaration') {
@@ -271,248 +468,1695 @@ function shouldSkipNode(language: string, node: Node): boolean {
 
 function getSymbolRangeNode(node: Node): Node {
   const parent = node.parent;
-  if (parent?.type === 'export_statement') {
+  // Python decorators live on a wrapping decorated_definition node
+  if (parent?.type === 'export_statement' || parent?.type === 'decorated_definition') {
     return parent;
   }
   return node;
 }
 
-function buildSymbol(node: Node, content: string): TreeSitterSymbol {
-  const rangeNode = getSymbolRangeNode(node);
-
-  return {
-    name: extractNodeName(node),
-    kind: getNodeKind(node.type),
-    startLine: rangeNode.startPosition.row + 1,
-    endLine: rangeNode.endPosition.row + 1,
-    startIndex: rangeNode.startIndex,
-    endIndex: rangeNode.endIndex,
-    content: extractNodeContent(rangeNode, content),
-    nodeType: node.type
-  };
+/**
+ * Python scopes by indentation, so the enclosing definition is found by walking
+ * ancestors (whose byte spans contain the node) rather than by matching braces.
+ */
+function findEnclosingPythonDefinition(node: Node): Node | null {
+  let cursor = node.parent;
+  while (cursor) {
+    if (cursor.type === 'class_definition' || cursor.type === 'function_definition') {
+      return cursor;
+    }
+    cursor = cursor.parent;
+  }
+  return null;
 }
 
-export async function extractTreeSitterSymbols(
-  content: string,
-  language: string
-): Promise<TreeSitterSymbolExtraction | null> {
-  if (!supportsTreeSitter(language) || !content.trim()) {
-    return null;
+function getSymbolKind(language: string, node: Node): string {
+  if (CLASS_FIELD_NODE_TYPES.has(node.type)) {
+    return 'method';
   }
-
-  if (Buffer.byteLength(content, 'utf8') > MAX_TREE_SITTER_PARSE_BYTES) {
-    return null;
+  if (language === 'rust' && node.type === 'function_item') {
+    // fn items sit in the declaration_list body of an impl or trait block
+    const container = node.parent?.parent?.type;
+    return container === 'impl_item' || container === 'trait_item' ? 'method' : 'function';
   }
-
-  try {
-    const parser = await getParserForLanguage(language);
-    setParseTimeout(parser);
-
-    let tree: ReturnType<Parser['parse']>;
-    try {
-      tree = parser.parse(content);
-    } catch (error) {
-      evictParser(language, parser);
-      throw error;
+  if (language === 'go' && node.type === 'type_spec') {
+    return getGoTypeSpecKind(node);
+  }
+  if (C_FAMILY_LANGUAGES.has(language)) {
+    if (node.type === 'union_specifier') return 'union';
+    if (node.type === 'namespace_definition') return 'namespace';
+    if (C_FUNCTION_NODE_TYPES.has(node.type)) {
+      return node.parent?.type === 'field_declaration_list' ? 'method' : 'function';
     }
+  }
+  if (language === 'python' && node.type === 'function_definition') {
+    // Covers `def` and `async def`; only direct class members are methods.
+    return findEnclosingPythonDefinition(node)?.type === 'class_definition'
+      ? 'method'
+      : 'function';
+  }
+  return getNodeKind(node.type);
+}
 
-    if (!tree) {
-      evictParser(language, parser);
-      return null;
+/**
+ * Class-level assignments (`count = 0`, `name: str = ""`) in a Python class body.
+ * Assignments inside methods (`self.value = ...`) are not class fields.
+ */
+function collectPythonClassFields(root: Node, content: string): TreeSitterSymbol[] {
+  const fields: TreeSitterSymbol[] = [];
+
+  for (const classNode of root.descendantsOfType('class_definition')) {
+    const body = classNode?.childForFieldName('body');
+    if (!body) continue;
+
+    for (const statement of body.namedChildren) {
+      if (statement?.type !== 'expression_statement') continue;
+      const assignment = statement.namedChildren[0];
+      if (assignment?.type !== 'assignment') continue;
+      const target = assignment.childForFieldName('left');
+      if (target?.type !== 'identifier') continue;
+
+      fields.push({
+        name: target.text,
+        kind: 'field',
+        startLine: statement.startPosition.row + 1,
+        endLine: statement.endPosition.row + 1,
+        startIndex: statement.startIndex,
+        endIndex: statement.endIndex,
+        content: extractNodeContent(statement, content),
+        nodeType: assignment.type,
+        scope: enclosingScope(statement, 'python')
+      });
     }
+  }
 
-    try {
-      const hasErrorValue = tree.rootNode.hasError as unknown;
-      const rootHasError =
-        typeof hasErrorValue === 'function'
-          ? Boolean((hasErrorValue as () => unknown)())
-          : Boolean(hasErrorValue);
-
-      if (rootHasError) {
-        return null;
-      }
+  return fields;
+}
 
-      const nodes = tree.rootNode.descendantsOfType([...SYMBOL_CANDIDATE_NODE_TYPES]);
-      const seen = new Set<string>();
-      const symbols: TreeSitterSymbol[] = [];
+/**
+ * Anonymous default exports (`export default function () {}`, `export default class {}`,
+ * `export default () => ...`) have no name node; they are recorded under the name `default`.
+ * Named default exports are regular declarations and are picked up as such.
+ */
+function collectAnonymousDefaultExports(root: Node, content: string): TreeSitterSymbol[] {
+  const symbols: TreeSitterSymbol[] = [];
+
+  for (const statement of root.namedChildren) {
+    if (statement?.type !== 'export_statement') continue;
+    if (!statement.children.some((child) => child?.type === 'default')) continue;
+
+    const value = statement.childForFieldName('value');
+    if (!value) continue;
+    const isClass = value.type === 'class';
+    if (!isClass && !FUNCTION_VALUE_NODE_TYPES.has(value.type)) continue;
+
+    symbols.push({
+      name: 'default',
+      kind: isClass ? 'class' : 'function',
+      startLine: statement.startPosition.row + 1,
+      endLine: statement.endPosition.row + 1,
+      startIndex: statement.startIndex,
+      endIndex: statement.endIndex,
+      content: extractNodeContent(statement, content),
+      nodeType: value.type
+    });
+  }
 
-      for (const node of nodes) {
-        if (!node || !node.isNamed || shouldSkipNode(language, node)) {
-          continue;
-        }
+  return symbols;
+}
 
-        const symbol = buildSymbol(node, content);
-        if (symbol.name === 'anonymous') {
-          continue;
-        }
+/** `type Calculator struct {...}` is a struct, `type Reader interface {...}` an interface. */
+function getGoTypeSpecKind(node: Node): string {
+  const typeNode = node.childForFieldName('type');
+  if (typeNode?.type === 'struct_type') return 'struct';
+  if (typeNode?.type === 'interface_type') return 'interface';
+  return 'type';
+}
 
-        const key = `${symbol.kind}:${symbol.name}:${symbol.startLine}:${symbol.endLine}`;
-        if (seen.has(key)) {
-          continue;
-        }
+/** Receiver type name: `Calculator` for `func (c *Calculator) Add()`, `Stack` for `Stack[T]`. */
+function extractGoReceiverType(node: Node): string | null {
+  const receiver = node.childForFieldName('receiver');
+  const parameter = receiver?.namedChildren.find(
+    (child) => child?.type === 'parameter_declaration'
+  );
+  let typeNode = parameter?.childForFieldName('type') ?? null;
+  while (typeNode && typeNode.type !== 'type_identifier') {
+    typeNode =
+      typeNode.type === 'pointer_type' || typeNode.type === 'parenthesized_type'
+        ? typeNode.namedChild(0)
+        : typeNode.childForFieldName('type');
+  }
+  return typeNode?.text ?? null;
+}
+
+/**
+ * Package-level `var` and `const` declarations. Each declared name becomes its own symbol, so
+ * `var ( a = 1; b, c = 2, 3 )` yields `a`, `b` and `c`. Declarations inside functions are locals.
+ */
+function collectGoPackageValues(root: Node, content: string): TreeSitterSymbol[] {
+  const symbols: TreeSitterSymbol[] = [];
 
-        seen.add(key);
-        symbols.push(symbol);
+  for (const declaration of root.namedChildren) {
+    if (declaration?.type !== 'var_declaration' && declaration?.type !== 'const_declaration') {
+      continue;
+    }
+    const kind = declaration.type === 'var_declaration' ? 'variable' : 'constant';
+    const specType = declaration.type === 'var_declaration' ? 'var_spec' : 'const_spec';
+    const grouped = declaration.children.some((child) => child?.type === '(');
+
+    // Newer grammars wrap grouped specs in a `var_spec_list`.
+    for (const spec of declaration.descendantsOfType(specType)) {
+      if (!spec) continue;
+      // An ungrouped declaration spans its `var`/`const` keyword; a grouped one spans its line.
+      const rangeNode = grouped ? spec : declaration;
+      for (const nameNode of spec.childrenForFieldName('name')) {
+        if (!nameNode || nameNode.text === '_') continue;
+        symbols.push({
+          name: nameNode.text,
+          kind,
+          startLine: rangeNode.startPosition.row + 1,
+          endLine: rangeNode.endPosition.row + 1,
+          startIndex: rangeNode.startIndex,
+          endIndex: rangeNode.endIndex,
+          content: extractNodeContent(rangeNode, content),
+          nodeType: spec.type
+        });
       }
+    }
+  }
 
-      symbols.sort((a, b) => {
-        if (a.startLine !== b.startLine) {
-          return a.startLine - b.startLine;
-        }
-        return a.endLine - b.endLine;
-      });
+  return symbols;
+}
 
-      return {
-        grammarFile: CURATED_LANGUAGE_TO_WASM[language] ?? language,
-        symbols
-      };
-    } finally {
-      tree.delete();
-    }
-  } catch (error) {
-    evictParser(language);
+const JAVA_TYPE_DECLARATION_TYPES = new Set([
+  'annotation_type_declaration',
+  'class_declaration',
+  'enum_declaration',
+  'interface_declaration',
+  'record_declaration'
+]);
+const JAVA_TYPE_BODY_TYPES = new Set([
+  'annotation_type_body',
+  'class_body',
+  'enum_body',
+  'enum_body_declarations',
+  'interface_body'
+]);
+const JAVA_CALLABLE_NODE_TYPES = new Set(['constructor_declaration', 'method_declaration']);
+const JAVA_FIELD_NODE_TYPES = ['constant_declaration', 'field_declaration'];
 
-    if (isTreeSitterDebugEnabled()) {
-      console.error(
-        `[DEBUG] Tree-sitter symbol extraction failed for '${language}':`,
-        error instanceof Error ? error.message : String(error)
-      );
-    }
+/**
+ * Name of the class, interface, enum or record whose body directly holds a Java member.
+ * Members of anonymous classes (`new Runnable() { ... }`) have no owner.
+ */
+function enclosingJavaTypeName(node: Node): string | null {
+  let cursor = node.parent;
+  while (cursor && JAVA_TYPE_BODY_TYPES.has(cursor.type)) {
+    cursor = cursor.parent;
+  }
+  if (!cursor || !JAVA_TYPE_DECLARATION_TYPES.has(cursor.type)) {
     return null;
   }
+  return cursor.childForFieldName('name')?.text ?? null;
 }
 
-export interface IdentifierOccurrence {
-  line: number;
-  startIndex: number;
-  endIndex: number;
-  nodeType: string;
-}
+/**
+ * Java fields and interface constants, one symbol per declared name, so `int a, b;` yields `a`
+ * and `b`. Annotations are part of the declaration's modifiers and never become symbols.
+ */
+function collectJavaFields(root: Node, content: string): TreeSitterSymbol[] {
+  const symbols: TreeSitterSymbol[] = [];
+
+  for (const declaration of root.descendantsOfType(JAVA_FIELD_NODE_TYPES)) {
+    if (!declaration) continue;
+    const owner = enclosingJavaTypeName(declaration);
+    if (!owner) continue;
+    for (const declarator of declaration.childrenForFieldName('declarator')) {
+      const nameNode = declarator?.childForFieldName('name');
+      if (!nameNode) continue;
+      symbols.push({
+        name: nameNode.text,
+        kind: declaration.type === 'constant_declaration' ? 'constant' : 'field',
+        startLine: declaration.startPosition.row + 1,
+        endLine: declaration.endPosition.row + 1,
+        startIndex: declaration.startIndex,
+        endIndex: declaration.endIndex,
+        content: extractNodeContent(declaration, content),
+        nodeType: declaration.type,
+        qualifier: owner,
+        scope: enclosingScope(declaration, 'java')
+      });
+    }
+  }
 
-const IDENTIFIER_NODE_TYPES = [
-  'identifier',
-  'type_identifier',
-  'property_identifier',
-  'field_identifier',
-  'shorthand_property_identifier_pattern',
-  'shorthand_property_identifier',
-  'jsx_identifier',
-  'scoped_identifier'
-] as const;
+  return symbols;
+}
 
-const NON_CODE_ANCESTOR_TYPE_FRAGMENTS = [
-  'comment',
-  'string',
-  'template_string',
-  'regex',
-  'jsx_text'
-] as const;
+/** `#[cfg(test)]` and `#[cfg(all(test, ...))]`, with whitespace removed */
+const RUST_CFG_TEST_ATTRIBUTE = /^#\[cfg\((?:test\)|all\((?:[^()]*,)?test[,)])/;
+/** `#[test]`, `#[tokio::test]`, `#[tokio::test(flavor = "multi_thread")]` */
+const RUST_TEST_FN_ATTRIBUTE = /^#\[(?:\w+::)*test(?:\(.*\))?\]$/;
+
+/** Outer attributes written above `node`, looking past doc comments between them. */
+function hasRustAttribute(node: Node, pattern: RegExp): boolean {
+  for (
+    let sibling = node.previousNamedSibling;
+    sibling && (sibling.type === 'attribute_item' || sibling.type.endsWith('comment'));
+    sibling = sibling.previousNamedSibling
+  ) {
+    if (sibling.type === 'attribute_item' && pattern.test(sibling.text.replace(/\s+/g, ''))) {
+      return true;
+    }
+  }
+  return false;
+}
 
-function isInsideNonCodeContext(node: Node): boolean {
-  let cursor: Node | null = node;
-  let depth = 0;
-  while (cursor && depth < 40) {
-    const cursorType = cursor.type;
-    for (const fragment of NON_CODE_ANCESTOR_TYPE_FRAGMENTS) {
-      if (cursorType.includes(fragment)) {
-        return true;
-      }
+/** A `#[test]` function, or any item nested in a module compiled only for tests. */
+function isRustTestCode(node: Node): boolean {
+  if (node.type === 'function_item' && hasRustAttribute(node, RUST_TEST_FN_ATTRIBUTE)) {
+    return true;
+  }
+  for (let cursor = node.parent; cursor; cursor = cursor.parent) {
+    if (cursor.type === 'mod_item' && hasRustAttribute(cursor, RUST_CFG_TEST_ATTRIBUTE)) {
+      return true;
     }
-    cursor = cursor.parent;
-    depth += 1;
   }
   return false;
 }
 
+const C_FAMILY_LANGUAGES = new Set(['c', 'cpp']);
+
+/** Definitions and prototypes; the latter are `declaration`s with a function declarator. */
+const C_FUNCTION_NODE_TYPES = new Set(['function_definition', 'declaration', 'field_declaration']);
+const C_RECORD_NODE_TYPES = new Set([
+  'class_specifier',
+  'enum_specifier',
+  'struct_specifier',
+  'union_specifier'
+]);
+const C_DECLARATOR_WRAPPER_TYPES = new Set([
+  'attributed_declarator',
+  'function_declarator',
+  'pointer_declarator',
+  'reference_declarator'
+]);
+const C_DECLARATOR_NAME_TYPES = new Set([
+  'destructor_name',
+  'field_identifier',
+  'identifier',
+  'operator_name',
+  'qualified_identifier',
+  'template_function'
+]);
+
 /**
- * Find identifier occurrences of `symbol` in `content` using Tree-sitter.
- * Returns null when Tree-sitter isn't available/supported, so callers can fall back safely.
+ * Walk a C/C++ declarator down to the declared name, noting whether it declares a function.
+ * `int (*handler)(int)` ends at a parenthesized declarator: a function pointer, so no name.
  */
-export async function findIdentifierOccurrences(
-  content: string,
-  language: string,
-  symbol: string
-): Promise<IdentifierOccurrence[] | null> {
-  const normalizedSymbol = symbol.trim();
-  if (!normalizedSymbol) {
-    return [];
+function resolveCDeclarator(node: Node): { name: Node; isFunction: boolean } | null {
+  let cursor = node.childForFieldName('declarator');
+  let isFunction = false;
+  while (cursor) {
+    if (C_DECLARATOR_NAME_TYPES.has(cursor.type)) {
+      return { name: cursor, isFunction };
+    }
+    if (!C_DECLARATOR_WRAPPER_TYPES.has(cursor.type)) {
+      return null;
+    }
+    isFunction ||= cursor.type === 'function_declarator';
+    cursor =
+      cursor.childForFieldName('declarator') ??
+      cursor.namedChildren.find(
+        (child) =>
+          child !== null &&
+          (C_DECLARATOR_NAME_TYPES.has(child.type) || C_DECLARATOR_WRAPPER_TYPES.has(child.type))
+      ) ??
+      null;
   }
+  return null;
+}
 
-  if (!supportsTreeSitter(language) || !content.trim()) {
-    return null;
+/** `ns::Calculator::add<T>` becomes `add` owned by `Calculator`, in scope `ns::Calculator`. */
+function splitCQualifiedName(text: string): {
+  name: string;
+  qualifier?: string;
+  scope?: string[];
+} {
+  let stripped = text.replace(/\s+/g, '');
+  for (let previous = ''; previous !== stripped; ) {
+    previous = stripped;
+    stripped = stripped.replace(/<[^<>]*>/g, '');
   }
+  const segments = stripped.split('::').filter(Boolean);
+  const name = segments.pop() ?? stripped;
+  return segments.length > 0
+    ? { name, qualifier: segments[segments.length - 1], scope: segments }
+    : { name };
+}
 
-  if (Buffer.byteLength(content, 'utf8') > MAX_TREE_SITTER_PARSE_BYTES) {
+/** Name of the class or struct whose body directly holds `node`. */
+function enclosingCRecordName(node: Node): string | null {
+  if (node.parent?.type !== 'field_declaration_list') {
     return null;
   }
+  const nameNode = node.parent.parent?.childForFieldName('name');
+  return nameNode ? splitCQualifiedName(nameNode.text).name : null;
+}
 
-  try {
-    const parser = await getParserForLanguage(language);
-    setParseTimeout(parser);
-
-    let tree: ReturnType<Parser['parse']>;
-    try {
-      tree = parser.parse(content);
-    } catch (error) {
-      evictParser(language, parser);
-      throw error;
+/**
+ * Name and owning type of a C/C++ function, or the typedef name of an anonymous
+ * `typedef struct { ... } Point;`. Null when the generic lookup applies.
+ */
+function cSymbolName(node: Node): { name: string; qualifier?: string; scope?: string[] } | null {
+  if (C_FUNCTION_NODE_TYPES.has(node.type)) {
+    const declarator = resolveCDeclarator(node);
+    if (!declarator) {
+      return null;
     }
+    const { name, qualifier, scope } = splitCQualifiedName(declarator.name.text);
+    const owner = qualifier ?? enclosingCRecordName(node);
+    if (!owner) return { name };
+    return scope ? { name, qualifier: owner, scope } : { name, qualifier: owner };
+  }
+  if (C_RECORD_NODE_TYPES.has(node.type) && !node.childForFieldName('name')) {
+    const typedefName =
+      node.parent?.type === 'type_definition' ? node.parent.childForFieldName('declarator') : null;
+    return typedefName ? { name: normalizeSymbolName(typedefName.text) } : null;
+  }
+  return null;
+}
 
-    if (!tree) {
-      evictParser(language, parser);
-      return null;
+function hasAncestorOfType(node: Node, type: string): boolean {
+  for (let cursor = node.parent; cursor; cursor = cursor.parent) {
+    if (cursor.type === type) {
+      return true;
     }
+  }
+  return false;
+}
 
-    try {
-      const hasErrorValue = tree.rootNode.hasError as unknown;
-      const rootHasError =
-        typeof hasErrorValue === 'function'
-          ? Boolean((hasErrorValue as () => unknown)())
-          : Boolean(hasErrorValue);
-
-      if (rootHasError) {
-        return null;
-      }
+function shouldSkipCNode(node: Node): boolean {
+  if (C_RECORD_NODE_TYPES.has(node.type)) {
+    // `struct point p;` refers to a type without defining it
+    return !node.childForFieldName('body');
+  }
+  if (node.type === 'declaration' || node.type === 'field_declaration') {
+    // Only function prototypes; local declarations inside bodies are not symbols
+    return (
+      !resolveCDeclarator(node)?.isFunction || hasAncestorOfType(node, 'compound_statement')
+    );
+  }
+  return false;
+}
 
-      const nodes = tree.rootNode.descendantsOfType([...IDENTIFIER_NODE_TYPES]);
-      const occurrences: IdentifierOccurrence[] = [];
-      const seen = new Set<string>();
-
-      for (const node of nodes) {
-        if (!node || !node.isNamed) continue;
-        if (node.text !== normalizedSymbol) continue;
-        if (isInsideNonCodeContext(node)) continue;
-
-        const occ: IdentifierOccurrence = {
-          line: node.startPosition.row + 1,
-          startIndex: node.startIndex,
-          endIndex: node.endIndex,
-          nodeType: node.type
-        };
-        const key = `${occ.line}:${occ.startIndex}:${occ.endIndex}:${occ.nodeType}`;
-        if (seen.has(key)) continue;
-        seen.add(key);
-        occurrences.push(occ);
-      }
+// Wrappers a JS/TS declaration sits in; a doc comment is written above the outermost one
+const JS_DOC_ANCHOR_PARENT_TYPES = new Set([
+  'export_statement',
+  'lexical_declaration',
+  'variable_declaration',
+  'variable_declarator'
+]);
 
-      occurrences.sort((a, b) => {
-        if (a.line !== b.line) return a.line - b.line;
-        return a.startIndex - b.startIndex;
-      });
+/** Last row holding text; comments that swallow their newline end at column 0 of the next. */
+function lastTextRow(node: Node): number {
+  return node.endPosition.column === 0 && node.endPosition.row > node.startPosition.row
+    ? node.endPosition.row - 1
+    : node.endPosition.row;
+}
 
-      return occurrences;
-    } finally {
-      tree.delete();
-    }
-  } catch (error) {
-    evictParser(language);
+function stripSharedIndent(lines: string[]): string[] {
+  const indents = lines
+    .filter((line) => line.trim())
+    .map((line) => line.length - line.trimStart().length);
+  const shared = indents.length > 0 ? Math.min(...indents) : 0;
+  return lines.map((line) => line.slice(shared));
+}
+
+/** Drop blank edges and the indentation every non-blank line shares. */
+function tidyDocLines(lines: string[]): string {
+  const trimmed = lines.map((line) => line.trimEnd());
+  while (trimmed.length > 0 && !trimmed[0].trim()) trimmed.shift();
+  while (trimmed.length > 0 && !trimmed[trimmed.length - 1].trim()) trimmed.pop();
+  return stripSharedIndent(trimmed).join('\n');
+}
+
+function isBlockDocComment(text: string): boolean {
+  return text.startsWith('/**') && !text.startsWith('/***') && text !== '/**/';
+}
+
+function isRustLineDocComment(text: string): boolean {
+  return text.startsWith('///') && !text.startsWith('////');
+}
 
-    if (isTreeSitterDebugEnabled()) {
-      console.error(
-        `[DEBUG] Tree-sitter identifier occurrence scan failed for '${language}':`,
-        error instanceof Error ? error.message : String(error)
-      );
+function cleanDocComments(texts: string[]): string {
+  const lines: string[] = [];
+  for (const text of texts) {
+    if (isBlockDocComment(text)) {
+      const [first, ...rest] = text.slice(3, text.endsWith('*/') ? -2 : undefined).split('\n');
+      lines.push(first.trim(), ...rest.map((line) => line.replace(/^\s*\* ?/, '')));
+    } else {
+      lines.push(text.replace(/^\/\/\/ ?/, ''));
     }
+  }
+  return tidyDocLines(lines);
+}
+
+/**
+ * Doc comments directly above `anchor`: one `/** *\/` block in JS/TS; in Rust any run of `///`
+ * lines and `/** *\/` blocks, with `#[...]` attributes allowed in between. A blank line or a
+ * plain comment ends the run.
+ */
+function leadingDocComment(
+  anchor: Node,
+  language: string,
+  content: string
+): { doc: string; startLine: number } | null {
+  const isRust = language === 'rust';
+  const comments: Node[] = [];
+  let nextRow = anchor.startPosition.row;
+  let sibling = anchor.previousNamedSibling;
+
+  while (sibling && lastTextRow(sibling) >= nextRow - 1) {
+    const text = sibling.type.endsWith('comment')
+      ? extractNodeContent(sibling, content).trim()
+      : '';
+    if (isBlockDocComment(text) || (isRust && isRustLineDocComment(text))) {
+      comments.unshift(sibling);
+      if (!isRust) break;
+    } else if (!(isRust && sibling.type === 'attribute_item')) {
+      break;
+    }
+    nextRow = sibling.startPosition.row;
+    sibling = sibling.previousNamedSibling;
+  }
+
+  if (comments.length === 0) return null;
+  const doc = cleanDocComments(
+    comments.map((comment) => extractNodeContent(comment, content).trim())
+  );
+  return doc ? { doc, startLine: comments[0].startPosition.row + 1 } : null;
+}
+
+const PYTHON_DOCSTRING_PATTERN = /^[rRuU]?("""|'''|"|')([\s\S]*)\1$/;
+
+/** Python docstring: the first statement of the body when it is a bare string literal. */
+function pythonDocstring(node: Node, content: string): string | null {
+  if (node.type !== 'function_definition' && node.type !== 'class_definition') return null;
+  const body = node.childForFieldName('body');
+  const first = body?.namedChildren.find((child) => child && child.type !== 'comment');
+  const literal = first?.type === 'expression_statement' ? first.firstNamedChild : null;
+  if (!literal || literal.type !== 'string') return null;
+
+  const match = extractNodeContent(literal, content).match(PYTHON_DOCSTRING_PATTERN);
+  if (!match) return null;
+  // inspect.cleandoc: the first line is stripped, the rest lose their common indentation
+  const [firstLine, ...rest] = match[2].split('\n');
+  const doc = tidyDocLines([firstLine.trim(), ...stripSharedIndent(rest)]);
+  return doc || null;
+}
+
+function attachDoc(
+  symbol: TreeSitterSymbol,
+  node: Node,
+  rangeNode: Node,
+  language: string,
+  content: string
+): void {
+  if (language === 'python') {
+    const doc = pythonDocstring(node, content);
+    if (doc) symbol.doc = doc;
+    return;
+  }
+  if (language !== 'rust' && !JS_FAMILY_LANGUAGES.has(language)) return;
+
+  let anchor = rangeNode;
+  while (anchor.parent && JS_DOC_ANCHOR_PARENT_TYPES.has(anchor.parent.type)) {
+    anchor = anchor.parent;
+  }
+  const leading = leadingDocComment(anchor, language, content);
+  if (leading) {
+    symbol.doc = leading.doc;
+    symbol.docStartLine = leading.startLine;
+  }
+}
+
+/** The block a symbol's signature stops at, looking through declarators bound to functions. */
+function symbolBodyNode(node: Node): Node | null {
+  return (
+    node.childForFieldName('body') ??
+    node.childForFieldName('value')?.childForFieldName('body') ??
+    null
+  );
+}
+
+/** `const f = () => ...` signatures keep their `export const` when the declarator stands alone. */
+function signatureStartNode(node: Node, rangeNode: Node): Node {
+  const declaration = node.parent;
+  if (
+    node.type !== 'variable_declarator' ||
+    !declaration ||
+    !['lexical_declaration', 'variable_declaration'].includes(declaration.type) ||
+    declaration.namedChildCount !== 1
+  ) {
+    return rangeNode;
+  }
+  return declaration.parent?.type === 'export_statement' ? declaration.parent : declaration;
+}
+
+function symbolSignature(node: Node, rangeNode: Node, content: string): string {
+  const start = signatureStartNode(node, rangeNode);
+  const body = symbolBodyNode(node);
+  if (!body || body.startIndex <= start.startIndex) {
+    return extractNodeContent(start, content).trim();
+  }
+  return extractNodeContent(start, content, body.startIndex).trim();
+}
+
+/** Scopes that are not symbols themselves but still name what they hold */
+const SCOPE_ONLY_NODE_TYPES = new Set(['internal_module', 'mod_item', 'module']);
+const SYMBOL_CANDIDATE_NODE_TYPE_SET = new Set<string>(SYMBOL_CANDIDATE_NODE_TYPES);
+
+/** Name an enclosing node gives what it holds: `Calculator` for `impl<T> Calculator<T>` */
+function scopeName(node: Node, language: string): string | null {
+  if (node.type === 'impl_item') {
+    const typeNode = node.childForFieldName('type');
+    return typeNode ? normalizeSymbolName(typeNode.text).replace(/<.*$/, '') : null;
+  }
+  const cName = C_FAMILY_LANGUAGES.has(language) ? cSymbolName(node) : null;
+  const name = cName?.name ?? extractNodeName(node);
+  return name === 'anonymous' ? null : name;
+}
+
+/** Names of the scopes around `node` within its file, outermost first. */
+function enclosingScope(node: Node, language: string): string[] {
+  const scope: string[] = [];
+  for (let cursor = node.parent; cursor; cursor = cursor.parent) {
+    const isScope =
+      SCOPE_ONLY_NODE_TYPES.has(cursor.type) ||
+      (SYMBOL_CANDIDATE_NODE_TYPE_SET.has(cursor.type) && !shouldSkipNode(language, cursor));
+    const name = isScope ? scopeName(cursor, language) : null;
+    if (name) scope.unshift(name);
+  }
+  return scope;
+}
+
+/** `package com.example.calc;` in Java, `package calc` in Go */
+function filePackageName(root: Node, language: string): string | null {
+  const type = language === 'java' ? 'package_declaration' : 'package_clause';
+  const declaration = root.namedChildren.find((child) => child?.type === type);
+  const nameNode = declaration?.namedChildren.find(
+    (child) => child !== null && child.type !== 'annotation' && child.type !== 'marker_annotation'
+  );
+  return nameNode ? normalizeSymbolName(nameNode.text).replace(/\s/g, '') : null;
+}
+
+function buildSymbol(node: Node, content: string, language: string): TreeSitterSymbol {
+  const rangeNode = getSymbolRangeNode(node);
+  const cName = C_FAMILY_LANGUAGES.has(language) ? cSymbolName(node) : null;
+  const symbol: TreeSitterSymbol = {
+    name: cName?.name ?? extractNodeName(node),
+    kind: getSymbolKind(language, node),
+    startLine: rangeNode.startPosition.row + 1,
+    endLine: rangeNode.endPosition.row + 1,
+    startIndex: rangeNode.startIndex,
+    endIndex: rangeNode.endIndex,
+    content: extractNodeContent(rangeNode, content),
+    nodeType: node.type,
+    signature: symbolSignature(node, rangeNode, content)
+  };
+
+  if (language === 'go' && node.type === 'method_declaration') {
+    const receiverType = extractGoReceiverType(node);
+    if (receiverType) {
+      symbol.qualifier = receiverType;
+    }
+  }
+  if (cName?.qualifier) {
+    symbol.qualifier = cName.qualifier;
+  }
+  if (language === 'rust' && isRustTestCode(node)) {
+    symbol.test = true;
+  }
+  if (language === 'java' && JAVA_CALLABLE_NODE_TYPES.has(node.type)) {
+    const owner = enclosingJavaTypeName(node);
+    if (owner) {
+      symbol.qualifier = owner;
+    }
+    const parameters = node.childForFieldName('parameters');
+    if (parameters) {
+      symbol.parameters = normalizeSymbolName(parameters.text);
+    }
+  }
+  if (cName && node.type !== 'function_definition' && C_FUNCTION_NODE_TYPES.has(node.type)) {
+    symbol.declaration = true;
+  }
+
+  const scope = [...enclosingScope(node, language), ...(cName?.scope ?? [])];
+  // A Go receiver is not an ancestor of its method; neither is a C++ owner outside its class
+  if (symbol.qualifier && scope[scope.length - 1] !== symbol.qualifier) {
+    scope.push(symbol.qualifier);
+  }
+  if (scope.length > 0) {
+    symbol.scope = scope;
+  }
+
+  attachDoc(symbol, node, rangeNode, language, content);
+  return symbol;
+}
+
+/**
+ * Symbols from one custom query: each capture mapped to a kind becomes a symbol named by the
+ * `@name` capture of the same match, or by the node's own name when the match has none.
+ */
+function collectCustomSymbols(
+  root: Node,
+  content: string,
+  language: string,
+  query: CustomSymbolQuery
+): TreeSitterSymbol[] {
+  const symbols: TreeSitterSymbol[] = [];
+  for (const match of query.compiled.query.matches(root)) {
+    const nameNode = match.captures.find((capture) => capture.name === 'name')?.node;
+    for (const { name, node } of match.captures) {
+      const kind = query.kinds[name];
+      if (!kind) {
+        continue;
+      }
+      const symbol: TreeSitterSymbol = {
+        name: nameNode ? normalizeSymbolName(nameNode.text) : extractNodeName(node),
+        kind,
+        startLine: node.startPosition.row + 1,
+        endLine: node.endPosition.row + 1,
+        startIndex: node.startIndex,
+        endIndex: node.endIndex,
+        content: extractNodeContent(node, content),
+        nodeType: node.type,
+        signature: symbolSignature(node, node, content)
+      };
+      const scope = enclosingScope(node, language);
+      if (scope.length > 0) {
+        symbol.scope = scope;
+      }
+      attachDoc(symbol, node, node, language, content);
+      symbols.push(symbol);
+    }
+  }
+  return symbols;
+}
+
+function nodeHasError(node: Node): boolean {
+  const hasErrorValue = node.hasError as unknown;
+  return typeof hasErrorValue === 'function'
+    ? Boolean((hasErrorValue as () => unknown)())
+    : Boolean(hasErrorValue);
+}
+
+function treeHasError(tree: NonNullable<ReturnType<Parser['parse']>>): boolean {
+  return nodeHasError(tree.rootNode);
+}
+
+/** Outermost ERROR and MISSING nodes, as line ranges. */
+function collectErrorRegions(root: Node): TreeSitterErrorRegion[] {
+  const regions: TreeSitterErrorRegion[] = [];
+  const visit = (node: Node): void => {
+    if (node.type === 'ERROR' || node.isMissing) {
+      regions.push({ startLine: node.startPosition.row + 1, endLine: node.endPosition.row + 1 });
+      return;
+    }
+    if (!nodeHasError(node)) {
+      return;
+    }
+    for (const child of node.children) {
+      if (child) visit(child);
+    }
+  };
+  visit(root);
+  return regions;
+}
+
+/**
+ * Whether a candidate in a tree with errors is still a real symbol. Tree-sitter wraps what it
+ * could not fit in ERROR nodes, which can hold intact definitions; those count unless they are
+ * damaged themselves. C/C++ are stricter: inside an ERROR, a macro call is easily misread as a
+ * well-formed function.
+ */
+function isWellFormedCandidate(node: Node, language: string): boolean {
+  if (!hasAncestorOfType(node, 'ERROR')) return true;
+  return !C_FAMILY_LANGUAGES.has(language) && !nodeHasError(node);
+}
+
+function logTreeSitterFailure(operation: string, language: string, error: unknown): void {
+  if (isTreeSitterDebugEnabled()) {
+    console.error(
+      `[DEBUG] Tree-sitter ${operation} failed for '${language}':`,
+      error instanceof Error ? error.message : String(error)
+    );
+  }
+}
+
+/**
+ * Parse `content`, reusing the unchanged subtrees of `oldTree` when it is given (it must already
+ * have been edited to match). The caller owns the returned tree, which may contain syntax errors.
+ * Returns null for unsupported languages, oversized input and parse failures.
+ */
+async function parseTree(
+  content: string,
+  language: string,
+  operation: string,
+  oldTree?: Tree
+): Promise<Tree | null> {
+  if (!supportsTreeSitter(language) || !content.trim()) {
+    return null;
+  }
+
+  const bytes = Buffer.byteLength(content, 'utf8');
+  if (bytes > MAX_TREE_SITTER_PARSE_BYTES) {
+    return null;
+  }
+
+  return withSpan('treesitter.parse', { language, bytes, operation }, async (span) => {
+    const tree = await parseTreeUntraced(content, language, operation, oldTree);
+    span.record({ parsed: tree !== null, incremental: oldTree !== undefined });
+    return tree;
+  });
+}
+
+async function parseTreeUntraced(
+  content: string,
+  language: string,
+  operation: string,
+  oldTree?: Tree
+): Promise<Tree | null> {
+  try {
+    const parser = await getParserForLanguage(language);
+    setParseTimeout(parser);
+
+    let tree: ReturnType<Parser['parse']>;
+    try {
+      tree = parser.parse(content, oldTree);
+    } catch (error) {
+      evictParser(language, parser);
+      throw error;
+    }
+
+    if (!tree) {
+      evictParser(language, parser);
+      return null;
+    }
+    return tree;
+  } catch (error) {
+    evictParser(language);
+    logTreeSitterFailure(operation, language, error);
+    return null;
+  }
+}
+
+/**
+ * Parse `content` and hand the root node to `visit`, releasing the tree afterwards.
+ * Returns null for unsupported languages, oversized input, parse failures and, unless
+ * `tolerateErrors` is set, trees with syntax errors, so every caller can fall back safely.
+ */
+async function withParsedTree<T>(
+  content: string,
+  language: string,
+  operation: string,
+  visit: (root: Node) => T,
+  options: { tolerateErrors?: boolean } = {}
+): Promise<T | null> {
+  const tree = await parseTree(content, language, operation);
+  if (!tree) {
+    return null;
+  }
+
+  try {
+    if (!options.tolerateErrors && treeHasError(tree)) {
+      return null;
+    }
+    return visit(tree.rootNode);
+  } catch (error) {
+    evictParser(language);
+    logTreeSitterFailure(operation, language, error);
+    return null;
+  } finally {
+    tree.delete();
+  }
+}
+
+/**
+ * Symbols of `content`, through the active extraction cache. With `withSites`, a cached
+ * extraction made without call and import sites counts as a miss, so the file is parsed once
+ * more and the complete extraction replaces it.
+ */
+export async function extractTreeSitterSymbols(
+  content: string,
+  language: string,
+  options: { withSites?: boolean } = {}
+): Promise<TreeSitterSymbolExtraction | null> {
+  return withSpan('treesitter.extract', { language }, async (span) => {
+    const cache = activeExtractionCache;
+    const cacheKey =
+      cache && supportsTreeSitter(language) ? symbolExtractionCacheKey(content, language) : null;
+    if (cache && cacheKey) {
+      const cached = cache.get(cacheKey, content);
+      if (cached && (!options.withSites || hasSites(cached))) {
+        span.record({ cached: true, symbols: cached.symbols.length });
+        return cached;
+      }
+    }
+
+    // Custom queries are compiled on this thread only, so their languages are parsed here
+    const pooled =
+      activeParsePool && !customSymbolQueries.has(language)
+        ? await activeParsePool.extract(content, language)
+        : undefined;
+    const extraction =
+      pooled === undefined ? await parseTreeSitterSymbols(content, language) : pooled;
+    span.record({
+      cached: false,
+      pooled: pooled !== undefined,
+      symbols: extraction?.symbols.length ?? 0
+    });
+    if (extraction && cache && cacheKey) {
+      cache.set(cacheKey, extraction);
+    }
+    return extraction;
+  });
+}
+
+function hasSites(extraction: TreeSitterSymbolExtraction): boolean {
+  return extraction.callSites !== undefined && extraction.importSites !== undefined;
+}
+
+function collectSymbols(
+  root: Node,
+  content: string,
+  language: string
+): TreeSitterSymbolExtraction {
+  const nodes = root.descendantsOfType([...SYMBOL_CANDIDATE_NODE_TYPES]);
+  const damaged = nodeHasError(root);
+  const seen = new Set<string>();
+  const symbols: TreeSitterSymbol[] = [];
+  const candidates: TreeSitterSymbol[] = [];
+
+  for (const node of nodes) {
+    if (!node || !node.isNamed || shouldSkipNode(language, node)) {
+      continue;
+    }
+    if (damaged && !isWellFormedCandidate(node, language)) {
+      continue;
+    }
+    candidates.push(buildSymbol(node, content, language));
+  }
+
+  if (language === 'python') {
+    candidates.push(...collectPythonClassFields(root, content));
+  }
+  if (JS_FAMILY_LANGUAGES.has(language)) {
+    candidates.push(...collectAnonymousDefaultExports(root, content));
+  }
+  if (language === 'go') {
+    candidates.push(...collectGoPackageValues(root, content));
+  }
+  if (language === 'java') {
+    candidates.push(...collectJavaFields(root, content));
+  }
+  for (const query of customSymbolQueries.get(language) ?? []) {
+    candidates.push(...collectCustomSymbols(root, content, language, query));
+  }
+
+  const packageName =
+    language === 'java' || language === 'go' ? filePackageName(root, language) : null;
+  if (packageName) {
+    for (const symbol of candidates) {
+      if (symbol.nodeType === 'package_declaration') continue;
+      symbol.scope = [packageName, ...(symbol.scope ?? [])];
+    }
+  }
+
+  for (const symbol of candidates) {
+    if (symbol.name === 'anonymous') {
+      continue;
+    }
+
+    const key = `${symbol.kind}:${symbol.name}:${symbol.startLine}:${symbol.endLine}`;
+    if (seen.has(key)) {
+      continue;
+    }
+
+    seen.add(key);
+    symbols.push(symbol);
+  }
+
+  symbols.sort((a, b) => {
+    if (a.startLine !== b.startLine) {
+      return a.startLine - b.startLine;
+    }
+    return a.endLine - b.endLine;
+  });
+
+  const errorRegions = damaged ? collectErrorRegions(root) : [];
+  return {
+    grammarFile: CURATED_LANGUAGE_TO_WASM[language] ?? language,
+    symbols,
+    ...(errorRegions.length > 0 ? { errorRegions } : {})
+  };
+}
+
+async function parseTreeSitterSymbols(
+  content: string,
+  language: string
+): Promise<TreeSitterSymbolExtraction | null> {
+  return withParsedTree(
+    content,
+    language,
+    'symbol extraction',
+    (root) => ({
+      ...collectSymbols(root, content, language),
+      callSites: collectCallSites(root),
+      importSites: collectImportSites(root)
+    }),
+    { tolerateErrors: true }
+  );
+}
+
+/** A row and column in the document; columns count like `TreeSitterEdit` indices. */
+export interface TreeSitterPoint {
+  row: number;
+  column: number;
+}
+
+/**
+ * One text change, described as tree-sitter expects it. Indices count UTF-16 code units of the
+ * JavaScript string (the unit web-tree-sitter parses in; equal to bytes for ASCII text).
+ */
+export interface TreeSitterEdit {
+  startIndex: number;
+  oldEndIndex: number;
+  newEndIndex: number;
+  startPosition: TreeSitterPoint;
+  oldEndPosition: TreeSitterPoint;
+  newEndPosition: TreeSitterPoint;
+}
+
+function pointAt(text: string, index: number): TreeSitterPoint {
+  let row = 0;
+  let lineStart = 0;
+  for (let i = text.indexOf('\n'); i !== -1 && i < index; i = text.indexOf('\n', i + 1)) {
+    row++;
+    lineStart = i + 1;
+  }
+  return { row, column: index - lineStart };
+}
+
+function isLowSurrogate(text: string, index: number): boolean {
+  const code = text.charCodeAt(index);
+  return code >= 0xdc00 && code <= 0xdfff;
+}
+
+/**
+ * The one edit turning `before` into `after`: whatever lies between their common prefix and
+ * common suffix was replaced. Null when they are equal. Lets callers that only have the old
+ * and new contents (a file watcher) reparse incrementally.
+ */
+export function computeTreeSitterEdit(before: string, after: string): TreeSitterEdit | null {
+  if (before === after) return null;
+  const shorter = Math.min(before.length, after.length);
+  let start = 0;
+  while (start < shorter && before.charCodeAt(start) === after.charCodeAt(start)) start++;
+  let suffix = 0;
+  while (
+    suffix < shorter - start &&
+    before.charCodeAt(before.length - 1 - suffix) === after.charCodeAt(after.length - 1 - suffix)
+  ) {
+    suffix++;
+  }
+  // Never split a surrogate pair between the kept and the replaced text
+  if (start > 0 && (isLowSurrogate(before, start) || isLowSurrogate(after, start))) start--;
+  let oldEnd = before.length - suffix;
+  let newEnd = after.length - suffix;
+  if (suffix > 0 && (isLowSurrogate(before, oldEnd) || isLowSurrogate(after, newEnd))) {
+    oldEnd++;
+    newEnd++;
+  }
+  return {
+    startIndex: start,
+    oldEndIndex: oldEnd,
+    newEndIndex: newEnd,
+    startPosition: pointAt(before, start),
+    oldEndPosition: pointAt(before, oldEnd),
+    newEndPosition: pointAt(after, newEnd)
+  };
+}
+
+/** Parse tree kept for one file so its next edit can be reparsed incrementally. */
+export interface RetainedParseTree {
+  language: string;
+  tree: Tree;
+}
+
+export interface IncrementalSymbolExtraction {
+  /** Null when the new contents could not be parsed; syntax errors are extracted around */
+  extraction: TreeSitterSymbolExtraction | null;
+  /** Tree to pass back with the next edit; null when parsing failed outright */
+  retained: RetainedParseTree | null;
+}
+
+/**
+ * Extract symbols, reparsing incrementally when `previous` is the tree of the contents before
+ * `edit`. Without both, or when the language changed, this is a full parse. Ownership of
+ * `previous` passes to this call: it is released here and must not be reused.
+ */
+export async function extractTreeSitterSymbolsIncremental(
+  content: string,
+  language: string,
+  previous?: RetainedParseTree,
+  edit?: TreeSitterEdit
+): Promise<IncrementalSymbolExtraction> {
+  let oldTree: Tree | undefined;
+  if (previous && edit && previous.language === language) {
+    try {
+      previous.tree.edit(edit);
+      oldTree = previous.tree;
+    } catch (error) {
+      logTreeSitterFailure('tree edit', language, error);
+    }
+  }
+
+  let tree: Tree | null;
+  try {
+    tree = await parseTree(content, language, 'incremental symbol extraction', oldTree);
+  } finally {
+    previous?.tree.delete();
+  }
+  if (!tree) {
+    return { extraction: null, retained: null };
+  }
+
+  const retained = { language, tree };
+  try {
+    return { extraction: collectSymbols(tree.rootNode, content, language), retained };
+  } catch (error) {
+    logTreeSitterFailure('incremental symbol extraction', language, error);
+    return { extraction: null, retained };
+  }
+}
+
+export interface IdentifierOccurrence {
+  line: number;
+  startIndex: number;
+  endIndex: number;
+  nodeType: string;
+}
+
+const IDENTIFIER_NODE_TYPES = [
+  'identifier',
+  'type_identifier',
+  'property_identifier',
+  'field_identifier',
+  'shorthand_property_identifier_pattern',
+  'shorthand_property_identifier',
+  'jsx_identifier',
+  'scoped_identifier'
+] as const;
+
+const NON_CODE_ANCESTOR_TYPE_FRAGMENTS = [
+  'comment',
+  'string',
+  'template_string',
+  'regex',
+  'jsx_text'
+] as const;
+
+function isInsideNonCodeContext(node: Node): boolean {
+  let cursor: Node | null = node;
+  let depth = 0;
+  while (cursor && depth < 40) {
+    const cursorType = cursor.type;
+    for (const fragment of NON_CODE_ANCESTOR_TYPE_FRAGMENTS) {
+      if (cursorType.includes(fragment)) {
+        return true;
+      }
+    }
+    cursor = cursor.parent;
+    depth += 1;
+  }
+  return false;
+}
+
+function collectIdentifierNodes(root: Node, symbol: string): Node[] {
+  const nodes = root.descendantsOfType([...IDENTIFIER_NODE_TYPES]);
+  const matches: Node[] = [];
+  const seen = new Set<string>();
+
+  for (const node of nodes) {
+    if (!node || !node.isNamed) continue;
+    if (node.text !== symbol) continue;
+    if (isInsideNonCodeContext(node)) continue;
+
+    const key = `${node.startIndex}:${node.endIndex}:${node.type}`;
+    if (seen.has(key)) continue;
+    seen.add(key);
+    matches.push(node);
+  }
+
+  matches.sort((a, b) => a.startIndex - b.startIndex);
+  return matches;
+}
+
+/**
+ * Find identifier occurrences of `symbol` in `content` using Tree-sitter.
+ * Returns null when Tree-sitter isn't available/supported, so callers can fall back safely.
+ */
+export async function findIdentifierOccurrences(
+  content: string,
+  language: string,
+  symbol: string
+): Promise<IdentifierOccurrence[] | null> {
+  const normalizedSymbol = symbol.trim();
+  if (!normalizedSymbol) {
+    return [];
+  }
+
+  return withParsedTree(content, language, 'identifier occurrence scan', (root) =>
+    collectIdentifierNodes(root, normalizedSymbol).map((node) => ({
+      line: node.startPosition.row + 1,
+      startIndex: node.startIndex,
+      endIndex: node.endIndex,
+      nodeType: node.type
+    }))
+  );
+}
+
+export interface CallSite {
+  /** Called name: the last segment of `a.b.callee()` or `Type::callee()` */
+  callee: string;
+  line: number;
+  startIndex: number;
+}
+
+const CALL_NODE_TYPES = ['call_expression', 'call', 'method_invocation'] as const;
+const CALLEE_NAME_NODE_TYPES = new Set([
+  'identifier',
+  'field_identifier',
+  'property_identifier',
+  'type_identifier',
+  'constant'
+]);
+/** Fields holding the final segment of a qualified callee, across grammars. */
+const CALLEE_MEMBER_FIELDS = ['property', 'attribute', 'field', 'name'] as const;
+const CALLEE_WRAPPER_FIELDS = ['function', 'method'] as const;
+
+function resolveCalleeName(node: Node | null, depth = 0): string | null {
+  if (!node || depth > 8) return null;
+  if (CALLEE_NAME_NODE_TYPES.has(node.type)) return node.text;
+
+  for (const field of CALLEE_MEMBER_FIELDS) {
+    const child = node.childForFieldName(field);
+    if (child && CALLEE_NAME_NODE_TYPES.has(child.type)) return child.text;
+  }
+  // Generic calls (`parse::<T>()`, `make<T>()`) wrap the real callee.
+  for (const field of CALLEE_WRAPPER_FIELDS) {
+    const child = node.childForFieldName(field);
+    if (child) return resolveCalleeName(child, depth + 1);
+  }
+  return null;
+}
+
+function getCalleeName(call: Node): string | null {
+  // Java `obj.run()` and Ruby `obj.run` name the method directly on the call node.
+  if (call.type === 'method_invocation' || call.childForFieldName('method')) {
+    return resolveCalleeName(call.childForFieldName('name') ?? call.childForFieldName('method'));
+  }
+  return resolveCalleeName(call.childForFieldName('function') ?? call.namedChild(0));
+}
+
+/**
+ * Every call expression under `root` with the name it calls. Names are syntactic, not resolved:
+ * `a.add()` and `add()` both report `add`.
+ */
+function collectCallSites(root: Node): CallSite[] {
+  const calls: CallSite[] = [];
+  for (const node of root.descendantsOfType([...CALL_NODE_TYPES])) {
+    if (!node) continue;
+    const callee = getCalleeName(node);
+    if (!callee) continue;
+    calls.push({ callee, line: node.startPosition.row + 1, startIndex: node.startIndex });
+  }
+  return calls.sort((a, b) => a.startIndex - b.startIndex);
+}
+
+export interface ImportSite {
+  /** `import`: an import/use statement. `module`: a Rust `mod name;` pulling in another file. */
+  kind: 'import' | 'module';
+  /**
+   * What is imported, as written: a JS/TS module specifier, a Python dotted module (leading dots
+   * for relative imports), or one expanded Rust use path such as `crate::a::b`.
+   */
+  specifier: string;
+  /** Python `from x import a, b`: the imported names, which may themselves be submodules */
+  names?: string[];
+  line: number;
+}
+
+const IMPORT_NODE_TYPES = [
+  'import_statement',
+  'export_statement',
+  'import_from_statement',
+  'use_declaration',
+  'mod_item'
+] as const;
+
+function unquote(text: string): string {
+  return text.replace(/^['"`]|['"`]$/g, '');
+}
+
+/** Expand a Rust use tree (`a::{b, c::{d, self}}`) into plain paths, aliases dropped. */
+export function expandRustUseTree(text: string): string[] {
+  const source = text.replace(/\s+/g, ' ').trim();
+  const expand = (tree: string, prefix: string): string[] => {
+    const trimmed = tree.trim().replace(/\s+as\s+\w+$/, '');
+    const brace = trimmed.indexOf('{');
+    if (brace === -1) {
+      if (trimmed === 'self') return prefix ? [prefix] : [];
+      return [prefix ? `${prefix}::${trimmed}` : trimmed];
+    }
+
+    const head = trimmed.slice(0, brace).replace(/::\s*$/, '').trim();
+    const base = head ? (prefix ? `${prefix}::${head}` : head) : prefix;
+    const body = trimmed.slice(brace + 1, trimmed.lastIndexOf('}'));
+    const parts: string[] = [];
+    let depth = 0;
+    let current = '';
+    for (const char of body) {
+      if (char === '{') depth++;
+      if (char === '}') depth--;
+      if (char === ',' && depth === 0) {
+        parts.push(current);
+        current = '';
+      } else {
+        current += char;
+      }
+    }
+    parts.push(current);
+    return parts.filter((part) => part.trim()).flatMap((part) => expand(part, base));
+  };
+  return expand(source, '');
+}
+
+function pythonImportNames(node: Node, field: string): string[] {
+  return node
+    .childrenForFieldName(field)
+    .map((child) => (child?.type === 'aliased_import' ? child.childForFieldName('name') : child))
+    .filter((child): child is Node => Boolean(child))
+    .map((child) => child.text);
+}
+
+/** Import and use statements under `root`, as written. Specifiers are not resolved to files. */
+function collectImportSites(root: Node): ImportSite[] {
+  const sites: ImportSite[] = [];
+  for (const node of root.descendantsOfType([...IMPORT_NODE_TYPES])) {
+    if (!node) continue;
+    const line = node.startPosition.row + 1;
+
+    switch (node.type) {
+      case 'import_statement': {
+        const source = node.childForFieldName('source');
+        if (source) {
+          sites.push({ kind: 'import', specifier: unquote(source.text), line });
+        } else {
+          // Python `import a.b, c as d`
+          for (const name of pythonImportNames(node, 'name')) {
+            sites.push({ kind: 'import', specifier: name, line });
+          }
+        }
+        break;
+      }
+      case 'export_statement': {
+        // Re-exports: `export { a } from './a'`
+        const source = node.childForFieldName('source');
+        if (source) sites.push({ kind: 'import', specifier: unquote(source.text), line });
+        break;
+      }
+      case 'import_from_statement': {
+        const moduleName = node.childForFieldName('module_name');
+        if (!moduleName) break;
+        const names = pythonImportNames(node, 'name');
+        sites.push({
+          kind: 'import',
+          specifier: moduleName.text.replace(/\s+/g, ''),
+          ...(names.length > 0 ? { names } : {}),
+          line
+        });
+        break;
+      }
+      case 'use_declaration': {
+        const argument = node.childForFieldName('argument');
+        if (!argument) break;
+        for (const usePath of expandRustUseTree(argument.text)) {
+          sites.push({ kind: 'import', specifier: usePath, line });
+        }
+        break;
+      }
+      case 'mod_item': {
+        // Only `mod name;` refers to another file; inline `mod name { ... }` does not.
+        const name = node.childForFieldName('name');
+        if (name && !node.childForFieldName('body')) {
+          sites.push({ kind: 'module', specifier: name.text, line });
+        }
+        break;
+      }
+    }
+  }
+  return sites;
+}
+
+const STATEMENT_BLOCK_NODE_TYPES = ['statement_block', 'block', 'compound_statement'] as const;
+
+/**
+ * Sorted 1-based lines on which a top-level statement of a function body starts: the places a
+ * long function can be cut without splitting a statement (or an `if`/loop body) in half.
+ * Returns null when Tree-sitter isn't available/supported, so callers can fall back safely.
+ */
+export async function findStatementStartLines(
+  content: string,
+  language: string
+): Promise<number[] | null> {
+  return withParsedTree(content, language, 'statement boundary scan', (root) => {
+    const lines = new Set<number>();
+    for (const block of root.descendantsOfType([...STATEMENT_BLOCK_NODE_TYPES])) {
+      if (!block?.parent || !FUNCTION_SCOPE_NODE_TYPES.has(block.parent.type)) continue;
+      for (const statement of block.namedChildren) {
+        if (statement && !statement.type.includes('comment')) {
+          lines.add(statement.startPosition.row + 1);
+        }
+      }
+    }
+    return Array.from(lines).sort((a, b) => a - b);
+  });
+}
+
+export interface ByteSpan {
+  startIndex: number;
+  endIndex: number;
+}
+
+export interface ScopedIdentifierOccurrence extends IdentifierOccurrence {
+  /** 1-based column of the identifier start */
+  column: number;
+  /** True when this occurrence declares the name (symbol name, variable, or parameter) */
+  isDefinition: boolean;
+  /** Span of the scope the name resolves to; null means file/module level */
+  bindingScope: ByteSpan | null;
+}
+
+const FUNCTION_SCOPE_NODE_TYPES = new Set([
+  'arrow_function',
+  'constructor_declaration',
+  'function_declaration',
+  'function_definition',
+  'function_expression',
+  'function_item',
+  'func_literal',
+  'generator_function',
+  'generator_function_declaration',
+  'lambda',
+  'lambda_expression',
+  'method_declaration',
+  'method_definition'
+]);
+
+const BLOCK_SCOPE_NODE_TYPES = new Set(['statement_block', 'block', 'compound_statement']);
+
+const DECLARATION_NODE_TYPES = new Set<string>([
+  ...SYMBOL_CANDIDATE_NODE_TYPES,
+  'abstract_class_declaration',
+  'const_spec',
+  'enum_specifier',
+  'function_signature',
+  'let_declaration',
+  'short_var_declaration',
+  'var_spec'
+]);
+
+function isSameNode(a: Node | null | undefined, b: Node): boolean {
+  return Boolean(
+    a && a.startIndex === b.startIndex && a.endIndex === b.endIndex && a.type === b.type
+  );
+}
+
+function isScopeNode(language: string, node: Node): boolean {
+  if (FUNCTION_SCOPE_NODE_TYPES.has(node.type)) return true;
+  // Python blocks are not scopes; only functions, lambdas and classes are.
+  if (language === 'python') return node.type === 'class_definition';
+  return BLOCK_SCOPE_NODE_TYPES.has(node.type);
+}
+
+function findEnclosingScope(language: string, node: Node | null): Node | null {
+  let cursor = node;
+  while (cursor) {
+    if (isScopeNode(language, cursor)) return cursor;
+    cursor = cursor.parent;
+  }
+  return null;
+}
+
+function isParameterIdentifier(node: Node): boolean {
+  const parent = node.parent;
+  if (!parent) return false;
+  if (parent.type === 'arrow_function') {
+    return isSameNode(parent.childForFieldName('parameter'), node);
+  }
+  if (parent.type === 'formal_parameters' || parent.type === 'parameters') {
+    return true;
+  }
+  if (!parent.type.includes('parameter')) return false;
+  return (
+    !isSameNode(parent.childForFieldName('type'), node) &&
+    !isSameNode(parent.childForFieldName('value'), node)
+  );
+}
+
+/**
+ * Classify an identifier as a declaration and return the scope node its name binds in.
+ * Returns undefined for plain references.
+ */
+function getDeclarationScope(language: string, node: Node): Node | null | undefined {
+  const parent = node.parent;
+  if (!parent) return undefined;
+
+  if (isParameterIdentifier(node)) {
+    return findEnclosingScope(language, parent);
+  }
+
+  const isNamedDeclaration =
+    DECLARATION_NODE_TYPES.has(parent.type) && isSameNode(parent.childForFieldName('name'), node);
+  const isPythonAssignment =
+    language === 'python' &&
+    parent.type === 'assignment' &&
+    isSameNode(parent.childForFieldName('left'), node);
+
+  if (!isNamedDeclaration && !isPythonAssignment) return undefined;
+
+  // The declared name is visible in the scope *around* the declaring node
+  // (a function's own name belongs to the enclosing scope, not its body).
+  const declaringNode = getSymbolRangeNode(parent);
+  return findEnclosingScope(language, declaringNode.parent);
+}
+
+function collectScopedIdentifierOccurrences(
+  root: Node,
+  language: string,
+  normalizedSymbol: string
+): ScopedIdentifierOccurrence[] {
+  const nodes = collectIdentifierNodes(root, normalizedSymbol);
+  const declarationScopes = new Set<string>();
+  const declarations = new Map<Node, Node | null>();
+  const spanKey = (node: Node | null) => (node ? `${node.startIndex}:${node.endIndex}` : 'root');
+
+  for (const node of nodes) {
+    const scope = getDeclarationScope(language, node);
+    if (scope === undefined) continue;
+    declarations.set(node, scope);
+    declarationScopes.add(spanKey(scope));
+  }
+
+  return nodes.map((node) => {
+    let bindingScope: Node | null;
+    if (declarations.has(node)) {
+      bindingScope = declarations.get(node) ?? null;
+    } else {
+      bindingScope = null;
+      let scope = findEnclosingScope(language, node.parent);
+      while (scope) {
+        if (declarationScopes.has(spanKey(scope))) {
+          bindingScope = scope;
+          break;
+        }
+        scope = findEnclosingScope(language, scope.parent);
+      }
+    }
+
+    return {
+      line: node.startPosition.row + 1,
+      column: node.startPosition.column + 1,
+      startIndex: node.startIndex,
+      endIndex: node.endIndex,
+      nodeType: node.type,
+      isDefinition: declarations.has(node),
+      bindingScope: bindingScope
+        ? { startIndex: bindingScope.startIndex, endIndex: bindingScope.endIndex }
+        : null
+    };
+  });
+}
+
+/**
+ * Like findIdentifierOccurrences, but also reports which occurrences declare the name and the
+ * scope each occurrence resolves to, so callers can separate shadowed locals from module-level
+ * symbols.
+ */
+export async function findScopedIdentifierOccurrences(
+  content: string,
+  language: string,
+  symbol: string
+): Promise<ScopedIdentifierOccurrence[] | null> {
+  const normalizedSymbol = symbol.trim();
+  if (!normalizedSymbol) {
+    return [];
+  }
+
+  return withParsedTree(content, language, 'scoped identifier scan', (root) =>
+    collectScopedIdentifierOccurrences(root, language, normalizedSymbol)
+  );
+}
+
+/**
+ * `findScopedIdentifierOccurrences` on a tree the caller already has, e.g. one kept by the
+ * symbol index. Null, like a fresh scan, when the tree has syntax errors.
+ */
+export function findScopedIdentifierOccurrencesInTree(
+  tree: Tree,
+  language: string,
+  symbol: string
+): ScopedIdentifierOccurrence[] | null {
+  const normalizedSymbol = symbol.trim();
+  if (!normalizedSymbol) {
+    return [];
+  }
+  if (treeHasError(tree)) {
+    return null;
+  }
+  try {
+    return collectScopedIdentifierOccurrences(tree.rootNode, language, normalizedSymbol);
+  } catch (error) {
+    logTreeSitterFailure('scoped identifier scan', language, error);
+    return null;
+  }
+}
+
+export class TreeSitterQueryError extends Error {
+  constructor(
+    message: string,
+    /** Offset into the query source where compilation failed, when the grammar reports it */
+    readonly offset?: number
+  ) {
+    super(message);
+    this.name = 'TreeSitterQueryError';
+  }
+}
+
+/** A query compiled against one grammar; release it with `dispose()`. */
+export interface CompiledTreeSitterQuery {
+  language: string;
+  query: Query;
+  dispose: () => void;
+}
+
+export interface TreeSitterQueryCapture {
+  /** Capture name without the leading `@` */
+  name: string;
+  startLine: number;
+  endLine: number;
+  startColumn: number;
+  text: string;
+}
+
+export interface TreeSitterQueryResult {
+  captures: TreeSitterQueryCapture[];
+  /** Set when the deadline cancelled the query before it finished this file */
+  timedOut: boolean;
+}
+
+/**
+ * Compile an S-expression query for `language`. Malformed queries (bad node or field names,
+ * unbalanced parentheses, unknown predicates) throw TreeSitterQueryError instead of crashing.
+ */
+export async function compileTreeSitterQuery(
+  language: string,
+  source: string
+): Promise<CompiledTreeSitterQuery> {
+  if (!supportsTreeSitter(language)) {
+    throw new TreeSitterQueryError(`No tree-sitter grammar is configured for '${language}'.`);
+  }
+
+  await ensureParserInitialized();
+  const grammar = await loadLanguage(language);
+  let query: Query;
+  try {
+    query = new Query(grammar, source);
+  } catch (error) {
+    const offset = (error as { index?: unknown }).index;
+    throw new TreeSitterQueryError(
+      error instanceof Error ? error.message : String(error),
+      typeof offset === 'number' ? offset : undefined
+    );
+  }
+
+  return {
+    language,
+    query,
+    dispose: () => query.delete()
+  };
+}
+
+/**
+ * Run a compiled query over `content` and return its captures in document order. Trees with
+ * syntax errors are still queried. `deadline` (epoch milliseconds) cancels a runaway query.
+ * Returns null when the file cannot be parsed.
+ */
+export async function runTreeSitterQuery(
+  content: string,
+  compiled: CompiledTreeSitterQuery,
+  options: { deadline: number }
+): Promise<TreeSitterQueryResult | null> {
+  const tree = await parseTree(content, compiled.language, 'query');
+  if (!tree) {
+    return null;
+  }
+
+  try {
+    let timedOut = false;
+    const captures = compiled.query.captures(tree.rootNode, {
+      progressCallback: () => {
+        timedOut = Date.now() > options.deadline;
+        return timedOut;
+      }
+    });
+
+    return {
+      captures: captures.map(({ name, node }) => ({
+        name,
+        startLine: node.startPosition.row + 1,
+        endLine: node.endPosition.row + 1,
+        startColumn: node.startPosition.column + 1,
+        text: extractNodeContent(node, content)
+      })),
+      timedOut
+    };
+  } catch (error) {
+    logTreeSitterFailure('query', compiled.language, error);
     return null;
+  } finally {
+    tree.delete();
   }
 }
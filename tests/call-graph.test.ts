@@ -0,0 +1,142 @@
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { CallGraph, collectFileCallEdges, type CallEdge } from '../src/core/call-graph.js';
+import {
+  CODEBASE_CONTEXT_DIRNAME,
+  RELATIONSHIPS_FILENAME
+} from '../src/constants/codebase-context.js';
+import {
+  extractTreeSitterSymbols,
+  setSymbolExtractionCache,
+  type TreeSitterSymbolExtraction
+} from '../src/utils/tree-sitter.js';
+import { setSpanSink, type SpanRecord } from '../src/utils/tracing.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const RUST_SOURCE = [
+  'fn add(a: i64, b: i64) -> i64 {',
+  '    a + b',
+  '}',
+  '',
+  'fn sum(values: &[i64]) -> i64 {',
+  '    values.iter().fold(0, |acc, v| add(acc, *v))',
+  '}',
+  '',
+  'fn report(values: &[i64]) {',
+  '    println!("{}", sum(values));',
+  '    let doubled = add(sum(values), sum(values));',
+  '}',
+  '',
+  'fn main() {',
+  '    report(&[1, 2, 3]);',
+  '}'
+].join('\n');
+
+function edge(caller: string, callee: string, line: number): CallEdge {
+  return { caller, callee, file: 'src/lib.rs', line };
+}
+
+describe('collectFileCallEdges', () => {
+  it('attributes Rust calls to their enclosing function', async () => {
+    const edges = await collectFileCallEdges('src/lib.rs', RUST_SOURCE, 'rust');
+    const graph = new CallGraph(edges);
+
+    expect(graph.callersOf('add').map((e) => `${e.caller}@${e.file}:${e.line}`)).toEqual([
+      'sum@src/lib.rs:6',
+      'report@src/lib.rs:11'
+    ]);
+    expect(graph.calleesOf('main').map((e) => e.callee)).toEqual(['report']);
+    expect(graph.calleesOf('sum').map((e) => e.callee)).toContain('fold');
+  });
+
+  it('keeps the edges of functions outside a syntax error', async () => {
+    const broken = `${RUST_SOURCE}\n\nfn unfinished( {\n`;
+    const graph = new CallGraph(await collectFileCallEdges('src/lib.rs', broken, 'rust'));
+    expect(graph.calleesOf('main').map((e) => e.callee)).toEqual(['report']);
+  });
+
+  it('reuses the cached extraction instead of parsing the file again', async () => {
+    const entries = new Map<string, TreeSitterSymbolExtraction>();
+    const parses: SpanRecord[] = [];
+    setSymbolExtractionCache({
+      get: (key) => entries.get(key),
+      set: (key, extraction) => void entries.set(key, extraction)
+    });
+    setSpanSink((span) => {
+      if (span.name === 'treesitter.parse') parses.push(span);
+    });
+    try {
+      await extractTreeSitterSymbols(RUST_SOURCE, 'rust');
+      const edges = await collectFileCallEdges('src/lib.rs', RUST_SOURCE, 'rust');
+      expect(edges.length).toBeGreaterThan(0);
+      expect(parses).toHaveLength(1);
+    } finally {
+      setSpanSink(null);
+      setSymbolExtractionCache(null);
+    }
+  });
+
+  it('returns no edges for languages without a grammar', async () => {
+    expect(await collectFileCallEdges('notes.txt', 'add(1, 2)', 'plaintext')).toEqual([]);
+  });
+});
+
+describe('CallGraph.traverse', () => {
+  const graph = new CallGraph([
+    edge('sum', 'add', 6),
+    edge('report', 'add', 11),
+    edge('report', 'sum', 10),
+    edge('main', 'report', 15),
+    edge('add', 'add', 2)
+  ]);
+
+  it('follows callers transitively without repeating edges', () => {
+    const direct = graph.traverse('add', 'callers');
+    expect(direct.edges.map((e) => e.caller)).toEqual(['sum', 'report', 'add']);
+
+    const transitive = graph.traverse('add', 'callers', 3);
+    expect(transitive.edges.map((e) => `${e.caller}->${e.callee}`)).toEqual([
+      'sum->add',
+      'report->add',
+      'add->add',
+      'report->sum',
+      'main->report'
+    ]);
+    expect(transitive.truncated).toBe(false);
+  });
+
+  it('stops at the edge limit and reports truncation', () => {
+    const limited = graph.traverse('add', 'callers', 5, 2);
+    expect(limited.edges).toHaveLength(2);
+    expect(limited.truncated).toBe(true);
+  });
+});
+
+describe('CallGraph.load', () => {
+  let tempRoot: string;
+
+  beforeEach(async () => {
+    tempRoot = await fs.mkdtemp(path.join(os.tmpdir(), 'call-graph-'));
+    await fs.mkdir(path.join(tempRoot, CODEBASE_CONTEXT_DIRNAME), { recursive: true });
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(tempRoot);
+  });
+
+  it('reads edges from the relationships sidecar and returns null for older indexes', async () => {
+    const relationshipsPath = path.join(tempRoot, CODEBASE_CONTEXT_DIRNAME, RELATIONSHIPS_FILENAME);
+    await fs.writeFile(relationshipsPath, JSON.stringify({ graph: { imports: {} } }));
+    expect(await CallGraph.load(tempRoot)).toBeNull();
+
+    await fs.writeFile(
+      relationshipsPath,
+      JSON.stringify({ callGraph: { edges: [edge('sum', 'add', 6), { caller: 'broken' }] } })
+    );
+    const graph = await CallGraph.load(tempRoot);
+    expect(graph?.callersOf('add')).toEqual([edge('sum', 'add', 6)]);
+    expect(graph?.edges).toHaveLength(1);
+  });
+});
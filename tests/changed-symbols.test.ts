@@ -0,0 +1,196 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { execFileSync } from 'child_process';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import { dispatchTool } from '../src/tools/index.js';
+import type { ToolContext } from '../src/tools/types.js';
+import { CODEBASE_CONTEXT_DIRNAME } from '../src/constants/codebase-context.js';
+import { parseUnifiedDiff } from '../src/utils/git-diff.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const ORIGINAL_MATH = [
+  'export function add(a: number, b: number): number {',
+  '  return a + b;',
+  '}',
+  '',
+  'export function sub(a: number, b: number): number {',
+  '  return a - b;',
+  '}',
+  '',
+  'export class Calc {',
+  '  total(values: number[]): number {',
+  '    return values.length;',
+  '  }',
+  '}',
+  ''
+].join('\n');
+
+function git(cwd: string, ...args: string[]): void {
+  execFileSync('git', ['-c', 'user.name=test', '-c', 'user.email=test@example.com', ...args], {
+    cwd,
+    stdio: 'ignore'
+  });
+}
+
+function contextFor(rootPath: string): ToolContext {
+  const baseDir = path.join(rootPath, CODEBASE_CONTEXT_DIRNAME);
+  return {
+    indexState: { status: 'ready' },
+    paths: {
+      baseDir,
+      memory: path.join(baseDir, 'memory.json'),
+      intelligence: path.join(baseDir, 'intelligence.json'),
+      keywordIndex: path.join(baseDir, 'index.json'),
+      vectorDb: path.join(baseDir, 'index')
+    },
+    rootPath,
+    performIndexing: () => undefined
+  };
+}
+
+async function call(name: string, args: Record<string, unknown>, rootPath: string) {
+  const result = await dispatchTool(name, args, contextFor(rootPath));
+  return { isError: result.isError, payload: JSON.parse(result.content![0].text) };
+}
+
+describe('parseUnifiedDiff', () => {
+  it('reads head-side ranges, pure deletions, renames and deleted files', () => {
+    const diff = [
+      'diff --git a/d.txt b/d.txt',
+      'deleted file mode 100644',
+      '--- a/d.txt',
+      '+++ /dev/null',
+      '@@ -1 +0,0 @@',
+      '-gone',
+      'diff --git a/r.txt b/r2.txt',
+      'similarity index 100%',
+      'rename from r.txt',
+      'rename to r2.txt',
+      'diff --git a/x.txt b/x.txt',
+      '--- a/x.txt',
+      '+++ b/x.txt',
+      '@@ -2 +1,0 @@ a',
+      '-b',
+      '@@ -9,0 +9,2 @@',
+      '+--- not a header',
+      '+y'
+    ].join('\n');
+
+    expect(parseUnifiedDiff(diff)).toEqual([
+      { file: 'd.txt', status: 'deleted', ranges: [] },
+      { file: 'r2.txt', status: 'renamed', previousFile: 'r.txt', ranges: [] },
+      {
+        file: 'x.txt',
+        status: 'modified',
+        ranges: [
+          { startLine: 1, endLine: 1 },
+          { startLine: 9, endLine: 10 }
+        ]
+      }
+    ]);
+  });
+});
+
+describe('changed_symbols tool', () => {
+  let tempDir: string;
+
+  beforeEach(async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'changed-symbols-'));
+    await fs.writeFile(path.join(tempDir, 'math.ts'), ORIGINAL_MATH);
+    await fs.writeFile(
+      path.join(tempDir, 'stable.ts'),
+      'export function addAll(values: number[]): number {\n  return values.length;\n}\n'
+    );
+    git(tempDir, 'init', '-q');
+    git(tempDir, 'add', '.');
+    git(tempDir, 'commit', '-q', '-m', 'initial');
+
+    await fs.writeFile(
+      path.join(tempDir, 'math.ts'),
+      ORIGINAL_MATH.replace('return a - b;', 'return b - a;').replace(
+        'return values.length;',
+        'return values.reduce((sum, value) => sum + value, 0);'
+      )
+    );
+    await fs.writeFile(path.join(tempDir, 'fresh.py'), 'def fresh():\n    return 1\n');
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+  });
+
+  function symbolNames(payload: { symbols: Array<{ name: string; parent?: string }> }) {
+    return payload.symbols.map((s) => (s.parent ? `${s.parent}.${s.name}` : s.name));
+  }
+
+  it('reports symbols the working tree changed, untracked files included', async () => {
+    const { payload } = await call('changed_symbols', {}, tempDir);
+
+    expect(payload.status).toBe('success');
+    expect(payload.head).toBe('working tree');
+    expect(symbolNames(payload)).toEqual(['fresh', 'sub', 'Calc.total']);
+    expect(payload.files.map((f: { file: string; status: string }) => [f.file, f.status])).toEqual([
+      ['fresh.py', 'added'],
+      ['math.ts', 'modified']
+    ]);
+  });
+
+  it('compares two refs and filters by kind', async () => {
+    git(tempDir, 'add', '.');
+    git(tempDir, 'commit', '-q', '-m', 'change');
+
+    const { payload } = await call(
+      'changed_symbols',
+      { base: 'HEAD~1', head: 'HEAD', kinds: ['method'] },
+      tempDir
+    );
+    expect(payload.head).toBe('HEAD');
+    expect(symbolNames(payload)).toEqual(['Calc.total']);
+  });
+
+  it('returns clear errors for unknown refs and non-repositories', async () => {
+    const badRef = await call('changed_symbols', { base: 'no-such-branch' }, tempDir);
+    expect(badRef.isError).toBe(true);
+    expect(badRef.payload.errorCode).toBe('invalid_ref');
+
+    const optionLike = await call('changed_symbols', { base: '--output=/tmp/x' }, tempDir);
+    expect(optionLike.payload.errorCode).toBe('invalid_ref');
+
+    const plainDir = await fs.mkdtemp(path.join(os.tmpdir(), 'changed-symbols-plain-'));
+    try {
+      const notRepo = await call('changed_symbols', {}, plainDir);
+      expect(notRepo.isError).toBe(true);
+      expect(notRepo.payload.errorCode).toBe('not_a_git_repository');
+    } finally {
+      await rmWithRetries(plainDir);
+    }
+  });
+
+  it('scopes search_codebase to changed lines with diff', async () => {
+    await new CodebaseIndexer({
+      rootPath: tempDir,
+      config: { skipEmbedding: true }
+    }).index();
+
+    const search = async (extra: Record<string, unknown>) => {
+      const { payload } = await call(
+        'search_codebase',
+        { query: 'values', gitBoost: false, limit: 10, ...extra },
+        tempDir
+      );
+      return [
+        ...new Set(
+          payload.results.map((r: { file: string }) => path.basename(r.file.split(':')[0]))
+        )
+      ].sort();
+    };
+
+    expect(await search({})).toEqual(['math.ts', 'stable.ts']);
+    expect(await search({ diff: {} })).toEqual(['math.ts']);
+  });
+});
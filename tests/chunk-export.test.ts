@@ -0,0 +1,121 @@
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import {
+  exportSemanticChunks,
+  readIndexedFiles,
+  splitLineRange,
+  type SemanticChunk
+} from '../src/core/chunk-export.js';
+import { CODEBASE_CONTEXT_DIRNAME, MANIFEST_FILENAME } from '../src/constants/codebase-context.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const SOURCE = [
+  'export class Cart {',
+  '  items: string[] = [];',
+  '',
+  '  add(item: string) {',
+  '    this.items.push(item);',
+  '  }',
+  '}',
+  '',
+  'export function checkout(cart: Cart) {',
+  '  const total = cart.items.length;',
+  '  if (total === 0) {',
+  '    return 0;',
+  '  }',
+  '  const fee = 2;',
+  '  const tax = 3;',
+  '  return total + fee + tax;',
+  '}'
+].join('\n');
+
+async function collect(
+  root: string,
+  options?: Parameters<typeof exportSemanticChunks>[2]
+): Promise<SemanticChunk[]> {
+  const chunks: SemanticChunk[] = [];
+  for await (const chunk of exportSemanticChunks(root, ['src/cart.ts'], options)) {
+    chunks.push(chunk);
+  }
+  return chunks;
+}
+
+describe('splitLineRange', () => {
+  it('cuts before statement boundaries and falls back to the line limit', () => {
+    expect(splitLineRange(1, 12, [2, 5, 8, 11], 5)).toEqual([
+      [1, 4],
+      [5, 7],
+      [8, 12]
+    ]);
+    expect(splitLineRange(1, 12, [], 5, 2)).toEqual([
+      [1, 5],
+      [4, 8],
+      [7, 11],
+      [10, 12]
+    ]);
+  });
+});
+
+describe('exportSemanticChunks', () => {
+  let tempRoot: string;
+
+  beforeEach(async () => {
+    tempRoot = await fs.mkdtemp(path.join(os.tmpdir(), 'chunk-export-'));
+    await fs.mkdir(path.join(tempRoot, 'src'), { recursive: true });
+    await fs.writeFile(path.join(tempRoot, 'src', 'cart.ts'), SOURCE);
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(tempRoot);
+  });
+
+  it('emits one chunk per symbol with parent, span and a stable id', async () => {
+    const chunks = await collect(tempRoot);
+
+    expect(chunks.map((c) => [c.name, c.kind, c.parent, c.startLine, c.endLine])).toEqual([
+      ['Cart', 'class', undefined, 1, 3],
+      ['add', 'method', 'Cart', 4, 6],
+      ['checkout', 'function', undefined, 9, 17]
+    ]);
+    expect(chunks[1].text).toBe('  add(item: string) {\n    this.items.push(item);\n  }');
+
+    const again = await collect(tempRoot);
+    expect(again.map((c) => c.id)).toEqual(chunks.map((c) => c.id));
+
+    // Moving a symbol changes its id even when the text is the same
+    await fs.writeFile(path.join(tempRoot, 'src', 'cart.ts'), `\n${SOURCE}`);
+    const moved = await collect(tempRoot);
+    expect(moved[1].text).toBe(chunks[1].text);
+    expect(moved[1].id).not.toBe(chunks[1].id);
+  });
+
+  it('splits long symbols before top-level statements, with overlap', async () => {
+    const chunks = (await collect(tempRoot, { maxLines: 4, overlapLines: 1 })).filter(
+      (c) => c.name === 'checkout'
+    );
+
+    expect(chunks.map((c) => [c.startLine, c.endLine, c.part, c.parts])).toEqual([
+      [9, 10, 1, 4],
+      [10, 13, 2, 4],
+      [13, 15, 3, 4],
+      [15, 17, 4, 4]
+    ]);
+  });
+
+  it('lists files from the index manifest', async () => {
+    expect(await readIndexedFiles(tempRoot)).toBeNull();
+
+    await fs.mkdir(path.join(tempRoot, CODEBASE_CONTEXT_DIRNAME), { recursive: true });
+    await fs.writeFile(
+      path.join(tempRoot, CODEBASE_CONTEXT_DIRNAME, MANIFEST_FILENAME),
+      JSON.stringify({
+        version: 1,
+        generatedAt: '2026-01-01T00:00:00.000Z',
+        files: { 'src/cart.ts': 'a', 'README.md': 'b' }
+      })
+    );
+    expect(await readIndexedFiles(tempRoot)).toEqual(['README.md', 'src/cart.ts']);
+  });
+});
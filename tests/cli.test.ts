@@ -81,6 +81,17 @@ describe('CLI', () => {
     });
   });
 
+  it('search --no-git-boost turns off git activity ranking', async () => {
+    toolMocks.dispatchTool.mockResolvedValue({
+      content: [{ type: 'text', text: JSON.stringify({ ok: true }) }]
+    });
+
+    await handleCliCommand(['search', '--query', 'foo', '--no-git-boost', '--json']);
+
+    const [, toolArgs] = toolMocks.dispatchTool.mock.calls[0] ?? [];
+    expect(toolArgs).toEqual({ query: 'foo', includeSnippets: true, gitBoost: false });
+  });
+
   it('patterns errors on invalid category', async () => {
     await expect(handleCliCommand(['patterns', '--category', 'nope'])).rejects.toThrow(/process\.exit:1/);
     expect(toolMocks.dispatchTool).not.toHaveBeenCalled();
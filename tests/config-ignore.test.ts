@@ -0,0 +1,82 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import { readIndexedFiles } from '../src/core/chunk-export.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const FILES: Record<string, string> = {
+  'src/app.ts': 'export const app = 1;\n',
+  'src/generated.ts': 'export const generated = 1;\n',
+  'src/__snapshots__/app.snap.ts': 'export const snapshot = 1;\n',
+  'tests/fixtures/large.ts': 'export const large = 1;\n',
+  'tests/fixtures/golden.ts': 'export const golden = 1;\n',
+  'tests/app.test.ts': 'export const test = 1;\n',
+  'vendor/lib/patched.ts': 'export const patched = 1;\n',
+  'vendor/lib/other.ts': 'export const other = 1;\n',
+  '.gitignore': 'src/generated.ts\nvendor/\n'
+};
+
+describe('config-level ignore and includeOverride', () => {
+  let tempDir: string;
+
+  beforeEach(async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'config-ignore-'));
+    for (const [file, content] of Object.entries(FILES)) {
+      await fs.mkdir(path.dirname(path.join(tempDir, file)), { recursive: true });
+      await fs.writeFile(path.join(tempDir, file), content);
+    }
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+  });
+
+  async function indexWith(config: { ignore?: string[]; includeOverride?: string[] }) {
+    await new CodebaseIndexer({
+      rootPath: tempDir,
+      config: { skipEmbedding: true, respectGitignore: true, ...config }
+    }).index();
+    return readIndexedFiles(tempDir);
+  }
+
+  it('skips files matching ignore globs on top of .gitignore', async () => {
+    expect(await indexWith({ ignore: ['tests/fixtures/**', '**/__snapshots__/**'] })).toEqual([
+      'src/app.ts',
+      'tests/app.test.ts'
+    ]);
+  });
+
+  it('re-includes includeOverride matches past ignore and .gitignore', async () => {
+    const files = await indexWith({
+      ignore: ['tests/fixtures/**', '**/__snapshots__/**'],
+      includeOverride: ['tests/fixtures/golden.ts', 'src/generated.ts', 'vendor/lib/patched.ts']
+    });
+
+    expect(files).toEqual([
+      'src/app.ts',
+      'src/generated.ts',
+      'tests/app.test.ts',
+      'tests/fixtures/golden.ts',
+      'vendor/lib/patched.ts'
+    ]);
+  });
+
+  it('walks into ignored directories that a wildcard override reaches', async () => {
+    const files = await indexWith({
+      ignore: ['tests/fixtures/**', '**/__snapshots__/**', 'vendor/**'],
+      includeOverride: ['tests/**/golden.ts', 'vendor/*/patched.ts']
+    });
+
+    expect(files).toEqual([
+      'src/app.ts',
+      'tests/app.test.ts',
+      'tests/fixtures/golden.ts',
+      'vendor/lib/patched.ts'
+    ]);
+  });
+});
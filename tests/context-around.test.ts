@@ -0,0 +1,38 @@
+import { describe, expect, it } from 'vitest';
+import path from 'path';
+import { fileURLToPath } from 'url';
+import { getContextAround } from '../src/core/context-around.js';
+
+const fixturesDir = path.join(path.dirname(fileURLToPath(import.meta.url)), 'fixtures', 'grammars');
+
+describe('getContextAround', () => {
+  it('clamps the window at the top and bottom of the file', async () => {
+    const top = await getContextAround(fixturesDir, 'rust.rs', 2, { before: 5, after: 1 });
+    if (top.status !== 'success') throw new Error(top.message);
+    expect([top.startLine, top.endLine, top.totalLines]).toEqual([1, 3, 25]);
+    expect(top.lines[0]).toEqual({ line: 1, text: 'struct Calculator {' });
+
+    const bottom = await getContextAround(fixturesDir, 'rust.rs', 24, { before: 1, after: 5 });
+    if (bottom.status !== 'success') throw new Error(bottom.message);
+    expect([bottom.startLine, bottom.endLine]).toEqual([23, 25]);
+    expect(bottom.lines.map((entry) => entry.line)).toEqual([23, 24, 25]);
+    expect(bottom.lines[2].text).toBe('}');
+  });
+
+  it('snaps outward to the innermost enclosing symbol', async () => {
+    const result = await getContextAround(fixturesDir, 'rust.rs', 11, {
+      before: 0,
+      after: 0,
+      snapToSymbol: true
+    });
+    if (result.status !== 'success') throw new Error(result.message);
+
+    expect(result.symbol).toEqual({ name: 'double', kind: 'method', startLine: 10, endLine: 12 });
+    expect([result.startLine, result.endLine]).toEqual([10, 12]);
+  });
+
+  it('rejects lines past the end of the file', async () => {
+    const result = await getContextAround(fixturesDir, 'rust.rs', 26);
+    expect(result.status).toBe('error');
+  });
+});
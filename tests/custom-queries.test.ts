@@ -0,0 +1,80 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { loadCustomSymbolQueries } from '../src/core/custom-queries.js';
+import { extractTreeSitterSymbols, setCustomSymbolQueries } from '../src/utils/tree-sitter.js';
+import type { CustomSymbolQuery } from '../src/utils/tree-sitter.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const HANDLER_QUERY = `
+(macro_invocation
+  macro: (identifier) @macro
+  (#eq? @macro "handler")
+  (token_tree . (identifier) @name)) @handler
+`;
+
+const RUST_SOURCE = [
+  'fn helper() -> u32 {',
+  '    1',
+  '}',
+  '',
+  'handler!(get_user, "/users/:id");',
+  'handler!(list_users, "/users");',
+  'other!(not_a_handler);',
+  ''
+].join('\n');
+
+describe('custom symbol queries', () => {
+  let tempDir: string;
+  let loaded: CustomSymbolQuery[] = [];
+
+  beforeEach(async () => {
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'custom-queries-'));
+  });
+
+  afterEach(async () => {
+    setCustomSymbolQueries([]);
+    for (const query of loaded) query.compiled.dispose();
+    loaded = [];
+    await rmWithRetries(tempDir);
+  });
+
+  it('merges captures mapped to a kind into the extracted symbols', async () => {
+    await fs.writeFile(path.join(tempDir, 'rust.scm'), HANDLER_QUERY);
+    await fs.writeFile(path.join(tempDir, 'kinds.json'), JSON.stringify({ handler: 'function' }));
+
+    const { queries, errors } = await loadCustomSymbolQueries(tempDir);
+    loaded = queries;
+    expect(errors).toEqual([]);
+    setCustomSymbolQueries(queries);
+
+    const extraction = await extractTreeSitterSymbols(RUST_SOURCE, 'rust');
+    const symbols = extraction?.symbols.map(({ name, kind, startLine }) => [name, kind, startLine]);
+    expect(symbols).toEqual([
+      ['helper', 'function', 1],
+      ['get_user', 'function', 5],
+      ['list_users', 'function', 6]
+    ]);
+  });
+
+  it('keeps the built-in symbols when no query is installed', async () => {
+    const extraction = await extractTreeSitterSymbols(RUST_SOURCE, 'rust');
+    expect(extraction?.symbols.map((symbol) => symbol.name)).toEqual(['helper']);
+  });
+
+  it('reports invalid query files and still loads the valid ones', async () => {
+    await fs.writeFile(path.join(tempDir, 'rust.scm'), '(macro_invocation) @definition.function');
+    await fs.writeFile(path.join(tempDir, 'python.scm'), '(no_such_node) @function');
+    await fs.writeFile(path.join(tempDir, 'go.scm'), '(function_declaration) @unmapped');
+    await fs.writeFile(path.join(tempDir, 'cobol.scm'), '(program) @function');
+
+    const { queries, errors } = await loadCustomSymbolQueries(tempDir);
+    loaded = queries;
+    expect(queries.map((query) => [query.source, query.kinds])).toEqual([
+      ['rust.scm', { 'definition.function': 'function' }]
+    ]);
+    expect(errors.map((error) => error.file)).toEqual(['cobol.scm', 'go.scm', 'python.scm']);
+    expect(errors[0].message).toContain("No tree-sitter grammar is configured for 'cobol'");
+  });
+});
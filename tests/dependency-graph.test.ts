@@ -0,0 +1,188 @@
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import {
+  buildDependencyGraph,
+  collectFileImports,
+  DependencyGraph,
+  type FileImports
+} from '../src/core/dependency-graph.js';
+import { expandRustUseTree } from '../src/utils/tree-sitter.js';
+import {
+  CODEBASE_CONTEXT_DIRNAME,
+  RELATIONSHIPS_FILENAME
+} from '../src/constants/codebase-context.js';
+import { rmWithRetries } from './test-helpers.js';
+
+async function importsOf(
+  sources: Record<string, { language: string; content: string }>
+): Promise<Map<string, FileImports>> {
+  const importsByFile = new Map<string, FileImports>();
+  for (const [file, { language, content }] of Object.entries(sources)) {
+    importsByFile.set(file, { language, sites: await collectFileImports(content, language) });
+  }
+  return importsByFile;
+}
+
+describe('expandRustUseTree', () => {
+  it('flattens nested groups and drops aliases', () => {
+    expect(expandRustUseTree('crate::{a::{b, c as d}, e}')).toEqual([
+      'crate::a::b',
+      'crate::a::c',
+      'crate::e'
+    ]);
+    expect(expandRustUseTree('std::io::*')).toEqual(['std::io::*']);
+  });
+});
+
+describe('buildDependencyGraph', () => {
+  it('resolves relative TypeScript imports and keeps packages unresolved', async () => {
+    const files = ['src/app.ts', 'src/a.ts', 'src/lib/index.ts', 'src/types.ts'];
+    const importsByFile = await importsOf({
+      'src/app.ts': {
+        language: 'typescript',
+        content: [
+          "import React from 'react';",
+          "import { a } from './a.js';",
+          "import { lib } from './lib';",
+          "export type { Shape } from './types.js';",
+          ''
+        ].join('\n')
+      }
+    });
+
+    const { edges, unresolved } = buildDependencyGraph(importsByFile, files);
+    expect(edges).toEqual([
+      { from: 'src/app.ts', to: 'src/a.ts', line: 2 },
+      { from: 'src/app.ts', to: 'src/lib/index.ts', line: 3 },
+      { from: 'src/app.ts', to: 'src/types.ts', line: 4 }
+    ]);
+    expect(unresolved).toEqual({ 'src/app.ts': ['react'] });
+  });
+
+  it('resolves Python absolute, relative and submodule imports', async () => {
+    const files = ['pkg/__init__.py', 'pkg/models.py', 'pkg/views.py', 'pkg/utils/text.py'];
+    const importsByFile = await importsOf({
+      'pkg/views.py': {
+        language: 'python',
+        content: [
+          'import os',
+          'from . import models',
+          'from pkg.utils import text',
+          'from requests import get',
+          ''
+        ].join('\n')
+      }
+    });
+
+    const { edges, unresolved } = buildDependencyGraph(importsByFile, files);
+    expect(edges.map((edge) => edge.to)).toEqual(['pkg/models.py', 'pkg/utils/text.py']);
+    expect(unresolved).toEqual({ 'pkg/views.py': ['os', 'requests'] });
+  });
+
+  it('resolves Rust mod declarations and crate paths to module files', async () => {
+    const files = ['src/lib.rs', 'src/util.rs', 'src/net/mod.rs', 'src/net/client.rs'];
+    const importsByFile = await importsOf({
+      'src/lib.rs': {
+        language: 'rust',
+        content: 'mod util;\nmod net;\nuse serde::Serialize;\n'
+      },
+      'src/net/client.rs': {
+        language: 'rust',
+        content: 'use crate::util::helper;\nuse super::Connection;\n'
+      }
+    });
+
+    const { edges, unresolved } = buildDependencyGraph(importsByFile, files);
+    expect(edges).toEqual([
+      { from: 'src/lib.rs', to: 'src/util.rs', line: 1 },
+      { from: 'src/lib.rs', to: 'src/net/mod.rs', line: 2 },
+      { from: 'src/net/client.rs', to: 'src/util.rs', line: 1 },
+      { from: 'src/net/client.rs', to: 'src/net/mod.rs', line: 2 }
+    ]);
+    expect(unresolved).toEqual({ 'src/lib.rs': ['serde'] });
+  });
+});
+
+describe('collectFileImports', () => {
+  it('keeps the imports of a file with a syntax error', async () => {
+    const content = "import { a } from './a.js';\nexport function broken( {\n";
+    expect(await collectFileImports(content, 'typescript')).toEqual([
+      { kind: 'import', specifier: './a.js', line: 1 }
+    ]);
+  });
+});
+
+describe('DependencyGraph', () => {
+  const cyclic = new DependencyGraph(
+    [
+      { from: 'a.ts', to: 'b.ts', line: 1 },
+      { from: 'b.ts', to: 'a.ts', line: 1 },
+      { from: 'b.ts', to: 'c.ts', line: 2 },
+      { from: 'c.ts', to: 'd.ts', line: 1 }
+    ],
+    { 'a.ts': ['react'] }
+  );
+
+  it('walks import cycles without looping and honours depth', () => {
+    expect(cyclic.dependenciesOf('b.ts').map((edge) => edge.to)).toEqual(['a.ts', 'c.ts']);
+    expect(cyclic.dependentsOf('a.ts').map((edge) => edge.from)).toEqual(['b.ts']);
+
+    const oneHop = cyclic.subgraph('a.ts', 'dependencies', 1);
+    expect(oneHop.edges.map((edge) => `${edge.from}>${edge.to}`)).toEqual(['a.ts>b.ts']);
+    expect(oneHop.unresolved).toEqual({ 'a.ts': ['react'] });
+
+    const all = cyclic.subgraph('a.ts', 'both', 5);
+    expect(all.edges.map((edge) => `${edge.from}>${edge.to}`)).toEqual([
+      'a.ts>b.ts',
+      'b.ts>a.ts',
+      'b.ts>c.ts',
+      'c.ts>d.ts'
+    ]);
+    expect(all.truncated).toBe(false);
+
+    const limited = cyclic.subgraph('a.ts', 'dependencies', 5, 2);
+    expect(limited.edges).toHaveLength(2);
+    expect(limited.truncated).toBe(true);
+  });
+
+  describe('load', () => {
+    let tempRoot: string;
+
+    beforeEach(async () => {
+      tempRoot = await fs.mkdtemp(path.join(os.tmpdir(), 'dependency-graph-'));
+      await fs.mkdir(path.join(tempRoot, CODEBASE_CONTEXT_DIRNAME), { recursive: true });
+    });
+
+    afterEach(async () => {
+      await rmWithRetries(tempRoot);
+    });
+
+    it('reads the sidecar and returns null for indexes without a graph', async () => {
+      const relationshipsPath = path.join(
+        tempRoot,
+        CODEBASE_CONTEXT_DIRNAME,
+        RELATIONSHIPS_FILENAME
+      );
+      await fs.writeFile(relationshipsPath, JSON.stringify({ graph: { imports: {} } }));
+      expect(await DependencyGraph.load(tempRoot)).toBeNull();
+
+      await fs.writeFile(
+        relationshipsPath,
+        JSON.stringify({
+          dependencyGraph: {
+            edges: [{ from: 'src/a.ts', to: 'src/b.ts', line: 3 }],
+            unresolved: { 'src/a.ts': ['zod'] }
+          }
+        })
+      );
+      const graph = await DependencyGraph.load(tempRoot);
+      expect(graph?.has('src/b.ts')).toBe(true);
+      expect(graph?.dependenciesOf('src/a.ts')).toEqual([
+        { from: 'src/a.ts', to: 'src/b.ts', line: 3 }
+      ]);
+      expect(graph?.unresolved).toEqual({ 'src/a.ts': ['zod'] });
+    });
+  });
+});
@@ -0,0 +1,140 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { fileURLToPath } from 'url';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import { dispatchTool } from '../src/tools/index.js';
+import type { ToolContext } from '../src/tools/types.js';
+import { CODEBASE_CONTEXT_DIRNAME } from '../src/constants/codebase-context.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const rustFixture = path.join(
+  path.dirname(fileURLToPath(import.meta.url)),
+  'fixtures',
+  'grammars',
+  'rust.rs'
+);
+
+interface OutlineEntry {
+  name: string;
+  signature?: string;
+  source?: string;
+  children?: OutlineEntry[];
+}
+
+function flatten(entries: OutlineEntry[]): OutlineEntry[] {
+  return entries.flatMap((entry) => [entry, ...flatten(entry.children ?? [])]);
+}
+
+describe('detail levels', () => {
+  let tempDir: string;
+  let ctx: ToolContext;
+
+  beforeEach(async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'detail-levels-'));
+    await fs.mkdir(path.join(tempDir, 'src'));
+    await fs.copyFile(rustFixture, path.join(tempDir, 'src', 'calc.rs'));
+    await new CodebaseIndexer({ rootPath: tempDir, config: { skipEmbedding: true } }).index();
+
+    const baseDir = path.join(tempDir, CODEBASE_CONTEXT_DIRNAME);
+    ctx = {
+      indexState: { status: 'ready' },
+      paths: {
+        baseDir,
+        memory: path.join(baseDir, 'memory.json'),
+        intelligence: path.join(baseDir, 'intelligence.json'),
+        keywordIndex: path.join(baseDir, 'index.json'),
+        vectorDb: path.join(baseDir, 'index')
+      },
+      rootPath: tempDir,
+      performIndexing: () => undefined
+    };
+  }, 30000);
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+  });
+
+  async function call(tool: string, args: Record<string, unknown>) {
+    const response = await dispatchTool(tool, args, ctx);
+    expect(response.isError).toBeFalsy();
+    const text = response.content![0].text;
+    return { text, parsed: JSON.parse(text) };
+  }
+
+  it('grows file_outline from names to signatures to source', async () => {
+    const byDefault = await call('file_outline', { file: 'src/calc.rs' });
+    const names = await call('file_outline', { file: 'src/calc.rs', detail: 'names' });
+    const signatures = await call('file_outline', { file: 'src/calc.rs', detail: 'signatures' });
+    const full = await call('file_outline', { file: 'src/calc.rs', detail: 'full' });
+
+    expect(names.parsed).toEqual(byDefault.parsed);
+    expect(names.text.length).toBeLessThan(signatures.text.length);
+    expect(signatures.text.length).toBeLessThan(full.text.length);
+
+    const nodes = flatten(signatures.parsed.outline);
+    expect(nodes.some((node) => node.signature)).toBe(true);
+    expect(nodes.some((node) => node.source)).toBe(false);
+    const add = flatten(full.parsed.outline).find((node) => node.name === 'add');
+    expect(add?.source).toBe('fn add(calc: &mut Calculator, n: i64) {\n    calc.value += n;\n}');
+  });
+
+  it('grows search_codebase results and keeps the default fields without a level', async () => {
+    const args = { query: 'Calculator', gitBoost: false, includeSnippets: true };
+    const byDefault = await call('search_codebase', args);
+    const names = await call('search_codebase', { ...args, detail: 'names' });
+    const signatures = await call('search_codebase', { ...args, detail: 'signatures' });
+    const full = await call('search_codebase', { ...args, detail: 'full' });
+
+    expect(names.parsed.results.length).toBeGreaterThan(0);
+    for (const result of names.parsed.results) {
+      expect(['file', 'qualifiedPath', 'root', 'score']).toEqual(
+        expect.arrayContaining(Object.keys(result))
+      );
+    }
+    expect(signatures.parsed.results[0]).toHaveProperty('summary');
+    expect(signatures.parsed.results.some((r: { signature?: string }) => r.signature)).toBe(true);
+    expect(full.parsed.results[0].source).toEqual(expect.any(String));
+    expect(byDefault.parsed.results[0]).toHaveProperty('snippet');
+    expect(byDefault.parsed.results[0]).not.toHaveProperty('source');
+
+    expect(names.text.length).toBeLessThan(signatures.text.length);
+    expect(signatures.text.length).toBeLessThan(full.text.length);
+  });
+
+  it('grows get_symbol_context snippets from single lines to whole definitions', async () => {
+    const levels = ['names', 'signatures', 'full'] as const;
+    const results = [];
+    for (const detail of levels) {
+      results.push((await call('get_symbol_context', { symbol: 'Calculator', detail })).parsed);
+    }
+    const [names, signatures, full] = results;
+
+    for (const snippet of names.snippets) {
+      expect(snippet.startLine).toBe(snippet.endLine);
+    }
+    const definitionOf = (result: { snippets: Array<{ role: string; text: string }> }) =>
+      result.snippets.find((snippet) => snippet.role === 'definition')?.text;
+    expect(definitionOf(names)).toBe('struct Calculator {');
+    expect(definitionOf(signatures)).toMatch(/^struct Calculator\b/);
+    expect(definitionOf(signatures)).not.toContain('value');
+    expect(definitionOf(full)).toBe('struct Calculator {\n    value: i64,\n}');
+
+    expect(names.tokensUsed).toBeLessThan(signatures.tokensUsed);
+    expect(signatures.tokensUsed).toBeLessThan(full.tokensUsed);
+  });
+
+  it('rejects an unknown level', async () => {
+    const response = await dispatchTool(
+      'file_outline',
+      { file: 'src/calc.rs', detail: 'all' },
+      ctx
+    );
+    expect(response.isError).toBe(true);
+    expect(JSON.parse(response.content![0].text).message).toContain('names, signatures, full');
+  });
+});
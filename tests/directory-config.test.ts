@@ -0,0 +1,152 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import {
+  DIRECTORY_CONFIG_FILENAME,
+  loadDirectoryConfigs,
+  parseDirectoryConfig
+} from '../src/utils/directory-config.js';
+import { setLanguageOverride } from '../src/utils/language-detection.js';
+import {
+  CODEBASE_CONTEXT_DIRNAME,
+  KEYWORD_INDEX_FILENAME
+} from '../src/constants/codebase-context.js';
+import { rmWithRetries } from './test-helpers.js';
+
+async function writeFile(root: string, relativePath: string, content: string): Promise<void> {
+  const target = path.join(root, relativePath);
+  await fs.mkdir(path.dirname(target), { recursive: true });
+  await fs.writeFile(target, content);
+}
+
+describe('parseDirectoryConfig', () => {
+  it('reads the supported TOML subset', () => {
+    const config = parseDirectoryConfig(
+      [
+        '# generated code',
+        'skip = false',
+        'max_file_size = 64_000',
+        'ignore = [',
+        '  "*.pb.go", # protobuf output',
+        "  'tmp/**',",
+        ']',
+        '',
+        '[languages]',
+        '".go" = "plaintext"',
+        'tmpl = "html"',
+        '',
+        '[watch]',
+        'debounce_ms = 500'
+      ].join('\n')
+    );
+
+    expect(config).toEqual({
+      skip: false,
+      maxFileSize: 64000,
+      ignore: ['*.pb.go', 'tmp/**'],
+      languages: { '.go': 'plaintext', '.tmpl': 'html' },
+      watchDebounceMs: 500
+    });
+  });
+
+  it('rejects lines it cannot parse', () => {
+    expect(() => parseDirectoryConfig('skip = maybe')).toThrow(/line 1/);
+    expect(() => parseDirectoryConfig('just text')).toThrow(/key = value/);
+  });
+});
+
+describe('directory config resolution', () => {
+  let tempDir: string;
+
+  beforeEach(async () => {
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'directory-config-'));
+    await writeFile(
+      tempDir,
+      DIRECTORY_CONFIG_FILENAME,
+      'max_file_size = 500000\nignore = ["*.log"]\n[languages]\n".tmpl" = "html"\n'
+    );
+    await writeFile(
+      tempDir,
+      `services/${DIRECTORY_CONFIG_FILENAME}`,
+      'max_file_size = 2000\nignore = ["*.pb.go"]\n[languages]\n".go" = "plaintext"\n'
+    );
+    await writeFile(
+      tempDir,
+      `services/api/gen/${DIRECTORY_CONFIG_FILENAME}`,
+      'skip = true\n[languages]\n".tmpl" = "plaintext"\n'
+    );
+    await writeFile(
+      tempDir,
+      `services/api/gen/kept/${DIRECTORY_CONFIG_FILENAME}`,
+      'skip = false\nignore = ["!debug.log"]\n'
+    );
+  });
+
+  afterEach(async () => {
+    setLanguageOverride(tempDir, null);
+    await rmWithRetries(tempDir);
+  });
+
+  it('merges ancestors with the nearest config winning', async () => {
+    const resolver = await loadDirectoryConfigs(tempDir);
+    expect(resolver.sources).toHaveLength(4);
+
+    expect(resolver.resolve('services/api/gen/kept/deep/file.go')).toEqual({
+      skip: false,
+      maxFileSize: 2000,
+      languages: { '.tmpl': 'plaintext', '.go': 'plaintext' },
+      sources: [
+        DIRECTORY_CONFIG_FILENAME,
+        `services/${DIRECTORY_CONFIG_FILENAME}`,
+        `services/api/gen/${DIRECTORY_CONFIG_FILENAME}`,
+        `services/api/gen/kept/${DIRECTORY_CONFIG_FILENAME}`
+      ]
+    });
+    expect(resolver.resolve('web/page.tmpl').maxFileSize).toBe(500000);
+    expect(resolver.languageFor('web/page.tmpl')).toBe('html');
+    expect(resolver.languageFor('services/api/gen/page.tmpl')).toBe('plaintext');
+
+    expect(resolver.ignores('services/api/gen/client.ts')).toBe(true);
+    expect(resolver.ignores('services/api/gen/kept/client.ts')).toBe(false);
+    expect(resolver.ignores('services/api/user.pb.go')).toBe(true);
+    expect(resolver.ignores('services/api/gen/kept/debug.log')).toBe(false);
+    expect(resolver.ignores('web/server.log')).toBe(true);
+  });
+
+  it('applies skips, ignores and language overrides while indexing', async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    await writeFile(tempDir, 'src/app.ts', 'export function app() {\n  return 1;\n}\n');
+    await writeFile(tempDir, 'services/api/user.pb.go', 'package api\n\nfunc Get() {}\n');
+    await writeFile(tempDir, 'services/api/handler.go', 'package api\n\nfunc Handle() {}\n');
+    await writeFile(tempDir, 'services/api/gen/client.ts', 'export const client = 1;\n');
+    // Over the 2000-byte limit set by services/
+    await writeFile(tempDir, 'services/big.ts', `export const big = '${'x'.repeat(3000)}';\n`);
+
+    const indexer = new CodebaseIndexer({
+      rootPath: tempDir,
+      config: { skipEmbedding: true, include: ['**/*.{ts,go}'] }
+    });
+    await indexer.index();
+
+    const indexPath = path.join(tempDir, CODEBASE_CONTEXT_DIRNAME, KEYWORD_INDEX_FILENAME);
+    const indexRaw = JSON.parse(await fs.readFile(indexPath, 'utf-8')) as {
+      chunks: Array<{ filePath: string; language: string }>;
+    };
+    const languages = new Map(
+      indexRaw.chunks.map((chunk) => [
+        path.relative(tempDir, chunk.filePath).replace(/\\/g, '/'),
+        chunk.language
+      ])
+    );
+
+    expect(languages.get('src/app.ts')).toBe('typescript');
+    expect(languages.get('services/api/handler.go')).toBe('plaintext');
+    expect(languages.has('services/api/user.pb.go')).toBe(false);
+    expect(languages.has('services/api/gen/client.ts')).toBe(false);
+    expect(languages.has('services/big.ts')).toBe(false);
+  });
+});
@@ -0,0 +1,112 @@
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { fileURLToPath } from 'url';
+import { extractTreeSitterSymbols } from '../src/utils/tree-sitter.js';
+import { getFileOutline } from '../src/core/file-outline.js';
+import { buildSymbolContext } from '../src/core/symbol-context.js';
+import {
+  CODEBASE_CONTEXT_DIRNAME,
+  INDEX_FORMAT_VERSION,
+  KEYWORD_INDEX_FILENAME
+} from '../src/constants/codebase-context.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const fixturesDir = path.join(
+  path.dirname(fileURLToPath(import.meta.url)),
+  'fixtures',
+  'doc-comments'
+);
+
+async function docsOf(file: string, language: string) {
+  const content = await fs.readFile(path.join(fixturesDir, file), 'utf-8');
+  const extraction = await extractTreeSitterSymbols(content, language);
+  if (!extraction) throw new Error(`could not parse ${file}`);
+  return Object.fromEntries(
+    extraction.symbols.map((symbol) => [
+      symbol.name,
+      { doc: symbol.doc, docStartLine: symbol.docStartLine }
+    ])
+  );
+}
+
+describe('doc comment extraction', () => {
+  it('attaches Rust /// runs across attributes and /** */ blocks', async () => {
+    const docs = await docsOf('lib.rs', 'rust');
+
+    expect(docs.add).toEqual({ doc: 'Adds two numbers.\n\nWraps on overflow.', docStartLine: 1 });
+    expect(docs.plain).toEqual({ doc: undefined, docStartLine: undefined });
+    expect(docs.Counter).toEqual({ doc: 'Block doc on a struct.', docStartLine: 12 });
+  });
+
+  it('reads Python docstrings from the body, cleaned like inspect.cleandoc', async () => {
+    const docs = await docsOf('greet.py', 'python');
+
+    expect(docs.greet).toEqual({
+      doc: 'Return a greeting.\n\nThe name is used as given.',
+      docStartLine: undefined
+    });
+    expect(docs.Greeter.doc).toBe('Greets people.');
+    expect(docs.wave.doc).toBeUndefined();
+  });
+
+  it('attaches JSDoc above exported functions and arrow constants', async () => {
+    const docs = await docsOf('total.ts', 'typescript');
+
+    expect(docs.total).toEqual({
+      doc: 'Sum of all items.\n@param items values to add',
+      docStartLine: 1
+    });
+    expect(docs.double.doc).toBe('Doubles a value.');
+    expect(docs.untouched.doc).toBeUndefined();
+  });
+});
+
+describe('doc comments in tool output', () => {
+  it('adds docs to outline nodes only when asked', async () => {
+    const plain = await getFileOutline(fixturesDir, 'greet.py');
+    const withDocs = await getFileOutline(fixturesDir, 'greet.py', { includeDocs: true });
+    if (plain.status !== 'success' || withDocs.status !== 'success') {
+      throw new Error('expected success');
+    }
+
+    expect(JSON.stringify(plain.outline)).not.toContain('"doc"');
+    expect(withDocs.outline[0].doc).toBe('Return a greeting.\n\nThe name is used as given.');
+    expect(withDocs.outline[1].doc).toBe('Greets people.');
+  });
+
+  describe('symbol context', () => {
+    let tempRoot: string;
+
+    beforeEach(async () => {
+      tempRoot = await fs.mkdtemp(path.join(os.tmpdir(), 'doc-comments-'));
+      const source = await fs.readFile(path.join(fixturesDir, 'total.ts'), 'utf-8');
+      await fs.writeFile(path.join(tempRoot, 'total.ts'), source);
+      await fs.mkdir(path.join(tempRoot, CODEBASE_CONTEXT_DIRNAME), { recursive: true });
+      await fs.writeFile(
+        path.join(tempRoot, CODEBASE_CONTEXT_DIRNAME, KEYWORD_INDEX_FILENAME),
+        JSON.stringify({
+          header: { buildId: 'test-doc-comments', formatVersion: INDEX_FORMAT_VERSION },
+          chunks: [{ content: source, startLine: 1, relativePath: 'total.ts' }]
+        })
+      );
+    });
+
+    afterEach(async () => {
+      await rmWithRetries(tempRoot);
+    });
+
+    it('starts the definition at its doc comment with includeDocs', async () => {
+      const plain = await buildSymbolContext(tempRoot, 'total');
+      const withDocs = await buildSymbolContext(tempRoot, 'total', { includeDocs: true });
+      if (plain.status !== 'success' || withDocs.status !== 'success') {
+        throw new Error('expected success');
+      }
+
+      expect(plain.snippets[0].startLine).toBe(5);
+      expect(withDocs.snippets[0]).toMatchObject({ role: 'definition', startLine: 1, endLine: 7 });
+      expect(withDocs.snippets[0].text).toContain(' * Sum of all items.');
+    });
+  });
+});
@@ -0,0 +1,73 @@
+import { describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { fileURLToPath } from 'url';
+import { getFileOutline } from '../src/core/file-outline.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const fixturesDir = path.join(path.dirname(fileURLToPath(import.meta.url)), 'fixtures', 'grammars');
+
+describe('getFileOutline', () => {
+  it('nests Rust methods under their impl block', async () => {
+    const result = await getFileOutline(fixturesDir, 'rust.rs');
+    if (result.status !== 'success') throw new Error(result.message);
+
+    expect(
+      result.outline.map((node) => [node.name, node.kind, node.startLine, node.endLine])
+    ).toEqual([
+      ['Calculator', 'struct', 1, 3],
+      ['impl Calculator', 'impl', 5, 13],
+      ['new_calculator', 'function', 15, 17],
+      ['add', 'function', 19, 21],
+      ['total', 'function', 23, 25]
+    ]);
+    expect(result.outline[1].children?.map((node) => `${node.kind}:${node.name}`)).toEqual([
+      'method:reset',
+      'method:double'
+    ]);
+    expect(JSON.stringify(result)).not.toContain('self.value');
+  });
+
+  it('attaches signatures on request', async () => {
+    const result = await getFileOutline(fixturesDir, 'rust.rs', { signaturesOnly: true });
+    if (result.status !== 'success') throw new Error(result.message);
+
+    expect(result.outline.map((node) => node.signature)).toEqual([
+      'struct Calculator',
+      'impl Calculator',
+      'fn new_calculator(initial: i64) -> Calculator',
+      'fn add(calc: &mut Calculator, n: i64)',
+      'fn total(calc: &Calculator) -> i64'
+    ]);
+    expect(result.outline[1].children?.[0].signature).toBe('fn reset(&mut self)');
+  });
+
+  it('limits the tree to the requested depth', async () => {
+    const result = await getFileOutline(fixturesDir, 'tsx.tsx', { depth: 1 });
+    if (result.status !== 'success') throw new Error(result.message);
+
+    const app = result.outline.find((node) => node.name === 'App');
+    expect(app?.children).toBeUndefined();
+    expect(app?.hiddenChildren).toBe(2);
+  });
+
+  it('rejects paths outside the project root', async () => {
+    const result = await getFileOutline(fixturesDir, '../../package.json');
+    expect(result.status).toBe('error');
+  });
+
+  it('accepts a directory whose name starts with two dots', async () => {
+    const root = await fs.mkdtemp(path.join(os.tmpdir(), 'outline-dots-'));
+    try {
+      await fs.mkdir(path.join(root, '..foo'));
+      await fs.writeFile(path.join(root, '..foo', 'bar.ts'), 'export function bar() {}\n');
+
+      const result = await getFileOutline(root, '..foo/bar.ts');
+      if (result.status !== 'success') throw new Error(result.message);
+      expect(result.outline.map((node) => node.name)).toEqual(['bar']);
+    } finally {
+      await rmWithRetries(root);
+    }
+  });
+});
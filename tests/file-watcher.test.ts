@@ -2,7 +2,11 @@ import { describe, it, expect, beforeEach, afterEach } from 'vitest';
 import { promises as fs } from 'fs';
 import path from 'path';
 import os from 'os';
-import { startFileWatcher } from '../src/core/file-watcher.js';
+import {
+  DEFAULT_DEBOUNCE_MS,
+  resolveDebounceMs,
+  startFileWatcher
+} from '../src/core/file-watcher.js';
 import { rmWithRetries } from './test-helpers.js';
 
 describe('FileWatcher', () => {
@@ -16,6 +20,14 @@ describe('FileWatcher', () => {
     await rmWithRetries(tempDir);
   });
 
+  it('takes the debounce from the environment, then the config, then the default', () => {
+    expect(DEFAULT_DEBOUNCE_MS).toBe(200);
+    expect(resolveDebounceMs('750', 500)).toBe(750);
+    expect(resolveDebounceMs('0', 500)).toBe(0);
+    expect(resolveDebounceMs('soon', 500)).toBe(500);
+    expect(resolveDebounceMs(undefined)).toBe(DEFAULT_DEBOUNCE_MS);
+  });
+
   it('triggers onChanged after debounce window', async () => {
     const debounceMs = 400;
     let callCount = 0;
@@ -159,4 +171,41 @@ describe('FileWatcher', () => {
       stop();
     }
   }, 5000);
+
+  it('reports an atomic save once, for the final path only', async () => {
+    const debounceMs = 1000;
+    const calls: string[][] = [];
+
+    let resolveReady!: () => void;
+    const ready = new Promise<void>((resolve) => {
+      resolveReady = resolve;
+    });
+
+    const target = path.join(tempDir, 'service.ts');
+    await fs.writeFile(target, 'export const v = 1;');
+
+    const stop = startFileWatcher({
+      rootPath: tempDir,
+      debounceMs,
+      onReady: () => resolveReady(),
+      onChanged: (changedPaths) => {
+        calls.push(changedPaths);
+      }
+    });
+
+    try {
+      await ready;
+      // Write-temp-then-rename, with a temp name the watcher tracks
+      const temp = path.join(tempDir, '.service.tmp.ts');
+      await fs.writeFile(temp, 'export const v = 2;');
+      // Let the temp file's add event through awaitWriteFinish before renaming it away
+      await new Promise((resolve) => setTimeout(resolve, 400));
+      await fs.rename(temp, target);
+      await new Promise((resolve) => setTimeout(resolve, debounceMs + 1200));
+
+      expect(calls).toEqual([[target]]);
+    } finally {
+      stop();
+    }
+  }, 8000);
 });
@@ -0,0 +1,113 @@
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import {
+  CODEBASE_CONTEXT_DIRNAME,
+  INDEX_FORMAT_VERSION,
+  KEYWORD_INDEX_FILENAME
+} from '../src/constants/codebase-context.js';
+import { findScopedReferences } from '../src/core/symbol-references.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const UTILS_SOURCE = [
+  'export function format(value: string) {',
+  '  return value.trim();',
+  '}',
+  '',
+  'export function render(input: string) {',
+  '  const format = (s: string) => s.toUpperCase();',
+  '  return format(input);',
+  '}',
+  ''
+].join('\n');
+
+const MAIN_SOURCE = [
+  "import { format } from './utils';",
+  '',
+  'export function main() {',
+  "  return format(' hi ');",
+  '}',
+  ''
+].join('\n');
+
+describe('findScopedReferences', () => {
+  let tempRoot: string;
+
+  beforeEach(async () => {
+    tempRoot = await fs.mkdtemp(path.join(os.tmpdir(), 'find-references-'));
+    await fs.writeFile(path.join(tempRoot, 'utils.ts'), UTILS_SOURCE);
+    await fs.writeFile(path.join(tempRoot, 'main.ts'), MAIN_SOURCE);
+
+    const contextDir = path.join(tempRoot, CODEBASE_CONTEXT_DIRNAME);
+    await fs.mkdir(contextDir, { recursive: true });
+    await fs.writeFile(
+      path.join(contextDir, KEYWORD_INDEX_FILENAME),
+      JSON.stringify({
+        header: { buildId: 'test-find-references', formatVersion: INDEX_FORMAT_VERSION },
+        chunks: [
+          { content: UTILS_SOURCE, startLine: 1, relativePath: 'utils.ts' },
+          { content: MAIN_SOURCE, startLine: 1, relativePath: 'main.ts' }
+        ]
+      })
+    );
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(tempRoot);
+  });
+
+  it('separates the definition from uses and skips shadowed locals', async () => {
+    const result = await findScopedReferences(tempRoot, 'format');
+    if (result.status !== 'success') throw new Error('expected success');
+
+    expect(result.scope).toBe('module');
+    expect(result.definitions).toEqual([
+      expect.objectContaining({ file: 'utils.ts', line: 1, column: 17, kind: 'definition' })
+    ]);
+
+    const refs = result.references.map((r) => `${r.file}:${r.line}`);
+    expect(refs).toEqual(expect.arrayContaining(['main.ts:1', 'main.ts:4']));
+    // The arrow-function local inside render() shadows the top-level function
+    expect(refs).not.toContain('utils.ts:6');
+    expect(refs).not.toContain('utils.ts:7');
+  });
+
+  it('scopes results to the enclosing block when a position is provided', async () => {
+    const result = await findScopedReferences(tempRoot, 'format', { file: 'utils.ts', line: 7 });
+    if (result.status !== 'success') throw new Error('expected success');
+
+    expect(result.scope).toBe('local');
+    expect(result.definitions.map((d) => d.line)).toEqual([6]);
+    expect(result.references.map((r) => `${r.file}:${r.line}`)).toEqual(['utils.ts:7']);
+  });
+
+  it('returns empty lists when the symbol is never referenced', async () => {
+    const result = await findScopedReferences(tempRoot, 'doesNotExist');
+
+    expect(result).toMatchObject({
+      status: 'success',
+      definitions: [],
+      references: [],
+      referenceCount: 0,
+      isComplete: true
+    });
+  });
+
+  it('rejects a position in a file outside the project root', async () => {
+    const outsideDir = await fs.mkdtemp(path.join(os.tmpdir(), 'find-references-outside-'));
+    const outsideFile = path.join(outsideDir, 'secret.ts');
+    await fs.writeFile(outsideFile, UTILS_SOURCE);
+    try {
+      const relative = path.relative(tempRoot, outsideFile);
+      for (const file of [relative, outsideFile, '../../etc/passwd']) {
+        expect(await findScopedReferences(tempRoot, 'format', { file, line: 1 })).toEqual({
+          status: 'error',
+          message: `File must be inside the project root: ${file}`
+        });
+      }
+    } finally {
+      await rmWithRetries(outsideDir);
+    }
+  });
+});
@@ -0,0 +1,13 @@
+def greet(name):
+    """Return a greeting.
+
+    The name is used as given.
+    """
+    return f"hello {name}"
+
+
+class Greeter:
+    '''Greets people.'''
+
+    def wave(self):
+        return "wave"
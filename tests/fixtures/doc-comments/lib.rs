@@ -0,0 +1,15 @@
+/// Adds two numbers.
+///
+/// Wraps on overflow.
+#[inline]
+pub fn add(a: u32, b: u32) -> u32 {
+    a.wrapping_add(b)
+}
+
+// Not a doc comment.
+pub fn plain() {}
+
+/** Block doc on a struct. */
+pub struct Counter {
+    value: u32,
+}
@@ -0,0 +1,13 @@
+/**
+ * Sum of all items.
+ * @param items values to add
+ */
+export function total(items: number[]): number {
+  return items.reduce((sum, item) => sum + item, 0);
+}
+
+/** Doubles a value. */
+export const double = (value: number): number => value * 2;
+
+// Regular comment, not attached.
+export function untouched(): void {}
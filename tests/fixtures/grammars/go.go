@@ -4,10 +4,26 @@ type Calculator struct {
 	value int
 }
 
+type Adder interface {
+	Add(n int)
+}
+
+const DefaultValue = 0
+
+var (
+	registry      = map[string]*Calculator{}
+	minimum, maxi = -100, 100
+)
+
 func NewCalculator(initial int) *Calculator {
 	return &Calculator{value: initial}
 }
 
 func (c *Calculator) Add(n int) {
-	c.value += n
+	var local = n
+	c.value += local
+}
+
+func Add(a, b int) int {
+	return a + b
 }
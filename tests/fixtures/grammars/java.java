@@ -1,12 +1,61 @@
+package com.example.calc;
+
+import java.util.List;
+
 public class Calculator {
-    private int value;
+    public static final int LIMIT = 100;
+    private int value, steps;
 
     public Calculator(int initial) {
         this.value = initial;
     }
 
+    @Override
+    public String toString() {
+        return "Calculator(" + value + ")";
+    }
+
     public int add(int n) {
         this.value += n;
+        this.steps++;
         return this.value;
     }
+
+    @Deprecated(since = "2.0")
+    public int add(int a, int b) {
+        return add(a + b);
+    }
+
+    public static class Memory {
+        private final List<Integer> slots;
+
+        Memory(List<Integer> slots) {
+            this.slots = slots;
+        }
+
+        int recall(int slot) {
+            return slots.get(slot);
+        }
+    }
+}
+
+interface Operation {
+    int PRIORITY = 1;
+
+    int apply(int left, int right);
+}
+
+enum Mode {
+    FAST,
+    SLOW;
+
+    boolean isFast() {
+        return this == FAST;
+    }
+}
+
+record Point(int x, int y) {
+    int sum() {
+        return x + y;
+    }
 }
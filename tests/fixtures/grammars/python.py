@@ -1,11 +1,40 @@
+import functools
+
+
 def greet(name):
     return "Hello, " + name
 
 
+def new_calculator(initial=0):
+    return Calculator(initial)
+
+
+async def fetch_total(client):
+    return await client.total()
+
+
+@functools.lru_cache(maxsize=None)
+def cached_square(n):
+    return n * n
+
+
 class Calculator:
+    precision = 2
+    label: str = "calc"
+
     def __init__(self, initial):
         self.value = initial
 
     def add(self, n):
         self.value += n
         return self
+
+    @staticmethod
+    def describe():
+        def inner():
+            return "calculator"
+
+        return inner()
+
+    async def refresh(self):
+        return self.value
@@ -2,6 +2,16 @@ struct Calculator {
     value: i64,
 }
 
+impl Calculator {
+    fn reset(&mut self) {
+        self.value = 0;
+    }
+
+    fn double(&mut self) {
+        self.value *= 2;
+    }
+}
+
 fn new_calculator(initial: i64) -> Calculator {
     Calculator { value: initial }
 }
@@ -9,3 +19,7 @@ fn new_calculator(initial: i64) -> Calculator {
 fn add(calc: &mut Calculator, n: i64) {
     calc.value += n;
 }
+
+fn total(calc: &Calculator) -> i64 {
+    calc.value
+}
@@ -1,14 +1,35 @@
-interface ButtonProps {
+import React from 'react';
+
+export { formatLabel } from './format';
+
+export interface ButtonProps {
   label: string;
   onClick: () => void;
 }
 
-function Button(props: ButtonProps) {
+export type ButtonVariant = 'primary' | 'secondary';
+
+export function Button(props: ButtonProps) {
   return <button onClick={props.onClick}>{props.label}</button>;
 }
 
-class App {
+export const Badge = ({ text }: { text: string }) => <span className="badge">{text}</span>;
+
+class App extends React.Component {
+  handleClick = () => {
+    console.log('clicked');
+  };
+
   render() {
-    return <Button label="Click" onClick={() => {}} />;
+    return (
+      <div>
+        <Badge text="new" />
+        <Button label="Click" onClick={this.handleClick} />
+      </div>
+    );
   }
 }
+
+export default function () {
+  return <App />;
+}
@@ -0,0 +1,26 @@
+pub fn parse_header(input: &str) -> Option<&str> {
+    input.lines().next()
+}
+
+pub fn count_fields(line: &str) -> usize {
+    line.split(',').count()
+}
+
+pub fn broken_total(values: &[i32]) -> i32 {
+    let sum = values.iter().sum::<i32>() + ;
+    sum
+}
+
+pub fn format_row(fields: &[&str]) -> String {
+    fields.join(",")
+}
+
+pub struct Report {
+    pub rows: usize,
+}
+
+impl Report {
+    pub fn new(rows: usize) -> Self {
+        Report { rows }
+    }
+}
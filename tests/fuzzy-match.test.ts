@@ -0,0 +1,106 @@
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { compareFuzzyMatches, fuzzyMatch } from '../src/utils/fuzzy-match.js';
+import { findFuzzySymbols } from '../src/core/symbol-references.js';
+import {
+  CODEBASE_CONTEXT_DIRNAME,
+  INDEX_FORMAT_VERSION,
+  KEYWORD_INDEX_FILENAME
+} from '../src/constants/codebase-context.js';
+import { rmWithRetries } from './test-helpers.js';
+
+describe('fuzzyMatch', () => {
+  it('matches case-insensitive subsequences across word boundaries', () => {
+    expect(fuzzyMatch('newCalc', 'new_calculator')).toMatchObject({ tier: 'fuzzy' });
+    expect(fuzzyMatch('NEWCALC', 'newCalculator')).toMatchObject({ tier: 'prefix' });
+    expect(fuzzyMatch('parseconfig', 'parseConfig')).toEqual({ tier: 'exact', score: 1 });
+    expect(fuzzyMatch('xyz', 'new_calculator')).toBeNull();
+  });
+
+  it('scores boundary-aligned matches above scattered ones', () => {
+    const aligned = fuzzyMatch('gu', 'getUser');
+    const scattered = fuzzyMatch('gu', 'logoutHandler');
+    if (!aligned || !scattered) throw new Error('expected both to match');
+
+    expect(aligned.score).toBeGreaterThan(scattered.score);
+  });
+
+  it('ranks exact, then prefix, then fuzzy matches', () => {
+    const names = ['renderer_cache', 'render', 'renderList'];
+    const ranked = names
+      .map((name) => ({ name, match: fuzzyMatch('render', name)! }))
+      .concat([{ name: 'rEndDer', match: fuzzyMatch('rndr', 'rEndDer')! }])
+      .sort(compareFuzzyMatches)
+      .map((entry) => entry.match.tier);
+
+    expect(ranked).toEqual(['exact', 'prefix', 'prefix', 'fuzzy']);
+  });
+});
+
+describe('findFuzzySymbols', () => {
+  let tempRoot: string;
+
+  beforeEach(async () => {
+    tempRoot = await fs.mkdtemp(path.join(os.tmpdir(), 'fuzzy-symbols-'));
+    const contextDir = path.join(tempRoot, CODEBASE_CONTEXT_DIRNAME);
+    await fs.mkdir(contextDir, { recursive: true });
+    await fs.writeFile(
+      path.join(contextDir, KEYWORD_INDEX_FILENAME),
+      JSON.stringify({
+        header: { buildId: 'test-fuzzy-symbols', formatVersion: INDEX_FORMAT_VERSION },
+        chunks: [
+          {
+            content: 'pub fn new_calculator() -> Calculator {\n    Calculator { total: 0 }\n}',
+            startLine: 15,
+            relativePath: 'src/lib.rs',
+            metadata: { symbolName: 'new_calculator', symbolKind: 'function' }
+          },
+          {
+            content: 'fn add(a: i32) {}\n\nfn new_counter() {}',
+            startLine: 19,
+            relativePath: 'src/lib.rs',
+            metadata: { symbolName: 'add+new_counter', symbolKind: 'function' }
+          },
+          {
+            content: 'export class NewCalc {}',
+            startLine: 1,
+            relativePath: 'src/calc.ts',
+            metadata: { symbolName: 'NewCalc', symbolKind: 'class', symbolPath: ['NewCalc'] }
+          }
+        ]
+      })
+    );
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(tempRoot);
+  });
+
+  it('finds half-remembered names and ranks exact matches first', async () => {
+    const result = await findFuzzySymbols(tempRoot, 'newCalc');
+    if (result.status !== 'success') throw new Error('expected success');
+
+    expect(result.matches.map((m) => `${m.match}:${m.name}`)).toEqual([
+      'exact:NewCalc',
+      'fuzzy:new_calculator'
+    ]);
+    expect(result.matches[1]).toMatchObject({ file: 'src/lib.rs', line: 15, kind: 'function' });
+  });
+
+  it('locates names inside merged chunks and honours limit and minScore', async () => {
+    const loose = await findFuzzySymbols(tempRoot, 'nc', { minScore: 0 });
+    if (loose.status !== 'success') throw new Error('expected success');
+    expect(loose.matches.find((m) => m.name === 'new_counter')?.line).toBe(21);
+
+    const limited = await findFuzzySymbols(tempRoot, 'nc', { minScore: 0, limit: 1 });
+    if (limited.status !== 'success') throw new Error('expected success');
+    expect(limited.matches).toHaveLength(1);
+    expect(limited.totalMatches).toBe(loose.totalMatches);
+
+    const strict = await findFuzzySymbols(tempRoot, 'nc', { minScore: 1 });
+    if (strict.status !== 'success') throw new Error('expected success');
+    expect(strict.matches).toEqual([]);
+  });
+});
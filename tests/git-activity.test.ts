@@ -0,0 +1,79 @@
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import { execFileSync } from 'child_process';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { computeGitActivityBoosts, MAX_GIT_ACTIVITY_BOOST } from '../src/core/git-activity.js';
+import {
+  clearFileCommitDatesCache,
+  getFileChangeStats,
+  type FileChangeStats
+} from '../src/utils/git-dates.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const DAY = 1000 * 60 * 60 * 24;
+
+function stats(daysAgo: number, commitCount: number): FileChangeStats {
+  return { lastCommit: new Date(Date.UTC(2026, 0, 1) - daysAgo * DAY), commitCount };
+}
+
+describe('computeGitActivityBoosts', () => {
+  it('ranks recent, frequently changed files above stale ones', () => {
+    const boosts = computeGitActivityBoosts(
+      new Map([
+        ['src/hot.ts', stats(0, 40)],
+        ['src/warm.ts', stats(30, 5)],
+        ['src/dead.ts', stats(900, 1)]
+      ])
+    );
+
+    const hot = boosts.get('src/hot.ts')!;
+    const warm = boosts.get('src/warm.ts')!;
+    const dead = boosts.get('src/dead.ts')!;
+    expect(hot).toBeCloseTo(MAX_GIT_ACTIVITY_BOOST);
+    expect(hot).toBeGreaterThan(warm);
+    expect(warm).toBeGreaterThan(dead);
+    expect(dead).toBeLessThan(0.01);
+  });
+
+  it('returns no boosts without git history', () => {
+    expect(computeGitActivityBoosts(new Map()).size).toBe(0);
+  });
+});
+
+describe('getFileChangeStats', () => {
+  let tempRoot: string;
+
+  beforeEach(async () => {
+    tempRoot = await fs.mkdtemp(path.join(os.tmpdir(), 'git-activity-'));
+    clearFileCommitDatesCache();
+  });
+
+  afterEach(async () => {
+    clearFileCommitDatesCache();
+    await rmWithRetries(tempRoot);
+  });
+
+  it('is empty outside a git repository', async () => {
+    expect((await getFileChangeStats(tempRoot)).size).toBe(0);
+  });
+
+  it('counts commits per file', async () => {
+    const git = (...args: string[]) =>
+      execFileSync('git', ['-c', 'user.name=test', '-c', 'user.email=test@example.com', ...args], {
+        cwd: tempRoot,
+        stdio: 'ignore'
+      });
+    git('init', '-q');
+    await fs.writeFile(path.join(tempRoot, 'a.ts'), 'export const a = 1;\n');
+    await fs.writeFile(path.join(tempRoot, 'b.ts'), 'export const b = 1;\n');
+    git('add', '-A');
+    git('commit', '-q', '-m', 'first');
+    await fs.writeFile(path.join(tempRoot, 'a.ts'), 'export const a = 2;\n');
+    git('commit', '-q', '-am', 'second');
+
+    const result = await getFileChangeStats(tempRoot);
+    expect(result.get('a.ts')?.commitCount).toBe(2);
+    expect(result.get('b.ts')?.commitCount).toBe(1);
+  });
+});
@@ -0,0 +1,249 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+import { promises as fs } from 'fs';
+import http from 'http';
+import os from 'os';
+import path from 'path';
+import { PassThrough, type Readable, type Writable } from 'stream';
+import { Client } from '@modelcontextprotocol/sdk/client/index.js';
+import { StreamableHTTPClientTransport } from '@modelcontextprotocol/sdk/client/streamableHttp.js';
+import { Server } from '@modelcontextprotocol/sdk/server/index.js';
+import { StdioServerTransport } from '@modelcontextprotocol/sdk/server/stdio.js';
+import { ReadBuffer, serializeMessage } from '@modelcontextprotocol/sdk/shared/stdio.js';
+import type { Transport } from '@modelcontextprotocol/sdk/shared/transport.js';
+import {
+  CallToolRequestSchema,
+  ListToolsRequestSchema,
+  type JSONRPCMessage
+} from '@modelcontextprotocol/sdk/types.js';
+import { parseServerArgs } from '../src/transports/args.js';
+import { startHttpTransport } from '../src/transports/http.js';
+import { rmWithRetries } from './test-helpers.js';
+
+/** Client side of a stdio connection, over in-process streams instead of a child process. */
+class StreamClientTransport implements Transport {
+  onmessage?: (message: JSONRPCMessage) => void;
+  onclose?: () => void;
+  onerror?: (error: Error) => void;
+  private readonly buffer = new ReadBuffer();
+
+  constructor(
+    private readonly input: Readable,
+    private readonly output: Writable
+  ) {}
+
+  async start(): Promise<void> {
+    this.input.on('data', (chunk: Buffer) => {
+      this.buffer.append(chunk);
+      for (let message = this.buffer.readMessage(); message; message = this.buffer.readMessage()) {
+        this.onmessage?.(message);
+      }
+    });
+  }
+
+  async send(message: JSONRPCMessage): Promise<void> {
+    this.output.write(serializeMessage(message));
+  }
+
+  async close(): Promise<void> {
+    this.onclose?.();
+  }
+}
+
+const clientInfo = { name: 'http-transport-test', version: '1.0.0' };
+
+describe('parseServerArgs', () => {
+  it('keeps stdio by default and separates flag values from roots', () => {
+    expect(parseServerArgs(['/repo', '/other'])).toEqual({
+      roots: ['/repo', '/other'],
+      transport: 'stdio',
+      host: '127.0.0.1',
+      port: 3000
+    });
+    expect(parseServerArgs(['/repo', '--transport', 'http', '--port=8123'])).toMatchObject({
+      roots: ['/repo'],
+      transport: 'http',
+      port: 8123
+    });
+    expect(parseServerArgs(['--transport', 'websocket']).error).toContain('invalid --transport');
+    expect(parseServerArgs(['--port', 'eighty']).error).toContain('invalid --port');
+  });
+});
+
+describe('HTTP transport', () => {
+  let tempRoot: string;
+  let originalArgv: string[];
+  let originalEnvRoot: string | undefined;
+
+  beforeEach(async () => {
+    vi.resetModules();
+    originalArgv = [...process.argv];
+    originalEnvRoot = process.env.CODEBASE_ROOT;
+    tempRoot = await fs.mkdtemp(path.join(os.tmpdir(), 'http-transport-'));
+    process.env.CODEBASE_ROOT = tempRoot;
+    process.argv = [process.argv[0], process.argv[1], tempRoot];
+  });
+
+  afterEach(async () => {
+    process.argv = originalArgv;
+    if (originalEnvRoot === undefined) {
+      delete process.env.CODEBASE_ROOT;
+    } else {
+      process.env.CODEBASE_ROOT = originalEnvRoot;
+    }
+    await rmWithRetries(tempRoot);
+  });
+
+  it('serves the same tools and results as the stdio path', async () => {
+    const { createServer } = await import('../src/index.js');
+
+    const toServer = new PassThrough();
+    const fromServer = new PassThrough();
+    const stdioServer = createServer();
+    await stdioServer.connect(new StdioServerTransport(toServer, fromServer));
+    const stdioClient = new Client(clientInfo);
+    await stdioClient.connect(new StreamClientTransport(fromServer, toServer));
+
+    const httpTransport = await startHttpTransport({ host: '127.0.0.1', port: 0, createServer });
+    const httpClient = new Client(clientInfo);
+    await httpClient.connect(new StreamableHTTPClientTransport(new URL(httpTransport.url)));
+
+    try {
+      const [stdioTools, httpTools] = await Promise.all([
+        stdioClient.listTools(),
+        httpClient.listTools()
+      ]);
+      expect(httpTools.tools.map((tool) => tool.name)).toEqual(
+        stdioTools.tools.map((tool) => tool.name)
+      );
+
+      const call = { name: 'get_indexing_status', arguments: {} };
+      const [stdioResult, httpResult] = await Promise.all([
+        stdioClient.callTool(call),
+        httpClient.callTool(call)
+      ]);
+      expect(JSON.parse((httpResult.content as Array<{ text: string }>)[0].text)).toMatchObject({
+        status: 'idle',
+        rootPath: tempRoot
+      });
+      expect(httpResult).toEqual(stdioResult);
+    } finally {
+      await Promise.all([stdioClient.close(), httpClient.close(), stdioServer.close()]);
+      await httpTransport.close();
+    }
+  });
+
+  it('refuses requests whose Host header is not loopback', async () => {
+    const { createServer } = await import('../src/index.js');
+    const httpTransport = await startHttpTransport({ host: '127.0.0.1', port: 0, createServer });
+    try {
+      const status = await new Promise<number | undefined>((resolve, reject) => {
+        const headers = { host: 'attacker.example', 'content-type': 'application/json' };
+        const request = http.request(httpTransport.url, { method: 'POST', headers }, (response) => {
+          response.resume();
+          resolve(response.statusCode);
+        });
+        request.on('error', reject);
+        request.end('{}');
+      });
+      expect(status).toBe(403);
+    } finally {
+      await httpTransport.close();
+    }
+  });
+
+  it('lets an in-flight call finish before close resolves', async () => {
+    let release: () => void = () => undefined;
+    let markStarted: () => void = () => undefined;
+    const started = new Promise<void>((resolve) => (markStarted = resolve));
+    const createSlowServer = () => {
+      const server = new Server(
+        { name: 'slow', version: '1.0.0' },
+        { capabilities: { tools: {} } }
+      );
+      server.setRequestHandler(ListToolsRequestSchema, async () => ({ tools: [] }));
+      server.setRequestHandler(CallToolRequestSchema, async () => {
+        const finished = new Promise<void>((resolve) => (release = resolve));
+        markStarted();
+        await finished;
+        return { content: [{ type: 'text', text: 'done' }] };
+      });
+      return server;
+    };
+
+    const httpTransport = await startHttpTransport({
+      host: '127.0.0.1',
+      port: 0,
+      createServer: createSlowServer
+    });
+    const client = new Client(clientInfo);
+    await client.connect(new StreamableHTTPClientTransport(new URL(httpTransport.url)));
+
+    const pending = client.callTool({ name: 'slow', arguments: {} });
+    await started;
+    let closed = false;
+    const closing = httpTransport.close().then(() => {
+      closed = true;
+    });
+
+    await new Promise((resolve) => setTimeout(resolve, 50));
+    expect(closed).toBe(false);
+    release();
+
+    expect(await pending).toMatchObject({ content: [{ type: 'text', text: 'done' }] });
+    await closing;
+    expect(closed).toBe(true);
+    await client.close();
+  });
+
+  it('closes sessions left idle without DELETE and keeps those with an open stream', async () => {
+    const createIdleServer = () => {
+      const server = new Server(
+        { name: 'idle', version: '1.0.0' },
+        { capabilities: { tools: {} } }
+      );
+      server.setRequestHandler(ListToolsRequestSchema, async () => ({ tools: [] }));
+      return server;
+    };
+    const httpTransport = await startHttpTransport({
+      host: '127.0.0.1',
+      port: 0,
+      createServer: createIdleServer,
+      sessionIdleTimeoutMs: 100
+    });
+    const post = (body: unknown, sessionId?: string) =>
+      fetch(httpTransport.url, {
+        method: 'POST',
+        headers: {
+          'content-type': 'application/json',
+          accept: 'application/json, text/event-stream',
+          ...(sessionId ? { 'mcp-session-id': sessionId } : {})
+        },
+        body: JSON.stringify(body)
+      });
+    // The SDK client keeps an SSE stream open for its session, as a live client does
+    const client = new Client(clientInfo);
+    await client.connect(new StreamableHTTPClientTransport(new URL(httpTransport.url)));
+
+    try {
+      // A client that initializes, then goes away without DELETE or a stream
+      const initialized = await post({
+        jsonrpc: '2.0',
+        id: 1,
+        method: 'initialize',
+        params: { protocolVersion: '2025-03-26', capabilities: {}, clientInfo }
+      });
+      const sessionId = initialized.headers.get('mcp-session-id') ?? undefined;
+      await initialized.text();
+      expect(sessionId).toBeDefined();
+
+      await new Promise((resolve) => setTimeout(resolve, 400));
+      const abandoned = await post({ jsonrpc: '2.0', id: 2, method: 'tools/list' }, sessionId);
+      await abandoned.text();
+      expect(abandoned.status).toBe(404);
+      expect(await client.listTools()).toEqual({ tools: [] });
+    } finally {
+      await client.close();
+      await httpTransport.close();
+    }
+  });
+});
@@ -0,0 +1,108 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { Client } from '@modelcontextprotocol/sdk/client/index.js';
+import { StreamableHTTPClientTransport } from '@modelcontextprotocol/sdk/client/streamableHttp.js';
+import { ResourceUpdatedNotificationSchema } from '@modelcontextprotocol/sdk/types.js';
+import {
+  IndexChangeNotifier,
+  describeIndexChange,
+  type IndexChange
+} from '../src/core/index-notifications.js';
+import { INDEX_RESOURCE_URI } from '../src/resources/uri.js';
+import { startHttpTransport } from '../src/transports/http.js';
+import { rmWithRetries } from './test-helpers.js';
+
+describe('IndexChangeNotifier', () => {
+  it('skips building a change nobody listens for', () => {
+    const notifier = new IndexChangeNotifier();
+    const build = vi.fn(() => describeIndexChange('/repo', ['/repo/a.ts'], 1));
+
+    expect(notifier.publish(build)).toBe(false);
+    expect(build).not.toHaveBeenCalled();
+
+    const received: IndexChange[] = [];
+    const unsubscribe = notifier.subscribe((change) => received.push(change));
+    expect(notifier.publish(build)).toBe(true);
+    unsubscribe();
+    expect(notifier.publish(build)).toBe(false);
+
+    expect(build).toHaveBeenCalledTimes(1);
+    expect(received).toEqual([{ indexVersion: 1, root: path.resolve('/repo'), files: ['a.ts'] }]);
+  });
+
+  it('lists changed files relative to the root, sorted and once each', () => {
+    const root = path.resolve('/repo');
+    const change = describeIndexChange(
+      root,
+      [path.join(root, 'src', 'z.ts'), path.join(root, 'a.ts'), path.join(root, 'src', 'z.ts')],
+      7
+    );
+    expect(change).toEqual({ indexVersion: 7, root, files: ['a.ts', 'src/z.ts'] });
+  });
+});
+
+describe('index change notifications over HTTP', () => {
+  let tempRoot: string;
+  let originalArgv: string[];
+
+  beforeEach(async () => {
+    vi.resetModules();
+    originalArgv = [...process.argv];
+    tempRoot = await fs.mkdtemp(path.join(os.tmpdir(), 'index-notifications-'));
+    process.argv = [process.argv[0], process.argv[1], tempRoot];
+  });
+
+  afterEach(async () => {
+    process.argv = originalArgv;
+    await rmWithRetries(tempRoot);
+  });
+
+  it('pushes the files a watched change touched to a subscribed session', async () => {
+    const { createServer, watchRoot } = await import('../src/index.js');
+    const httpTransport = await startHttpTransport({ host: '127.0.0.1', port: 0, createServer });
+    const client = new Client({ name: 'index-notifications-test', version: '1.0.0' });
+    await client.connect(new StreamableHTTPClientTransport(new URL(httpTransport.url)));
+
+    const received = new Promise<Record<string, unknown>>((resolve) => {
+      client.setNotificationHandler(ResourceUpdatedNotificationSchema, (notification) =>
+        resolve(notification.params)
+      );
+    });
+
+    let markReady: () => void = () => undefined;
+    const ready = new Promise<void>((resolve) => (markReady = resolve));
+    // The notification waits for the reindex that follows the change
+    let reindexed = false;
+    const reindex = () =>
+      new Promise<void>((resolve) => setTimeout(resolve, 200)).then(() => {
+        reindexed = true;
+      });
+    const stopWatcher = watchRoot(tempRoot, 500, reindex, () => markReady());
+
+    try {
+      const before = await client.readResource({ uri: INDEX_RESOURCE_URI });
+      const versionBefore = JSON.parse(String(before.contents[0].text)).indexVersion as number;
+      await client.subscribeResource({ uri: INDEX_RESOURCE_URI });
+      await ready;
+
+      await fs.mkdir(path.join(tempRoot, 'src'));
+      await fs.writeFile(path.join(tempRoot, 'src', 'b.ts'), 'export const b = 2;\n');
+      await fs.writeFile(path.join(tempRoot, 'a.ts'), 'export const a = 1;\n');
+
+      const params = await received;
+      expect(params).toMatchObject({
+        uri: INDEX_RESOURCE_URI,
+        root: path.resolve(tempRoot),
+        files: ['a.ts', 'src/b.ts']
+      });
+      expect(params.indexVersion).toBeGreaterThan(versionBefore);
+      expect(reindexed).toBe(true);
+    } finally {
+      stopWatcher();
+      await client.close();
+      await httpTransport.close();
+    }
+  }, 10000);
+});
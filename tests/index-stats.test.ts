@@ -0,0 +1,101 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import { dispatchTool } from '../src/tools/index.js';
+import type { ToolContext } from '../src/tools/types.js';
+import { CODEBASE_CONTEXT_DIRNAME } from '../src/constants/codebase-context.js';
+import { rmWithRetries } from './test-helpers.js';
+
+describe('stats tool', () => {
+  let tempDir: string;
+
+  beforeEach(async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'index-stats-'));
+    await fs.mkdir(path.join(tempDir, 'src'), { recursive: true });
+    await fs.writeFile(
+      path.join(tempDir, 'src', 'math.ts'),
+      [
+        'export function add(a: number, b: number): number {',
+        '  return a + b;',
+        '}',
+        '',
+        'export class Calculator {',
+        '  total = 0;',
+        '}',
+        ''
+      ].join('\n')
+    );
+    await fs.writeFile(path.join(tempDir, 'src', 'util.py'), 'def shout(text):\n    return text\n');
+    await fs.writeFile(
+      path.join(tempDir, 'huge.ts'),
+      `export const blob = '${'x'.repeat(4096)}';\n`
+    );
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+  });
+
+  function contextFor(rootPath: string): ToolContext {
+    const baseDir = path.join(rootPath, CODEBASE_CONTEXT_DIRNAME);
+    return {
+      indexState: { status: 'ready' },
+      paths: {
+        baseDir,
+        memory: path.join(baseDir, 'memory.json'),
+        intelligence: path.join(baseDir, 'intelligence.json'),
+        keywordIndex: path.join(baseDir, 'index.json'),
+        vectorDb: path.join(baseDir, 'index')
+      },
+      rootPath,
+      performIndexing: () => undefined
+    };
+  }
+
+  async function statsOf(rootPath: string) {
+    const result = await dispatchTool('stats', {}, contextFor(rootPath));
+    return JSON.parse(result.content![0].text);
+  }
+
+  it('reports no_index before the first run', async () => {
+    const stats = await statsOf(tempDir);
+    expect(stats.status).toBe('no_index');
+    expect(stats.roots).toEqual([tempDir]);
+    expect(stats.grammars.available).toContain('rust');
+  });
+
+  it('reads counters tallied during indexing', async () => {
+    await new CodebaseIndexer({
+      rootPath: tempDir,
+      config: {
+        skipEmbedding: true,
+        include: ['**/*.{ts,py}'],
+        parsing: { maxFileSize: 2048 }
+      }
+    }).index();
+
+    const stats = await statsOf(tempDir);
+    expect(stats.status).toBe('success');
+    expect(stats.files).toMatchObject({
+      scanned: 2,
+      indexed: 2,
+      unanalyzed: 0,
+      byLanguage: { typescript: 1, python: 1 }
+    });
+    expect(stats.files.skipped).toMatchObject({
+      total: 1,
+      byReason: { too_large: 1 },
+      files: [{ file: 'huge.ts', reason: 'too_large' }]
+    });
+    expect(stats.symbols.byKind).toMatchObject({ function: 2, class: 1 });
+    expect(stats.parseErrors).toBe(0);
+    expect(stats.recoveredParseErrors).toBe(0);
+    expect(stats.memory.indexedBytes).toBeGreaterThan(0);
+    expect(stats.grammars.loaded).toEqual(expect.arrayContaining(['python', 'typescript']));
+  });
+});
@@ -0,0 +1,132 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import { mapWithConcurrency, parseConcurrencyFromEnv } from '../src/utils/concurrency.js';
+import {
+  CODEBASE_CONTEXT_DIRNAME,
+  KEYWORD_INDEX_FILENAME,
+  RELATIONSHIPS_FILENAME
+} from '../src/constants/codebase-context.js';
+import { rmWithRetries } from './test-helpers.js';
+
+describe('mapWithConcurrency', () => {
+  it('keeps at most `limit` calls in flight and yields in input order', async () => {
+    let inFlight = 0;
+    let peak = 0;
+    const results: number[] = [];
+
+    for await (const value of mapWithConcurrency([5, 1, 4, 2, 3, 0], 2, async (delay) => {
+      inFlight++;
+      peak = Math.max(peak, inFlight);
+      await new Promise((resolve) => setTimeout(resolve, delay));
+      inFlight--;
+      return delay;
+    })) {
+      results.push(value);
+    }
+
+    expect(results).toEqual([5, 1, 4, 2, 3, 0]);
+    expect(peak).toBe(2);
+  });
+
+  it('reads the limit from the environment, ignoring invalid values', () => {
+    expect(parseConcurrencyFromEnv('3')).toBe(3);
+    expect(parseConcurrencyFromEnv('0')).toBe(parseConcurrencyFromEnv(undefined));
+    expect(parseConcurrencyFromEnv('many')).toBeGreaterThanOrEqual(1);
+  });
+});
+
+describe('Indexer parse concurrency', () => {
+  let tempDir: string;
+
+  beforeEach(async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'indexer-concurrency-'));
+    await fs.mkdir(path.join(tempDir, 'src'), { recursive: true });
+    for (let i = 0; i < 40; i++) {
+      const next = (i + 1) % 40;
+      await fs.writeFile(
+        path.join(tempDir, 'src', `module${i}.ts`),
+        [
+          `import { helper${next} } from './module${next}.js';`,
+          '',
+          `export function helper${i}(value: number): number {`,
+          `  return value + ${i};`,
+          '}',
+          '',
+          `export class Service${i} {`,
+          `  run(): number {`,
+          `    return helper${next}(${i});`,
+          '  }',
+          '}',
+          ''
+        ].join('\n')
+      );
+    }
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+  });
+
+  async function indexWith(parsing?: { concurrency?: number; workers?: number }) {
+    const indexer = new CodebaseIndexer({
+      rootPath: tempDir,
+      config: {
+        skipEmbedding: true,
+        symbolCache: { enabled: false },
+        ...(parsing ? { parsing } : {})
+      }
+    });
+    const stats = await indexer.index();
+
+    const contextDir = path.join(tempDir, CODEBASE_CONTEXT_DIRNAME);
+    const keywordIndex = JSON.parse(
+      await fs.readFile(path.join(contextDir, KEYWORD_INDEX_FILENAME), 'utf-8')
+    ) as {
+      chunks: Array<{
+        relativePath: string;
+        startLine: number;
+        endLine: number;
+        metadata?: { symbolName?: string };
+      }>;
+    };
+    const relationships = JSON.parse(
+      await fs.readFile(path.join(contextDir, RELATIONSHIPS_FILENAME), 'utf-8')
+    ) as { callGraph?: unknown; dependencyGraph?: { edges: unknown[] } };
+
+    return {
+      parseWorkers: stats.parseWorkers,
+      indexedFiles: stats.indexedFiles,
+      symbols: keywordIndex.chunks.map(({ relativePath, startLine, endLine, metadata }) =>
+        [relativePath, startLine, endLine, metadata?.symbolName ?? ''].join(':')
+      ),
+      callGraph: relationships.callGraph,
+      dependencyGraph: relationships.dependencyGraph
+    };
+  }
+
+  it('produces identical symbols and graphs with one worker and the default', async () => {
+    const sequential = await indexWith({ concurrency: 1 });
+    const parallel = await indexWith();
+
+    expect(sequential.indexedFiles).toBe(40);
+    expect(sequential.symbols.length).toBeGreaterThanOrEqual(40);
+    expect(sequential.dependencyGraph?.edges).toHaveLength(40);
+    expect(parallel).toEqual(sequential);
+  });
+
+  it('produces identical symbols and graphs when worker threads parse the files', async () => {
+    const sequential = await indexWith({ concurrency: 1, workers: 0 });
+    const { parseWorkers, ...threaded } = await indexWith({ concurrency: 4, workers: 4 });
+
+    expect(sequential.parseWorkers).toBeUndefined();
+    expect(parseWorkers?.workers).toBe(4);
+    expect(parseWorkers?.parsedFiles).toBe(40);
+    expect({ ...threaded, parseWorkers: undefined }).toEqual(sequential);
+  }, 30000);
+});
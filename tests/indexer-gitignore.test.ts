@@ -0,0 +1,140 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { glob, type Path } from 'glob';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import {
+  CODEBASE_CONTEXT_DIRNAME,
+  KEYWORD_INDEX_FILENAME
+} from '../src/constants/codebase-context.js';
+import { gitignoreWalkIgnore, loadGitignoreMatcher } from '../src/utils/gitignore.js';
+import { rmWithRetries } from './test-helpers.js';
+
+async function writeFile(root: string, relativePath: string, content: string): Promise<void> {
+  const target = path.join(root, relativePath);
+  await fs.mkdir(path.dirname(target), { recursive: true });
+  await fs.writeFile(target, content);
+}
+
+async function indexedRelativePaths(root: string, respectGitignore: boolean): Promise<Set<string>> {
+  const indexer = new CodebaseIndexer({
+    rootPath: root,
+    config: { skipEmbedding: true, respectGitignore }
+  });
+  await indexer.index();
+
+  const indexPath = path.join(root, CODEBASE_CONTEXT_DIRNAME, KEYWORD_INDEX_FILENAME);
+  const indexRaw = JSON.parse(await fs.readFile(indexPath, 'utf-8')) as {
+    chunks: Array<{ filePath: string }>;
+  };
+  return new Set(
+    indexRaw.chunks.map((chunk) => path.relative(root, chunk.filePath).replace(/\\/g, '/'))
+  );
+}
+
+describe('Indexer gitignore handling', () => {
+  let tempDir: string;
+
+  beforeEach(async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'indexer-gitignore-'));
+
+    await writeFile(tempDir, '.gitignore', 'generated/\n');
+    await writeFile(tempDir, '.git/info/exclude', 'scratch.ts\n');
+    await writeFile(tempDir, 'packages/app/.gitignore', 'local.ts\n');
+    await writeFile(tempDir, 'src/kept.ts', 'export const kept = 1;\n');
+    await writeFile(tempDir, 'generated/output.ts', 'export const output = 1;\n');
+    await writeFile(tempDir, 'scratch.ts', 'export const scratch = 1;\n');
+    await writeFile(tempDir, 'packages/app/local.ts', 'export const local = 1;\n');
+    await writeFile(tempDir, 'packages/app/main.ts', 'export const main = 1;\n');
+    await writeFile(tempDir, 'generated/nested/.gitignore', '!*.ts\n');
+    await writeFile(tempDir, 'generated/nested/revived.ts', 'export const revived = 1;\n');
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+  });
+
+  it('skips files ignored by root, nested and info/exclude rules', async () => {
+    const files = await indexedRelativePaths(tempDir, true);
+
+    expect(files.has('src/kept.ts')).toBe(true);
+    expect(files.has('packages/app/main.ts')).toBe(true);
+    expect(files.has('generated/output.ts')).toBe(false);
+    expect(files.has('scratch.ts')).toBe(false);
+    expect(files.has('packages/app/local.ts')).toBe(false);
+    // Ignore files inside an ignored directory are never consulted
+    expect(files.has('generated/nested/revived.ts')).toBe(false);
+  });
+
+  it('never walks into ignored directories', async () => {
+    const matcher = await loadGitignoreMatcher(tempDir);
+    const walkIgnore = gitignoreWalkIgnore(tempDir, matcher, {});
+    const visited: string[] = [];
+    await glob('**/*', {
+      cwd: tempDir,
+      nodir: true,
+      ignore: {
+        ignored: (p: Path) => {
+          visited.push(p.relative().replace(/\\/g, '/'));
+          return false;
+        },
+        childrenIgnored: walkIgnore.childrenIgnored
+      }
+    });
+
+    expect(visited).toContain('src/kept.ts');
+    expect(visited.filter((file) => file.startsWith('generated/'))).toEqual([]);
+  });
+
+  it('indexes ignored files when respectGitignore is disabled', async () => {
+    const files = await indexedRelativePaths(tempDir, false);
+
+    expect(files.has('generated/output.ts')).toBe(true);
+    expect(files.has('packages/app/local.ts')).toBe(true);
+  });
+
+  it('lets nested rules scope to their own directory', async () => {
+    await writeFile(tempDir, 'src/local.ts', 'export const other = 1;\n');
+    const matcher = await loadGitignoreMatcher(tempDir);
+
+    expect(matcher.ignores('packages/app/local.ts')).toBe(true);
+    expect(matcher.ignores('src/local.ts')).toBe(false);
+    expect(matcher.sources).toEqual(
+      expect.arrayContaining(['.gitignore', '.git/info/exclude', 'packages/app/.gitignore'])
+    );
+  });
+
+  it('reads the global excludes file named by core.excludesFile', async () => {
+    const configDir = await fs.mkdtemp(path.join(os.tmpdir(), 'indexer-gitignore-config-'));
+    const excludesFile = path.join(configDir, 'custom-excludes');
+    await fs.writeFile(excludesFile, 'notes.ts\n');
+    await fs.writeFile(
+      path.join(configDir, 'gitconfig'),
+      `[core]\n\texcludesFile = ${excludesFile.replace(/\\/g, '/')}\n`
+    );
+    const saved = {
+      GIT_CONFIG_GLOBAL: process.env.GIT_CONFIG_GLOBAL,
+      GIT_CONFIG_NOSYSTEM: process.env.GIT_CONFIG_NOSYSTEM
+    };
+    process.env.GIT_CONFIG_GLOBAL = path.join(configDir, 'gitconfig');
+    process.env.GIT_CONFIG_NOSYSTEM = '1';
+    try {
+      await writeFile(tempDir, 'notes.ts', 'export const notes = 1;\n');
+      const matcher = await loadGitignoreMatcher(tempDir);
+
+      expect(matcher.ignores('notes.ts')).toBe(true);
+      expect(matcher.ignores('src/kept.ts')).toBe(false);
+      expect(matcher.sources).toContain(path.resolve(excludesFile));
+    } finally {
+      for (const [name, value] of Object.entries(saved)) {
+        if (value === undefined) delete process.env[name];
+        else process.env[name] = value;
+      }
+      await rmWithRetries(configDir);
+    }
+  });
+});
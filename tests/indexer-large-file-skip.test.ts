@@ -57,4 +57,37 @@ describe('Indexer large file skip regression', () => {
     expect(indexedFiles.has('big.ts')).toBe(false);
     expect(indexedFiles.has('big.generated.ts')).toBe(false);
   });
+
+  it('reports oversized files as too_large and minified ones only when asked', async () => {
+    await fs.writeFile(path.join(tempDir, 'small.ts'), 'export const keep = 1;\n');
+    await fs.writeFile(
+      path.join(tempDir, 'big.ts'),
+      `export const huge = '${'x'.repeat(9000)}';\n`
+    );
+    await fs.writeFile(
+      path.join(tempDir, 'src', 'vendor.min.js'),
+      `var a=${'[1,2,3],'.repeat(300)}0;\n`
+    );
+
+    const indexWith = (skipMinified: boolean) =>
+      new CodebaseIndexer({
+        rootPath: tempDir,
+        config: {
+          skipEmbedding: true,
+          include: ['**/*.{ts,js}'],
+          parsing: { maxFileSize: 8192, skipMinified }
+        }
+      }).index();
+
+    const lenient = await indexWith(false);
+    expect(lenient.skipped).toEqual([{ file: 'big.ts', reason: 'too_large', size: 9024 }]);
+    expect(lenient.indexedFiles).toBe(2);
+
+    const strict = await indexWith(true);
+    expect(strict.skipped?.map(({ file, reason }) => `${file}:${reason}`).sort()).toEqual([
+      'big.ts:too_large',
+      'src/vendor.min.js:minified'
+    ]);
+    expect(strict.indexedFiles).toBe(1);
+  });
 });
@@ -0,0 +1,51 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import { readIndexedFiles } from '../src/core/chunk-export.js';
+import { rmWithRetries } from './test-helpers.js';
+
+describe('indexer symlink handling', () => {
+  let tempDir: string;
+  let outsideDir: string;
+
+  beforeEach(async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'indexer-symlinks-'));
+    outsideDir = await fs.mkdtemp(path.join(os.tmpdir(), 'indexer-symlinks-outside-'));
+    await fs.mkdir(path.join(tempDir, 'src'));
+    await fs.mkdir(path.join(tempDir, 'lib'));
+    await fs.writeFile(path.join(tempDir, 'src', 'app.ts'), 'export const app = 1;\n');
+    await fs.writeFile(path.join(tempDir, 'lib', 'util.ts'), 'export const util = 1;\n');
+    await fs.writeFile(path.join(outsideDir, 'shared.ts'), 'export const shared = 1;\n');
+
+    // A cycle back to the root, a file alias inside the root, and a link leaving the root
+    await fs.symlink('..', path.join(tempDir, 'src', 'loop'), 'dir');
+    await fs.symlink(path.join('..', 'lib', 'util.ts'), path.join(tempDir, 'src', 'alias.ts'));
+    await fs.symlink(outsideDir, path.join(tempDir, 'vendor'), 'dir');
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+    await rmWithRetries(outsideDir);
+  });
+
+  async function indexWith(followSymlinks?: boolean) {
+    await new CodebaseIndexer({
+      rootPath: tempDir,
+      config: { skipEmbedding: true, ...(followSymlinks ? { followSymlinks } : {}) }
+    }).index();
+    return readIndexedFiles(tempDir);
+  }
+
+  it('does not follow directory symlinks by default and indexes a file alias once', async () => {
+    expect(await indexWith()).toEqual(['lib/util.ts', 'src/app.ts']);
+  });
+
+  it('follows links out of the root when enabled and terminates on a cycle', async () => {
+    expect(await indexWith(true)).toEqual(['lib/util.ts', 'src/app.ts', 'vendor/shared.ts']);
+  });
+});
@@ -0,0 +1,127 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import {
+  detectLanguage,
+  detectLanguageFromContent,
+  isTestFile,
+  sniffFile
+} from '../src/utils/language-detection.js';
+import {
+  CODEBASE_CONTEXT_DIRNAME,
+  KEYWORD_INDEX_FILENAME
+} from '../src/constants/codebase-context.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const PYTHON_SCRIPT = [
+  '#!/usr/bin/env python3',
+  '',
+  'def greet(name):',
+  '    return f"hello {name}"',
+  '',
+  'print(greet("world"))',
+  ''
+].join('\n');
+
+describe('detectLanguage', () => {
+  it('prefers the extension and looks through .in templates', () => {
+    expect(detectLanguage('src/app.ts', '#!/usr/bin/env python3')).toBe('typescript');
+    expect(detectLanguage('config.h.in')).toBe('c');
+    expect(detectLanguage('bin/tool')).toBe('plaintext');
+  });
+
+  it('reads a .h header as C++ when it uses C++ syntax', () => {
+    expect(detectLanguage('include/calc.h', 'int add(int a, int b);\n')).toBe('c');
+    expect(detectLanguage('include/calc.h', 'namespace calc {\nclass Calculator;\n}\n')).toBe(
+      'cpp'
+    );
+  });
+
+  it('falls back to the shebang, then to modelines and opening tags', () => {
+    expect(detectLanguage('bin/tool', PYTHON_SCRIPT)).toBe('python');
+    expect(detectLanguageFromContent('#!/usr/bin/env -S node --no-warnings\n')).toBe('javascript');
+    expect(detectLanguageFromContent('#!/bin/bash\nset -e\n')).toBe('shellscript');
+    expect(detectLanguageFromContent('# -*- mode: ruby -*-\nputs 1\n')).toBe('ruby');
+    expect(detectLanguageFromContent('<?php\necho 1;\n')).toBe('php');
+    expect(detectLanguageFromContent('# -*- coding: utf-8 -*-\n')).toBeNull();
+    expect(detectLanguageFromContent('MIT License\n')).toBeNull();
+  });
+});
+
+describe('isTestFile', () => {
+  it('recognizes test path conventions across languages', () => {
+    for (const file of [
+      'tests/integration.rs',
+      'src/__tests__/app.tsx',
+      'src/app.test.ts',
+      'pkg/server/handler_test.go',
+      'tools/test_cli.py',
+      'src/test/java/com/example/CalculatorTest.java'
+    ]) {
+      expect(isTestFile(file), file).toBe(true);
+    }
+    for (const file of ['src/contest.ts', 'src/latest.go', 'src/testing_utils.py', 'Test.java']) {
+      expect(isTestFile(file), file).toBe(false);
+    }
+  });
+});
+
+describe('Indexer content sniffing', () => {
+  let tempDir: string;
+
+  beforeEach(async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'language-detection-'));
+    await fs.mkdir(path.join(tempDir, 'bin'), { recursive: true });
+    await fs.writeFile(path.join(tempDir, 'bin', 'greet'), PYTHON_SCRIPT);
+    await fs.writeFile(path.join(tempDir, 'LICENSE'), 'MIT License\n');
+    await fs.writeFile(
+      path.join(tempDir, 'packed.py'),
+      Buffer.concat([Buffer.from('def packed():\n    pass\n'), Buffer.from([0, 1, 2, 0])])
+    );
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+  });
+
+  it('reports NUL bytes in the first KB as binary', async () => {
+    expect(await sniffFile(path.join(tempDir, 'packed.py'))).toEqual({
+      binary: true,
+      language: 'plaintext',
+      minified: false
+    });
+    expect(await sniffFile(path.join(tempDir, 'bin', 'greet'))).toEqual({
+      binary: false,
+      language: 'python',
+      minified: false
+    });
+  });
+
+  it('parses a shebang script without an extension as Python and skips binaries', async () => {
+    const indexer = new CodebaseIndexer({
+      rootPath: tempDir,
+      config: { skipEmbedding: true }
+    });
+    await indexer.index();
+
+    const indexPath = path.join(tempDir, CODEBASE_CONTEXT_DIRNAME, KEYWORD_INDEX_FILENAME);
+    const indexRaw = JSON.parse(await fs.readFile(indexPath, 'utf-8')) as {
+      chunks: Array<{ filePath: string; language: string }>;
+    };
+    const languages = new Map(
+      indexRaw.chunks.map((chunk) => [
+        path.relative(tempDir, chunk.filePath).replace(/\\/g, '/'),
+        chunk.language
+      ])
+    );
+
+    expect(languages.get('bin/greet')).toBe('python');
+    expect(languages.has('packed.py')).toBe(false);
+    expect(languages.has('LICENSE')).toBe(false);
+  });
+});
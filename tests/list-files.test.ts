@@ -0,0 +1,57 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import { listIndexedFiles } from '../src/core/list-files.js';
+import { rmWithRetries } from './test-helpers.js';
+
+describe('listIndexedFiles', () => {
+  let tempDir: string;
+
+  beforeEach(async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'list-files-'));
+    await fs.mkdir(path.join(tempDir, 'src', 'parser'), { recursive: true });
+    await fs.writeFile(path.join(tempDir, 'src', 'main.rs'), 'fn main() {}\n');
+    await fs.writeFile(
+      path.join(tempDir, 'src', 'parser', 'lexer.rs'),
+      'pub struct Lexer;\n\nimpl Lexer {\n    pub fn next(&self) {}\n}\n'
+    );
+    await fs.writeFile(path.join(tempDir, 'src', 'app.ts'), 'export function app() {}\n');
+    await fs.writeFile(path.join(tempDir, 'tool.py'), 'def run():\n    pass\n');
+    await new CodebaseIndexer({
+      rootPath: tempDir,
+      config: { skipEmbedding: true, include: ['**/*.{rs,ts,py}'] }
+    }).index();
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+  });
+
+  it('returns only Rust files for a *.rs glob, relative to the root', async () => {
+    const result = await listIndexedFiles(tempDir, { glob: '**/*.rs' });
+
+    expect(result?.files.map((f) => f.file)).toEqual(['src/main.rs', 'src/parser/lexer.rs']);
+    expect(result?.files.every((f) => f.language === 'rust' && f.symbolCount > 0)).toBe(true);
+    expect(result?.totalMatches).toBe(2);
+    expect(result?.nextCursor).toBeUndefined();
+  });
+
+  it('filters by language and pages with a cursor', async () => {
+    expect((await listIndexedFiles(tempDir, { language: 'python' }))?.files).toEqual([
+      expect.objectContaining({ file: 'tool.py', language: 'python' })
+    ]);
+
+    const first = await listIndexedFiles(tempDir, { limit: 2 });
+    expect(first?.files.map((f) => f.file)).toEqual(['src/app.ts', 'src/main.rs']);
+    expect(first?.nextCursor).toBe('src/main.rs');
+
+    const second = await listIndexedFiles(tempDir, { limit: 2, cursor: first?.nextCursor });
+    expect(second?.files.map((f) => f.file)).toEqual(['src/parser/lexer.rs', 'tool.py']);
+    expect(second?.nextCursor).toBeUndefined();
+  });
+});
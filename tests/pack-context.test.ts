@@ -0,0 +1,91 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { packContext } from '../src/core/pack-context.js';
+import { rmWithRetries } from './test-helpers.js';
+
+describe('packContext', () => {
+  let tempDir: string;
+
+  beforeEach(async () => {
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'pack-context-'));
+    await fs.mkdir(path.join(tempDir, 'src'));
+    await fs.writeFile(path.join(tempDir, 'src', 'b.py'), 'def add(a, b):\n    return a + b\n');
+    await fs.writeFile(
+      path.join(tempDir, 'src', 'a.ts'),
+      'export const one = 1;\nexport const two = 2;\n'
+    );
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+  });
+
+  it('renders snippets from two files with headers and language fences', async () => {
+    const result = await packContext(tempDir, [
+      { file: 'src/b.py' },
+      { file: 'src/a.ts', startLine: 2, endLine: 2 }
+    ]);
+
+    expect(result.packed).toBe(
+      [
+        '# Context (2 snippets from 2 files)',
+        '',
+        '- src/a.ts:2-2',
+        '- src/b.py:1-2',
+        '',
+        '## src/a.ts',
+        '',
+        'Lines 2-2:',
+        '```typescript',
+        'export const two = 2;',
+        '```',
+        '',
+        '## src/b.py',
+        '',
+        'Lines 1-2:',
+        '```python',
+        'def add(a, b):',
+        '    return a + b',
+        '```',
+        ''
+      ].join('\n')
+    );
+    expect(result.included.map(({ file, startLine }) => [file, startLine])).toEqual([
+      ['src/a.ts', 2],
+      ['src/b.py', 1]
+    ]);
+    expect(result.excluded).toEqual([]);
+    expect(result.tokensUsed).toBeLessThanOrEqual(result.maxTokens);
+  });
+
+  it('applies custom templates', async () => {
+    const result = await packContext(tempDir, [{ file: 'src/a.ts', startLine: 1, endLine: 1 }], {
+      templates: {
+        header: '<context>\n',
+        file: '',
+        snippet: '<file path="{file}" lines="{startLine}-{endLine}">\n{code}\n</file>\n'
+      }
+    });
+
+    expect(result.packed).toBe(
+      '<context>\n<file path="src/a.ts" lines="1-1">\nexport const one = 1;\n</file>\n'
+    );
+  });
+
+  it('keeps earlier references when the budget runs out and lists the rest', async () => {
+    const result = await packContext(
+      tempDir,
+      [{ file: 'src/a.ts' }, { file: 'src/b.py' }, { file: 'src/missing.ts' }],
+      { maxTokens: 40 }
+    );
+
+    expect(result.included.map((entry) => entry.file)).toEqual(['src/a.ts']);
+    expect(result.excluded).toEqual([
+      { file: 'src/b.py', startLine: 1, endLine: 2, reason: 'budget' },
+      { file: 'src/missing.ts', reason: 'not_found', message: 'File not found: src/missing.ts' }
+    ]);
+    expect(result.tokensUsed).toBeLessThanOrEqual(40);
+  });
+});
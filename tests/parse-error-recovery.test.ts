@@ -0,0 +1,65 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { fileURLToPath } from 'url';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import { readIndexingStats } from '../src/core/index-stats.js';
+import { extractTreeSitterSymbols } from '../src/utils/tree-sitter.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const brokenRust = path.join(
+  path.dirname(fileURLToPath(import.meta.url)),
+  'fixtures',
+  'parse-errors',
+  'broken.rs'
+);
+
+describe('extraction around parse errors', () => {
+  it('keeps the Rust symbols before and after a broken function', async () => {
+    const content = await fs.readFile(brokenRust, 'utf-8');
+    const extraction = await extractTreeSitterSymbols(content, 'rust');
+
+    expect(extraction).not.toBeNull();
+    const names = extraction!.symbols.map((symbol) => symbol.name);
+    expect(names).toEqual(
+      expect.arrayContaining(['parse_header', 'count_fields', 'format_row', 'Report', 'new'])
+    );
+    expect(extraction!.errorRegions?.length).toBeGreaterThan(0);
+    expect(extraction!.errorRegions![0].startLine).toBeGreaterThanOrEqual(9);
+    expect(extraction!.errorRegions![0].endLine).toBeLessThanOrEqual(12);
+  });
+
+  it('flags the file and each of its chunks', async () => {
+    const analysis = await new GenericAnalyzer().analyze(
+      brokenRust,
+      await fs.readFile(brokenRust, 'utf-8')
+    );
+    expect(analysis.metadata).toMatchObject({ hasParseErrors: true, chunkStrategy: 'ast-aligned' });
+    expect(analysis.chunks.every((chunk) => chunk.metadata.hasParseErrors === true)).toBe(true);
+  });
+});
+
+describe('indexing files with parse errors', () => {
+  let tempDir: string;
+
+  beforeEach(async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'parse-errors-'));
+    await fs.copyFile(brokenRust, path.join(tempDir, 'broken.rs'));
+    await fs.writeFile(path.join(tempDir, 'ok.rs'), 'pub fn fine() -> i32 {\n    1\n}\n');
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+  });
+
+  it('counts files recovered from syntax errors', async () => {
+    await new CodebaseIndexer({ rootPath: tempDir, config: { skipEmbedding: true } }).index();
+
+    const stats = await readIndexingStats(tempDir);
+    expect(stats).toMatchObject({ indexedFiles: 2, parseErrors: 0, recoveredParseErrors: 1 });
+  }, 30000);
+});
@@ -0,0 +1,148 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import { resolveQualifiedPath } from '../src/core/resolve-path.js';
+import { extractTreeSitterSymbols } from '../src/utils/tree-sitter.js';
+import { qualifiedSymbolPath, rustModulePath } from '../src/utils/qualified-path.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const GEOMETRY_RS = [
+  'pub mod shapes {',
+  '    pub struct Circle {',
+  '        pub radius: f64,',
+  '    }',
+  '',
+  '    impl Circle {',
+  '        pub fn area(&self) -> f64 {',
+  '            3.14 * self.radius * self.radius',
+  '        }',
+  '    }',
+  '}',
+  '',
+  'pub struct Square {',
+  '    pub side: f64,',
+  '}',
+  '',
+  'impl Square {',
+  '    pub fn area(&self) -> f64 {',
+  '        self.side * self.side',
+  '    }',
+  '}',
+  ''
+].join('\n');
+
+const CALCULATOR_JAVA = [
+  'package com.example.calc;',
+  '',
+  'public class Calculator {',
+  '    public int add(int a, int b) {',
+  '        return a + b;',
+  '    }',
+  '',
+  '    static class Audit {',
+  '        void add(String entry) {}',
+  '    }',
+  '}',
+  ''
+].join('\n');
+
+async function qualifiedPaths(
+  content: string,
+  language: string,
+  file: string
+): Promise<Record<string, string[]>> {
+  const extraction = await extractTreeSitterSymbols(content, language);
+  const paths: Record<string, string[]> = {};
+  for (const symbol of extraction?.symbols ?? []) {
+    (paths[symbol.name] ??= []).push(qualifiedSymbolPath(symbol, language, file));
+  }
+  return paths;
+}
+
+describe('qualified symbol paths', () => {
+  it('tells same-named methods in different structs apart', async () => {
+    const paths = await qualifiedPaths(GEOMETRY_RS, 'rust', 'src/geometry.rs');
+
+    expect(paths.area).toEqual(['geometry::shapes::Circle::area', 'geometry::Square::area']);
+    expect(paths.Circle).toEqual(['geometry::shapes::Circle']);
+  });
+
+  it('starts Java paths at the package', async () => {
+    const paths = await qualifiedPaths(CALCULATOR_JAVA, 'java', 'src/Calculator.java');
+
+    expect(paths.add).toEqual([
+      'com.example.calc.Calculator.add',
+      'com.example.calc.Calculator.Audit.add'
+    ]);
+    expect(paths['com.example.calc']).toEqual(['com.example.calc']);
+  });
+
+  it('derives Rust module paths from the file path', () => {
+    expect(rustModulePath('src/lib.rs')).toEqual([]);
+    expect(rustModulePath('crates/core/src/main.rs')).toEqual([]);
+    expect(rustModulePath('src/geometry/mod.rs')).toEqual(['geometry']);
+    expect(rustModulePath('/repo/src/geometry/shapes.rs')).toEqual(['geometry', 'shapes']);
+  });
+});
+
+describe('resolveQualifiedPath', () => {
+  let tempDir: string;
+
+  beforeEach(async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'qualified-paths-'));
+    await fs.mkdir(path.join(tempDir, 'src'), { recursive: true });
+    await fs.writeFile(path.join(tempDir, 'src', 'geometry.rs'), GEOMETRY_RS);
+    await new CodebaseIndexer({ rootPath: tempDir, config: { skipEmbedding: true } }).index();
+  }, 30000);
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+  });
+
+  it('returns the one symbol a full path names', async () => {
+    const result = await resolveQualifiedPath(tempDir, 'geometry::Square::area');
+
+    expect(result).toMatchObject({
+      status: 'success',
+      symbol: {
+        qualifiedPath: 'geometry::Square::area',
+        file: 'src/geometry.rs',
+        kind: 'method',
+        startLine: 18,
+        endLine: 20
+      }
+    });
+    expect(result).not.toHaveProperty('partial');
+  });
+
+  it('accepts a unique trailing part and reports a bare name as ambiguous', async () => {
+    await expect(resolveQualifiedPath(tempDir, 'Circle.area')).resolves.toMatchObject({
+      status: 'success',
+      partial: true,
+      symbol: { qualifiedPath: 'geometry::shapes::Circle::area' }
+    });
+
+    const bare = await resolveQualifiedPath(tempDir, 'area');
+    expect(bare).toMatchObject({ status: 'ambiguous', candidateCount: 2 });
+  });
+
+  it('labels search chunks with their qualified path', async () => {
+    const analysis = await new GenericAnalyzer().analyze(
+      path.join(tempDir, 'src', 'geometry.rs'),
+      GEOMETRY_RS
+    );
+    const labelled = analysis.chunks.map((chunk) => chunk.metadata.qualifiedPath).filter(Boolean);
+    expect(labelled).toEqual(expect.arrayContaining(['geometry::Square::area']));
+  });
+
+  it('fails for a path that names nothing', async () => {
+    await expect(resolveQualifiedPath(tempDir, 'geometry::Triangle::area')).resolves.toMatchObject(
+      { status: 'error' }
+    );
+  });
+});
@@ -0,0 +1,138 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { execFileSync } from 'child_process';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { directoryDistance, findRelatedFiles } from '../src/core/related-files.js';
+import {
+  CODEBASE_CONTEXT_DIRNAME,
+  MANIFEST_FILENAME,
+  RELATIONSHIPS_FILENAME
+} from '../src/constants/codebase-context.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const FILES = [
+  'src/core/engine.ts',
+  'src/core/parser.ts',
+  'src/core/unrelated.ts',
+  'src/cli/main.ts',
+  'src/util/log.ts',
+  'docs/notes.md'
+];
+
+function git(cwd: string, ...args: string[]): void {
+  execFileSync('git', ['-c', 'user.name=test', '-c', 'user.email=test@example.com', ...args], {
+    cwd,
+    stdio: 'ignore'
+  });
+}
+
+describe('findRelatedFiles', () => {
+  let tempDir: string;
+
+  beforeEach(async () => {
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'related-files-'));
+    const contextDir = path.join(tempDir, CODEBASE_CONTEXT_DIRNAME);
+    await fs.mkdir(contextDir, { recursive: true });
+    for (const file of FILES) {
+      await fs.mkdir(path.dirname(path.join(tempDir, file)), { recursive: true });
+      await fs.writeFile(path.join(tempDir, file), `// ${file}\n`);
+    }
+    await fs.writeFile(
+      path.join(contextDir, MANIFEST_FILENAME),
+      JSON.stringify({
+        version: 1,
+        generatedAt: new Date().toISOString(),
+        files: Object.fromEntries(FILES.map((file) => [file, 'hash']))
+      })
+    );
+    await fs.writeFile(
+      path.join(contextDir, RELATIONSHIPS_FILENAME),
+      JSON.stringify({
+        dependencyGraph: {
+          edges: [
+            { from: 'src/cli/main.ts', to: 'src/core/engine.ts', line: 1 },
+            { from: 'src/core/engine.ts', to: 'src/core/parser.ts', line: 1 },
+            { from: 'src/core/engine.ts', to: 'src/util/log.ts', line: 2 },
+            { from: 'src/core/parser.ts', to: 'src/util/log.ts', line: 1 }
+          ],
+          unresolved: { 'src/core/engine.ts': ['lodash'], 'src/core/parser.ts': ['lodash'] }
+        }
+      })
+    );
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+  });
+
+  it('ranks the most connected file first and explains why', async () => {
+    const result = await findRelatedFiles(tempDir, 'src/core/engine.ts');
+    if (result.status !== 'success') throw new Error(result.message);
+
+    expect(result.related.map((entry) => entry.file)).toEqual([
+      'src/core/parser.ts',
+      'src/cli/main.ts',
+      'src/util/log.ts',
+      'src/core/unrelated.ts'
+    ]);
+    expect(result.related[0].reasons).toEqual([
+      'imported by this file',
+      'shares 2 imports (lodash, src/util/log.ts)',
+      'same directory'
+    ]);
+    expect(result.related[0].score).toBeCloseTo(1.9);
+    // Outside a git repository the co-change signal drops out instead of failing the call
+    expect(result.unavailable).toEqual(['coChange']);
+  });
+
+  it('lets each signal be weighted or turned off', async () => {
+    const result = await findRelatedFiles(tempDir, 'src/core/engine.ts', {
+      weights: { imports: 0, sharedImports: 0, directory: 1 }
+    });
+    if (result.status !== 'success') throw new Error(result.message);
+
+    expect(result.related.map((entry) => [entry.file, entry.score])).toEqual([
+      ['src/core/parser.ts', 1],
+      ['src/core/unrelated.ts', 1],
+      ['src/cli/main.ts', 0.333],
+      ['src/util/log.ts', 0.333]
+    ]);
+  });
+
+  it('adds files changed in the same commits', async () => {
+    git(tempDir, 'init', '-q');
+    git(tempDir, 'add', 'src/core/engine.ts', 'src/core/unrelated.ts');
+    git(tempDir, 'commit', '-q', '-m', 'first');
+    await fs.appendFile(path.join(tempDir, 'src/core/engine.ts'), '// more\n');
+    git(tempDir, 'commit', '-q', '-am', 'engine only');
+
+    const result = await findRelatedFiles(tempDir, 'src/core/engine.ts', {
+      weights: { imports: 0, sharedImports: 0, directory: 0, coChange: 1 }
+    });
+    if (result.status !== 'success') throw new Error(result.message);
+
+    expect(result.unavailable).toBeUndefined();
+    expect(result.related).toEqual([
+      {
+        file: 'src/core/unrelated.ts',
+        score: 0.5,
+        reasons: ['changed together in 1 of 2 commits']
+      }
+    ]);
+  });
+
+  it('rejects files that are not in the index', async () => {
+    const result = await findRelatedFiles(tempDir, 'src/missing.ts');
+    expect(result).toMatchObject({ status: 'error' });
+  });
+});
+
+describe('directoryDistance', () => {
+  it('counts levels through the nearest common directory', () => {
+    expect(directoryDistance('a/b/x.ts', 'a/b/y.ts')).toBe(0);
+    expect(directoryDistance('a/b/x.ts', 'a/y.ts')).toBe(1);
+    expect(directoryDistance('a/b/x.ts', 'a/c/y.ts')).toBe(2);
+    expect(directoryDistance('x.ts', 'a/b/y.ts')).toBe(2);
+  });
+});
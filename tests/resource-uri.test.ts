@@ -1,7 +1,9 @@
 import { describe, it, expect } from 'vitest';
 import {
   CONTEXT_RESOURCE_URI,
+  INDEX_RESOURCE_URI,
   isContextResourceUri,
+  isIndexResourceUri,
   normalizeResourceUri
 } from '../src/resources/uri.js';
 
@@ -17,6 +19,12 @@ describe('resource URI normalization', () => {
     expect(isContextResourceUri(namespaced)).toBe(true);
   });
 
+  it('recognizes the index resource, namespaced or not', () => {
+    expect(isIndexResourceUri(INDEX_RESOURCE_URI)).toBe(true);
+    expect(normalizeResourceUri(`codebase-context/${INDEX_RESOURCE_URI}`)).toBe(INDEX_RESOURCE_URI);
+    expect(isContextResourceUri(INDEX_RESOURCE_URI)).toBe(false);
+  });
+
   it('rejects unknown URIs', () => {
     expect(isContextResourceUri('codebase://other')).toBe(false);
     expect(isContextResourceUri('other/codebase://other')).toBe(false);
@@ -0,0 +1,127 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { execFileSync } from 'child_process';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import { ToolResultCache, normalizeArgs } from '../src/core/result-cache.js';
+import { dispatchTool } from '../src/tools/index.js';
+import type { ToolContext, ToolResponse } from '../src/tools/types.js';
+import { CODEBASE_CONTEXT_DIRNAME } from '../src/constants/codebase-context.js';
+import { rmWithRetries } from './test-helpers.js';
+
+describe('ToolResultCache', () => {
+  it('normalizes argument order and unset values into one key', () => {
+    expect(normalizeArgs({ b: 1, a: { d: null, c: [2, 1] }, e: undefined })).toBe(
+      normalizeArgs({ a: { c: [2, 1] }, b: 1 })
+    );
+  });
+
+  it('evicts the least recently used entry past maxEntries', () => {
+    const cache = new ToolResultCache<string>({ maxEntries: 2 });
+    cache.set('t', { q: 1 }, 'one');
+    cache.set('t', { q: 2 }, 'two');
+    cache.get('t', { q: 1 });
+    cache.set('t', { q: 3 }, 'three');
+
+    expect(cache.get('t', { q: 1 })).toBe('one');
+    expect(cache.get('t', { q: 2 })).toBeUndefined();
+    expect(cache.get('t', { q: 3 })).toBe('three');
+  });
+
+  it('stays within maxBytes and never serves entries from before invalidate()', () => {
+    const cache = new ToolResultCache<string>({ maxBytes: 20 });
+    cache.set('t', { q: 1 }, 'x'.repeat(12));
+    cache.set('t', { q: 2 }, 'y'.repeat(12));
+    expect(cache.stats()).toMatchObject({ entries: 1, bytes: 14 });
+
+    cache.invalidate();
+    expect(cache.get('t', { q: 2 })).toBeUndefined();
+    expect(cache.stats()).toMatchObject({ version: 1, entries: 0, bytes: 0 });
+  });
+});
+
+describe('dispatchTool result caching', () => {
+  let tempDir: string;
+  let cache: ToolResultCache<ToolResponse>;
+  let ctx: ToolContext;
+
+  beforeEach(async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'result-cache-'));
+    await fs.writeFile(
+      path.join(tempDir, 'math.ts'),
+      'export function add(a: number, b: number): number {\n  return a + b;\n}\n'
+    );
+    await new CodebaseIndexer({ rootPath: tempDir, config: { skipEmbedding: true } }).index();
+
+    cache = new ToolResultCache<ToolResponse>();
+    const baseDir = path.join(tempDir, CODEBASE_CONTEXT_DIRNAME);
+    ctx = {
+      indexState: { status: 'ready' },
+      paths: {
+        baseDir,
+        memory: path.join(baseDir, 'memory.json'),
+        intelligence: path.join(baseDir, 'intelligence.json'),
+        keywordIndex: path.join(baseDir, 'index.json'),
+        vectorDb: path.join(baseDir, 'index')
+      },
+      rootPath: tempDir,
+      performIndexing: () => undefined,
+      resultCache: cache
+    };
+  }, 30000);
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+  });
+
+  async function definitionText(): Promise<string> {
+    const result = await dispatchTool('get_definition', { symbol: 'add' }, ctx);
+    return JSON.parse(result.content![0].text).definitions[0].text;
+  }
+
+  it('serves a repeated call from the cache until a file update invalidates it', async () => {
+    const first = await definitionText();
+    expect(await definitionText()).toBe(first);
+    expect(cache.stats()).toMatchObject({ hits: 1, misses: 1 });
+
+    await fs.writeFile(
+      path.join(tempDir, 'math.ts'),
+      'export function add(a: number, b: number): number {\n  return b + a;\n}\n'
+    );
+    // The server also invalidates on watcher events and when any reindex finishes
+    await dispatchTool('refresh_index', { incrementalOnly: true }, ctx);
+
+    expect(await definitionText()).toContain('return b + a;');
+    expect(cache.stats()).toMatchObject({ hits: 1, misses: 2 });
+  });
+
+  it('does not cache tools that are not opted in', async () => {
+    await dispatchTool('stats', {}, ctx);
+    await dispatchTool('stats', {}, ctx);
+    expect(cache.stats()).toMatchObject({ hits: 0, misses: 0, entries: 0 });
+  });
+
+  it('misses the search cache once HEAD moves', async () => {
+    const git = (...args: string[]) =>
+      execFileSync('git', ['-c', 'user.name=test', '-c', 'user.email=test@example.com', ...args], {
+        cwd: tempDir,
+        stdio: 'ignore'
+      });
+    git('init', '-q');
+    git('add', 'math.ts');
+    git('commit', '-q', '-m', 'add');
+    const search = () => dispatchTool('search_codebase', { query: 'add', gitBoost: false }, ctx);
+
+    await search();
+    await search();
+    expect(cache.stats()).toMatchObject({ hits: 1, misses: 1 });
+
+    git('commit', '-q', '--allow-empty', '-m', 'empty');
+    await search();
+    expect(cache.stats()).toMatchObject({ hits: 1, misses: 2 });
+  });
+});
@@ -0,0 +1,98 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import { normalizeSnippet } from '../src/core/snippet-dedup.js';
+import { dispatchTool } from '../src/tools/index.js';
+import type { ToolContext } from '../src/tools/types.js';
+import { CODEBASE_CONTEXT_DIRNAME } from '../src/constants/codebase-context.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const FORMAT_CURRENCY = [
+  'export function formatCurrency(amount: number): string {',
+  '  const rounded = Math.round(amount * 100) / 100;',
+  "  return `$${rounded.toFixed(2)}`;",
+  '}',
+  ''
+].join('\n');
+
+function contextFor(rootPath: string): ToolContext {
+  const baseDir = path.join(rootPath, CODEBASE_CONTEXT_DIRNAME);
+  return {
+    indexState: { status: 'ready' },
+    paths: {
+      baseDir,
+      memory: path.join(baseDir, 'memory.json'),
+      intelligence: path.join(baseDir, 'intelligence.json'),
+      keywordIndex: path.join(baseDir, 'index.json'),
+      vectorDb: path.join(baseDir, 'index')
+    },
+    rootPath,
+    performIndexing: () => undefined
+  };
+}
+
+async function search(rootPath: string, extra: Record<string, unknown> = {}) {
+  const result = await dispatchTool(
+    'search_codebase',
+    { query: 'formatCurrency', gitBoost: false, ...extra },
+    contextFor(rootPath)
+  );
+  return JSON.parse(result.content![0].text);
+}
+
+describe('search_codebase dedupSnippets', () => {
+  let tempDir: string;
+
+  beforeEach(async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'snippet-dedup-'));
+    await fs.mkdir(path.join(tempDir, 'src', 'billing'), { recursive: true });
+    await fs.mkdir(path.join(tempDir, 'src', 'generated'), { recursive: true });
+    await fs.writeFile(path.join(tempDir, 'src', 'billing', 'format.ts'), FORMAT_CURRENCY);
+    // Same code with trailing whitespace the default normalization ignores
+    await fs.writeFile(
+      path.join(tempDir, 'src', 'generated', 'format.ts'),
+      FORMAT_CURRENCY.replace('{\n', '{   \n')
+    );
+    await new CodebaseIndexer({ rootPath: tempDir, config: { skipEmbedding: true } }).index();
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+  });
+
+  it('lists no duplicates unless asked to collapse them', async () => {
+    const payload = await search(tempDir);
+    expect(payload.results.length).toBeGreaterThan(0);
+    expect(payload.results.some((r: { duplicates?: string[] }) => r.duplicates)).toBe(false);
+  });
+
+  it('collapses identical code into one result listing both locations', async () => {
+    const payload = await search(tempDir, { dedupSnippets: 'exact' });
+    const hits = payload.results.filter((r: { file: string }) => r.file.includes('format.ts'));
+
+    expect(hits).toHaveLength(1);
+    const locations = [hits[0].file, ...hits[0].duplicates].map((f: string) =>
+      f.includes(path.join('src', 'billing')) ? 'billing' : 'generated'
+    );
+    expect(locations.sort()).toEqual(['billing', 'generated']);
+  });
+
+  it('rejects unknown dedup modes', async () => {
+    const payload = await search(tempDir, { dedupSnippets: 'fuzzy' });
+    expect(payload.errorCode).toBe('invalid_params');
+  });
+});
+
+describe('normalizeSnippet', () => {
+  it('ignores all whitespace differences only in whitespace mode', () => {
+    const a = 'if (x) {\n  return y;\n}';
+    const b = 'if (x) {\n\treturn  y;\n}\n\n';
+    expect(normalizeSnippet(a, 'exact')).not.toBe(normalizeSnippet(b, 'exact'));
+    expect(normalizeSnippet(a, 'whitespace')).toBe(normalizeSnippet(b, 'whitespace'));
+  });
+});
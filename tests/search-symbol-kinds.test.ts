@@ -0,0 +1,77 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import { dispatchTool } from '../src/tools/index.js';
+import type { ToolContext } from '../src/tools/types.js';
+import { CODEBASE_CONTEXT_DIRNAME } from '../src/constants/codebase-context.js';
+import { rmWithRetries } from './test-helpers.js';
+
+describe('search_codebase kinds filter', () => {
+  let tempDir: string;
+
+  beforeEach(async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'search-kinds-'));
+    await fs.writeFile(
+      path.join(tempDir, 'ledger.py'),
+      'class Ledger:\n    add = 0\n    total = 0\n'
+    );
+    await fs.writeFile(path.join(tempDir, 'maths.py'), 'def add(a, b):\n    return a + b\n');
+    await new CodebaseIndexer({
+      rootPath: tempDir,
+      config: { skipEmbedding: true }
+    }).index();
+  }, 30000);
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+  });
+
+  function contextFor(rootPath: string): ToolContext {
+    const baseDir = path.join(rootPath, CODEBASE_CONTEXT_DIRNAME);
+    return {
+      indexState: { status: 'ready' },
+      paths: {
+        baseDir,
+        memory: path.join(baseDir, 'memory.json'),
+        intelligence: path.join(baseDir, 'intelligence.json'),
+        keywordIndex: path.join(baseDir, 'index.json'),
+        vectorDb: path.join(baseDir, 'index')
+      },
+      rootPath,
+      performIndexing: () => undefined
+    };
+  }
+
+  async function searchFiles(args: Record<string, unknown>): Promise<string[]> {
+    const result = await dispatchTool(
+      'search_codebase',
+      { query: 'add', gitBoost: false, ...args },
+      contextFor(tempDir)
+    );
+    const parsed = JSON.parse(result.content![0].text);
+    return parsed.results.map((r: { file: string }) => path.basename(r.file.split(':')[0]));
+  }
+
+  it('separates a field and a function with the same name', async () => {
+    expect(await searchFiles({ kinds: ['field'] })).toEqual(['ledger.py']);
+    expect(await searchFiles({ kinds: ['function'] })).toEqual(['maths.py']);
+    expect((await searchFiles({ kinds: [] })).sort()).toEqual(['ledger.py', 'maths.py']);
+  });
+
+  it('rejects unknown kinds', async () => {
+    const result = await dispatchTool(
+      'search_codebase',
+      { query: 'add', kinds: ['function', 'fucntion'] },
+      contextFor(tempDir)
+    );
+    const parsed = JSON.parse(result.content![0].text);
+    expect(result.isError).toBe(true);
+    expect(parsed.errorCode).toBe('invalid_params');
+    expect(parsed.message).toContain('fucntion');
+  });
+});
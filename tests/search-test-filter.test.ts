@@ -0,0 +1,85 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import { dispatchTool } from '../src/tools/index.js';
+import type { ToolContext } from '../src/tools/types.js';
+import { CODEBASE_CONTEXT_DIRNAME } from '../src/constants/codebase-context.js';
+import { rmWithRetries } from './test-helpers.js';
+
+describe('search_codebase test filters', () => {
+  let tempDir: string;
+
+  beforeEach(async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'search-tests-filter-'));
+    await fs.writeFile(
+      path.join(tempDir, 'maths.ts'),
+      'export function add(a: number, b: number): number {\n  return a + b;\n}\n'
+    );
+    await fs.writeFile(
+      path.join(tempDir, 'maths.test.ts'),
+      "import { add } from './maths';\n\nexport function checkAdd(): boolean {\n" +
+        '  return add(1, 2) === 3;\n}\n'
+    );
+    await fs.writeFile(
+      path.join(tempDir, 'test_maths.py'),
+      'def test_add():\n    assert add(1, 2) == 3\n'
+    );
+    await new CodebaseIndexer({
+      rootPath: tempDir,
+      config: { skipEmbedding: true }
+    }).index();
+  }, 30000);
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+  });
+
+  function contextFor(rootPath: string): ToolContext {
+    const baseDir = path.join(rootPath, CODEBASE_CONTEXT_DIRNAME);
+    return {
+      indexState: { status: 'ready' },
+      paths: {
+        baseDir,
+        memory: path.join(baseDir, 'memory.json'),
+        intelligence: path.join(baseDir, 'intelligence.json'),
+        keywordIndex: path.join(baseDir, 'index.json'),
+        vectorDb: path.join(baseDir, 'index')
+      },
+      rootPath,
+      performIndexing: () => undefined
+    };
+  }
+
+  async function searchFiles(args: Record<string, unknown>): Promise<string[]> {
+    const result = await dispatchTool(
+      'search_codebase',
+      { query: 'add', gitBoost: false, ...args },
+      contextFor(tempDir)
+    );
+    const parsed = JSON.parse(result.content![0].text);
+    const files = parsed.results.map((r: { file: string }) => path.basename(r.file.split(':')[0]));
+    return Array.from(new Set<string>(files)).sort();
+  }
+
+  it('drops test files with includeTests: false and keeps only them with testsOnly', async () => {
+    expect(await searchFiles({})).toContain('maths.ts');
+    expect(await searchFiles({ includeTests: false })).toEqual(['maths.ts']);
+    expect(await searchFiles({ testsOnly: true })).toEqual(['maths.test.ts', 'test_maths.py']);
+  });
+
+  it('rejects testsOnly combined with includeTests: false', async () => {
+    const result = await dispatchTool(
+      'search_codebase',
+      { query: 'add', includeTests: false, testsOnly: true },
+      contextFor(tempDir)
+    );
+    const parsed = JSON.parse(result.content![0].text);
+    expect(result.isError).toBe(true);
+    expect(parsed.errorCode).toBe('invalid_params');
+  });
+});
@@ -0,0 +1,117 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import { dispatchTool } from '../src/tools/index.js';
+import type { ToolContext } from '../src/tools/types.js';
+import { CODEBASE_CONTEXT_DIRNAME } from '../src/constants/codebase-context.js';
+import { registerEmbeddingProvider, type EmbeddingProvider } from '../src/embeddings/index.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const KEYWORDS = ['apple', 'banana', 'cherry'];
+
+/** One dimension per keyword occurrence count, plus a constant so no vector is all zeros. */
+class KeywordEmbeddingProvider implements EmbeddingProvider {
+  readonly name = 'keyword-test';
+  readonly modelName = 'counts';
+  readonly dimensions = KEYWORDS.length + 1;
+  batchCalls = 0;
+
+  async initialize(): Promise<void> {}
+
+  isReady(): boolean {
+    return true;
+  }
+
+  async embed(text: string): Promise<number[]> {
+    const words = text.toLowerCase().match(/[a-z]+/g) ?? [];
+    return [...KEYWORDS.map((keyword) => words.filter((w) => w === keyword).length), 1];
+  }
+
+  async embedBatch(texts: string[]): Promise<number[][]> {
+    this.batchCalls++;
+    return Promise.all(texts.map((text) => this.embed(text)));
+  }
+}
+
+function contextFor(rootPath: string): ToolContext {
+  const baseDir = path.join(rootPath, CODEBASE_CONTEXT_DIRNAME);
+  return {
+    indexState: { status: 'ready' },
+    paths: {
+      baseDir,
+      memory: path.join(baseDir, 'memory.json'),
+      intelligence: path.join(baseDir, 'intelligence.json'),
+      keywordIndex: path.join(baseDir, 'index.json'),
+      vectorDb: path.join(baseDir, 'index')
+    },
+    rootPath,
+    performIndexing: () => undefined
+  };
+}
+
+describe('semantic_search tool', () => {
+  let tempDir: string;
+  let provider: KeywordEmbeddingProvider;
+
+  beforeEach(async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    provider = new KeywordEmbeddingProvider();
+    registerEmbeddingProvider(provider);
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'semantic-search-'));
+    await fs.writeFile(path.join(tempDir, 'a.ts'), 'export const fruit = "apple apple";\n');
+    await fs.writeFile(path.join(tempDir, 'b.ts'), 'export const fruit = "banana banana";\n');
+    await fs.writeFile(path.join(tempDir, 'c.ts'), 'export const fruit = "cherry and apple";\n');
+  });
+
+  afterEach(async () => {
+    registerEmbeddingProvider(null);
+    await rmWithRetries(tempDir);
+  });
+
+  it('returns the nearest chunks first by cosine similarity', async () => {
+    await new CodebaseIndexer({ rootPath: tempDir, config: { skipEmbedding: false } }).index();
+
+    const result = await dispatchTool(
+      'semantic_search',
+      { query: 'cherry', k: 3 },
+      contextFor(tempDir)
+    );
+    const payload = JSON.parse(result.content![0].text);
+
+    expect(payload.status).toBe('success');
+    expect(payload.model).toBe('keyword-test:counts');
+    const files = payload.matches.map((m: { file: string }) => m.file);
+    expect(files[0]).toBe('c.ts');
+    expect(new Set(files)).toEqual(new Set(['a.ts', 'b.ts', 'c.ts']));
+    const scores = payload.matches.map((m: { score: number }) => m.score);
+    expect(scores).toEqual([...scores].sort((x, y) => y - x));
+    expect(payload.matches[0]).toMatchObject({ startLine: 1, language: 'typescript' });
+  });
+
+  it('reuses cached chunk embeddings across full rebuilds', async () => {
+    const first = await new CodebaseIndexer({
+      rootPath: tempDir,
+      config: { skipEmbedding: false }
+    }).index();
+    expect(first.embeddingCache?.hits).toBe(0);
+    const callsAfterFirst = provider.batchCalls;
+    expect(callsAfterFirst).toBeGreaterThan(0);
+
+    const second = await new CodebaseIndexer({
+      rootPath: tempDir,
+      config: { skipEmbedding: false }
+    }).index();
+    expect(provider.batchCalls).toBe(callsAfterFirst);
+    expect(second.embeddingCache).toEqual({ hits: first.embeddingCache?.misses, misses: 0 });
+  });
+
+  it('rejects an empty query', async () => {
+    const result = await dispatchTool('semantic_search', { query: ' ' }, contextFor(tempDir));
+    expect(result.isError).toBe(true);
+    expect(JSON.parse(result.content![0].text).errorCode).toBe('invalid_params');
+  });
+});
@@ -0,0 +1,109 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { fileURLToPath } from 'url';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import { dispatchTool } from '../src/tools/index.js';
+import type { ToolContext } from '../src/tools/types.js';
+import { CODEBASE_CONTEXT_DIRNAME } from '../src/constants/codebase-context.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const RUST_FIXTURE = path.join(
+  path.dirname(fileURLToPath(import.meta.url)),
+  'fixtures',
+  'grammars',
+  'rust.rs'
+);
+
+describe('structural_search tool', () => {
+  let tempDir: string;
+
+  beforeEach(async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'structural-search-'));
+    await fs.copyFile(RUST_FIXTURE, path.join(tempDir, 'calculator.rs'));
+    await fs.writeFile(path.join(tempDir, 'calc.py'), 'def add(calc, n):\n    return calc + n\n');
+    await new CodebaseIndexer({
+      rootPath: tempDir,
+      config: { skipEmbedding: true }
+    }).index();
+  }, 30000);
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+  });
+
+  function contextFor(rootPath: string): ToolContext {
+    const baseDir = path.join(rootPath, CODEBASE_CONTEXT_DIRNAME);
+    return {
+      indexState: { status: 'ready' },
+      paths: {
+        baseDir,
+        memory: path.join(baseDir, 'memory.json'),
+        intelligence: path.join(baseDir, 'intelligence.json'),
+        keywordIndex: path.join(baseDir, 'index.json'),
+        vectorDb: path.join(baseDir, 'index')
+      },
+      rootPath,
+      performIndexing: () => undefined
+    };
+  }
+
+  async function search(args: Record<string, unknown>) {
+    const result = await dispatchTool('structural_search', args, contextFor(tempDir));
+    return { isError: result.isError, payload: JSON.parse(result.content![0].text) };
+  }
+
+  it('captures the &mut Calculator parameter in the Rust fixture', async () => {
+    const { payload } = await search({
+      language: 'rust',
+      query:
+        '(parameter type: (reference_type (mutable_specifier) type: (type_identifier) @type) ' +
+        '(#eq? @type "Calculator")) @param'
+    });
+
+    expect(payload.status).toBe('success');
+    expect(payload.filesSearched).toBe(1);
+    expect(payload.matches).toEqual([
+      {
+        file: 'calculator.rs',
+        capture: 'param',
+        startLine: 19,
+        endLine: 19,
+        startColumn: 8,
+        text: 'calc: &mut Calculator'
+      },
+      {
+        file: 'calculator.rs',
+        capture: 'type',
+        startLine: 19,
+        endLine: 19,
+        startColumn: 19,
+        text: 'Calculator'
+      }
+    ]);
+  });
+
+  it('caps the number of captures', async () => {
+    const { payload } = await search({ language: 'rust', query: '(identifier) @id', limit: 2 });
+
+    expect(payload.matches).toHaveLength(2);
+    expect(payload.truncated).toBe(true);
+  });
+
+  it('reports malformed queries as parse errors', async () => {
+    const unbalanced = await search({
+      language: 'rust',
+      query: '(parameter type: (reference_type'
+    });
+    expect(unbalanced.isError).toBe(true);
+    expect(unbalanced.payload.errorCode).toBe('query_parse_error');
+
+    const unknownNode = await search({ language: 'rust', query: '(no_such_node) @node' });
+    expect(unknownNode.isError).toBe(true);
+    expect(unknownNode.payload.message).toContain('no_such_node');
+  });
+});
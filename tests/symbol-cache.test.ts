@@ -0,0 +1,136 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import { DiskSymbolCache, SYMBOL_CACHE_VERSION } from '../src/core/symbol-cache.js';
+import {
+  CODEBASE_CONTEXT_DIRNAME,
+  KEYWORD_INDEX_FILENAME,
+  SYMBOL_CACHE_DIRNAME,
+  SYMBOL_CACHE_FILENAME
+} from '../src/constants/codebase-context.js';
+import { extractTreeSitterSymbols, symbolExtractionCacheKey } from '../src/utils/tree-sitter.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const SOURCES: Record<string, { language: string; content: string }> = {
+  'src/math.ts': {
+    language: 'typescript',
+    content: [
+      'export function add(a: number, b: number): number {',
+      '  return a + b;',
+      '}',
+      '',
+      'export class Counter {',
+      '  count = 0;',
+      '  increment(): void {',
+      '    this.count = add(this.count, 1);',
+      '  }',
+      '}',
+      ''
+    ].join('\n')
+  },
+  'tools/report.py': {
+    language: 'python',
+    content: ['def render(rows):', '    return "\\n".join(rows)', ''].join('\n')
+  }
+};
+
+async function runIndexer(root: string, enabled = true) {
+  const indexer = new CodebaseIndexer({
+    rootPath: root,
+    config: { skipEmbedding: true, symbolCache: { enabled } }
+  });
+  return indexer.index();
+}
+
+async function readChunks(root: string): Promise<unknown[]> {
+  const indexPath = path.join(root, CODEBASE_CONTEXT_DIRNAME, KEYWORD_INDEX_FILENAME);
+  const raw = JSON.parse(await fs.readFile(indexPath, 'utf-8')) as {
+    chunks: Array<{ filePath: string; startLine: number; endLine: number; content: string }>;
+  };
+  return raw.chunks.map(({ filePath, startLine, endLine, content }) => ({
+    filePath,
+    startLine,
+    endLine,
+    content
+  }));
+}
+
+describe('DiskSymbolCache', () => {
+  let tempDir: string;
+  let cacheDir: string;
+
+  beforeEach(async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'symbol-cache-'));
+    cacheDir = path.join(tempDir, CODEBASE_CONTEXT_DIRNAME, SYMBOL_CACHE_DIRNAME);
+    for (const [relativePath, { content }] of Object.entries(SOURCES)) {
+      const target = path.join(tempDir, relativePath);
+      await fs.mkdir(path.dirname(target), { recursive: true });
+      await fs.writeFile(target, content);
+    }
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+  });
+
+  it('reloads identical symbols from disk after the in-memory index is dropped', async () => {
+    const first = await runIndexer(tempDir);
+    expect(first.symbolCache?.misses).toBeGreaterThan(0);
+
+    const reloaded = await DiskSymbolCache.load(cacheDir);
+    expect(reloaded.size).toBe(Object.keys(SOURCES).length);
+
+    for (const { language, content } of Object.values(SOURCES)) {
+      const fresh = await extractTreeSitterSymbols(content, language);
+      expect(fresh).not.toBeNull();
+      expect(reloaded.get(symbolExtractionCacheKey(content, language))).toEqual(fresh);
+    }
+  });
+
+  it('skips parsing unchanged contents on the next run and re-parses edited files', async () => {
+    await runIndexer(tempDir);
+    const chunksBefore = await readChunks(tempDir);
+
+    // Touch every file: mtimes move but contents do not
+    const now = new Date(Date.now() + 60_000);
+    for (const relativePath of Object.keys(SOURCES)) {
+      await fs.utimes(path.join(tempDir, relativePath), now, now);
+    }
+
+    const second = await runIndexer(tempDir);
+    expect(second.symbolCache?.misses).toBe(0);
+    expect(second.symbolCache?.hits).toBeGreaterThan(0);
+    expect(await readChunks(tempDir)).toEqual(chunksBefore);
+
+    await fs.appendFile(path.join(tempDir, 'tools/report.py'), 'def footer():\n    return ""\n');
+    const third = await runIndexer(tempDir);
+    expect(third.symbolCache?.misses).toBe(1);
+  });
+
+  it('discards a cache written with a different version', async () => {
+    await fs.mkdir(cacheDir, { recursive: true });
+    await fs.writeFile(
+      path.join(cacheDir, SYMBOL_CACHE_FILENAME),
+      JSON.stringify({
+        version: SYMBOL_CACHE_VERSION + 1,
+        entries: { stale: { grammarFile: 'x.wasm', symbols: [] } }
+      })
+    );
+
+    const cache = await DiskSymbolCache.load(cacheDir);
+    expect(cache.size).toBe(0);
+    expect(cache.get('stale')).toBeUndefined();
+  });
+
+  it('does not read or write the cache when disabled', async () => {
+    const stats = await runIndexer(tempDir, false);
+
+    expect(stats.symbolCache).toBeUndefined();
+    await expect(fs.access(path.join(cacheDir, SYMBOL_CACHE_FILENAME))).rejects.toThrow();
+  });
+});
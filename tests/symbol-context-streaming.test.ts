@@ -0,0 +1,89 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { fileURLToPath } from 'url';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import { dispatchTool } from '../src/tools/index.js';
+import type { ToolContext } from '../src/tools/types.js';
+import { CODEBASE_CONTEXT_DIRNAME } from '../src/constants/codebase-context.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const rustFixture = path.join(
+  path.dirname(fileURLToPath(import.meta.url)),
+  'fixtures',
+  'grammars',
+  'rust.rs'
+);
+
+describe('get_symbol_context streaming', () => {
+  let tempDir: string;
+  let ctx: ToolContext;
+
+  beforeEach(async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'symbol-context-streaming-'));
+    await fs.mkdir(path.join(tempDir, 'src'));
+    await fs.copyFile(rustFixture, path.join(tempDir, 'src', 'calc.rs'));
+    await new CodebaseIndexer({
+      rootPath: tempDir,
+      config: { skipEmbedding: true }
+    }).index();
+
+    const baseDir = path.join(tempDir, CODEBASE_CONTEXT_DIRNAME);
+    ctx = {
+      indexState: { status: 'ready' },
+      paths: {
+        baseDir,
+        memory: path.join(baseDir, 'memory.json'),
+        intelligence: path.join(baseDir, 'intelligence.json'),
+        keywordIndex: path.join(baseDir, 'index.json'),
+        vectorDb: path.join(baseDir, 'index')
+      },
+      rootPath: tempDir,
+      performIndexing: () => undefined
+    };
+  }, 30000);
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+  });
+
+  it('sends each snippet once as progress and leaves them out of the result', async () => {
+    const buffered = JSON.parse(
+      (await dispatchTool('get_symbol_context', { symbol: 'Calculator' }, ctx)).content![0].text
+    );
+    expect(buffered.snippets.length).toBeGreaterThan(0);
+
+    const progress: Array<{ progress: number; message?: string }> = [];
+    const streamingCtx: ToolContext = {
+      ...ctx,
+      reportProgress: async (value, message) => {
+        progress.push({ progress: value, message });
+      }
+    };
+    const response = await dispatchTool(
+      'get_symbol_context',
+      { symbol: 'Calculator' },
+      streamingCtx
+    );
+    const streamed = JSON.parse(response.content![0].text);
+
+    expect(progress.map(({ message }) => JSON.parse(message!))).toEqual(buffered.snippets);
+    expect(progress.map(({ progress: value }) => value)).toEqual(
+      buffered.snippets.map((_: unknown, i: number) => i + 1)
+    );
+    expect(streamed).not.toHaveProperty('snippets');
+    for (const snippet of buffered.snippets as Array<{ text: string }>) {
+      expect(response.content![0].text).not.toContain(JSON.stringify(snippet.text));
+    }
+    expect(streamed).toMatchObject({
+      status: 'success',
+      tokensUsed: buffered.tokensUsed,
+      droppedSnippets: buffered.droppedSnippets,
+      streamedSnippets: buffered.snippets.length
+    });
+  });
+});
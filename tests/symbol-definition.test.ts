@@ -0,0 +1,154 @@
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { fileURLToPath } from 'url';
+import { dedentLines, findDefinitions } from '../src/core/symbol-definition.js';
+import {
+  CODEBASE_CONTEXT_DIRNAME,
+  INDEX_FORMAT_VERSION,
+  KEYWORD_INDEX_FILENAME
+} from '../src/constants/codebase-context.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const FIXTURE = path.join(
+  path.dirname(fileURLToPath(import.meta.url)),
+  'fixtures',
+  'grammars',
+  'rust.rs'
+);
+
+describe('dedentLines', () => {
+  it('strips shared indentation and ignores blank lines', () => {
+    expect(dedentLines(['    fn a() {', '', '        b();', '    }'])).toEqual([
+      'fn a() {',
+      '',
+      '    b();',
+      '}'
+    ]);
+    expect(dedentLines(['fn a() {}'])).toEqual(['fn a() {}']);
+  });
+});
+
+describe('findDefinitions', () => {
+  let tempRoot: string;
+
+  beforeEach(async () => {
+    tempRoot = await fs.mkdtemp(path.join(os.tmpdir(), 'symbol-definition-'));
+    await fs.mkdir(path.join(tempRoot, 'src'), { recursive: true });
+    await fs.mkdir(path.join(tempRoot, CODEBASE_CONTEXT_DIRNAME), { recursive: true });
+    const source = await fs.readFile(FIXTURE, 'utf-8');
+    await fs.writeFile(path.join(tempRoot, 'src', 'calc.rs'), source);
+    await fs.writeFile(
+      path.join(tempRoot, 'src', 'other.rs'),
+      'mod util {\n    pub fn reset() {\n        println!("reset");\n    }\n}\n'
+    );
+    await fs.writeFile(
+      path.join(tempRoot, CODEBASE_CONTEXT_DIRNAME, KEYWORD_INDEX_FILENAME),
+      JSON.stringify({
+        header: { buildId: 'test', formatVersion: INDEX_FORMAT_VERSION },
+        chunks: [
+          { relativePath: 'src/calc.rs', startLine: 1, content: source },
+          {
+            relativePath: 'src/other.rs',
+            startLine: 1,
+            content: 'pub fn reset() { println!("reset"); }'
+          }
+        ]
+      })
+    );
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(tempRoot);
+  });
+
+  it('returns the whole function node with line and byte ranges', async () => {
+    const result = await findDefinitions(tempRoot, 'new_calculator');
+    if (result.status !== 'success') throw new Error(result.message);
+
+    expect(result.definitions).toHaveLength(1);
+    const [found] = result.definitions;
+    expect(found).toMatchObject({
+      file: 'src/calc.rs',
+      name: 'new_calculator',
+      kind: 'function',
+      startLine: 15,
+      endLine: 17
+    });
+    expect(found.text).toBe(
+      'fn new_calculator(initial: i64) -> Calculator {\n' +
+        '    Calculator { value: initial }\n' +
+        '}'
+    );
+
+    const source = Buffer.from(await fs.readFile(FIXTURE, 'utf-8'));
+    expect(source.subarray(found.startByte, found.endByte).toString()).toBe(found.text);
+  });
+
+  it('returns every symbol sharing a name, dedented, unless a file is given', async () => {
+    const result = await findDefinitions(tempRoot, 'reset');
+    if (result.status !== 'success') throw new Error(result.message);
+
+    expect(result.definitions.map((d) => [d.file, d.startLine, d.endLine])).toEqual([
+      ['src/calc.rs', 6, 8],
+      ['src/other.rs', 2, 4]
+    ]);
+    expect(result.definitions[0].text).toBe('fn reset(&mut self) {\n    self.value = 0;\n}');
+    expect(result.definitions[1].text).toBe('pub fn reset() {\n    println!("reset");\n}');
+
+    const scoped = await findDefinitions(tempRoot, 'reset', { file: 'src/other.rs' });
+    expect(scoped.status === 'success' && scoped.definitions.map((d) => d.file)).toEqual([
+      'src/other.rs'
+    ]);
+  });
+
+  it('reports truncation and rejects files outside the root', async () => {
+    const result = await findDefinitions(tempRoot, 'Calculator', { maxLines: 2 });
+    if (result.status !== 'success') throw new Error(result.message);
+    const struct = result.definitions.find((d) => d.kind === 'struct');
+    expect(struct?.truncated).toBe(true);
+    expect(struct?.text).toBe('struct Calculator {\n    value: i64,');
+    expect(struct?.endLine).toBe(3);
+
+    const outside = await findDefinitions(tempRoot, 'reset', { file: '../elsewhere.rs' });
+    expect(outside.status).toBe('error');
+  });
+
+  it('resolves a C++ header prototype to the definition in the sibling source', async () => {
+    await fs.writeFile(
+      path.join(tempRoot, 'src', 'geometry.h'),
+      [
+        'class Calculator {',
+        'public:',
+        '    int add(int n);',
+        '};',
+        '',
+        'int make_default(void);',
+        ''
+      ].join('\n')
+    );
+    await fs.writeFile(
+      path.join(tempRoot, 'src', 'geometry.cpp'),
+      '#include "geometry.h"\n\nint Calculator::add(int n) {\n    return n;\n}\n'
+    );
+
+    const linked = await findDefinitions(tempRoot, 'Calculator.add', { file: 'src/geometry.h' });
+    if (linked.status !== 'success') throw new Error(linked.message);
+    expect(linked.definitions).toHaveLength(1);
+    expect(linked.definitions[0]).toMatchObject({
+      file: 'src/geometry.cpp',
+      name: 'add',
+      qualifier: 'Calculator',
+      startLine: 3,
+      declaredIn: { file: 'src/geometry.h', startLine: 3, endLine: 3 }
+    });
+    expect(linked.definitions[0].text).toBe('int Calculator::add(int n) {\n    return n;\n}');
+
+    const unlinked = await findDefinitions(tempRoot, 'make_default', { file: 'src/geometry.h' });
+    if (unlinked.status !== 'success') throw new Error(unlinked.message);
+    expect(unlinked.definitions).toMatchObject([
+      { file: 'src/geometry.h', name: 'make_default', declaration: true }
+    ]);
+  });
+});
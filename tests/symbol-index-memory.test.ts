@@ -0,0 +1,123 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { Client } from '@modelcontextprotocol/sdk/client/index.js';
+import { StreamableHTTPClientTransport } from '@modelcontextprotocol/sdk/client/streamableHttp.js';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import { startHttpTransport } from '../src/transports/http.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const FILE_COUNT = 8;
+const UTIL_SOURCE = 'export function helper(value: number) {\n  return value + 1;\n}\n';
+const moduleSource = (i: number, version: number) =>
+  [
+    "import { helper } from './util';",
+    '',
+    `export function run${i}() {`,
+    `  return helper(${i * 100 + version});`,
+    '}',
+    ''
+  ].join('\n');
+
+describe('server parse-tree memory', () => {
+  let tempRoot: string;
+  let originalArgv: string[];
+  const originalBudget = process.env.CODEBASE_CONTEXT_TREE_CACHE_BYTES;
+  // Room for about two of the files' trees
+  const budget = 2 * moduleSource(0, 0).length + 8;
+
+  beforeEach(async () => {
+    vi.resetModules();
+    analyzerRegistry.register(new GenericAnalyzer());
+    originalArgv = [...process.argv];
+    tempRoot = await fs.mkdtemp(path.join(os.tmpdir(), 'symbol-index-memory-'));
+    process.argv = [process.argv[0], process.argv[1], tempRoot];
+    process.env.CODEBASE_CONTEXT_TREE_CACHE_BYTES = String(budget);
+
+    await fs.writeFile(path.join(tempRoot, 'util.ts'), UTIL_SOURCE);
+    for (let i = 0; i < FILE_COUNT; i++) {
+      await fs.writeFile(path.join(tempRoot, `mod${i}.ts`), moduleSource(i, 0));
+    }
+  });
+
+  afterEach(async () => {
+    process.argv = originalArgv;
+    if (originalBudget === undefined) delete process.env.CODEBASE_CONTEXT_TREE_CACHE_BYTES;
+    else process.env.CODEBASE_CONTEXT_TREE_CACHE_BYTES = originalBudget;
+    await rmWithRetries(tempRoot);
+  });
+
+  it('stays within its tree budget across watched changes and reference lookups', async () => {
+    const { createServer, symbolIndex, watchRoot } = await import('../src/index.js');
+    expect(symbolIndex.maxTreeBytes).toBe(budget);
+    const reindex = (incrementalOnly: boolean) =>
+      new CodebaseIndexer({
+        rootPath: tempRoot,
+        incrementalOnly,
+        symbolIndex,
+        config: { skipEmbedding: true }
+      }).index();
+    await reindex(false);
+
+    const httpTransport = await startHttpTransport({ host: '127.0.0.1', port: 0, createServer });
+    const client = new Client({ name: 'symbol-index-memory-test', version: '1.0.0' });
+    await client.connect(new StreamableHTTPClientTransport(new URL(httpTransport.url)));
+
+    // Like the server's own reindex callback, one incremental run per watcher window
+    let windowDone: () => void = () => undefined;
+    let markReady: () => void = () => undefined;
+    const ready = new Promise<void>((resolve) => (markReady = resolve));
+    const stopWatcher = watchRoot(
+      tempRoot,
+      300,
+      () => void reindex(true).finally(() => windowDone()),
+      () => markReady()
+    );
+
+    const withinBudget = () => {
+      const stats = symbolIndex.treeCacheStats();
+      expect(stats.bytes).toBeLessThanOrEqual(budget);
+      return stats;
+    };
+
+    try {
+      await ready;
+      for (const version of [1, 2]) {
+        const done = new Promise<void>((resolve) => (windowDone = resolve));
+        for (let i = 0; i < FILE_COUNT; i++) {
+          await fs.writeFile(path.join(tempRoot, `mod${i}.ts`), moduleSource(i, version));
+        }
+        await done;
+        withinBudget();
+
+        const result = await client.callTool({
+          name: 'find_references',
+          arguments: { symbol: 'helper', limit: 50 }
+        });
+        const content = result.content as Array<{ type: string; text: string }>;
+        const payload = JSON.parse(content[0].text) as { status: string; referenceCount: number };
+        expect(payload.status).toBe('success');
+        // Each module imports and calls helper
+        expect(payload.referenceCount).toBeGreaterThanOrEqual(2 * FILE_COUNT);
+        withinBudget();
+      }
+
+      const stats = withinBudget();
+      // Changed files keep trees and lookups reparse evicted ones, all under the one budget
+      expect(stats.trees).toBeGreaterThan(0);
+      expect(stats.trees).toBeLessThan(FILE_COUNT);
+      expect(stats.evictions).toBeGreaterThan(0);
+      expect(stats.reparses).toBeGreaterThan(0);
+      expect(symbolIndex.getSymbols(path.join(tempRoot, 'mod3.ts'))).toMatchObject([
+        { name: 'run3' }
+      ]);
+    } finally {
+      stopWatcher();
+      await client.close();
+      await httpTransport.close();
+    }
+  }, 30000);
+});
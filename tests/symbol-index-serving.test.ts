@@ -0,0 +1,118 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import { SymbolIndex } from '../src/core/symbol-index.js';
+import { getFileOutline } from '../src/core/file-outline.js';
+import { findDefinitions } from '../src/core/symbol-definition.js';
+import { findScopedReferences } from '../src/core/symbol-references.js';
+import { setSpanSink, type SpanRecord } from '../src/utils/tracing.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const UTILS_SOURCE = 'export function format(value: string) {\n  return value.trim();\n}\n';
+const MAIN_SOURCE = [
+  "import { format } from './utils';",
+  '',
+  'export function main() {',
+  "  return format(' hi ');",
+  '}',
+  ''
+].join('\n');
+
+/** Spans recorded while `fn` runs. */
+async function traced(fn: () => Promise<unknown>): Promise<SpanRecord[]> {
+  const spans: SpanRecord[] = [];
+  setSpanSink((span) => spans.push(span));
+  try {
+    await fn();
+  } finally {
+    setSpanSink(null);
+  }
+  return spans;
+}
+
+const parses = (spans: SpanRecord[]) => spans.filter((span) => span.name === 'treesitter.parse');
+
+describe('SymbolIndex served to indexing and lookups', () => {
+  let tempRoot: string;
+
+  beforeEach(async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    tempRoot = await fs.mkdtemp(path.join(os.tmpdir(), 'symbol-index-serving-'));
+    await fs.writeFile(path.join(tempRoot, 'utils.ts'), UTILS_SOURCE);
+    await fs.writeFile(path.join(tempRoot, 'main.ts'), MAIN_SOURCE);
+  });
+
+  afterEach(async () => {
+    setSpanSink(null);
+    await rmWithRetries(tempRoot);
+  });
+
+  const index = (symbolIndex: SymbolIndex, incrementalOnly = false) =>
+    new CodebaseIndexer({
+      rootPath: tempRoot,
+      incrementalOnly,
+      symbolIndex,
+      config: { skipEmbedding: true }
+    }).index();
+
+  it('is filled by indexing, reused by the next run and pruned of deleted files', async () => {
+    const symbolIndex = new SymbolIndex(tempRoot);
+    await index(symbolIndex);
+    expect(symbolIndex.files()).toEqual(['main.ts', 'utils.ts']);
+
+    // What the watcher does for a change: the reindex that follows must not extract it again
+    await fs.writeFile(path.join(tempRoot, 'utils.ts'), `${UTILS_SOURCE}export const n = 1;\n`);
+    expect((await symbolIndex.refreshFile('utils.ts')).status).toBe('updated');
+    const spans = await traced(() => index(symbolIndex, true));
+    const extractions = spans.filter((span) => span.name === 'treesitter.extract');
+    expect(extractions.length).toBeGreaterThan(0);
+    expect(extractions.filter((span) => span.fields.cached === false)).toEqual([]);
+
+    await fs.rm(path.join(tempRoot, 'main.ts'));
+    await index(symbolIndex, true);
+    expect(symbolIndex.files()).toEqual(['utils.ts']);
+
+    // A server restarting on an existing index loads it from the manifest and symbol cache
+    const restarted = new SymbolIndex(tempRoot);
+    const loading = await traced(() =>
+      new CodebaseIndexer({ rootPath: tempRoot, symbolIndex: restarted }).syncSymbolIndex()
+    );
+    expect(restarted.files()).toEqual(['utils.ts']);
+    expect(parses(loading)).toEqual([]);
+  }, 30000);
+
+  it('answers outlines, definitions and references without reparsing current files', async () => {
+    const symbolIndex = new SymbolIndex(tempRoot);
+    await index(symbolIndex);
+    // The first reference lookup parses the trees it scans; later lookups reuse them
+    await findScopedReferences(tempRoot, 'format', { symbolIndex });
+
+    let outline: Awaited<ReturnType<typeof getFileOutline>> | undefined;
+    let definitions: Awaited<ReturnType<typeof findDefinitions>> | undefined;
+    let references: Awaited<ReturnType<typeof findScopedReferences>> | undefined;
+    const spans = await traced(async () => {
+      outline = await getFileOutline(tempRoot, 'utils.ts', { symbolIndex });
+      definitions = await findDefinitions(tempRoot, 'format', { symbolIndex });
+      references = await findScopedReferences(tempRoot, 'format', { symbolIndex });
+    });
+    expect(parses(spans)).toEqual([]);
+
+    expect(outline).toMatchObject({ status: 'success', outline: [{ name: 'format' }] });
+    expect(definitions).toMatchObject({
+      status: 'success',
+      definitions: [{ file: 'utils.ts', startLine: 1, endLine: 3 }]
+    });
+    expect(references).toEqual(await findScopedReferences(tempRoot, 'format'));
+
+    // A file edited since it was indexed is synced before it is answered from
+    await fs.writeFile(path.join(tempRoot, 'utils.ts'), 'export function parse() {}\n');
+    expect(await getFileOutline(tempRoot, 'utils.ts', { symbolIndex })).toMatchObject({
+      status: 'success',
+      outline: [{ name: 'parse' }]
+    });
+  }, 30000);
+});
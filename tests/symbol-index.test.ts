@@ -0,0 +1,336 @@
+import { describe, it, expect, beforeEach, afterEach, vi } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { fileURLToPath } from 'url';
+import { SymbolIndex } from '../src/core/symbol-index.js';
+import {
+  computeTreeSitterEdit,
+  extractTreeSitterSymbols,
+  symbolExtractionCacheKey,
+  type TreeSitterEdit
+} from '../src/utils/tree-sitter.js';
+import { setSpanSink, type SpanRecord } from '../src/utils/tracing.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const brokenRust = path.join(
+  path.dirname(fileURLToPath(import.meta.url)),
+  'fixtures',
+  'parse-errors',
+  'broken.rs'
+);
+
+function pointAt(text: string, index: number) {
+  const lines = text.slice(0, index).split('\n');
+  return { row: lines.length - 1, column: lines[lines.length - 1].length };
+}
+
+/** Replace `text[start, oldEnd)` with `insert`, returning the new text and its edit descriptor. */
+function applyEdit(text: string, start: number, oldEnd: number, insert: string) {
+  const contents = text.slice(0, start) + insert + text.slice(oldEnd);
+  const newEnd = start + insert.length;
+  const edit: TreeSitterEdit = {
+    startIndex: start,
+    oldEndIndex: oldEnd,
+    newEndIndex: newEnd,
+    startPosition: pointAt(text, start),
+    oldEndPosition: pointAt(text, oldEnd),
+    newEndPosition: pointAt(contents, newEnd)
+  };
+  return { contents, edit };
+}
+
+describe('SymbolIndex', () => {
+  let tempDir: string;
+
+  beforeEach(async () => {
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'symbol-index-test-'));
+    await fs.writeFile(path.join(tempDir, 'a.ts'), 'export function alpha() {\n  return 1;\n}\n');
+    await fs.writeFile(path.join(tempDir, 'b.ts'), 'export function beta() {\n  return 2;\n}\n');
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+  });
+
+  it('reparses only the mutated file', async () => {
+    const index = new SymbolIndex(tempDir);
+    await index.refreshFiles([path.join(tempDir, 'a.ts'), path.join(tempDir, 'b.ts')]);
+
+    const alphaBefore = index.getSymbols('a.ts');
+    expect(alphaBefore.map((s) => s.name)).toEqual(['alpha']);
+    expect(index.getSymbols('b.ts').map((s) => s.name)).toEqual(['beta']);
+
+    const update = await index.updateFile(
+      path.join(tempDir, 'b.ts'),
+      'export function gamma() {\n  return 3;\n}\n'
+    );
+
+    expect(update.status).toBe('updated');
+    expect(index.getSymbols('b.ts').map((s) => s.name)).toEqual(['gamma']);
+    expect(index.findByName('beta')).toEqual([]);
+    // Untouched file keeps the exact same symbol objects
+    expect(index.getSymbols('a.ts')).toBe(alphaBefore);
+  });
+
+  it('purges symbols of deleted files', async () => {
+    const index = new SymbolIndex(tempDir);
+    await index.refreshFiles(['a.ts', 'b.ts']);
+
+    await fs.rm(path.join(tempDir, 'b.ts'));
+    const update = await index.refreshFile('b.ts');
+
+    expect(update.status).toBe('removed');
+    expect(index.files()).toEqual(['a.ts']);
+    expect(index.findByName('beta')).toEqual([]);
+  });
+
+  it('keeps previous symbols when the new contents fail to parse', async () => {
+    const index = new SymbolIndex(tempDir);
+    await index.refreshFile('a.ts');
+
+    // Mid-edit: the name is retyped, so recovery around the errors cannot find alpha
+    const update = await index.updateFile('a.ts', 'export function ( {\n  return ;;; }}}\n');
+
+    expect(update.status).toBe('kept-previous');
+    expect(index.getSymbols('a.ts').map((s) => s.name)).toEqual(['alpha']);
+  });
+
+  it('takes a recovered parse that keeps every previous symbol', async () => {
+    const index = new SymbolIndex(tempDir);
+    await fs.writeFile(path.join(tempDir, 'lib.rs'), 'pub fn parse_header() {}\n');
+    await index.refreshFile('lib.rs');
+
+    const broken = await fs.readFile(brokenRust, 'utf-8');
+    const update = await index.updateFile('lib.rs', broken);
+
+    expect(update.status).toBe('updated');
+    expect(index.getSymbols('lib.rs').map((s) => s.name)).toEqual(
+      expect.arrayContaining(['parse_header', 'count_fields', 'format_row'])
+    );
+    expect(index.getEntry('lib.rs')?.errorRegions?.length).toBeGreaterThan(0);
+  });
+
+  it('refreshes a batch to the same ordered symbols with one worker or many', async () => {
+    const files: string[] = [];
+    for (let i = 0; i < 24; i++) {
+      // Larger files first, so with several workers the small ones finish before them
+      const padding = '\n'.repeat((24 - i) * 40);
+      const file = path.join(tempDir, `mod${String(i).padStart(2, '0')}.ts`);
+      await fs.writeFile(
+        file,
+        `export function shared${i % 2}() {}\n${padding}export function own${i}() {}\n`
+      );
+      files.push(file);
+    }
+
+    const snapshot = async (concurrency: number) => {
+      const index = new SymbolIndex(tempDir);
+      const updates = await index.refreshFiles([...files, files[0]], concurrency);
+      return {
+        updates: updates.map((u) => `${u.relativePath}:${u.status}`),
+        files: index.files(),
+        shared: index.findByName('shared0').map((m) => `${m.relativePath}:${m.symbol.startLine}`)
+      };
+    };
+
+    const sequential = await snapshot(1);
+    expect(sequential.updates).toHaveLength(24);
+    expect(sequential.shared).toHaveLength(12);
+    expect(sequential.shared).toEqual([...sequential.shared].sort());
+    expect(await snapshot(8)).toEqual(sequential);
+  });
+
+  it('keeps previous symbols when the file changes while it is read', async () => {
+    const index = new SymbolIndex(tempDir);
+    const file = path.join(tempDir, 'a.ts');
+    await index.refreshFile(file);
+    await fs.utimes(file, new Date(), new Date(Date.now() - 60_000));
+
+    const readFile = fs.readFile;
+    const spy = vi.spyOn(fs, 'readFile').mockImplementationOnce(async (...args) => {
+      const torn = await readFile(...(args as Parameters<typeof readFile>));
+      await fs.writeFile(file, 'export function omega() {\n  return 3;\n}\n// grown\n');
+      return torn;
+    });
+    try {
+      const update = await index.refreshFile(file);
+      expect(update.status).toBe('kept-previous');
+      expect(index.getSymbols('a.ts').map((s) => s.name)).toEqual(['alpha']);
+    } finally {
+      spy.mockRestore();
+    }
+
+    expect((await index.refreshFile(file)).status).toBe('updated');
+    expect(index.getSymbols('a.ts').map((s) => s.name)).toEqual(['omega']);
+  });
+
+  it('keeps symbol ranges in memory and slices their content back from the file', async () => {
+    const index = new SymbolIndex(tempDir);
+    const file = path.join(tempDir, 'a.ts');
+    const contents =
+      "const face = '\u{1F600}';\nexport class Widget {\n  render() {\n    return 'w';\n  }\n}\n";
+    await fs.writeFile(file, contents);
+    await index.refreshFile(file);
+
+    expect(JSON.stringify(index.getSymbols('a.ts'))).not.toContain("return 'w'");
+    const fresh = await extractTreeSitterSymbols(contents, 'typescript');
+    expect((await index.readIndexed(file))?.symbols).toEqual(fresh?.symbols);
+  });
+
+  it('skips reparsing when mtime is unchanged', async () => {
+    const index = new SymbolIndex(tempDir);
+    const first = await index.refreshFile('a.ts');
+    const second = await index.refreshFile('a.ts');
+
+    expect(first.status).toBe('updated');
+    expect(second.status).toBe('unchanged');
+  });
+
+  it('reparses edits incrementally with the same symbols as a full parse', async () => {
+    const index = new SymbolIndex(tempDir);
+    const original = [
+      'export function alpha() {',
+      '  return 1;',
+      '}',
+      '',
+      'export class Widget {',
+      '  render(): string {',
+      "    return 'w';",
+      '  }',
+      '}',
+      ''
+    ].join('\n');
+    const opened = applyEdit(original, 0, 0, '');
+    await index.updateFile('a.ts', opened.contents, undefined, opened.edit);
+
+    const renamed = applyEdit(original, original.indexOf('alpha'), original.indexOf('('), 'omega');
+    const tail = renamed.contents.length;
+    const added = applyEdit(renamed.contents, tail, tail, 'export function delta() {}\n');
+
+    for (const step of [renamed, added]) {
+      const update = await index.updateFile('a.ts', step.contents, undefined, step.edit);
+      expect(update.status).toBe('updated');
+      const fresh = await extractTreeSitterSymbols(step.contents, 'typescript');
+      const key = symbolExtractionCacheKey(step.contents, 'typescript');
+      expect(index.extractionCache(null).get(key, step.contents)?.symbols).toEqual(fresh?.symbols);
+    }
+    expect(index.getSymbols('a.ts').map((s) => s.name)).toEqual([
+      'omega',
+      'Widget',
+      'render',
+      'delta'
+    ]);
+  });
+
+  it('computes the edit between two contents', () => {
+    const before = "const s = '\u{1F600}';\nexport function alpha() {}\n";
+    const start = before.indexOf('alpha');
+    const renamed = applyEdit(before, start, start + 'alpha'.length, 'omega');
+    expect(computeTreeSitterEdit(before, renamed.contents)).toEqual(renamed.edit);
+    // Common text around an emoji change never splits its surrogate pair
+    const emoji = applyEdit(before, 11, 13, '\u{1F601}');
+    expect(computeTreeSitterEdit(before, emoji.contents)).toEqual(emoji.edit);
+    expect(computeTreeSitterEdit(before, before)).toBeNull();
+  });
+
+  it('reparses files changed on disk incrementally without an edit descriptor', async () => {
+    const index = new SymbolIndex(tempDir);
+    const file = path.join(tempDir, 'a.ts');
+    const changes = [
+      'export function alpha() {\n  return 1;\n}\nexport function beta() {}\n',
+      'export function alpha() {\n  return 2;\n}\nexport function beta() {}\n'
+    ];
+    const expected = await Promise.all(
+      changes.map((contents) => extractTreeSitterSymbols(contents, 'typescript'))
+    );
+
+    const parses: SpanRecord[] = [];
+    setSpanSink((span) => {
+      if (span.name === 'treesitter.parse') parses.push(span);
+    });
+    try {
+      await index.refreshFile(file);
+      for (const [i, contents] of changes.entries()) {
+        await fs.writeFile(file, contents);
+        await fs.utimes(file, new Date(), new Date(Date.now() + (i + 1) * 1000));
+        expect((await index.refreshFile(file)).status).toBe('updated');
+        expect((await index.readIndexed(file))?.symbols).toEqual(expected[i]?.symbols);
+      }
+    } finally {
+      setSpanSink(null);
+    }
+    // First sight parses from scratch and keeps no tree; the first change keeps one to reuse
+    expect(parses.map((span) => span.fields.incremental)).toEqual([false, false, true]);
+  });
+});
+
+describe('SymbolIndex tree cache', () => {
+  let tempDir: string;
+  const files = Array.from({ length: 6 }, (_, i) => `f${i}.ts`);
+  const source = (i: number) => `export function f${i}() {\n  return ${i};\n}\n`;
+
+  beforeEach(async () => {
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'symbol-index-trees-'));
+    for (const [i, file] of files.entries()) {
+      await fs.writeFile(path.join(tempDir, file), source(i));
+    }
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+  });
+
+  it('evicts least recently used trees past the budget and reparses them on demand', async () => {
+    // Room for three of the equally sized files
+    const index = new SymbolIndex(tempDir, { maxTreeBytes: 3 * source(0).length });
+    await index.refreshFiles(files);
+    expect(index.treeCacheStats()).toMatchObject({ trees: 0, reparses: 0 });
+
+    for (const file of files) {
+      await index.withTree(file, (tree) => tree.rootNode.text);
+    }
+    expect(index.treeCacheStats()).toMatchObject({
+      trees: 3,
+      bytes: 3 * source(0).length,
+      evictions: 3,
+      reparses: 6
+    });
+    // Symbols stay resident whatever happens to the trees
+    expect(files.flatMap((file) => index.getSymbols(file).map((s) => s.name))).toEqual([
+      'f0',
+      'f1',
+      'f2',
+      'f3',
+      'f4',
+      'f5'
+    ]);
+
+    // f5 is still cached; f0 was evicted first and comes back parsed from disk
+    expect(await index.withTree('f5.ts', (tree) => tree.rootNode.text)).toBe(source(5));
+    expect(index.treeCacheStats().reparses).toBe(6);
+    expect(await index.withTree('f0.ts', (tree) => tree.rootNode.text)).toBe(source(0));
+    expect(index.treeCacheStats().reparses).toBe(7);
+    expect(await index.withTree('missing.ts', () => 'unreachable')).toBeNull();
+  });
+
+  it('keeps a tree alive while in use and parses once for concurrent callers', async () => {
+    const index = new SymbolIndex(tempDir, { maxTreeBytes: 1 });
+    await index.refreshFiles(files);
+
+    const held = await index.withTree('f0.ts', async (tree) => {
+      // Everything else is parsed and evicted while f0's tree is held
+      await Promise.all(files.slice(1).map((file) => index.withTree(file, () => undefined)));
+      return tree.rootNode.text;
+    });
+    expect(held).toBe(source(0));
+    expect(index.treeCacheStats()).toMatchObject({ trees: 0, bytes: 0, evictions: 6 });
+
+    const before = index.treeCacheStats().reparses;
+    const texts = await Promise.all(
+      [1, 2, 3].map(() => index.withTree('f1.ts', (tree) => tree.rootNode.text))
+    );
+    expect(texts).toEqual([source(1), source(1), source(1)]);
+    expect(index.treeCacheStats().reparses).toBe(before + 1);
+  });
+});
@@ -0,0 +1,140 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import { getFileOutline } from '../src/core/file-outline.js';
+import {
+  decodeSourceBytes,
+  isUndecodable,
+  originalLineSpan,
+  type DecodedSource
+} from '../src/utils/text-decoding.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const CALC_LINES = [
+  '// Running total',
+  'export class Calculator {',
+  '  total = 0;',
+  '',
+  '  add(n: number): void {',
+  '    this.total += n;',
+  '  }',
+  '}',
+  '',
+  'export function reset(calc: Calculator): void {',
+  '  calc.total = 0;',
+  '}',
+  ''
+];
+
+const UTF8_BOM = Buffer.from([0xef, 0xbb, 0xbf]);
+const UTF16LE_BOM = Buffer.from([0xff, 0xfe]);
+
+function crlfWithBom(lines: string[]): Buffer {
+  return Buffer.concat([UTF8_BOM, Buffer.from(lines.join('\r\n'), 'utf8')]);
+}
+
+function decodeOk(bytes: Uint8Array): DecodedSource {
+  const source = decodeSourceBytes(bytes);
+  if (isUndecodable(source)) throw new Error(`expected text, got ${source.reason}`);
+  return source;
+}
+
+describe('decodeSourceBytes', () => {
+  it('strips a UTF-8 BOM and normalizes CRLF while keeping the original lines', () => {
+    const source = decodeOk(crlfWithBom(CALC_LINES));
+
+    expect(source).toMatchObject({ encoding: 'utf-8', bom: true, lineEnding: 'crlf' });
+    expect(source.text).toBe(CALC_LINES.join('\n'));
+    expect(originalLineSpan(source, 5, 7)).toBe(
+      '  add(n: number): void {\r\n    this.total += n;\r\n  }'
+    );
+  });
+
+  it('decodes UTF-16 with or without a BOM and falls back to Windows-1252', () => {
+    const text = 'const café = "日本";\r\n';
+    const withBom = decodeOk(Buffer.concat([UTF16LE_BOM, Buffer.from(text, 'utf16le')]));
+    expect(withBom).toMatchObject({ encoding: 'utf-16le', bom: true, lineEnding: 'crlf' });
+    expect(withBom.text).toBe('const café = "日本";\n');
+
+    const bigEndian = Buffer.from(text, 'utf16le').swap16();
+    expect(decodeOk(bigEndian)).toMatchObject({ encoding: 'utf-16be', bom: false });
+
+    const legacy = decodeOk(Buffer.from('const café = 1;\r\r', 'latin1'));
+    expect(legacy).toMatchObject({
+      encoding: 'windows-1252',
+      lineEnding: 'cr',
+      text: 'const café = 1;\n\n'
+    });
+  });
+
+  it('reports binary and undecodable bytes instead of text', () => {
+    expect(decodeSourceBytes(Buffer.from([0x7f, 0x45, 0x4c, 0x46, 0x00, 0x01]))).toMatchObject({
+      reason: 'binary'
+    });
+    expect(decodeSourceBytes(Buffer.concat([UTF16LE_BOM, Buffer.from([0x61, 0x00, 0x62])])))
+      .toMatchObject({ reason: 'undecodable' });
+    expect(decodeSourceBytes(Buffer.concat([UTF8_BOM, Buffer.from([0x63, 0xff])]))).toMatchObject({
+      reason: 'undecodable'
+    });
+  });
+});
+
+describe('indexing files saved with BOMs, CRLF and UTF-16', () => {
+  let tempDir: string;
+
+  beforeEach(async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'text-decoding-'));
+    await fs.mkdir(path.join(tempDir, 'src'));
+    await fs.writeFile(path.join(tempDir, 'src', 'calc-lf.ts'), CALC_LINES.join('\n'));
+    await fs.writeFile(path.join(tempDir, 'src', 'calc-crlf.ts'), crlfWithBom(CALC_LINES));
+    await fs.writeFile(
+      path.join(tempDir, 'src', 'calc-wide.ts'),
+      Buffer.concat([UTF16LE_BOM, Buffer.from(CALC_LINES.join('\r\n'), 'utf16le')])
+    );
+    // A UTF-16 BOM followed by an odd number of bytes cannot be decoded
+    await fs.writeFile(
+      path.join(tempDir, 'src', 'broken.ts'),
+      Buffer.concat([UTF16LE_BOM, Buffer.from('export const x = 1;', 'utf8')])
+    );
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+  });
+
+  it('reports the same lines for a symbol however the file was saved', async () => {
+    const stats = await new CodebaseIndexer({
+      rootPath: tempDir,
+      config: { skipEmbedding: true }
+    }).index();
+    expect(stats.skipped).toEqual([{ file: 'src/broken.ts', reason: 'undecodable', size: 21 }]);
+
+    const outlines = await Promise.all(
+      ['calc-lf.ts', 'calc-crlf.ts', 'calc-wide.ts'].map((file) =>
+        getFileOutline(tempDir, `src/${file}`)
+      )
+    );
+    const [lf, crlf, wide] = outlines;
+    expect(lf).toMatchObject({
+      status: 'success',
+      outline: [
+        {
+          name: 'Calculator',
+          startLine: 2,
+          endLine: 8,
+          children: expect.arrayContaining([
+            expect.objectContaining({ name: 'add', startLine: 5, endLine: 7 })
+          ])
+        },
+        { name: 'reset', startLine: 10, endLine: 12 }
+      ]
+    });
+    expect(crlf).toEqual({ ...lf, file: 'src/calc-crlf.ts' });
+    expect(wide).toEqual({ ...lf, file: 'src/calc-wide.ts' });
+  }, 30000);
+});
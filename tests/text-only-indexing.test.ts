@@ -0,0 +1,101 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import { dispatchTool } from '../src/tools/index.js';
+import type { ToolContext } from '../src/tools/types.js';
+import { CODEBASE_CONTEXT_DIRNAME } from '../src/constants/codebase-context.js';
+import { rmWithRetries } from './test-helpers.js';
+
+const SCHEMA_SQL = [
+  'CREATE TABLE invoices (id INTEGER PRIMARY KEY, amount NUMERIC NOT NULL);',
+  '',
+  'CREATE FUNCTION invoice_totals() RETURNS NUMERIC AS $$',
+  '  SELECT SUM(amount) FROM invoices;',
+  '$$ LANGUAGE SQL;',
+  ''
+].join('\n');
+
+function contextFor(rootPath: string): ToolContext {
+  const baseDir = path.join(rootPath, CODEBASE_CONTEXT_DIRNAME);
+  return {
+    indexState: { status: 'ready' },
+    paths: {
+      baseDir,
+      memory: path.join(baseDir, 'memory.json'),
+      intelligence: path.join(baseDir, 'intelligence.json'),
+      keywordIndex: path.join(baseDir, 'index.json'),
+      vectorDb: path.join(baseDir, 'index')
+    },
+    rootPath,
+    performIndexing: () => undefined
+  };
+}
+
+async function call(name: string, args: Record<string, unknown>, rootPath: string) {
+  const result = await dispatchTool(name, args, contextFor(rootPath));
+  return JSON.parse(result.content![0].text);
+}
+
+describe('text-only fallback indexing', () => {
+  let tempDir: string;
+
+  beforeEach(async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'text-only-'));
+    await fs.mkdir(path.join(tempDir, 'db'));
+    await fs.writeFile(path.join(tempDir, 'db', 'schema.sql'), SCHEMA_SQL);
+    await fs.writeFile(path.join(tempDir, 'app.ini'), '[server]\nport = 8080\n');
+    await fs.writeFile(
+      path.join(tempDir, 'app.ts'),
+      'export function startServer(): void {\n  console.log("listening");\n}\n'
+    );
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(tempDir);
+  });
+
+  it('marks files without a grammar as text-only with no symbols', async () => {
+    const result = await new GenericAnalyzer().analyze(
+      path.join(tempDir, 'db', 'schema.sql'),
+      SCHEMA_SQL
+    );
+
+    expect(result.components).toEqual([]);
+    expect(result.metadata).toMatchObject({ textOnly: true, chunkStrategy: 'lines' });
+    expect(result.chunks.length).toBeGreaterThan(0);
+    expect(result.chunks.every((chunk) => chunk.metadata.textOnly === true)).toBe(true);
+  });
+
+  it('finds text inside a .sql file while symbol lookups find nothing', async () => {
+    await new CodebaseIndexer({ rootPath: tempDir, config: { skipEmbedding: true } }).index();
+
+    const search = await call(
+      'search_codebase',
+      { query: 'invoice_totals', gitBoost: false, limit: 5 },
+      tempDir
+    );
+    const sqlHit = search.results.find((r: { file: string }) => r.file.includes('schema.sql'));
+    expect(sqlHit).toBeDefined();
+    expect(sqlHit.textOnly).toBe(true);
+
+    const ini = await call('search_codebase', { query: 'port 8080', gitBoost: false }, tempDir);
+    expect(ini.results.some((r: { file: string }) => r.file.includes('app.ini'))).toBe(true);
+
+    const definition = await call('get_definition', { symbol: 'invoice_totals' }, tempDir);
+    expect(definition.definitionCount).toBe(0);
+
+    const byKind = await call(
+      'search_codebase',
+      { query: 'invoice_totals', kinds: ['function'], gitBoost: false },
+      tempDir
+    );
+    expect(byKind.results.some((r: { file: string }) => r.file.includes('schema.sql'))).toBe(
+      false
+    );
+  });
+});
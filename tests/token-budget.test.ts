@@ -0,0 +1,235 @@
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import {
+  CharRatioTokenEstimator,
+  packSnippetStream,
+  packSnippetsWithinBudget,
+  SnippetBudget,
+  type BudgetCandidate,
+  type PackedSnippet,
+  type TokenEstimator
+} from '../src/core/token-budget.js';
+import { buildSymbolContext, streamSymbolContext } from '../src/core/symbol-context.js';
+import {
+  CODEBASE_CONTEXT_DIRNAME,
+  INDEX_FORMAT_VERSION,
+  KEYWORD_INDEX_FILENAME
+} from '../src/constants/codebase-context.js';
+import { rmWithRetries } from './test-helpers.js';
+
+describe('packSnippetsWithinBudget', () => {
+  it('packs definitions before references before imports', () => {
+    const packed = packSnippetsWithinBudget(
+      [
+        { role: 'import' as const, text: 'import { a } from "./a";' },
+        { role: 'reference' as const, text: 'a();' },
+        { role: 'definition' as const, text: 'function a() {}' }
+      ],
+      1000
+    );
+
+    expect(packed.snippets.map((s) => s.role)).toEqual(['definition', 'reference', 'import']);
+    expect(packed.droppedSnippets).toBe(0);
+  });
+
+  it('drops whole snippets that do not fit and reports usage', () => {
+    const packed = packSnippetsWithinBudget(
+      [
+        { role: 'definition' as const, text: 'x'.repeat(40) },
+        { role: 'reference' as const, text: 'y'.repeat(40) },
+        { role: 'reference' as const, text: 'z'.repeat(4) }
+      ],
+      12
+    );
+
+    expect(packed.snippets.map((s) => s.text[0])).toEqual(['x', 'z']);
+    expect(packed.tokensUsed).toBe(11);
+    expect(packed.droppedSnippets).toBe(1);
+  });
+
+  it('truncates an oversized first snippet at a line boundary', () => {
+    const text = ['line one', 'line two', 'line three'].join('\n');
+    const packed = packSnippetsWithinBudget([{ role: 'definition' as const, text }], 5);
+
+    expect(packed.snippets[0].text).toBe('line one\nline two');
+    expect(packed.snippets[0].truncated).toBe(true);
+    expect(packed.tokensUsed).toBeLessThanOrEqual(5);
+  });
+
+  it('cuts a long snippet with a logarithmic number of estimates', () => {
+    const lines = Array.from({ length: 10_000 }, (_, i) => `const value${i} = ${i};`);
+    const charEstimator = new CharRatioTokenEstimator();
+    let estimates = 0;
+    const counting: TokenEstimator = {
+      estimate: (t) => {
+        estimates++;
+        return charEstimator.estimate(t);
+      }
+    };
+    const packed = packSnippetsWithinBudget(
+      [{ role: 'definition' as const, text: lines.join('\n') }],
+      500,
+      counting
+    );
+
+    const kept = packed.snippets[0].text.split('\n');
+    expect(kept).toEqual(lines.slice(0, kept.length));
+    expect(charEstimator.estimate(kept.join('\n'))).toBeLessThanOrEqual(500);
+    // One more line would not have fit
+    expect(charEstimator.estimate(lines.slice(0, kept.length + 1).join('\n'))).toBeGreaterThan(500);
+    expect(estimates).toBeLessThan(40);
+  });
+
+  it('accepts a custom estimator', () => {
+    const wordEstimator: TokenEstimator = { estimate: (t) => t.split(/\s+/).length };
+    const packed = packSnippetsWithinBudget(
+      [{ role: 'reference' as const, text: 'one two three' }],
+      3,
+      wordEstimator
+    );
+
+    expect(packed.tokensUsed).toBe(3);
+    expect(new CharRatioTokenEstimator().estimate('abcdefgh')).toBe(2);
+  });
+});
+
+describe('packSnippetStream', () => {
+  it('holds one candidate at a time while producing a large context', async () => {
+    const snippetText = 'reference();\n'.repeat(80);
+    const snippetCount = 2000;
+    let produced = 0;
+    let consumed = 0;
+    let peakBuffered = 0;
+
+    async function* candidates(): AsyncGenerator<BudgetCandidate> {
+      for (let i = 0; i < snippetCount; i++) {
+        produced += snippetText.length;
+        peakBuffered = Math.max(peakBuffered, produced - consumed);
+        yield { role: 'reference', text: snippetText };
+      }
+    }
+
+    const budget = new SnippetBudget<BudgetCandidate>(Number.MAX_SAFE_INTEGER);
+    let packedCount = 0;
+    for await (const snippet of packSnippetStream(candidates(), budget)) {
+      // A consumer that is slower than the producer
+      await new Promise((resolve) => setImmediate(resolve));
+      consumed += snippet.text.length;
+      packedCount++;
+    }
+
+    const totalOutput = snippetText.length * snippetCount;
+    expect(packedCount).toBe(snippetCount);
+    expect(consumed).toBe(totalOutput);
+    expect(peakBuffered).toBe(snippetText.length);
+    expect(peakBuffered).toBeLessThan(totalOutput / 1000);
+  });
+
+  it('applies the same budget rules as packSnippetsWithinBudget', async () => {
+    const budget = new SnippetBudget<BudgetCandidate>(12);
+    const packed: Array<PackedSnippet<BudgetCandidate>> = [];
+    for await (const snippet of packSnippetStream(
+      [
+        { role: 'definition' as const, text: `${'x'.repeat(20)}\n${'x'.repeat(60)}` },
+        { role: 'reference' as const, text: 'y'.repeat(4) },
+        { role: 'reference' as const, text: 'z'.repeat(40) }
+      ],
+      budget
+    )) {
+      packed.push(snippet);
+    }
+
+    expect(packed.map((s) => [s.text, s.truncated])).toEqual([
+      ['x'.repeat(20), true],
+      ['yyyy', undefined]
+    ]);
+    expect(budget.tokensUsed).toBe(6);
+    expect(budget.droppedSnippets).toBe(1);
+  });
+});
+
+describe('buildSymbolContext', () => {
+  let tempRoot: string;
+
+  beforeEach(async () => {
+    tempRoot = await fs.mkdtemp(path.join(os.tmpdir(), 'symbol-context-'));
+    const lib = 'export function total(items: number[]) {\n  return items.length;\n}\n';
+    const app = "import { total } from './lib';\n\nconsole.log(total([1, 2]));\n";
+    await fs.writeFile(path.join(tempRoot, 'lib.ts'), lib);
+    await fs.writeFile(path.join(tempRoot, 'app.ts'), app);
+
+    const contextDir = path.join(tempRoot, CODEBASE_CONTEXT_DIRNAME);
+    await fs.mkdir(contextDir, { recursive: true });
+    await fs.writeFile(
+      path.join(contextDir, KEYWORD_INDEX_FILENAME),
+      JSON.stringify({
+        header: { buildId: 'test-symbol-context', formatVersion: INDEX_FORMAT_VERSION },
+        chunks: [
+          { content: lib, startLine: 1, relativePath: 'lib.ts' },
+          { content: app, startLine: 1, relativePath: 'app.ts' }
+        ]
+      })
+    );
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(tempRoot);
+  });
+
+  it('returns definition, usage and import within the budget', async () => {
+    const result = await buildSymbolContext(tempRoot, 'total', { maxTokens: 200 });
+    if (result.status !== 'success') throw new Error('expected success');
+
+    expect(result.snippets.map((s) => `${s.role}:${s.file}`)).toEqual([
+      'definition:lib.ts',
+      'reference:app.ts',
+      'import:app.ts'
+    ]);
+    expect(result.snippets[0].text).toContain('return items.length;');
+    expect(result.tokensUsed).toBeLessThanOrEqual(200);
+    expect(result.droppedSnippets).toBe(0);
+  });
+
+  it('cuts definitions down to their signature with signaturesOnly', async () => {
+    const result = await buildSymbolContext(tempRoot, 'total', {
+      maxTokens: 200,
+      signaturesOnly: true
+    });
+    if (result.status !== 'success') throw new Error('expected success');
+
+    expect(result.snippets[0]).toMatchObject({
+      role: 'definition',
+      startLine: 1,
+      endLine: 1,
+      text: 'export function total(items: number[])'
+    });
+  });
+
+  it('drops lower-priority snippets when the budget is tight', async () => {
+    const result = await buildSymbolContext(tempRoot, 'total', { maxTokens: 20 });
+    if (result.status !== 'success') throw new Error('expected success');
+
+    expect(result.snippets[0].role).toBe('definition');
+    expect(result.tokensUsed).toBeLessThanOrEqual(20);
+    expect(result.droppedSnippets).toBeGreaterThan(0);
+  });
+
+  it('streams the same snippets it would return in one response', async () => {
+    const stream = await streamSymbolContext(tempRoot, 'total', { maxTokens: 200 });
+    if (stream.status !== 'success') throw new Error('expected success');
+
+    const first = await stream.snippets.next();
+    expect(first.done).toBe(false);
+    expect(first.value?.role).toBe('definition');
+    expect(stream.totals().tokensUsed).toBe(first.value?.tokens);
+
+    const roles = [first.value?.role];
+    for await (const snippet of stream.snippets) roles.push(snippet.role);
+    expect(roles).toEqual(['definition', 'reference', 'import']);
+
+    const buffered = await buildSymbolContext(tempRoot, 'total', { maxTokens: 200 });
+    expect(buffered.status === 'success' && buffered.tokensUsed).toBe(stream.totals().tokensUsed);
+  });
+});
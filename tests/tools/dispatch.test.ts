@@ -3,8 +3,8 @@ import { TOOLS, dispatchTool } from '../../src/tools/index.js';
 import type { ToolContext } from '../../src/tools/types.js';
 
 describe('Tool Dispatch', () => {
-  it('exports all 10 tools', () => {
-    expect(TOOLS.length).toBe(10);
+  it('exports all 26 tools', () => {
+    expect(TOOLS.length).toBe(26);
     expect(TOOLS.map((t) => t.name)).toEqual([
       'search_codebase',
       'get_codebase_metadata',
@@ -15,7 +15,23 @@ describe('Tool Dispatch', () => {
       'get_symbol_references',
       'detect_circular_dependencies',
       'remember',
-      'get_memory'
+      'get_memory',
+      'find_references',
+      'get_symbol_context',
+      'file_outline',
+      'call_graph',
+      'export_chunks',
+      'get_definition',
+      'dependency_graph',
+      'stats',
+      'structural_search',
+      'changed_symbols',
+      'semantic_search',
+      'context_around',
+      'list_files',
+      'pack_context',
+      'related_files',
+      'resolve_path'
     ]);
   });
 
@@ -0,0 +1,139 @@
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { CodebaseIndexer } from '../src/core/indexer.js';
+import { analyzerRegistry } from '../src/core/analyzer-registry.js';
+import { GenericAnalyzer } from '../src/analyzers/generic/index.js';
+import { dispatchTool } from '../src/tools/index.js';
+import type { ToolContext } from '../src/tools/types.js';
+import { CODEBASE_CONTEXT_DIRNAME } from '../src/constants/codebase-context.js';
+import { extractTreeSitterSymbols } from '../src/utils/tree-sitter.js';
+import {
+  setSpanSink,
+  spanSinkFromEnv,
+  tracingEnabled,
+  withSpan,
+  type SpanRecord
+} from '../src/utils/tracing.js';
+import { rmWithRetries } from './test-helpers.js';
+
+describe('withSpan', () => {
+  afterEach(() => {
+    setSpanSink(null);
+  });
+
+  it('only calls the function when no sink is set', async () => {
+    expect(tracingEnabled()).toBe(false);
+    await expect(withSpan('noop', {}, () => 42)).resolves.toBe(42);
+  });
+
+  it('nests spans and records errors', async () => {
+    const spans: SpanRecord[] = [];
+    setSpanSink((span) => spans.push(span));
+
+    await expect(
+      withSpan('outer', { root: 'a' }, async () =>
+        withSpan('inner', {}, async () => {
+          throw new Error('boom');
+        })
+      )
+    ).rejects.toThrow('boom');
+
+    const [inner, outer] = spans;
+    expect(outer).toMatchObject({ name: 'outer', fields: { root: 'a' }, error: 'boom' });
+    expect(inner).toMatchObject({ name: 'inner', parentId: outer.id, error: 'boom' });
+  });
+
+  it('filters CODEBASE_CONTEXT_TRACE by span name prefix', async () => {
+    const lines: string[] = [];
+    setSpanSink(spanSinkFromEnv('tool, search.', (line) => lines.push(line)));
+    await withSpan('tool.call', { tool: 'x' }, () => undefined);
+    await withSpan('index.file', { file: 'y' }, () => undefined);
+    await withSpan('search.keyword', {}, () => undefined);
+
+    expect(lines.map((line) => (JSON.parse(line) as SpanRecord).name)).toEqual([
+      'tool.call',
+      'search.keyword'
+    ]);
+    expect(spanSinkFromEnv('0')).toBeNull();
+    expect(spanSinkFromEnv(undefined)).toBeNull();
+  });
+});
+
+describe('instrumented paths', () => {
+  let tempDir: string;
+  let spans: SpanRecord[];
+  let ctx: ToolContext;
+
+  beforeEach(async () => {
+    analyzerRegistry.register(new GenericAnalyzer());
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'tracing-'));
+    await fs.writeFile(
+      path.join(tempDir, 'math.ts'),
+      'export function add(a: number, b: number): number {\n  return a + b;\n}\n'
+    );
+    await fs.writeFile(path.join(tempDir, 'use.ts'), "import { add } from './math';\nadd(1, 2);\n");
+
+    spans = [];
+    setSpanSink((span) => spans.push(span));
+    await new CodebaseIndexer({ rootPath: tempDir, config: { skipEmbedding: true } }).index();
+
+    const baseDir = path.join(tempDir, CODEBASE_CONTEXT_DIRNAME);
+    ctx = {
+      indexState: { status: 'ready' },
+      paths: {
+        baseDir,
+        memory: path.join(baseDir, 'memory.json'),
+        intelligence: path.join(baseDir, 'intelligence.json'),
+        keywordIndex: path.join(baseDir, 'index.json'),
+        vectorDb: path.join(baseDir, 'index')
+      },
+      rootPath: tempDir,
+      performIndexing: () => undefined
+    };
+  }, 30000);
+
+  afterEach(async () => {
+    setSpanSink(null);
+    await rmWithRetries(tempDir);
+  });
+
+  it('records the scan and one span per indexed file', () => {
+    const scan = spans.find((span) => span.name === 'index.scan');
+    expect(scan?.fields).toMatchObject({ files: 2, skipped: 0 });
+
+    const files = spans.filter((span) => span.name === 'index.file');
+    expect(files.map((span) => path.basename(String(span.fields.file))).sort()).toEqual([
+      'math.ts',
+      'use.ts'
+    ]);
+    expect(files[0].fields).toMatchObject({ language: 'typescript' });
+    expect(files[0].fields.bytes).toBeGreaterThan(0);
+  });
+
+  it('breaks a get_symbol_context call down into its phases', async () => {
+    spans = [];
+    const response = await dispatchTool('get_symbol_context', { symbol: 'add' }, ctx);
+    expect(response.isError).toBeFalsy();
+
+    const call = spans.find((span) => span.name === 'tool.call');
+    const assemble = spans.find((span) => span.name === 'context.assemble');
+    const references = spans.find((span) => span.name === 'context.references');
+    expect(call?.fields).toMatchObject({ tool: 'get_symbol_context', isError: false });
+    expect(assemble?.parentId).toBe(call?.id);
+    expect(assemble?.fields.snippets).toBeGreaterThan(0);
+    expect(references?.parentId).toBe(assemble?.id);
+  });
+
+  it('records tree-sitter parses with their language and size', async () => {
+    spans = [];
+    const source = `export function traced${Date.now()}(): void {}\n`;
+    await extractTreeSitterSymbols(source, 'typescript');
+
+    const parse = spans.find((span) => span.name === 'treesitter.parse');
+    expect(parse?.fields).toMatchObject({ language: 'typescript', bytes: source.length });
+    const extract = spans.find((span) => span.name === 'treesitter.extract');
+    expect(parse?.parentId).toBe(extract?.id);
+  });
+});
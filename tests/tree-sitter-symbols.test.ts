@@ -1,7 +1,12 @@
 import { describe, expect, it } from 'vitest';
+import { promises as fs } from 'fs';
+import path from 'path';
+import { fileURLToPath } from 'url';
 import { GenericAnalyzer } from '../src/analyzers/generic/index';
 import { extractTreeSitterSymbols, supportsTreeSitter } from '../src/utils/tree-sitter';
 
+const fixturesDir = path.join(path.dirname(fileURLToPath(import.meta.url)), 'fixtures', 'grammars');
+
 describe('Tree-sitter symbol extraction', () => {
   it('extracts TypeScript symbols including function variables', async () => {
     const source = [
@@ -26,6 +31,42 @@ describe('Tree-sitter symbol extraction', () => {
     expect(names).toContain('User');
   });
 
+  it('extracts React components, interfaces and default exports from TSX', async () => {
+    const source = await fs.readFile(path.join(fixturesDir, 'tsx.tsx'), 'utf8');
+    const extracted = await extractTreeSitterSymbols(source, 'typescriptreact');
+
+    expect(extracted).not.toBeNull();
+    expect(extracted!.grammarFile).toBe('tree-sitter-tsx.wasm');
+    const kinds = Object.fromEntries(extracted!.symbols.map((s) => [s.name, s.kind]));
+
+    expect(kinds).toEqual({
+      ButtonProps: 'interface',
+      ButtonVariant: 'type',
+      Button: 'function',
+      Badge: 'function',
+      App: 'class',
+      handleClick: 'method',
+      render: 'method',
+      default: 'function'
+    });
+    // JSX tags are usages, never definitions; re-exported names live in their source module
+    const buttonSymbols = extracted!.symbols.filter((s) => s.name === 'Button');
+    expect(buttonSymbols).toHaveLength(1);
+    expect(buttonSymbols[0].content.startsWith('export function Button')).toBe(true);
+  });
+
+  it('records TSX imports and re-exports in the generic analyzer', async () => {
+    const analyzer = new GenericAnalyzer();
+    const source = await fs.readFile(path.join(fixturesDir, 'tsx.tsx'), 'utf8');
+
+    const result = await analyzer.analyze('/virtual/Button.tsx', source);
+
+    expect(result.imports.map((imp) => imp.source)).toEqual(['react', './format']);
+    expect(result.exports.map((exp) => exp.name)).toEqual(
+      expect.arrayContaining(['formatLabel', 'ButtonProps', 'Button', 'Badge', 'default'])
+    );
+  });
+
   it('uses symbol boundaries for python chunks in generic analyzer', async () => {
     const analyzer = new GenericAnalyzer();
     const source = [
@@ -56,7 +97,264 @@ describe('Tree-sitter symbol extraction', () => {
     expect(topLevelChunk!.content).toContain('def top_level');
   });
 
-  it('falls back when python parse tree has errors', async () => {
+  it('extracts python functions, methods, decorators and class fields', async () => {
+    const source = await fs.readFile(path.join(fixturesDir, 'python.py'), 'utf8');
+    const extracted = await extractTreeSitterSymbols(source, 'python');
+
+    expect(extracted).not.toBeNull();
+    const byName = new Map(extracted!.symbols.map((s) => [s.name, s]));
+
+    expect(byName.get('new_calculator')?.kind).toBe('function');
+    expect(byName.get('greet')?.kind).toBe('function');
+    expect(byName.get('fetch_total')?.kind).toBe('function');
+    expect(byName.get('Calculator')?.kind).toBe('class');
+    expect(byName.get('add')?.kind).toBe('method');
+    expect(byName.get('refresh')?.kind).toBe('method');
+    // Functions nested inside a method are not methods of the class
+    expect(byName.get('inner')?.kind).toBe('function');
+
+    // Decorated definitions include their decorators in the symbol range
+    const cached = byName.get('cached_square');
+    expect(cached?.kind).toBe('function');
+    expect(cached?.content.startsWith('@functools.lru_cache')).toBe(true);
+    expect(byName.get('describe')?.content).toContain('@staticmethod');
+
+    expect(byName.get('precision')?.kind).toBe('field');
+    expect(byName.get('label')?.kind).toBe('field');
+    // Instance attributes assigned inside methods are not class fields
+    expect(extracted!.symbols.some((s) => s.kind === 'field' && s.name.includes('value'))).toBe(
+      false
+    );
+  });
+
+  it('captures Rust signatures without their bodies', async () => {
+    const source = await fs.readFile(path.join(fixturesDir, 'rust.rs'), 'utf8');
+    const extracted = await extractTreeSitterSymbols(source, 'rust');
+
+    expect(extracted).not.toBeNull();
+    const signatures = Object.fromEntries(extracted!.symbols.map((s) => [s.name, s.signature]));
+    expect(signatures).toMatchObject({
+      Calculator: 'struct Calculator',
+      'impl Calculator': 'impl Calculator',
+      reset: 'fn reset(&mut self)',
+      double: 'fn double(&mut self)',
+      new_calculator: 'fn new_calculator(initial: i64) -> Calculator',
+      add: 'fn add(calc: &mut Calculator, n: i64)',
+      total: 'fn total(calc: &Calculator) -> i64'
+    });
+    for (const symbol of extracted!.symbols) {
+      expect(symbol.signature).not.toContain('calc.value');
+      expect(symbol.signature).not.toContain('self.value');
+    }
+  });
+
+  it('ends Python and TypeScript signatures at the body', async () => {
+    const python = await extractTreeSitterSymbols(
+      await fs.readFile(path.join(fixturesDir, 'python.py'), 'utf8'),
+      'python'
+    );
+    const pySignatures = new Map(python!.symbols.map((s) => [s.name, s.signature]));
+    expect(pySignatures.get('add')).toBe('def add(self, n):');
+    expect(pySignatures.get('cached_square')).toBe(
+      '@functools.lru_cache(maxsize=None)\ndef cached_square(n):'
+    );
+    expect(pySignatures.get('Calculator')).toBe('class Calculator:');
+
+    const ts = await extractTreeSitterSymbols(
+      [
+        'export function scale(value: number, factor = 2): number {',
+        '  return value * factor;',
+        '}',
+        'export const half = (value: number): number => {',
+        '  return value / 2;',
+        '};'
+      ].join('\n'),
+      'typescript'
+    );
+    const tsSignatures = new Map(ts!.symbols.map((s) => [s.name, s.signature]));
+    expect(tsSignatures.get('scale')).toBe(
+      'export function scale(value: number, factor = 2): number'
+    );
+    expect(tsSignatures.get('half')).toBe('export const half = (value: number): number =>');
+  });
+
+  it('extracts Go types, receiver-qualified methods and grouped package vars', async () => {
+    const source = await fs.readFile(path.join(fixturesDir, 'go.go'), 'utf8');
+    const extracted = await extractTreeSitterSymbols(source, 'go');
+
+    expect(extracted).not.toBeNull();
+    expect(
+      extracted!.symbols.map((s) => [s.qualifier ? `${s.qualifier}.${s.name}` : s.name, s.kind])
+    ).toEqual([
+      ['Calculator', 'struct'],
+      ['Adder', 'interface'],
+      ['DefaultValue', 'constant'],
+      ['registry', 'variable'],
+      ['minimum', 'variable'],
+      ['maxi', 'variable'],
+      ['NewCalculator', 'function'],
+      ['Calculator.Add', 'method'],
+      ['Add', 'function']
+    ]);
+
+    // Each name in a grouped declaration spans its own line; locals inside functions are skipped
+    const minimum = extracted!.symbols.find((s) => s.name === 'minimum');
+    expect(minimum?.content).toBe('minimum, maxi = -100, 100');
+    expect(extracted!.symbols.find((s) => s.name === 'DefaultValue')?.content).toBe(
+      'const DefaultValue = 0'
+    );
+  });
+
+  it('extracts Java packages, members with their owning type and distinct overloads', async () => {
+    const source = await fs.readFile(path.join(fixturesDir, 'java.java'), 'utf8');
+    const extracted = await extractTreeSitterSymbols(source, 'java');
+
+    expect(extracted).not.toBeNull();
+    expect(
+      extracted!.symbols.map((s) => [s.qualifier ? `${s.qualifier}.${s.name}` : s.name, s.kind])
+    ).toEqual([
+      ['com.example.calc', 'namespace'],
+      ['Calculator', 'class'],
+      ['Calculator.LIMIT', 'field'],
+      ['Calculator.value', 'field'],
+      ['Calculator.steps', 'field'],
+      ['Calculator.Calculator', 'method'],
+      ['Calculator.toString', 'method'],
+      ['Calculator.add', 'method'],
+      ['Calculator.add', 'method'],
+      ['Memory', 'class'],
+      ['Memory.slots', 'field'],
+      ['Memory.Memory', 'method'],
+      ['Memory.recall', 'method'],
+      ['Operation', 'interface'],
+      ['Operation.PRIORITY', 'constant'],
+      ['Operation.apply', 'method'],
+      ['Mode', 'enum'],
+      ['Mode.isFast', 'method'],
+      ['Point', 'class'],
+      ['Point.sum', 'method']
+    ]);
+
+    // Overloads differ by parameters; annotations stay in the range but are not symbols
+    const adds = extracted!.symbols.filter((s) => s.name === 'add');
+    expect(adds.map((s) => s.parameters)).toEqual(['(int n)', '(int a, int b)']);
+    expect(adds[1].startLine).toBe(24);
+    expect(adds[1].signature).toBe('@Deprecated(since = "2.0")\n    public int add(int a, int b)');
+  });
+
+  it('flags functions in a Rust #[cfg(test)] module and #[test] functions as tests', async () => {
+    const source = [
+      'pub fn add(a: i64, b: i64) -> i64 {',
+      '    a + b',
+      '}',
+      '',
+      '#[cfg(test)]',
+      'mod tests {',
+      '    use super::*;',
+      '',
+      '    fn fixture() -> i64 {',
+      '        2',
+      '    }',
+      '',
+      '    #[test]',
+      '    fn adds() {',
+      '        assert_eq!(add(fixture(), 2), 4);',
+      '    }',
+      '}',
+      '',
+      '#[cfg(not(test))]',
+      'mod release {',
+      '    pub fn banner() {}',
+      '}',
+      '',
+      '#[test]',
+      'fn smoke() {}'
+    ].join('\n');
+
+    const extracted = await extractTreeSitterSymbols(source, 'rust');
+
+    expect(extracted).not.toBeNull();
+    expect(extracted!.symbols.map((s) => [s.name, s.test === true])).toEqual([
+      ['add', false],
+      ['fixture', true],
+      ['adds', true],
+      ['banner', false],
+      ['smoke', true]
+    ]);
+  });
+
+  it('extracts C++ namespaces, records and qualified method prototypes', async () => {
+    const source = [
+      'namespace geometry {',
+      '',
+      'class Calculator {',
+      'public:',
+      '    int add(int n);',
+      '    int value() const { return total; }',
+      'private:',
+      '    int total;',
+      '};',
+      '',
+      'union Number {',
+      '    int i;',
+      '    float f;',
+      '};',
+      '',
+      'enum Mode { FAST, SLOW };',
+      '',
+      '}',
+      '',
+      'int geometry_version(void);'
+    ].join('\n');
+
+    const extracted = await extractTreeSitterSymbols(source, 'cpp');
+
+    expect(extracted).not.toBeNull();
+    const byName = Object.fromEntries(
+      extracted!.symbols.map((s) => [
+        s.name,
+        { kind: s.kind, qualifier: s.qualifier, declaration: s.declaration }
+      ])
+    );
+    expect(byName).toEqual({
+      geometry: { kind: 'namespace' },
+      Calculator: { kind: 'class' },
+      add: { kind: 'method', qualifier: 'Calculator', declaration: true },
+      value: { kind: 'method', qualifier: 'Calculator' },
+      Number: { kind: 'union' },
+      Mode: { kind: 'enum' },
+      geometry_version: { kind: 'function', declaration: true }
+    });
+  });
+
+  it('extracts C around parse errors and reports the failed regions', async () => {
+    const source = [
+      'typedef struct {',
+      '    int x;',
+      '} Point;',
+      '',
+      'int point_sum(Point p) {',
+      '    return p.x;',
+      '}',
+      '',
+      '#ifdef __cplusplus',
+      '}',
+      '#endif',
+      ''
+    ].join('\n');
+
+    const extracted = await extractTreeSitterSymbols(source, 'c');
+
+    expect(extracted).not.toBeNull();
+    expect(extracted!.symbols.map((s) => [s.name, s.kind])).toEqual([
+      ['Point', 'struct'],
+      ['point_sum', 'function']
+    ]);
+    expect(extracted!.errorRegions?.length).toBeGreaterThan(0);
+    expect(extracted!.errorRegions![0].startLine).toBeGreaterThanOrEqual(9);
+  });
+
+  it('extracts python around parse errors', async () => {
     const analyzer = new GenericAnalyzer();
     const source = [
       'class Greeter:',
@@ -68,13 +366,14 @@ describe('Tree-sitter symbol extraction', () => {
     ].join('\n');
 
     const extracted = await extractTreeSitterSymbols(source, 'python');
-    expect(extracted).toBeNull();
+    expect(extracted).not.toBeNull();
+    expect(extracted!.symbols.map((symbol) => symbol.name)).toEqual(
+      expect.arrayContaining(['Greeter', 'top_level'])
+    );
+    expect(extracted!.errorRegions?.length).toBeGreaterThan(0);
 
     const result = await analyzer.analyze('/virtual/sample.py', source);
-    expect(result.metadata.chunkStrategy).toBe('line-or-component');
-
-    const componentNames = result.chunks.map((chunk) => chunk.metadata.componentName);
-    expect(componentNames).toEqual(expect.arrayContaining(['Greeter', 'hello', 'top_level']));
+    expect(result.metadata).toMatchObject({ chunkStrategy: 'ast-aligned', hasParseErrors: true });
   });
 
   it('reports unsupported language grammars', () => {
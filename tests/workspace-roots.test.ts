@@ -0,0 +1,115 @@
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { SymbolIndex } from '../src/core/symbol-index.js';
+import { findScopedReferencesInRoots } from '../src/core/symbol-references.js';
+import {
+  parseWorkspaceRootList,
+  resolveWorkspaceRoots,
+  selectWorkspaceRoots
+} from '../src/core/workspace-roots.js';
+import {
+  CODEBASE_CONTEXT_DIRNAME,
+  INDEX_FORMAT_VERSION,
+  KEYWORD_INDEX_FILENAME
+} from '../src/constants/codebase-context.js';
+import { rmWithRetries } from './test-helpers.js';
+
+async function writeRoot(root: string, content: string): Promise<void> {
+  await fs.mkdir(path.join(root, 'src'), { recursive: true });
+  await fs.writeFile(path.join(root, 'src', 'main.ts'), content);
+
+  const contextDir = path.join(root, CODEBASE_CONTEXT_DIRNAME);
+  await fs.mkdir(contextDir, { recursive: true });
+  await fs.writeFile(
+    path.join(contextDir, KEYWORD_INDEX_FILENAME),
+    JSON.stringify({
+      header: { buildId: `test-${path.basename(root)}`, formatVersion: INDEX_FORMAT_VERSION },
+      chunks: [{ content, startLine: 1, relativePath: 'src/main.ts' }]
+    })
+  );
+}
+
+describe('multiple workspace roots', () => {
+  let parentDir: string;
+  let rootA: string;
+  let rootB: string;
+
+  beforeEach(async () => {
+    parentDir = await fs.mkdtemp(path.join(os.tmpdir(), 'workspace-roots-'));
+    rootA = path.join(parentDir, 'alpha');
+    rootB = path.join(parentDir, 'beta');
+    // Same relative path in both roots; only absolute paths tell them apart.
+    await writeRoot(
+      rootA,
+      'export function shared() {\n  return 1;\n}\n\n' +
+        'export function onlyA() {\n  return shared();\n}\n'
+    );
+    await writeRoot(
+      rootB,
+      'export function shared() {\n  return 2;\n}\n\n' +
+        'export function onlyB() {\n  return shared();\n}\n'
+    );
+  });
+
+  afterEach(async () => {
+    await rmWithRetries(parentDir);
+  });
+
+  it('indexes symbols from both roots into one SymbolIndex', async () => {
+    const index = new SymbolIndex([rootA, rootB]);
+    await index.refreshFiles([
+      path.join(rootA, 'src', 'main.ts'),
+      path.join(rootB, 'src', 'main.ts')
+    ]);
+
+    expect(index.findByName('onlyA').map((m) => m.root)).toEqual([rootA]);
+    expect(index.findByName('onlyB').map((m) => m.root)).toEqual([rootB]);
+
+    const shared = index.findByName('shared');
+    expect(shared.map((m) => m.relativePath)).toEqual(['src/main.ts', 'src/main.ts']);
+    expect(new Set(shared.map((m) => m.absolutePath)).size).toBe(2);
+
+    expect(index.findByName('shared', { root: rootB }).map((m) => m.absolutePath)).toEqual([
+      path.join(rootB, 'src', 'main.ts')
+    ]);
+    expect(index.files(rootB)).toEqual(['src/main.ts']);
+  });
+
+  it('merges references across roots and tags each location with its root', async () => {
+    const result = await findScopedReferencesInRoots([rootA, rootB], 'shared');
+    if (result.status !== 'success') throw new Error('expected success');
+
+    expect(result.definitions.map((d) => d.root).sort()).toEqual([rootA, rootB].sort());
+    expect(result.references.map((r) => r.root).sort()).toEqual([rootA, rootB].sort());
+
+    const scoped = await findScopedReferencesInRoots([rootB], 'shared');
+    if (scoped.status !== 'success') throw new Error('expected success');
+    expect(scoped.definitions).toHaveLength(1);
+  });
+
+  it('reports roots without an index instead of failing', async () => {
+    const rootC = path.join(parentDir, 'gamma');
+    await fs.mkdir(rootC);
+
+    const result = await findScopedReferencesInRoots([rootA, rootC], 'shared');
+    if (result.status !== 'success') throw new Error('expected success');
+
+    expect(result.definitions.map((d) => d.root)).toEqual([rootA]);
+    expect(result.unavailableRoots).toEqual([rootC]);
+  });
+
+  it('resolves and selects roots by path or directory name', () => {
+    const roots = resolveWorkspaceRoots(
+      rootA,
+      parseWorkspaceRootList([rootB, rootA].join(path.delimiter))
+    );
+
+    expect(roots).toEqual([rootA, rootB]);
+    expect(selectWorkspaceRoots(roots)).toEqual(roots);
+    expect(selectWorkspaceRoots(roots, 'beta')).toEqual([rootB]);
+    expect(selectWorkspaceRoots(roots, rootB)).toEqual([rootB]);
+    expect(selectWorkspaceRoots(roots, 'missing')).toBeNull();
+  });
+});